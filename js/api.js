@@ -55,12 +55,32 @@ export async function sendToLLM(messages, tools) {
     }
 }
 
+/**
+ * Describe the unit catalogue for the LLM, one line per category
+ * @param {Function} unitCatalogue - The WASM unit_catalogue function
+ * @param {Function} categoryName - The WASM unit_category_name function
+ * @returns {string} The units grouped by category
+ */
+function describeUnits(unitCatalogue, categoryName) {
+    const groups = new Map();
+    for (const unit of unitCatalogue()) {
+        const category = categoryName(unit.category());
+        if (!groups.has(category)) {
+            groups.set(category, []);
+        }
+        groups.get(category).push(unit.symbol());
+        unit.free();
+    }
+    return Array.from(groups, ([category, symbols]) => `${category}: ${symbols.join(', ')}`).join('; ');
+}
+
 /**
  * Define calculator tools for the LLM
+ * @param {Object} calculatorFunctions - Object containing WASM calculator functions
  * @returns {Array} Array of tool definitions
  */
-export function defineCalculatorTools() {
-    return [
+export function defineCalculatorTools(calculatorFunctions = {}) {
+    const tools = [
         {
             name: 'calculate',
             description: 'Perform a calculation with two numbers',
@@ -115,6 +135,36 @@ export function defineCalculatorTools() {
             }
         }
     ];
+
+    const { unit_catalogue, unit_category_name } = calculatorFunctions;
+    if (unit_catalogue && unit_category_name) {
+        tools.push({
+            name: 'convert_units',
+            description: 'Convert a value between units of the same kind. Metric units also accept SI prefixes ' +
+                '(e.g. Gm, mbar) and data units accept IEC prefixes (e.g. PiB). Available units: ' +
+                describeUnits(unit_catalogue, unit_category_name),
+            input_schema: {
+                type: 'object',
+                properties: {
+                    value: {
+                        type: 'number',
+                        description: 'The value to convert'
+                    },
+                    from: {
+                        type: 'string',
+                        description: 'The unit of the value, as a symbol or name'
+                    },
+                    to: {
+                        type: 'string',
+                        description: 'The unit to convert to, as a symbol or name'
+                    }
+                },
+                required: ['value', 'from', 'to']
+            }
+        });
+    }
+
+    return tools;
 }
 
 /**
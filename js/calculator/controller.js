@@ -125,7 +125,7 @@ export class CalculatorController {
    */
   handleMemoryStore() {
     // Get the current display value as a number
-    const value = parseFloat(this.state.display_value());
+    const value = parseFloat(this.state.raw_display_value());
     
     // Store it in memory
     window.memory_store(value);
@@ -150,7 +150,7 @@ export class CalculatorController {
    */
   handleMemoryAdd() {
     // Get the current display value as a number
-    const value = parseFloat(this.state.display_value());
+    const value = parseFloat(this.state.raw_display_value());
     
     // Add it to memory
     window.memory_add(value);
@@ -161,7 +161,7 @@ export class CalculatorController {
    */
   handleMemorySubtract() {
     // Get the current display value as a number
-    const value = parseFloat(this.state.display_value());
+    const value = parseFloat(this.state.raw_display_value());
     
     // Subtract it from memory
     window.memory_subtract(value);
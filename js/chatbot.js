@@ -20,7 +20,7 @@ export function initChatbot(calculatorFunctions) {
     let chatHistory = [];
     
     // Define calculator tools for the LLM
-    const tools = defineCalculatorTools();
+    const tools = defineCalculatorTools(calculatorFunctions);
     
     /**
      * Add a message to the chat interface
@@ -164,6 +164,16 @@ export function initChatbot(calculatorFunctions) {
                     calculatorFunctions.memory_clear();
                     addMessage(`I've cleared the memory.`, 'assistant');
                     break;
+
+                case 'convert_units':
+                    try {
+                        result = calculatorFunctions.convert(args.value, args.from, args.to);
+                    } catch (message) {
+                        addMessage(`I couldn't convert ${args.from} to ${args.to}: ${message}`, 'assistant');
+                        return;
+                    }
+                    addMessage(`${args.value} ${args.from} is ${Number(result.toPrecision(10))} ${args.to}.`, 'assistant');
+                    break;
             }
         } catch (error) {
             console.error(`Error executing function ${name}:`, error);
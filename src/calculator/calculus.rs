@@ -0,0 +1,336 @@
+//! Calculator calculus module.
+//!
+//! This module provides numeric calculus on functions of one variable:
+//! - Derivatives at a point, by Ridders' extrapolation of central
+//!   differences
+//! - Definite integrals, by adaptive Gauss–Kronrod (7–15 point) quadrature
+//! - Sums and products over whole-number ranges
+//!
+//! Derivatives and integrals come with an estimate of their error. Every
+//! method has a fixed budget of steps, so a diverging integral or a sum of
+//! too many terms raises an iteration limit error instead of running on.
+//!
+//! A function value that underflows counts as 0, so `exp(-x²)` can be
+//! integrated far into its tails; only the final result is checked for
+//! underflow.
+//!
+//! These operations take Rust closures; JavaScript reaches them through
+//! `derivative`, `integral`, `sum` and `product` in expressions.
+
+use crate::calculator::operations::check_result;
+use crate::errors::{CalculatorError, CalculatorErrorType, CalcResult};
+
+/// The first step of the central difference, relative to the size of the
+/// point.
+const INITIAL_STEP: f64 = 0.1;
+/// The factor by which the step shrinks between rows of Ridders' tableau.
+const STEP_SHRINK: f64 = 1.4;
+/// The number of rows of Ridders' tableau.
+const TABLE_SIZE: usize = 10;
+/// The number of times the first step is shrunk to stay inside the
+/// function's domain.
+const MAX_DOMAIN_RETRIES: usize = 8;
+/// The relative accuracy sought for integrals.
+const RELATIVE_TOLERANCE: f64 = 1e-10;
+/// The absolute accuracy sought for integrals near zero.
+const ABSOLUTE_TOLERANCE: f64 = 1e-13;
+/// The number of subintervals an integral may be split into.
+const MAX_SUBINTERVALS: usize = 500;
+/// The number of terms a sum or product may have.
+const MAX_TERMS: f64 = 1_000_000.0;
+
+/// Gauss–Kronrod nodes on [-1, 1], from the outermost to the centre; the
+/// odd-indexed nodes and the centre are also the 7-point Gauss nodes.
+const KRONROD_NODES: [f64; 8] = [
+    0.991_455_371_120_812_6,
+    0.949_107_912_342_758_5,
+    0.864_864_423_359_769_1,
+    0.741_531_185_599_394_4,
+    0.586_087_235_467_691_1,
+    0.405_845_151_377_397_2,
+    0.207_784_955_007_898_5,
+    0.0,
+];
+/// The 15-point Kronrod weights for `KRONROD_NODES`.
+const KRONROD_WEIGHTS: [f64; 8] = [
+    0.022_935_322_010_529_225,
+    0.063_092_092_629_978_55,
+    0.104_790_010_322_250_18,
+    0.140_653_259_715_525_92,
+    0.169_004_726_639_267_9,
+    0.190_350_578_064_785_4,
+    0.204_432_940_075_298_9,
+    0.209_482_141_084_727_83,
+];
+/// The 7-point Gauss weights for `KRONROD_NODES[1]`, `[3]`, `[5]` and the
+/// centre.
+const GAUSS_WEIGHTS: [f64; 4] = [
+    0.129_484_966_168_869_7,
+    0.279_705_391_489_276_7,
+    0.381_830_050_505_118_9,
+    0.417_959_183_673_469_4,
+];
+
+/// Represents a numeric result with an estimate of its absolute error.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    /// The estimated value
+    value: f64,
+    /// The estimated absolute error
+    error: f64,
+}
+
+impl Estimate {
+    /// Gets the estimated value.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Gets the estimated absolute error.
+    pub fn error(&self) -> f64 {
+        self.error
+    }
+}
+
+/// Computes the derivative of a function at a point.
+///
+/// Central differences with shrinking steps are extrapolated to a step of
+/// zero, keeping the value whose successive extrapolations agree best. If
+/// the function fails next to the point, such as at the edge of its
+/// domain, the first step is shrunk.
+///
+/// # Arguments
+///
+/// * `f` - The function
+/// * `x` - The point
+///
+/// # Returns
+///
+/// * `Ok(estimate)` - The derivative and its estimated error
+/// * `Err(error)` - An invalid input error for a non-finite point, or the
+///   error raised by the function
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::calculator::calculus::derivative;
+///
+/// let slope = derivative(|x| Ok(x.sin()), 0.0).unwrap();
+/// assert!((slope.value() - 1.0).abs() < 1e-12);
+/// ```
+pub fn derivative(f: impl FnMut(f64) -> CalcResult, x: f64) -> CalcResult<Estimate> {
+    if !x.is_finite() {
+        return Err(CalculatorError::invalid_input(Some("the point must be a finite number")));
+    }
+    let mut f = flushing_underflow(f);
+    let mut central = |h: f64| -> CalcResult { Ok((f(x + h)? - f(x - h)?) / (2.0 * h)) };
+
+    let mut h = INITIAL_STEP * x.abs().max(1.0);
+    let mut retries = 0;
+    while retries < MAX_DOMAIN_RETRIES && central(h).is_err() {
+        h /= 10.0;
+        retries += 1;
+    }
+    if retries > 0 {
+        // Keep the differences well clear of the edge of the domain
+        h /= 10.0;
+    }
+
+    // Ridders' tableau: each column extrapolates the one before it
+    let mut previous = vec![central(h)?];
+    let mut best = Estimate { value: previous[0], error: f64::INFINITY };
+    for _ in 1..TABLE_SIZE {
+        h /= STEP_SHRINK;
+        let mut row = vec![central(h)?];
+        let mut factor = STEP_SHRINK * STEP_SHRINK;
+        for j in 1..=previous.len() {
+            let value = (row[j - 1] * factor - previous[j - 1]) / (factor - 1.0);
+            factor *= STEP_SHRINK * STEP_SHRINK;
+            let error = (value - row[j - 1]).abs().max((value - previous[j - 1]).abs());
+            if error <= best.error {
+                best = Estimate { value, error };
+            }
+            row.push(value);
+        }
+        // Stop once higher orders make the estimate worse
+        let (last, before) = (row[row.len() - 1], previous[previous.len() - 1]);
+        previous = row;
+        if (last - before).abs() >= 2.0 * best.error {
+            break;
+        }
+    }
+    Ok(Estimate { value: check_result(best.value, false)?, error: best.error })
+}
+
+/// Computes the definite integral of a function over an interval.
+///
+/// The subinterval with the largest error estimate is halved until the
+/// total error is within 1e-10 of the integral (or 1e-13 for integrals
+/// near zero). The function is not evaluated at the endpoints, so
+/// integrable singularities there, as in `1/√x` on [0, 1], are allowed.
+///
+/// # Arguments
+///
+/// * `f` - The function
+/// * `a` - The lower limit
+/// * `b` - The upper limit, which may be below `a`
+///
+/// # Returns
+///
+/// * `Ok(estimate)` - The integral and its estimated error
+/// * `Err(error)` - An invalid input error for non-finite limits, an
+///   iteration limit error if the accuracy is not reached within 500
+///   subintervals, or the error raised by the function
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::calculator::calculus::integrate;
+///
+/// let area = integrate(|x| Ok(x * x), 0.0, 3.0).unwrap();
+/// assert!((area.value() - 9.0).abs() < 1e-12);
+/// ```
+pub fn integrate(f: impl FnMut(f64) -> CalcResult, a: f64, b: f64) -> CalcResult<Estimate> {
+    if !a.is_finite() || !b.is_finite() {
+        return Err(CalculatorError::invalid_input(Some("the limits of an integral must be finite numbers")));
+    }
+    if a == b {
+        return Ok(Estimate { value: 0.0, error: 0.0 });
+    }
+
+    let mut f = flushing_underflow(f);
+    let mut pieces = vec![(a, b, kronrod(&mut f, a, b)?)];
+    loop {
+        let value: f64 = pieces.iter().map(|(_, _, estimate)| estimate.value).sum();
+        let error: f64 = pieces.iter().map(|(_, _, estimate)| estimate.error).sum();
+        if error <= ABSOLUTE_TOLERANCE.max(RELATIVE_TOLERANCE * value.abs()) {
+            return Ok(Estimate { value: check_result(value, false)?, error });
+        }
+        if pieces.len() >= MAX_SUBINTERVALS {
+            return Err(CalculatorError::iteration_limit(Some(&format!(
+                "the integral did not converge within {} subintervals (estimate {} ± {})",
+                MAX_SUBINTERVALS, value, error
+            ))));
+        }
+
+        let worst = (0..pieces.len())
+            .max_by(|&i, &j| pieces[i].2.error.total_cmp(&pieces[j].2.error))
+            .unwrap_or(0);
+        let (start, end, _) = pieces.swap_remove(worst);
+        let middle = 0.5 * (start + end);
+        pieces.push((start, middle, kronrod(&mut f, start, middle)?));
+        pieces.push((middle, end, kronrod(&mut f, middle, end)?));
+    }
+}
+
+/// Computes the sum of a function over the whole numbers in a range.
+///
+/// # Arguments
+///
+/// * `f` - The function giving each term
+/// * `from` - The first whole number
+/// * `to` - The last whole number; the sum is 0 if it is below `from`
+///
+/// # Returns
+///
+/// * `Ok(sum)` - The sum, accumulated with compensation for rounding
+/// * `Err(error)` - An invalid input error if a limit is not a whole
+///   number, an iteration limit error for more than 1,000,000 terms, an
+///   overflow error, or the error raised by the function
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::calculator::calculus::sum;
+///
+/// assert_eq!(sum(|k| Ok(k * k), 1.0, 100.0).unwrap(), 338350.0);
+/// ```
+pub fn sum(f: impl FnMut(f64) -> CalcResult, from: f64, to: f64) -> CalcResult {
+    let mut f = flushing_underflow(f);
+    let mut total = 0.0;
+    let mut compensation = 0.0;
+    for k in terms(from, to, "sum")? {
+        let term = f(k)?;
+        let next = total + term;
+        // Neumaier's variant of Kahan summation
+        compensation += if total.abs() >= term.abs() { (total - next) + term } else { (term - next) + total };
+        total = next;
+    }
+    check_result(total + compensation, false)
+}
+
+/// Computes the product of a function over the whole numbers in a range.
+///
+/// # Arguments
+///
+/// * `f` - The function giving each factor
+/// * `from` - The first whole number
+/// * `to` - The last whole number; the product is 1 if it is below `from`
+///
+/// # Returns
+///
+/// * `Ok(product)` - The product
+/// * `Err(error)` - An invalid input error if a limit is not a whole
+///   number, an iteration limit error for more than 1,000,000 factors, an
+///   overflow error, or the error raised by the function
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::calculator::calculus::product;
+///
+/// assert_eq!(product(|k| Ok(k), 1.0, 5.0).unwrap(), 120.0);
+/// ```
+pub fn product(f: impl FnMut(f64) -> CalcResult, from: f64, to: f64) -> CalcResult {
+    let mut f = flushing_underflow(f);
+    let mut total = 1.0;
+    for k in terms(from, to, "product")? {
+        total *= f(k)?;
+    }
+    check_result(total, false)
+}
+
+/// Wraps a function so that values which underflow are 0.
+fn flushing_underflow(mut f: impl FnMut(f64) -> CalcResult) -> impl FnMut(f64) -> CalcResult {
+    move |x| match f(x) {
+        Err(error) if error.error_type() == CalculatorErrorType::Underflow => Ok(0.0),
+        Ok(value) if value.is_subnormal() => Ok(0.0),
+        result => result,
+    }
+}
+
+/// Applies the 15-point Kronrod rule to an interval, estimating its error
+/// by the difference from the embedded 7-point Gauss rule.
+fn kronrod(f: &mut impl FnMut(f64) -> CalcResult, a: f64, b: f64) -> CalcResult<Estimate> {
+    let centre = 0.5 * (a + b);
+    let half = 0.5 * (b - a);
+    let f_centre = f(centre)?;
+    let mut kronrod = KRONROD_WEIGHTS[7] * f_centre;
+    let mut gauss = GAUSS_WEIGHTS[3] * f_centre;
+    for (i, node) in KRONROD_NODES[..7].iter().enumerate() {
+        let pair = f(centre - half * node)? + f(centre + half * node)?;
+        kronrod += KRONROD_WEIGHTS[i] * pair;
+        if i % 2 == 1 {
+            gauss += GAUSS_WEIGHTS[i / 2] * pair;
+        }
+    }
+    Ok(Estimate { value: kronrod * half, error: ((kronrod - gauss) * half).abs() })
+}
+
+/// Checks the limits of a sum or product and lists its whole numbers.
+fn terms(from: f64, to: f64, what: &str) -> CalcResult<impl Iterator<Item = f64>> {
+    if from.fract() != 0.0 || to.fract() != 0.0 || !from.is_finite() || !to.is_finite() {
+        return Err(CalculatorError::invalid_input(Some(&format!(
+            "the limits of a {} must be whole numbers",
+            what
+        ))));
+    }
+    let count = (to - from + 1.0).max(0.0);
+    if count > MAX_TERMS {
+        return Err(CalculatorError::iteration_limit(Some(&format!(
+            "a {} of {} terms is more than the limit of {}",
+            what, count, MAX_TERMS
+        ))));
+    }
+    Ok((0..count as u64).map(move |i| from + i as f64))
+}
@@ -0,0 +1,256 @@
+//! Calculator combinatorics module.
+//!
+//! This module provides exact counting functions:
+//! - Factorial (`n!`)
+//! - Permutations (`nPr`)
+//! - Combinations (`nCr`)
+//!
+//! Results are computed with arbitrary-precision integers and returned as
+//! decimal strings, since they quickly exceed what an `f64` can hold
+//! exactly (`23!` already has more than 53 significant bits).
+//!
+//! All operations are exposed to JavaScript through WebAssembly bindings.
+
+use std::fmt;
+use wasm_bindgen::prelude::*;
+use crate::errors::{CalculatorError, CalcResult};
+
+/// The largest argument accepted by the exact counting functions.
+///
+/// `10000!` has 35,660 digits, which is still quick to compute and print.
+pub const MAX_EXACT_ARGUMENT: u32 = 10_000;
+
+/// A non-negative integer of arbitrary size.
+///
+/// Only the operations needed for counting are implemented: multiplying
+/// and dividing by a machine-sized integer.
+#[derive(Debug, Clone, PartialEq)]
+struct BigNatural {
+    /// Base 2³² digits, least significant first
+    limbs: Vec<u32>,
+}
+
+impl BigNatural {
+    /// Creates the number one.
+    fn one() -> Self {
+        Self { limbs: vec![1] }
+    }
+
+    /// Multiplies in place by a small, non-zero factor.
+    fn mul_small(&mut self, factor: u32) {
+        let mut carry = 0u64;
+        for limb in self.limbs.iter_mut() {
+            let product = *limb as u64 * factor as u64 + carry;
+            *limb = product as u32;
+            carry = product >> 32;
+        }
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+    }
+
+    /// Divides in place by a small divisor, returning the remainder.
+    fn div_small(&mut self, divisor: u32) -> u32 {
+        let mut remainder = 0u64;
+        for limb in self.limbs.iter_mut().rev() {
+            let value = (remainder << 32) | *limb as u64;
+            *limb = (value / divisor as u64) as u32;
+            remainder = value % divisor as u64;
+        }
+        while self.limbs.len() > 1 && self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        remainder as u32
+    }
+
+    /// Checks whether the number is zero.
+    fn is_zero(&self) -> bool {
+        self.limbs.iter().all(|limb| *limb == 0)
+    }
+}
+
+impl fmt::Display for BigNatural {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Peel off nine decimal digits at a time
+        let mut rest = self.clone();
+        let mut chunks = Vec::new();
+        loop {
+            chunks.push(rest.div_small(1_000_000_000));
+            if rest.is_zero() {
+                break;
+            }
+        }
+
+        let mut chunks = chunks.iter().rev();
+        if let Some(first) = chunks.next() {
+            write!(f, "{}", first)?;
+        }
+        for chunk in chunks {
+            write!(f, "{:09}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+/// Validates a counting argument.
+///
+/// # Arguments
+///
+/// * `value` - The argument
+/// * `name` - The argument's name, for the error message
+///
+/// # Returns
+///
+/// * `Ok(count)` - The argument as an integer
+/// * `Err(error)` - An invalid input error if the argument is not a whole
+///   number from 0 to `MAX_EXACT_ARGUMENT`
+pub(crate) fn to_count(value: f64, name: &str) -> CalcResult<u32> {
+    if !value.is_finite() || value < 0.0 || value.fract() != 0.0 {
+        return Err(CalculatorError::invalid_input(Some(&format!(
+            "{} must be a non-negative integer",
+            name
+        ))));
+    }
+    if value > MAX_EXACT_ARGUMENT as f64 {
+        return Err(CalculatorError::invalid_input(Some(&format!(
+            "{} must be at most {}",
+            name, MAX_EXACT_ARGUMENT
+        ))));
+    }
+    Ok(value as u32)
+}
+
+/// Computes `n!` exactly.
+///
+/// # Arguments
+///
+/// * `n` - A whole number from 0 to `MAX_EXACT_ARGUMENT`
+///
+/// # Returns
+///
+/// * `Ok(digits)` - The factorial as a decimal string
+/// * `Err(error)` - An invalid input error if `n` is out of range
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::calculator::factorial;
+///
+/// assert_eq!(factorial(5.0).unwrap(), "120");
+/// assert_eq!(factorial(25.0).unwrap(), "15511210043330985984000000");
+/// assert!(factorial(-1.0).is_err());
+/// ```
+pub fn factorial(n: f64) -> CalcResult<String> {
+    let n = to_count(n, "n")?;
+    let mut result = BigNatural::one();
+    for factor in 2..=n {
+        result.mul_small(factor);
+    }
+    Ok(result.to_string())
+}
+
+/// Computes the number of ordered selections `nPr = n! / (n - r)!` exactly.
+///
+/// # Arguments
+///
+/// * `n` - The number of items
+/// * `r` - The number of items selected
+///
+/// # Returns
+///
+/// * `Ok(digits)` - The count as a decimal string (`"0"` if `r > n`)
+/// * `Err(error)` - An invalid input error if an argument is out of range
+pub fn permutations(n: f64, r: f64) -> CalcResult<String> {
+    let n = to_count(n, "n")?;
+    let r = to_count(r, "r")?;
+    if r > n {
+        return Ok("0".to_string());
+    }
+
+    let mut result = BigNatural::one();
+    for factor in n - r + 1..=n {
+        result.mul_small(factor);
+    }
+    Ok(result.to_string())
+}
+
+/// Computes the number of unordered selections `nCr = n! / (r! (n - r)!)` exactly.
+///
+/// # Arguments
+///
+/// * `n` - The number of items
+/// * `r` - The number of items selected
+///
+/// # Returns
+///
+/// * `Ok(digits)` - The count as a decimal string (`"0"` if `r > n`)
+/// * `Err(error)` - An invalid input error if an argument is out of range
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::calculator::combinations;
+///
+/// assert_eq!(combinations(5.0, 2.0).unwrap(), "10");
+/// assert_eq!(combinations(100.0, 50.0).unwrap(), "100891344545564193334812497256");
+/// ```
+pub fn combinations(n: f64, r: f64) -> CalcResult<String> {
+    let n = to_count(n, "n")?;
+    let r = to_count(r, "r")?;
+    if r > n {
+        return Ok("0".to_string());
+    }
+
+    // Each partial product C(n - r + i, i) is an integer, so the division is exact
+    let r = r.min(n - r);
+    let mut result = BigNatural::one();
+    for i in 1..=r {
+        result.mul_small(n - r + i);
+        result.div_small(i);
+    }
+    Ok(result.to_string())
+}
+
+/// Computes `n!` exactly from JavaScript.
+///
+/// # Arguments
+///
+/// * `n` - A whole number from 0 to `MAX_EXACT_ARGUMENT`
+///
+/// # Returns
+///
+/// The factorial as a decimal string, or an error message
+#[wasm_bindgen(js_name = factorial)]
+pub fn factorial_wasm(n: f64) -> Result<String, JsValue> {
+    factorial(n).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Computes `nPr` exactly from JavaScript.
+///
+/// # Arguments
+///
+/// * `n` - The number of items
+/// * `r` - The number of items selected
+///
+/// # Returns
+///
+/// The count as a decimal string, or an error message
+#[wasm_bindgen(js_name = permutations)]
+pub fn permutations_wasm(n: f64, r: f64) -> Result<String, JsValue> {
+    permutations(n, r).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Computes `nCr` exactly from JavaScript.
+///
+/// # Arguments
+///
+/// * `n` - The number of items
+/// * `r` - The number of items selected
+///
+/// # Returns
+///
+/// The count as a decimal string, or an error message
+#[wasm_bindgen(js_name = combinations)]
+pub fn combinations_wasm(n: f64, r: f64) -> Result<String, JsValue> {
+    combinations(n, r).map_err(|e| JsValue::from_str(e.message()))
+}
@@ -0,0 +1,553 @@
+//! Calculator complex number module.
+//!
+//! This module provides complex arithmetic, as used for impedances:
+//! - Rectangular (`3 + 4i`) and polar (`5∠53.13°`) entry and display
+//! - Arithmetic, conjugate, modulus and argument
+//! - Principal values of powers, roots, logarithms and the trigonometric
+//!   and hyperbolic functions
+//!
+//! Arguments are in radians unless written with a degree sign. Both `i` and
+//! the engineering `j` are accepted as the imaginary unit.
+//!
+//! All operations are exposed to JavaScript through WebAssembly bindings.
+
+use std::f64::consts::{FRAC_PI_2, PI};
+use std::fmt;
+use wasm_bindgen::prelude::*;
+use crate::errors::{CalculatorError, CalcResult};
+use crate::state::format_significant;
+
+/// The number of significant digits shown, which hides rounding noise
+/// such as 1.2246467991473532e-16 for the imaginary part of e^(iπ).
+const DISPLAY_DIGITS: usize = 15;
+
+/// Integer powers up to this size are computed by repeated multiplication,
+/// so that `i^2` is exactly -1.
+const MAX_EXACT_POWER: f64 = 64.0;
+
+/// Represents a complex number in rectangular form.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Complex {
+    /// The real part
+    re: f64,
+    /// The imaginary part
+    im: f64,
+}
+
+/// Constructor and getters for Complex
+#[wasm_bindgen]
+impl Complex {
+    /// Creates a complex number from its real and imaginary parts.
+    ///
+    /// # Arguments
+    ///
+    /// * `re` - The real part
+    /// * `im` - The imaginary part
+    ///
+    /// # Returns
+    ///
+    /// A new `Complex` instance
+    #[wasm_bindgen(constructor)]
+    pub fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    /// Gets the real part.
+    pub fn re(&self) -> f64 {
+        self.re
+    }
+
+    /// Gets the imaginary part.
+    pub fn im(&self) -> f64 {
+        self.im
+    }
+
+    /// Gets the modulus (absolute value).
+    pub fn modulus(&self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// Gets the argument in radians, between -π and π.
+    pub fn argument(&self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    /// Gets the complex conjugate.
+    pub fn conjugate(&self) -> Complex {
+        Self::new(self.re, -self.im)
+    }
+
+    /// Checks whether the imaginary part is zero.
+    pub fn is_real(&self) -> bool {
+        self.im == 0.0
+    }
+
+    /// Formats the number in rectangular form (e.g. `"3 - 4i"`).
+    pub fn format_rectangular(&self) -> String {
+        self.to_string()
+    }
+
+    /// Formats the number in polar form (e.g. `"5∠53.130102354156°"`).
+    ///
+    /// # Arguments
+    ///
+    /// * `degrees` - Whether to show the argument in degrees rather than radians
+    ///
+    /// # Returns
+    ///
+    /// The modulus and argument separated by `∠`
+    pub fn format_polar(&self, degrees: bool) -> String {
+        let argument = if degrees { self.argument().to_degrees() } else { self.argument() };
+        format!(
+            "{}∠{}{}",
+            display_part(self.modulus()),
+            display_part(argument),
+            if degrees { "°" } else { "" }
+        )
+    }
+}
+
+impl Complex {
+    /// The imaginary unit
+    pub const I: Complex = Complex { re: 0.0, im: 1.0 };
+
+    /// Creates a complex number with no imaginary part.
+    ///
+    /// # Arguments
+    ///
+    /// * `re` - The real number
+    ///
+    /// # Returns
+    ///
+    /// The number as a complex number
+    pub fn real(re: f64) -> Self {
+        Self::new(re, 0.0)
+    }
+
+    /// Creates a complex number from polar coordinates.
+    ///
+    /// # Arguments
+    ///
+    /// * `modulus` - The modulus
+    /// * `argument` - The argument in radians
+    ///
+    /// # Returns
+    ///
+    /// The complex number
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::calculator::complex::Complex;
+    ///
+    /// let z = Complex::from_polar(2.0, std::f64::consts::FRAC_PI_2);
+    /// assert_eq!(z.to_string(), "2i");
+    /// ```
+    pub fn from_polar(modulus: f64, argument: f64) -> Self {
+        let (sin, cos) = argument.sin_cos();
+        Self::new(modulus * cos, modulus * sin)
+    }
+
+    /// Parses a complex number in rectangular or polar form.
+    ///
+    /// Rectangular numbers are written `a+bi`, `a-bj`, `bi` or `a`; polar
+    /// numbers are written `r∠θ`, with θ in radians, or `r∠θ°` in degrees.
+    /// Spaces are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The number
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(z)` - The complex number
+    /// * `Err(error)` - An invalid input error if the text is not a complex number
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::calculator::complex::Complex;
+    ///
+    /// assert_eq!(Complex::parse("3 - 4i").unwrap(), Complex::new(3.0, -4.0));
+    /// assert!((Complex::parse("2∠90°").unwrap().im() - 2.0).abs() < 1e-15);
+    /// assert!(Complex::parse("3 + 4k").is_err());
+    /// ```
+    pub fn parse(text: &str) -> CalcResult<Self> {
+        let compact: String = text.chars().filter(|c| !c.is_whitespace()).map(|c| if c == '−' { '-' } else { c }).collect();
+        let parsed = match compact.split_once('∠') {
+            Some((modulus, argument)) => parse_polar(modulus, argument),
+            None => parse_rectangular(&compact),
+        };
+        parsed
+            .filter(|z| z.re.is_finite() && z.im.is_finite())
+            .ok_or_else(|| CalculatorError::invalid_input(Some(&format!("'{}' is not a complex number", text.trim()))))
+    }
+
+    /// Adds two complex numbers.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The number to add
+    ///
+    /// # Returns
+    ///
+    /// The sum
+    pub fn add(&self, other: &Complex) -> Complex {
+        Self::new(self.re + other.re, self.im + other.im)
+    }
+
+    /// Subtracts a complex number.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The number to subtract
+    ///
+    /// # Returns
+    ///
+    /// The difference
+    pub fn subtract(&self, other: &Complex) -> Complex {
+        Self::new(self.re - other.re, self.im - other.im)
+    }
+
+    /// Multiplies two complex numbers.
+    ///
+    /// If the products of the parts overflow, the parts are multiplied
+    /// scaled down by powers of two and scaled back up, so a part that is
+    /// too large becomes infinite (an overflow) rather than NaN.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The number to multiply by
+    ///
+    /// # Returns
+    ///
+    /// The product
+    pub fn multiply(&self, other: &Complex) -> Complex {
+        let product = Self::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        );
+        let finite = |z: &Complex| z.re.is_finite() && z.im.is_finite();
+        if !(product.re.is_nan() || product.im.is_nan()) || !finite(self) || !finite(other) {
+            return product;
+        }
+
+        let (a, a_scale) = self.scaled();
+        let (b, b_scale) = other.scaled();
+        let scaled = a.multiply(&b);
+        Self::new(scaled.re * a_scale * b_scale, scaled.im * a_scale * b_scale)
+    }
+
+    /// Divides by a complex number, scaling to avoid intermediate overflow
+    /// (Smith's algorithm).
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The divisor
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(quotient)` - The quotient
+    /// * `Err(error)` - A division by zero error if the divisor is zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::calculator::complex::Complex;
+    ///
+    /// let z = Complex::new(1.0, 0.0).divide(&Complex::I).unwrap();
+    /// assert_eq!(z, Complex::new(0.0, -1.0));
+    /// ```
+    pub fn divide(&self, other: &Complex) -> CalcResult<Complex> {
+        if other.re == 0.0 && other.im == 0.0 {
+            return Err(CalculatorError::division_by_zero());
+        }
+        Ok(if other.re.abs() >= other.im.abs() {
+            let ratio = other.im / other.re;
+            let scale = other.re + other.im * ratio;
+            Self::new((self.re + self.im * ratio) / scale, (self.im - self.re * ratio) / scale)
+        } else {
+            let ratio = other.re / other.im;
+            let scale = other.re * ratio + other.im;
+            Self::new((self.re * ratio + self.im) / scale, (self.im * ratio - self.re) / scale)
+        })
+    }
+
+    /// Raises to a complex power, using the principal logarithm.
+    ///
+    /// Small integer powers are computed by repeated multiplication, so
+    /// they are exact where the parts are.
+    ///
+    /// # Arguments
+    ///
+    /// * `exponent` - The exponent
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(power)` - The principal value of the power
+    /// * `Err(error)` - A division by zero error for zero raised to a power
+    ///   whose real part is not positive
+    pub fn power(&self, exponent: &Complex) -> CalcResult<Complex> {
+        if exponent.is_real() && exponent.re.fract() == 0.0 && exponent.re.abs() <= MAX_EXACT_POWER {
+            let mut result = Self::real(1.0);
+            for _ in 0..exponent.re.abs() as u32 {
+                result = result.multiply(self);
+            }
+            return if exponent.re < 0.0 { Self::real(1.0).divide(&result) } else { Ok(result) };
+        }
+        if self.re == 0.0 && self.im == 0.0 {
+            return if exponent.re > 0.0 { Ok(Self::real(0.0)) } else { Err(CalculatorError::division_by_zero()) };
+        }
+        Ok(exponent.multiply(&self.ln()).exp())
+    }
+
+    /// Computes the principal square root, whose real part is not negative.
+    ///
+    /// # Returns
+    ///
+    /// The square root
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::calculator::complex::Complex;
+    ///
+    /// assert_eq!(Complex::real(-4.0).sqrt(), Complex::new(0.0, 2.0));
+    /// ```
+    pub fn sqrt(&self) -> Complex {
+        let modulus = self.modulus();
+        let re = ((modulus + self.re) / 2.0).sqrt();
+        let im = ((modulus - self.re) / 2.0).sqrt();
+        Self::new(re, if self.im < 0.0 { -im } else { im })
+    }
+
+    /// Computes the principal cube root.
+    ///
+    /// # Returns
+    ///
+    /// The cube root with an argument between -π/3 and π/3
+    pub fn cbrt(&self) -> Complex {
+        Self::from_polar(self.modulus().cbrt(), self.argument() / 3.0)
+    }
+
+    /// Computes the exponential.
+    ///
+    /// # Returns
+    ///
+    /// e raised to this number
+    pub fn exp(&self) -> Complex {
+        Self::from_polar(self.re.exp(), self.im)
+    }
+
+    /// Computes the principal natural logarithm.
+    ///
+    /// # Returns
+    ///
+    /// The logarithm, whose imaginary part is between -π and π
+    pub fn ln(&self) -> Complex {
+        Self::new(self.modulus().ln(), self.argument())
+    }
+
+    /// Computes the principal logarithm to a base.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(logarithm)` - The logarithm
+    /// * `Err(error)` - A division by zero error for base 1
+    pub fn log(&self, base: &Complex) -> CalcResult<Complex> {
+        self.ln().divide(&base.ln())
+    }
+
+    /// Computes the sine.
+    pub fn sin(&self) -> Complex {
+        Self::new(self.re.sin() * self.im.cosh(), self.re.cos() * self.im.sinh())
+    }
+
+    /// Computes the cosine.
+    pub fn cos(&self) -> Complex {
+        Self::new(self.re.cos() * self.im.cosh(), -self.re.sin() * self.im.sinh())
+    }
+
+    /// Computes the tangent.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(tangent)` - The tangent
+    /// * `Err(error)` - A division by zero error where the cosine is zero
+    pub fn tan(&self) -> CalcResult<Complex> {
+        self.sin().divide(&self.cos())
+    }
+
+    /// Computes the hyperbolic sine.
+    pub fn sinh(&self) -> Complex {
+        Self::new(self.re.sinh() * self.im.cos(), self.re.cosh() * self.im.sin())
+    }
+
+    /// Computes the hyperbolic cosine.
+    pub fn cosh(&self) -> Complex {
+        Self::new(self.re.cosh() * self.im.cos(), self.re.sinh() * self.im.sin())
+    }
+
+    /// Computes the hyperbolic tangent.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(tangent)` - The hyperbolic tangent
+    /// * `Err(error)` - A division by zero error where the hyperbolic cosine is zero
+    pub fn tanh(&self) -> CalcResult<Complex> {
+        self.sinh().divide(&self.cosh())
+    }
+
+    /// Computes the principal inverse sine, `-i ln(iz + √(1 - z²))`.
+    pub fn asin(&self) -> Complex {
+        let root = Self::real(1.0).subtract(&self.multiply(self)).sqrt();
+        let log = Self::I.multiply(self).add(&root).ln();
+        Self::new(log.im, -log.re)
+    }
+
+    /// Computes the principal inverse cosine, `π/2 - asin(z)`.
+    pub fn acos(&self) -> Complex {
+        Self::real(FRAC_PI_2).subtract(&self.asin())
+    }
+
+    /// Computes the principal inverse tangent, `(i/2) (ln(1 - iz) - ln(1 + iz))`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(angle)` - The inverse tangent
+    /// * `Err(error)` - A division by zero error at ±i, where it has poles
+    pub fn atan(&self) -> CalcResult<Complex> {
+        let iz = Self::I.multiply(self);
+        if iz == Self::real(1.0) || iz == Self::real(-1.0) {
+            return Err(CalculatorError::division_by_zero());
+        }
+        let difference = Self::real(1.0).subtract(&iz).ln().subtract(&Self::real(1.0).add(&iz).ln());
+        Ok(Self::new(-difference.im / 2.0, difference.re / 2.0))
+    }
+
+    /// Splits off a power of two, leaving parts of magnitude below 2.
+    fn scaled(&self) -> (Complex, f64) {
+        let largest = self.re.abs().max(self.im.abs());
+        if largest == 0.0 {
+            return (*self, 1.0);
+        }
+        let scale = 2f64.powi(largest.log2().floor() as i32);
+        (Self::new(self.re / scale, self.im / scale), scale)
+    }
+
+    /// Checks that both parts are finite numbers, and clears a part that
+    /// is only rounding noise next to the other (below 1e-15 of the
+    /// modulus), so `e^(iπ)` is exactly -1.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(z)` - The cleaned number
+    /// * `Err(error)` - An overflow error for an infinite part, or a NaN
+    ///   error for an undefined part
+    pub fn checked(&self) -> CalcResult<Complex> {
+        if self.re.is_nan() || self.im.is_nan() {
+            return Err(CalculatorError::not_a_number());
+        }
+        if self.re.is_infinite() || self.im.is_infinite() {
+            return Err(CalculatorError::overflow());
+        }
+        let noise = self.modulus() * 1e-15;
+        let clean = |part: f64| if part.abs() < noise { 0.0 } else { part };
+        Ok(Self::new(clean(self.re), clean(self.im)))
+    }
+}
+
+impl fmt::Display for Complex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let z = self.checked().unwrap_or(*self);
+        let imaginary = match display_part(z.im.abs()).as_str() {
+            "1" => String::new(),
+            digits => digits.to_string(),
+        };
+        match (z.re == 0.0, z.im == 0.0) {
+            (_, true) => write!(f, "{}", display_part(z.re)),
+            (true, false) => write!(f, "{}{}i", if z.im < 0.0 { "-" } else { "" }, imaginary),
+            (false, false) => write!(
+                f,
+                "{} {} {}i",
+                display_part(z.re),
+                if z.im < 0.0 { "-" } else { "+" },
+                imaginary
+            ),
+        }
+    }
+}
+
+/// Parses a complex number from JavaScript.
+///
+/// # Arguments
+///
+/// * `text` - The number in rectangular or polar form
+///
+/// # Returns
+///
+/// The complex number, or an error message
+#[wasm_bindgen(js_name = parse_complex)]
+pub fn parse_complex_wasm(text: &str) -> Result<Complex, JsValue> {
+    Complex::parse(text).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Creates a complex number from polar coordinates from JavaScript.
+///
+/// # Arguments
+///
+/// * `modulus` - The modulus
+/// * `argument` - The argument
+/// * `degrees` - Whether the argument is in degrees rather than radians
+///
+/// # Returns
+///
+/// The complex number
+#[wasm_bindgen]
+pub fn complex_from_polar(modulus: f64, argument: f64, degrees: bool) -> Complex {
+    let argument = if degrees { argument.to_radians() } else { argument };
+    Complex::from_polar(modulus, argument)
+}
+
+/// Parses `a+bi`, `bi` or `a`, with `j` accepted for `i`.
+fn parse_rectangular(text: &str) -> Option<Complex> {
+    let Some(body) = text.strip_suffix(['i', 'j']) else {
+        return text.parse().ok().map(Complex::real);
+    };
+    // The sign starting the imaginary part, skipping exponent signs such as 1e-3
+    let split = body
+        .char_indices()
+        .filter(|&(at, c)| at > 0 && (c == '+' || c == '-') && !body[..at].ends_with(['e', 'E']))
+        .map(|(at, _)| at)
+        .next_back();
+    let (re, im) = match split {
+        Some(at) => (body[..at].parse().ok()?, &body[at..]),
+        None => (0.0, body),
+    };
+    let im = match im {
+        "" | "+" => 1.0,
+        "-" => -1.0,
+        coefficient => coefficient.parse().ok()?,
+    };
+    Some(Complex::new(re, im))
+}
+
+/// Parses the modulus and argument of `r∠θ` or `r∠θ°`.
+fn parse_polar(modulus: &str, argument: &str) -> Option<Complex> {
+    let modulus: f64 = modulus.parse().ok()?;
+    let argument = match argument.strip_suffix('°') {
+        Some(degrees) => degrees.parse::<f64>().ok()? * PI / 180.0,
+        None => argument.parse().ok()?,
+    };
+    Some(Complex::from_polar(modulus, argument))
+}
+
+/// Formats one part for display, to 15 significant digits.
+fn display_part(value: f64) -> String {
+    format_significant(value, DISPLAY_DIGITS)
+}
@@ -0,0 +1,580 @@
+//! Calculator date and time module.
+//!
+//! This module provides calendar and duration arithmetic:
+//! - Days between dates, and adding years, months and days to a date
+//! - Weekday lookup
+//! - Business-day counting and offsetting with a supplied holiday list
+//! - Durations written as `h:m:s`, parsed to and formatted from seconds
+//!
+//! Dates use the proleptic Gregorian calendar and are written as ISO 8601
+//! `YYYY-MM-DD` strings. Business days are Monday to Friday. Nothing here
+//! reads the clock, so results depend only on the arguments.
+//!
+//! All operations are exposed to JavaScript through WebAssembly bindings.
+
+use std::fmt;
+use wasm_bindgen::prelude::*;
+use crate::errors::{CalculatorError, CalcResult};
+
+/// The largest number of business days `add_business_days` will step over.
+pub const MAX_BUSINESS_DAY_OFFSET: i32 = 1_000_000;
+
+/// Represents a day of the week, numbered as in ISO 8601.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Weekday {
+    Monday = 1,
+    Tuesday = 2,
+    Wednesday = 3,
+    Thursday = 4,
+    Friday = 5,
+    Saturday = 6,
+    Sunday = 7,
+}
+
+impl Weekday {
+    /// Checks whether this is a business day (Monday to Friday).
+    ///
+    /// # Returns
+    ///
+    /// `true` for Monday to Friday
+    pub fn is_business_day(&self) -> bool {
+        !matches!(self, Weekday::Saturday | Weekday::Sunday)
+    }
+}
+
+/// A calendar date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    /// The year, from 1 to 9999
+    year: i32,
+    /// The month, from 1 to 12
+    month: u32,
+    /// The day of the month
+    day: u32,
+}
+
+impl Date {
+    /// Creates a date.
+    ///
+    /// # Arguments
+    ///
+    /// * `year` - The year, from 1 to 9999
+    /// * `month` - The month, from 1 to 12
+    /// * `day` - The day of the month
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(date)` - The date
+    /// * `Err(error)` - An invalid input error if the date does not exist
+    pub fn new(year: i32, month: u32, day: u32) -> CalcResult<Self> {
+        if !(1..=9999).contains(&year) {
+            return Err(CalculatorError::invalid_input(Some("year must be between 1 and 9999")));
+        }
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(CalculatorError::invalid_input(Some(&format!(
+                "{:04}-{:02}-{:02} is not a valid date",
+                year, month, day
+            ))));
+        }
+        Ok(Self { year, month, day })
+    }
+
+    /// Parses an ISO 8601 date (`YYYY-MM-DD`).
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The date text
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(date)` - The date
+    /// * `Err(error)` - An invalid input error if the text is not a valid date
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::calculator::datetime::Date;
+    ///
+    /// let date = Date::parse("2024-02-29").unwrap();
+    /// assert_eq!(date.to_string(), "2024-02-29");
+    /// assert!(Date::parse("2023-02-29").is_err());
+    /// ```
+    pub fn parse(text: &str) -> CalcResult<Self> {
+        let invalid = || CalculatorError::invalid_input(Some(&format!("{} is not a YYYY-MM-DD date", text)));
+        let parts: Vec<&str> = text.trim().split('-').collect();
+        if parts.len() != 3 || parts[0].len() != 4 || parts[1].len() != 2 || parts[2].len() != 2 {
+            return Err(invalid());
+        }
+        let year = parts[0].parse().map_err(|_| invalid())?;
+        let month = parts[1].parse().map_err(|_| invalid())?;
+        let day = parts[2].parse().map_err(|_| invalid())?;
+        Self::new(year, month, day)
+    }
+
+    /// Creates the date a number of days after 1970-01-01.
+    ///
+    /// # Arguments
+    ///
+    /// * `days` - The day number (negative before 1970)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(date)` - The date
+    /// * `Err(error)` - An invalid input error if the date is outside the
+    ///   years 1 to 9999
+    pub fn from_day_number(days: i64) -> CalcResult<Self> {
+        // Inverse of `day_number`, after Howard Hinnant's civil_from_days
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u32;
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        if !(1..=9999).contains(&year) {
+            return Err(CalculatorError::invalid_input(Some("year must be between 1 and 9999")));
+        }
+        Self::new(year as i32, month, day)
+    }
+
+    /// Gets the number of days since 1970-01-01.
+    ///
+    /// # Returns
+    ///
+    /// The day number (negative before 1970)
+    pub fn day_number(&self) -> i64 {
+        // After Howard Hinnant's days_from_civil
+        let year = self.year as i64 - if self.month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let shifted_month = (self.month as i64 + 9) % 12;
+        let day_of_year = (153 * shifted_month + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Gets the day of the week.
+    ///
+    /// # Returns
+    ///
+    /// The weekday
+    pub fn weekday(&self) -> Weekday {
+        // 1970-01-01 was a Thursday
+        match (self.day_number() + 3).rem_euclid(7) {
+            0 => Weekday::Monday,
+            1 => Weekday::Tuesday,
+            2 => Weekday::Wednesday,
+            3 => Weekday::Thursday,
+            4 => Weekday::Friday,
+            5 => Weekday::Saturday,
+            _ => Weekday::Sunday,
+        }
+    }
+
+    /// Adds a number of days.
+    ///
+    /// # Arguments
+    ///
+    /// * `days` - The number of days (negative to go back)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(date)` - The new date
+    /// * `Err(error)` - An invalid input error if the result is out of range
+    pub fn add_days(&self, days: i64) -> CalcResult<Self> {
+        Self::from_day_number(self.day_number() + days)
+    }
+
+    /// Adds a number of months, keeping the day of the month where possible.
+    ///
+    /// If the day does not exist in the target month, the last day of that
+    /// month is used, so one month after 2024-01-31 is 2024-02-29.
+    ///
+    /// # Arguments
+    ///
+    /// * `months` - The number of months (negative to go back)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(date)` - The new date
+    /// * `Err(error)` - An invalid input error if the result is out of range
+    pub fn add_months(&self, months: i64) -> CalcResult<Self> {
+        let index = self.year as i64 * 12 + (self.month as i64 - 1) + months;
+        let year = index.div_euclid(12);
+        let month = (index.rem_euclid(12) + 1) as u32;
+        if !(1..=9999).contains(&year) {
+            return Err(CalculatorError::invalid_input(Some("year must be between 1 and 9999")));
+        }
+        let year = year as i32;
+        Self::new(year, month, self.day.min(days_in_month(year, month)))
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Counts the days from one date to another.
+///
+/// # Arguments
+///
+/// * `from` - The start date (`YYYY-MM-DD`)
+/// * `to` - The end date
+///
+/// # Returns
+///
+/// * `Ok(days)` - The number of days, negative if `to` is before `from`
+/// * `Err(error)` - An invalid input error if a date is invalid
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::calculator::datetime::days_between;
+///
+/// assert_eq!(days_between("2024-01-01", "2024-12-25").unwrap(), 359.0);
+/// ```
+pub fn days_between(from: &str, to: &str) -> CalcResult<f64> {
+    Ok((Date::parse(to)?.day_number() - Date::parse(from)?.day_number()) as f64)
+}
+
+/// Adds a duration in years, months and days to a date.
+///
+/// Years and months are added first, clamping to the end of the month,
+/// then days.
+///
+/// # Arguments
+///
+/// * `date` - The date (`YYYY-MM-DD`)
+/// * `years` - The number of years (negative to subtract)
+/// * `months` - The number of months (negative to subtract)
+/// * `days` - The number of days (negative to subtract)
+///
+/// # Returns
+///
+/// * `Ok(date)` - The new date as `YYYY-MM-DD`
+/// * `Err(error)` - An invalid input error if a date is invalid or out of range
+pub fn add_duration(date: &str, years: i32, months: i32, days: i32) -> CalcResult<String> {
+    let date = Date::parse(date)?
+        .add_months(years as i64 * 12 + months as i64)?
+        .add_days(days as i64)?;
+    Ok(date.to_string())
+}
+
+/// Looks up the day of the week of a date.
+///
+/// # Arguments
+///
+/// * `date` - The date (`YYYY-MM-DD`)
+///
+/// # Returns
+///
+/// * `Ok(weekday)` - The weekday
+/// * `Err(error)` - An invalid input error if the date is invalid
+pub fn weekday(date: &str) -> CalcResult<Weekday> {
+    Ok(Date::parse(date)?.weekday())
+}
+
+/// Counts business days between two dates, including both ends.
+///
+/// Weekends and the given holidays are not counted. This matches the
+/// `NETWORKDAYS` spreadsheet function.
+///
+/// # Arguments
+///
+/// * `from` - The start date (`YYYY-MM-DD`)
+/// * `to` - The end date
+/// * `holidays` - Dates that are not business days
+///
+/// # Returns
+///
+/// * `Ok(days)` - The number of business days, negative if `to` is before `from`
+/// * `Err(error)` - An invalid input error if a date is invalid
+pub fn business_days(from: &str, to: &str, holidays: &[String]) -> CalcResult<f64> {
+    let holidays = parse_holidays(holidays)?;
+    Ok(count_business_days(Date::parse(from)?.day_number(), Date::parse(to)?.day_number(), &holidays) as f64)
+}
+
+/// Moves a date by a number of business days.
+///
+/// Weekends and the given holidays are skipped. This matches the `WORKDAY`
+/// spreadsheet function, so an offset of 0 returns the date unchanged.
+///
+/// # Arguments
+///
+/// * `date` - The date (`YYYY-MM-DD`)
+/// * `days` - The number of business days (negative to go back)
+/// * `holidays` - Dates that are not business days
+///
+/// # Returns
+///
+/// * `Ok(date)` - The new date as `YYYY-MM-DD`
+/// * `Err(error)` - An invalid input error if a date is invalid or the
+///   offset is larger than `MAX_BUSINESS_DAY_OFFSET`
+pub fn add_business_days(date: &str, days: i32, holidays: &[String]) -> CalcResult<String> {
+    let holidays = parse_holidays(holidays)?;
+    let day = offset_business_days(Date::parse(date)?.day_number(), days as i64, &holidays)?;
+    Ok(Date::from_day_number(day)?.to_string())
+}
+
+/// Parses a duration written as `h:m:s` or `h:m`.
+///
+/// Hours may be any size; minutes and seconds must be below 60. The last
+/// part may have a fraction, and a leading `-` makes the duration negative.
+///
+/// # Arguments
+///
+/// * `text` - The duration text
+///
+/// # Returns
+///
+/// * `Ok(seconds)` - The duration in seconds
+/// * `Err(error)` - An invalid input error if the text is not a duration
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::calculator::datetime::{format_duration, parse_duration};
+///
+/// let total = parse_duration("1:45:30").unwrap() + parse_duration("0:20:45").unwrap();
+/// assert_eq!(format_duration(total), "2:06:15");
+/// ```
+pub fn parse_duration(text: &str) -> CalcResult<f64> {
+    let invalid = || CalculatorError::invalid_input(Some(&format!("{} is not an h:m:s duration", text)));
+    let trimmed = text.trim();
+    let (sign, body) = match trimmed.strip_prefix('-') {
+        Some(rest) => (-1.0, rest),
+        None => (1.0, trimmed),
+    };
+
+    let parts: Vec<&str> = body.split(':').collect();
+    if !(2..=3).contains(&parts.len()) {
+        return Err(invalid());
+    }
+
+    let mut seconds = 0.0;
+    for (index, part) in parts.iter().enumerate() {
+        let is_last = index == parts.len() - 1;
+        let valid = !part.is_empty()
+            && part.chars().all(|c| c.is_ascii_digit() || (is_last && c == '.'));
+        if !valid {
+            return Err(invalid());
+        }
+        let value: f64 = part.parse().map_err(|_| invalid())?;
+        if index > 0 && value >= 60.0 {
+            return Err(CalculatorError::invalid_input(Some("minutes and seconds must be below 60")));
+        }
+        seconds = seconds * 60.0 + value;
+    }
+
+    // `h:m` has no seconds part
+    if parts.len() == 2 {
+        seconds *= 60.0;
+    }
+    Ok(sign * seconds)
+}
+
+/// Formats a duration as `h:mm:ss`.
+///
+/// Fractions of a second are shown to the millisecond, without trailing
+/// zeros.
+///
+/// # Arguments
+///
+/// * `seconds` - The duration in seconds
+///
+/// # Returns
+///
+/// The formatted duration (e.g. `"2:06:15"` or `"-0:01:30.5"`)
+#[wasm_bindgen]
+pub fn format_duration(seconds: f64) -> String {
+    if !seconds.is_finite() {
+        return seconds.to_string();
+    }
+
+    let sign = if seconds < 0.0 { "-" } else { "" };
+    let millis = (seconds.abs() * 1000.0).round() as u64;
+    let (hours, minutes, whole, fraction) = (
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000,
+    );
+
+    if fraction == 0 {
+        format!("{}{}:{:02}:{:02}", sign, hours, minutes, whole)
+    } else {
+        let fraction = format!("{:03}", fraction);
+        format!("{}{}:{:02}:{:02}.{}", sign, hours, minutes, whole, fraction.trim_end_matches('0'))
+    }
+}
+
+/// Counts the days from one date to another from JavaScript.
+///
+/// # Arguments
+///
+/// * `from` - The start date (`YYYY-MM-DD`)
+/// * `to` - The end date
+///
+/// # Returns
+///
+/// The number of days, or an error message
+#[wasm_bindgen(js_name = days_between)]
+pub fn days_between_wasm(from: &str, to: &str) -> Result<f64, JsValue> {
+    days_between(from, to).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Adds a duration to a date from JavaScript.
+///
+/// # Arguments
+///
+/// * `date` - The date (`YYYY-MM-DD`)
+/// * `years` - The number of years
+/// * `months` - The number of months
+/// * `days` - The number of days
+///
+/// # Returns
+///
+/// The new date, or an error message
+#[wasm_bindgen(js_name = add_duration)]
+pub fn add_duration_wasm(date: &str, years: i32, months: i32, days: i32) -> Result<String, JsValue> {
+    add_duration(date, years, months, days).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Looks up the day of the week from JavaScript.
+///
+/// # Arguments
+///
+/// * `date` - The date (`YYYY-MM-DD`)
+///
+/// # Returns
+///
+/// The weekday, or an error message
+#[wasm_bindgen(js_name = weekday)]
+pub fn weekday_wasm(date: &str) -> Result<Weekday, JsValue> {
+    weekday(date).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Counts business days between two dates from JavaScript.
+///
+/// # Arguments
+///
+/// * `from` - The start date (`YYYY-MM-DD`)
+/// * `to` - The end date
+/// * `holidays` - Dates that are not business days
+///
+/// # Returns
+///
+/// The number of business days, or an error message
+#[wasm_bindgen(js_name = business_days)]
+pub fn business_days_wasm(from: &str, to: &str, holidays: Vec<String>) -> Result<f64, JsValue> {
+    business_days(from, to, &holidays).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Moves a date by a number of business days from JavaScript.
+///
+/// # Arguments
+///
+/// * `date` - The date (`YYYY-MM-DD`)
+/// * `days` - The number of business days
+/// * `holidays` - Dates that are not business days
+///
+/// # Returns
+///
+/// The new date, or an error message
+#[wasm_bindgen(js_name = add_business_days)]
+pub fn add_business_days_wasm(date: &str, days: i32, holidays: Vec<String>) -> Result<String, JsValue> {
+    add_business_days(date, days, &holidays).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Parses an `h:m:s` duration from JavaScript.
+///
+/// # Arguments
+///
+/// * `text` - The duration text
+///
+/// # Returns
+///
+/// The duration in seconds, or an error message
+#[wasm_bindgen(js_name = parse_duration)]
+pub fn parse_duration_wasm(text: &str) -> Result<f64, JsValue> {
+    parse_duration(text).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Counts business days in the inclusive range between two day numbers,
+/// negated if the range runs backwards.
+pub(crate) fn count_business_days(from: i64, to: i64, holidays: &[i64]) -> i64 {
+    if from > to {
+        return -count_business_days(to, from, holidays);
+    }
+
+    let mut holidays: Vec<i64> = holidays
+        .iter()
+        .copied()
+        .filter(|day| (from..=to).contains(day) && is_business_day(*day))
+        .collect();
+    holidays.sort_unstable();
+    holidays.dedup();
+
+    weekdays_before(to + 1) - weekdays_before(from) - holidays.len() as i64
+}
+
+/// Moves a day number by a number of business days.
+pub(crate) fn offset_business_days(start: i64, days: i64, holidays: &[i64]) -> CalcResult<i64> {
+    if days.abs() > MAX_BUSINESS_DAY_OFFSET as i64 {
+        return Err(CalculatorError::invalid_input(Some(&format!(
+            "business day offset must be at most {}",
+            MAX_BUSINESS_DAY_OFFSET
+        ))));
+    }
+
+    let step = days.signum();
+    let mut day = start;
+    let mut remaining = days.abs();
+    while remaining > 0 {
+        day += step;
+        if is_business_day(day) && !holidays.contains(&day) {
+            remaining -= 1;
+        }
+    }
+    Ok(day)
+}
+
+/// Checks whether a day number falls on Monday to Friday.
+fn is_business_day(day: i64) -> bool {
+    (day + 3).rem_euclid(7) < 5
+}
+
+/// Counts the weekdays from the Monday 1969-12-29 up to (not including) a
+/// day number, negated for days before it.
+fn weekdays_before(day: i64) -> i64 {
+    let since_monday = day + 3;
+    since_monday.div_euclid(7) * 5 + since_monday.rem_euclid(7).min(5)
+}
+
+/// Parses a holiday list into day numbers.
+fn parse_holidays(holidays: &[String]) -> CalcResult<Vec<i64>> {
+    holidays.iter().map(|holiday| Date::parse(holiday).map(|date| date.day_number())).collect()
+}
+
+/// Checks whether a year is a leap year.
+fn is_leap_year(year: i32) -> bool {
+    // Dates are limited to positive years
+    let year = year as u32;
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+/// Gets the number of days in a month.
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
@@ -0,0 +1,744 @@
+//! Calculator financial module.
+//!
+//! This module provides time-value-of-money calculations:
+//! - A TVM worksheet (N, I/Y, PV, PMT, FV, P/Y, C/Y, begin/end mode) that
+//!   solves for any one unknown
+//! - Amortization of a range of payments, or a full schedule
+//! - Net present value and internal rate of return of cash flows
+//! - Simple and compound interest, and nominal/effective rate conversion
+//!
+//! As on a financial calculator, cash paid out is negative and cash
+//! received is positive, so a loan has a positive PV and a negative PMT.
+//! All rates are percentages (`6` means 6%).
+//!
+//! All operations are exposed to JavaScript through WebAssembly bindings.
+
+use wasm_bindgen::prelude::*;
+use crate::calculator::operations::{check_result, checked_add};
+use crate::errors::{CalculatorError, CalcResult};
+
+/// The smallest growth factor per period searched for a rate, as a power of 10.
+const MIN_GROWTH_EXPONENT: f64 = -3.0;
+/// The largest growth factor per period searched for a rate, as a power of 10.
+const MAX_GROWTH_EXPONENT: f64 = 3.0;
+/// The number of grid points used to bracket a rate.
+const RATE_GRID_POINTS: usize = 600;
+/// Iteration cap for bisection.
+const MAX_ITERATIONS: usize = 200;
+/// The largest payment number that can be amortized or listed.
+const MAX_PAYMENTS: u32 = 100_000;
+
+/// Represents when payments are made within each period.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PaymentTiming {
+    /// Payments at the end of each period (ordinary annuity)
+    End,
+    /// Payments at the beginning of each period (annuity due)
+    Begin,
+}
+
+/// Represents a variable of the TVM worksheet that can be solved for.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TvmVariable {
+    /// Number of payments (N)
+    Periods,
+    /// Nominal annual interest rate in percent (I/Y)
+    InterestRate,
+    /// Present value (PV)
+    PresentValue,
+    /// Payment per period (PMT)
+    Payment,
+    /// Future value (FV)
+    FutureValue,
+}
+
+/// The time-value-of-money worksheet.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TvmWorksheet {
+    /// Number of payments
+    n: f64,
+    /// Nominal annual interest rate in percent
+    i_y: f64,
+    /// Present value
+    pv: f64,
+    /// Payment per period
+    pmt: f64,
+    /// Future value
+    fv: f64,
+    /// Payments per year
+    p_y: f64,
+    /// Compounding periods per year
+    c_y: f64,
+    /// When payments are made
+    timing: PaymentTiming,
+}
+
+/// Amortization of one payment or a range of payments.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Amortization {
+    /// The first payment of the range (1-based)
+    first: u32,
+    /// The last payment of the range
+    last: u32,
+    /// The principal repaid over the range
+    principal: f64,
+    /// The interest paid over the range
+    interest: f64,
+    /// The balance after the last payment
+    balance: f64,
+}
+
+impl Default for TvmWorksheet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Constructor, getters and setters for TvmWorksheet
+#[wasm_bindgen]
+impl TvmWorksheet {
+    /// Creates a worksheet with all values 0, one payment and one
+    /// compounding period per year, and end-of-period payments.
+    ///
+    /// # Returns
+    ///
+    /// A new `TvmWorksheet` instance
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self { n: 0.0, i_y: 0.0, pv: 0.0, pmt: 0.0, fv: 0.0, p_y: 1.0, c_y: 1.0, timing: PaymentTiming::End }
+    }
+
+    /// Gets the number of payments (N).
+    pub fn n(&self) -> f64 {
+        self.n
+    }
+
+    /// Sets the number of payments (N).
+    pub fn set_n(&mut self, n: f64) {
+        self.n = n;
+    }
+
+    /// Gets the nominal annual interest rate in percent (I/Y).
+    pub fn i_y(&self) -> f64 {
+        self.i_y
+    }
+
+    /// Sets the nominal annual interest rate in percent (I/Y).
+    pub fn set_i_y(&mut self, i_y: f64) {
+        self.i_y = i_y;
+    }
+
+    /// Gets the present value (PV).
+    pub fn pv(&self) -> f64 {
+        self.pv
+    }
+
+    /// Sets the present value (PV).
+    pub fn set_pv(&mut self, pv: f64) {
+        self.pv = pv;
+    }
+
+    /// Gets the payment per period (PMT).
+    pub fn pmt(&self) -> f64 {
+        self.pmt
+    }
+
+    /// Sets the payment per period (PMT).
+    pub fn set_pmt(&mut self, pmt: f64) {
+        self.pmt = pmt;
+    }
+
+    /// Gets the future value (FV).
+    pub fn fv(&self) -> f64 {
+        self.fv
+    }
+
+    /// Sets the future value (FV).
+    pub fn set_fv(&mut self, fv: f64) {
+        self.fv = fv;
+    }
+
+    /// Gets the number of payments per year (P/Y).
+    pub fn p_y(&self) -> f64 {
+        self.p_y
+    }
+
+    /// Sets the number of payments per year (P/Y).
+    ///
+    /// As on a financial calculator, this also sets C/Y to the same value.
+    pub fn set_p_y(&mut self, p_y: f64) {
+        self.p_y = p_y;
+        self.c_y = p_y;
+    }
+
+    /// Gets the number of compounding periods per year (C/Y).
+    pub fn c_y(&self) -> f64 {
+        self.c_y
+    }
+
+    /// Sets the number of compounding periods per year (C/Y).
+    pub fn set_c_y(&mut self, c_y: f64) {
+        self.c_y = c_y;
+    }
+
+    /// Gets when payments are made.
+    pub fn timing(&self) -> PaymentTiming {
+        self.timing
+    }
+
+    /// Sets when payments are made.
+    pub fn set_timing(&mut self, timing: PaymentTiming) {
+        self.timing = timing;
+    }
+
+    /// Solves for one variable from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `variable` - The variable to solve for
+    ///
+    /// # Returns
+    ///
+    /// The solved value, or an error message
+    #[wasm_bindgen(js_name = solve)]
+    pub fn solve_wasm(&mut self, variable: TvmVariable) -> Result<f64, JsValue> {
+        self.solve(variable).map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Amortizes a range of payments from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - The first payment (1-based)
+    /// * `last` - The last payment
+    ///
+    /// # Returns
+    ///
+    /// The amortization of the range, or an error message
+    #[wasm_bindgen(js_name = amortize)]
+    pub fn amortize_wasm(&self, first: u32, last: u32) -> Result<Amortization, JsValue> {
+        self.amortize(first, last).map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Builds the amortization schedule from JavaScript.
+    ///
+    /// # Returns
+    ///
+    /// One entry per payment, or an error message
+    #[wasm_bindgen(js_name = schedule)]
+    pub fn schedule_wasm(&self) -> Result<Vec<Amortization>, JsValue> {
+        self.schedule().map_err(|e| JsValue::from_str(e.message()))
+    }
+}
+
+impl TvmWorksheet {
+    /// Solves for one variable and stores the result in the worksheet.
+    ///
+    /// # Arguments
+    ///
+    /// * `variable` - The variable to solve for
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(value)` - The solved value
+    /// * `Err(error)` - An invalid input error if P/Y or C/Y is not positive
+    ///   or N is 0 where it divides, or a no solution error if no value of
+    ///   the variable balances the cash flows
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::calculator::financial::{TvmVariable, TvmWorksheet};
+    ///
+    /// // A 30-year, $200,000 mortgage at 6% with monthly payments
+    /// let mut tvm = TvmWorksheet::new();
+    /// tvm.set_n(360.0);
+    /// tvm.set_i_y(6.0);
+    /// tvm.set_p_y(12.0);
+    /// tvm.set_pv(200_000.0);
+    /// let pmt = tvm.solve(TvmVariable::Payment).unwrap();
+    /// assert!((pmt + 1199.10).abs() < 0.01);
+    /// ```
+    pub fn solve(&mut self, variable: TvmVariable) -> CalcResult {
+        if !(self.p_y > 0.0 && self.c_y > 0.0) {
+            return Err(CalculatorError::invalid_input(Some("P/Y and C/Y must be positive")));
+        }
+
+        let value = match variable {
+            TvmVariable::Periods => self.solve_periods()?,
+            TvmVariable::InterestRate => self.solve_rate()?,
+            TvmVariable::PresentValue => {
+                let i = self.periodic_rate();
+                let (growth, annuity) = factors(i, self.n);
+                -(self.pmt * self.due_factor(i) * annuity + self.fv) / growth
+            }
+            TvmVariable::Payment => {
+                let i = self.periodic_rate();
+                let (growth, annuity) = factors(i, self.n);
+                if annuity == 0.0 {
+                    return Err(CalculatorError::invalid_input(Some("N must not be 0")));
+                }
+                -(self.pv * growth + self.fv) / (self.due_factor(i) * annuity)
+            }
+            TvmVariable::FutureValue => {
+                let i = self.periodic_rate();
+                let (growth, annuity) = factors(i, self.n);
+                -(self.pv * growth + self.pmt * self.due_factor(i) * annuity)
+            }
+        };
+
+        if !value.is_finite() {
+            return Err(CalculatorError::no_solution(None));
+        }
+
+        match variable {
+            TvmVariable::Periods => self.n = value,
+            TvmVariable::InterestRate => self.i_y = value,
+            TvmVariable::PresentValue => self.pv = value,
+            TvmVariable::Payment => self.pmt = value,
+            TvmVariable::FutureValue => self.fv = value,
+        }
+        Ok(value)
+    }
+
+    /// Amortizes a range of payments using the worksheet's PV, PMT and rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - The first payment (1-based)
+    /// * `last` - The last payment
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(amortization)` - The principal and interest paid over the range
+    ///   and the balance after it, with the worksheet's sign convention
+    /// * `Err(error)` - An invalid input error if the range is empty,
+    ///   starts at 0 or ends after payment 100000, or an overflow error if
+    ///   the balance or a total grows too large to represent
+    pub fn amortize(&self, first: u32, last: u32) -> CalcResult<Amortization> {
+        if first == 0 || last < first {
+            return Err(CalculatorError::invalid_input(Some(
+                "payment range must start at 1 or later and not be empty",
+            )));
+        }
+        if last > MAX_PAYMENTS {
+            return Err(CalculatorError::invalid_input(Some("payment range must end by payment 100000")));
+        }
+
+        let i = self.periodic_rate();
+        let mut balance = self.pv;
+        let mut principal = 0.0;
+        let mut interest = 0.0;
+
+        for period in 1..=last {
+            let (period_principal, period_interest) = self.split_payment(period, balance, i)?;
+            balance = checked_add(balance, period_principal)?;
+
+            if period >= first {
+                principal = checked_add(principal, period_principal)?;
+                interest = checked_add(interest, period_interest)?;
+            }
+        }
+
+        Ok(Amortization { first, last, principal, interest, balance })
+    }
+
+    /// Builds the amortization schedule for all N payments.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(schedule)` - One entry per payment (N rounded up)
+    /// * `Err(error)` - An invalid input error if N is not positive or is
+    ///   too large to list, or an overflow error if the balance grows too
+    ///   large to represent
+    pub fn schedule(&self) -> CalcResult<Vec<Amortization>> {
+        if !(self.n > 0.0 && self.n <= MAX_PAYMENTS as f64) {
+            return Err(CalculatorError::invalid_input(Some("N must be between 1 and 100000")));
+        }
+
+        let i = self.periodic_rate();
+        let mut balance = self.pv;
+        let periods = self.n.ceil() as u32;
+        let mut schedule = Vec::with_capacity(periods as usize);
+        for period in 1..=periods {
+            let (principal, interest) = self.split_payment(period, balance, i)?;
+            balance = checked_add(balance, principal)?;
+            schedule.push(Amortization { first: period, last: period, principal, interest, balance });
+        }
+        Ok(schedule)
+    }
+
+    /// Splits one payment into principal and interest, given the balance
+    /// before it and the periodic rate.
+    fn split_payment(&self, period: u32, balance: f64, i: f64) -> CalcResult<(f64, f64)> {
+        // With payments in advance, the first payment carries no interest
+        let interest = if period == 1 && self.timing == PaymentTiming::Begin {
+            0.0
+        } else {
+            check_result(-balance * i, false)?
+        };
+        Ok((checked_add(self.pmt, -interest)?, interest))
+    }
+
+    /// Gets the interest rate per payment period as a fraction.
+    fn periodic_rate(&self) -> f64 {
+        let per_compounding = self.i_y / 100.0 / self.c_y;
+        ((self.c_y / self.p_y) * per_compounding.ln_1p()).exp_m1()
+    }
+
+    /// Gets the factor `1 + i·k` for payments in advance (k = 1) or in
+    /// arrears (k = 0).
+    fn due_factor(&self, i: f64) -> f64 {
+        match self.timing {
+            PaymentTiming::Begin => 1.0 + i,
+            PaymentTiming::End => 1.0,
+        }
+    }
+
+    /// Gets the amount by which the cash flows fail to balance at rate i.
+    fn imbalance(&self, i: f64) -> f64 {
+        let (growth, annuity) = factors(i, self.n);
+        self.pv * growth + self.pmt * self.due_factor(i) * annuity + self.fv
+    }
+
+    /// Solves for N in closed form.
+    fn solve_periods(&self) -> CalcResult {
+        let i = self.periodic_rate();
+        if i == 0.0 {
+            if self.pmt == 0.0 {
+                return Err(CalculatorError::no_solution(Some("PMT must not be 0 at 0% interest")));
+            }
+            return non_negative_periods(-(self.pv + self.fv) / self.pmt);
+        }
+
+        let payment = self.pmt * self.due_factor(i);
+        let growth = (payment - self.fv * i) / (payment + self.pv * i);
+        if !(growth > 0.0 && growth.is_finite()) {
+            return Err(CalculatorError::no_solution(Some("the cash flows never balance")));
+        }
+        non_negative_periods(growth.ln() / i.ln_1p())
+    }
+
+    /// Solves for I/Y by bracketing and bisection.
+    fn solve_rate(&self) -> CalcResult {
+        let i = find_rate(|i| self.imbalance(i)).ok_or_else(|| {
+            CalculatorError::no_solution(Some("no interest rate balances the cash flows"))
+        })?;
+        let per_compounding = ((self.p_y / self.c_y) * i.ln_1p()).exp_m1();
+        Ok(per_compounding * self.c_y * 100.0)
+    }
+}
+
+/// Getters for Amortization
+#[wasm_bindgen]
+impl Amortization {
+    /// Gets the first payment of the range (1-based).
+    pub fn first(&self) -> u32 {
+        self.first
+    }
+
+    /// Gets the last payment of the range.
+    pub fn last(&self) -> u32 {
+        self.last
+    }
+
+    /// Gets the principal repaid over the range.
+    pub fn principal(&self) -> f64 {
+        self.principal
+    }
+
+    /// Gets the interest paid over the range.
+    pub fn interest(&self) -> f64 {
+        self.interest
+    }
+
+    /// Gets the balance after the last payment.
+    pub fn balance(&self) -> f64 {
+        self.balance
+    }
+}
+
+/// Computes the net present value of cash flows.
+///
+/// # Arguments
+///
+/// * `rate` - The discount rate per period in percent
+/// * `cash_flows` - The cash flows, the first at time 0 and then one per period
+///
+/// # Returns
+///
+/// * `Ok(npv)` - The net present value
+/// * `Err(error)` - An invalid input error if there are no cash flows or
+///   the rate is -100% or less
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::calculator::financial::npv;
+///
+/// let value = npv(10.0, &[-1000.0, 500.0, 500.0, 500.0]).unwrap();
+/// assert!((value - 243.43).abs() < 0.01);
+/// ```
+pub fn npv(rate: f64, cash_flows: &[f64]) -> CalcResult {
+    if cash_flows.is_empty() {
+        return Err(CalculatorError::invalid_input(Some("there are no cash flows")));
+    }
+    if !(rate > -100.0 && rate.is_finite()) {
+        return Err(CalculatorError::invalid_input(Some("rate must be greater than -100%")));
+    }
+    Ok(present_value(rate / 100.0, cash_flows))
+}
+
+/// Computes the internal rate of return of cash flows.
+///
+/// If the cash flows have several rates of return, the one closest to 0
+/// is returned.
+///
+/// # Arguments
+///
+/// * `cash_flows` - The cash flows, the first at time 0 and then one per period
+///
+/// # Returns
+///
+/// * `Ok(irr)` - The rate per period in percent at which the NPV is 0
+/// * `Err(error)` - A no solution error if no rate makes the NPV 0 (for
+///   example, when all cash flows have the same sign)
+pub fn irr(cash_flows: &[f64]) -> CalcResult {
+    if cash_flows.is_empty() {
+        return Err(CalculatorError::invalid_input(Some("there are no cash flows")));
+    }
+    let has_inflow = cash_flows.iter().any(|flow| *flow > 0.0);
+    let has_outflow = cash_flows.iter().any(|flow| *flow < 0.0);
+    if !(has_inflow && has_outflow) {
+        return Err(CalculatorError::no_solution(Some(
+            "cash flows must include both inflows and outflows",
+        )));
+    }
+
+    find_rate(|rate| present_value(rate, cash_flows))
+        .map(|rate| rate * 100.0)
+        .ok_or_else(|| CalculatorError::no_solution(Some("no rate makes the NPV zero")))
+}
+
+/// Computes simple interest.
+///
+/// # Arguments
+///
+/// * `principal` - The amount invested or borrowed
+/// * `rate` - The annual rate in percent
+/// * `years` - The time in years
+///
+/// # Returns
+///
+/// The interest earned, `principal · rate · years`
+pub fn simple_interest(principal: f64, rate: f64, years: f64) -> f64 {
+    principal * rate / 100.0 * years
+}
+
+/// Computes the value of an amount with compound interest.
+///
+/// # Arguments
+///
+/// * `principal` - The amount invested or borrowed
+/// * `rate` - The nominal annual rate in percent
+/// * `compounds_per_year` - The number of compounding periods per year
+/// * `years` - The time in years
+///
+/// # Returns
+///
+/// * `Ok(value)` - The principal plus interest
+/// * `Err(error)` - An invalid input error if the compounding frequency is
+///   not positive
+pub fn compound_amount(principal: f64, rate: f64, compounds_per_year: f64, years: f64) -> CalcResult {
+    check_frequency(compounds_per_year)?;
+    let growth = (compounds_per_year * years * (rate / 100.0 / compounds_per_year).ln_1p()).exp();
+    Ok(principal * growth)
+}
+
+/// Converts a nominal annual rate to an effective annual rate.
+///
+/// # Arguments
+///
+/// * `nominal` - The nominal annual rate in percent
+/// * `compounds_per_year` - The number of compounding periods per year
+///
+/// # Returns
+///
+/// * `Ok(rate)` - The effective annual rate in percent
+/// * `Err(error)` - An invalid input error if the compounding frequency is
+///   not positive
+pub fn effective_rate(nominal: f64, compounds_per_year: f64) -> CalcResult {
+    check_frequency(compounds_per_year)?;
+    Ok((compounds_per_year * (nominal / 100.0 / compounds_per_year).ln_1p()).exp_m1() * 100.0)
+}
+
+/// Converts an effective annual rate to a nominal annual rate.
+///
+/// # Arguments
+///
+/// * `effective` - The effective annual rate in percent
+/// * `compounds_per_year` - The number of compounding periods per year
+///
+/// # Returns
+///
+/// * `Ok(rate)` - The nominal annual rate in percent
+/// * `Err(error)` - An invalid input error if the compounding frequency is
+///   not positive
+pub fn nominal_rate(effective: f64, compounds_per_year: f64) -> CalcResult {
+    check_frequency(compounds_per_year)?;
+    Ok(((effective / 100.0).ln_1p() / compounds_per_year).exp_m1() * compounds_per_year * 100.0)
+}
+
+/// Computes the net present value of cash flows from JavaScript.
+///
+/// # Arguments
+///
+/// * `rate` - The discount rate per period in percent
+/// * `cash_flows` - The cash flows, the first at time 0
+///
+/// # Returns
+///
+/// The net present value, or an error message
+#[wasm_bindgen(js_name = npv)]
+pub fn npv_wasm(rate: f64, cash_flows: &[f64]) -> Result<f64, JsValue> {
+    npv(rate, cash_flows).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Computes the internal rate of return of cash flows from JavaScript.
+///
+/// # Arguments
+///
+/// * `cash_flows` - The cash flows, the first at time 0
+///
+/// # Returns
+///
+/// The rate per period in percent, or an error message
+#[wasm_bindgen(js_name = irr)]
+pub fn irr_wasm(cash_flows: &[f64]) -> Result<f64, JsValue> {
+    irr(cash_flows).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Computes simple interest from JavaScript.
+#[wasm_bindgen(js_name = simple_interest)]
+pub fn simple_interest_wasm(principal: f64, rate: f64, years: f64) -> f64 {
+    simple_interest(principal, rate, years)
+}
+
+/// Computes the value of an amount with compound interest from JavaScript.
+#[wasm_bindgen(js_name = compound_amount)]
+pub fn compound_amount_wasm(principal: f64, rate: f64, compounds_per_year: f64, years: f64) -> Result<f64, JsValue> {
+    compound_amount(principal, rate, compounds_per_year, years).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Converts a nominal annual rate to an effective one from JavaScript.
+#[wasm_bindgen(js_name = effective_rate)]
+pub fn effective_rate_wasm(nominal: f64, compounds_per_year: f64) -> Result<f64, JsValue> {
+    effective_rate(nominal, compounds_per_year).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Converts an effective annual rate to a nominal one from JavaScript.
+#[wasm_bindgen(js_name = nominal_rate)]
+pub fn nominal_rate_wasm(effective: f64, compounds_per_year: f64) -> Result<f64, JsValue> {
+    nominal_rate(effective, compounds_per_year).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Gets the growth factor `(1 + i)^n` and the annuity factor
+/// `((1 + i)^n - 1) / i`, which is `n` at `i = 0`.
+fn factors(i: f64, n: f64) -> (f64, f64) {
+    let exponent = n * i.ln_1p();
+    let annuity = if i == 0.0 { n } else { exponent.exp_m1() / i };
+    (exponent.exp(), annuity)
+}
+
+/// Rejects a negative number of periods, which would mean the cash flows
+/// only balance before they start.
+fn non_negative_periods(n: f64) -> CalcResult {
+    if n >= 0.0 {
+        Ok(n)
+    } else {
+        Err(CalculatorError::no_solution(Some("the cash flows never balance")))
+    }
+}
+
+/// Discounts cash flows at a rate given as a fraction.
+fn present_value(rate: f64, cash_flows: &[f64]) -> f64 {
+    let discount = 1.0 / (1.0 + rate);
+    cash_flows.iter().rev().fold(0.0, |sum, flow| sum * discount + flow)
+}
+
+/// Checks a compounding frequency.
+fn check_frequency(compounds_per_year: f64) -> CalcResult<()> {
+    if compounds_per_year > 0.0 && compounds_per_year.is_finite() {
+        Ok(())
+    } else {
+        Err(CalculatorError::invalid_input(Some("compounding frequency must be positive")))
+    }
+}
+
+/// Finds a root of `f` over periodic rates, preferring the root closest to 0.
+///
+/// Scans growth factors `1 + i` from 0.001 to 1000 on a logarithmic grid
+/// for sign changes and exact zeros, then bisects the bracket nearest to
+/// `i = 0`.
+///
+/// # Returns
+///
+/// The rate as a fraction, or `None` if `f` never changes sign
+fn find_rate(f: impl Fn(f64) -> f64) -> Option<f64> {
+    let step = (MAX_GROWTH_EXPONENT - MIN_GROWTH_EXPONENT) / RATE_GRID_POINTS as f64;
+    let rate_at = |k: usize| 10f64.powf(MIN_GROWTH_EXPONENT + k as f64 * step) - 1.0;
+
+    let mut best: Option<(f64, f64)> = None;
+    let mut previous = (rate_at(0), f(rate_at(0)));
+    for k in 1..=RATE_GRID_POINTS {
+        let rate = rate_at(k);
+        let value = f(rate);
+        // An exact zero is a bracket of its own, and the sign changes next
+        // to it lead to the same root
+        let bracket = if value == 0.0 {
+            Some((rate, rate))
+        } else if value.is_finite()
+            && previous.1.is_finite()
+            && previous.1 != 0.0
+            && (value < 0.0) != (previous.1 < 0.0)
+        {
+            Some((previous.0, rate))
+        } else {
+            None
+        };
+        if let Some(bracket) = bracket {
+            if best.is_none_or(|best| distance_from_zero(bracket) < distance_from_zero(best)) {
+                best = Some(bracket);
+            }
+        }
+        previous = (rate, value);
+    }
+
+    let (mut lower, mut upper) = best?;
+    let lower_sign = f(lower) < 0.0;
+    for _ in 0..MAX_ITERATIONS {
+        let middle = lower + (upper - lower) / 2.0;
+        if middle <= lower || middle >= upper {
+            break;
+        }
+        if (f(middle) < 0.0) == lower_sign {
+            lower = middle;
+        } else {
+            upper = middle;
+        }
+    }
+    Some(lower + (upper - lower) / 2.0)
+}
+
+/// Gets how far a bracket of rates is from 0 (0 if it contains 0).
+fn distance_from_zero((lower, upper): (f64, f64)) -> f64 {
+    if lower <= 0.0 && upper >= 0.0 {
+        0.0
+    } else {
+        lower.abs().min(upper.abs())
+    }
+}
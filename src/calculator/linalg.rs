@@ -0,0 +1,1023 @@
+//! Calculator linear algebra module.
+//!
+//! This module provides vectors and matrices of real numbers:
+//! - Element-wise addition, subtraction and multiplication, and scaling
+//! - Dot and cross products and the Euclidean norm of vectors
+//! - Matrix multiplication, transpose, determinant, inverse and rank
+//! - Solving linear systems `A x = b`
+//!
+//! Matrices are stored row by row. Determinants, inverses and solutions use
+//! an LU decomposition with partial pivoting; a pivot that is negligible
+//! next to the matrix's largest entry (within `n · ε` of it) makes the
+//! matrix singular. Matrices have at most `MAX_DIMENSION` rows and
+//! columns.
+//!
+//! All operations are exposed to JavaScript through WebAssembly bindings.
+
+use std::fmt;
+use wasm_bindgen::prelude::*;
+use crate::calculator::operations::check_result;
+use crate::errors::{CalculatorError, CalcResult};
+use crate::state::format_significant;
+
+/// The number of significant digits shown, which hides rounding noise
+/// from elimination such as 0.6000000000000001.
+const DISPLAY_DIGITS: usize = 15;
+
+/// The largest number of rows or columns a matrix may have.
+pub const MAX_DIMENSION: usize = 1000;
+
+/// Represents a vector of real numbers.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vector {
+    /// The components
+    values: Vec<f64>,
+}
+
+/// Constructor, getters and operations for Vector
+#[wasm_bindgen]
+impl Vector {
+    /// Creates a vector from its components.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The components
+    ///
+    /// # Returns
+    ///
+    /// A new `Vector` instance
+    #[wasm_bindgen(constructor)]
+    pub fn new(values: &[f64]) -> Self {
+        Self { values: values.to_vec() }
+    }
+
+    /// Gets the number of components.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Checks whether the vector has no components.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Gets the components.
+    pub fn values(&self) -> Vec<f64> {
+        self.values.clone()
+    }
+
+    /// Gets the Euclidean norm (length).
+    pub fn norm(&self) -> f64 {
+        self.values.iter().fold(0.0, |norm: f64, value| norm.hypot(*value))
+    }
+
+    /// Adds a vector from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The vector to add
+    ///
+    /// # Returns
+    ///
+    /// The sum, or an error message
+    #[wasm_bindgen(js_name = add)]
+    pub fn add_wasm(&self, other: &Vector) -> Result<Vector, JsValue> {
+        self.add(other).map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Subtracts a vector from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The vector to subtract
+    ///
+    /// # Returns
+    ///
+    /// The difference, or an error message
+    #[wasm_bindgen(js_name = subtract)]
+    pub fn subtract_wasm(&self, other: &Vector) -> Result<Vector, JsValue> {
+        self.subtract(other).map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Multiplies by a vector component by component from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The vector to multiply by
+    ///
+    /// # Returns
+    ///
+    /// The element-wise product, or an error message
+    #[wasm_bindgen(js_name = multiply_elements)]
+    pub fn multiply_elements_wasm(&self, other: &Vector) -> Result<Vector, JsValue> {
+        self.multiply_elements(other).map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Scales the vector from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `factor` - The scale factor
+    ///
+    /// # Returns
+    ///
+    /// The scaled vector, or an error message
+    #[wasm_bindgen(js_name = scale)]
+    pub fn scale_wasm(&self, factor: f64) -> Result<Vector, JsValue> {
+        self.scale(factor).map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Computes the dot product from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other vector
+    ///
+    /// # Returns
+    ///
+    /// The dot product, or an error message
+    #[wasm_bindgen(js_name = dot)]
+    pub fn dot_wasm(&self, other: &Vector) -> Result<f64, JsValue> {
+        self.dot(other).map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Computes the cross product from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other three-dimensional vector
+    ///
+    /// # Returns
+    ///
+    /// The cross product, or an error message
+    #[wasm_bindgen(js_name = cross)]
+    pub fn cross_wasm(&self, other: &Vector) -> Result<Vector, JsValue> {
+        self.cross(other).map_err(|e| JsValue::from_str(e.message()))
+    }
+}
+
+impl Vector {
+    /// Adds a vector.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The vector to add
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(sum)` - The sum
+    /// * `Err(error)` - An invalid input error if the lengths differ, or an
+    ///   overflow error
+    pub fn add(&self, other: &Vector) -> CalcResult<Vector> {
+        self.zip(other, "add", |a, b| a + b)
+    }
+
+    /// Subtracts a vector.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The vector to subtract
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(difference)` - The difference
+    /// * `Err(error)` - An invalid input error if the lengths differ, or an
+    ///   overflow error
+    pub fn subtract(&self, other: &Vector) -> CalcResult<Vector> {
+        self.zip(other, "subtract", |a, b| a - b)
+    }
+
+    /// Multiplies by a vector component by component.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The vector to multiply by
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(product)` - The element-wise product
+    /// * `Err(error)` - An invalid input error if the lengths differ, or an
+    ///   overflow error
+    pub fn multiply_elements(&self, other: &Vector) -> CalcResult<Vector> {
+        self.zip(other, "multiply", |a, b| a * b)
+    }
+
+    /// Multiplies every component by a factor.
+    ///
+    /// # Arguments
+    ///
+    /// * `factor` - The scale factor
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(vector)` - The scaled vector
+    /// * `Err(error)` - An overflow error
+    pub fn scale(&self, factor: f64) -> CalcResult<Vector> {
+        checked(self.values.iter().map(|value| value * factor).collect()).map(|values| Self { values })
+    }
+
+    /// Computes the dot product.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other vector
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(product)` - The sum of the products of the components
+    /// * `Err(error)` - An invalid input error if the lengths differ, or an
+    ///   overflow error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::calculator::linalg::Vector;
+    ///
+    /// let a = Vector::new(&[1.0, 2.0, 3.0]);
+    /// assert_eq!(a.dot(&Vector::new(&[4.0, 5.0, 6.0])).unwrap(), 32.0);
+    /// assert!(a.dot(&Vector::new(&[1.0])).is_err());
+    /// ```
+    pub fn dot(&self, other: &Vector) -> CalcResult {
+        let products = self.multiply_elements(other)?;
+        Ok(checked(vec![products.values.iter().sum()])?[0])
+    }
+
+    /// Computes the cross product of three-dimensional vectors.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other vector
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(product)` - The vector perpendicular to both
+    /// * `Err(error)` - An invalid input error unless both vectors have
+    ///   three components, or an overflow error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::calculator::linalg::Vector;
+    ///
+    /// let x = Vector::new(&[1.0, 0.0, 0.0]);
+    /// let y = Vector::new(&[0.0, 1.0, 0.0]);
+    /// assert_eq!(x.cross(&y).unwrap().values(), vec![0.0, 0.0, 1.0]);
+    /// ```
+    pub fn cross(&self, other: &Vector) -> CalcResult<Vector> {
+        let ([a1, a2, a3], [b1, b2, b3]) = (self.values.as_slice(), other.values.as_slice()) else {
+            return Err(CalculatorError::invalid_input(Some("the cross product needs two vectors of length 3")));
+        };
+        let values = vec![a2 * b3 - a3 * b2, a3 * b1 - a1 * b3, a1 * b2 - a2 * b1];
+        checked(values).map(|values| Self { values })
+    }
+
+    /// Combines two vectors of the same length component by component.
+    fn zip(&self, other: &Vector, action: &str, f: impl Fn(f64, f64) -> f64) -> CalcResult<Vector> {
+        if self.len() != other.len() {
+            return Err(CalculatorError::invalid_input(Some(&format!(
+                "cannot {} vectors of lengths {} and {}",
+                action,
+                self.len(),
+                other.len()
+            ))));
+        }
+        let values = self.values.iter().zip(&other.values).map(|(a, b)| f(*a, *b)).collect();
+        checked(values).map(|values| Self { values })
+    }
+}
+
+impl fmt::Display for Vector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}]", join(&self.values))
+    }
+}
+
+/// Represents a matrix of real numbers.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix {
+    /// The number of rows
+    rows: usize,
+    /// The number of columns
+    cols: usize,
+    /// The entries, row by row
+    data: Vec<f64>,
+}
+
+/// Constructor, getters and operations for Matrix
+#[wasm_bindgen]
+impl Matrix {
+    /// Creates a matrix from its shape and entries from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - The number of rows
+    /// * `cols` - The number of columns
+    /// * `data` - The entries, row by row, as a `Float64Array`
+    ///
+    /// # Returns
+    ///
+    /// The matrix, or an error message if the shape does not fit the entries
+    #[wasm_bindgen(constructor)]
+    pub fn new_wasm(rows: usize, cols: usize, data: &[f64]) -> Result<Matrix, JsValue> {
+        Self::new(rows, cols, data).map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Gets the number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Gets the number of columns.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Gets the shape as `[rows, cols]`.
+    pub fn shape(&self) -> Vec<u32> {
+        vec![self.rows as u32, self.cols as u32]
+    }
+
+    /// Gets the entries, row by row.
+    pub fn data(&self) -> Vec<f64> {
+        self.data.clone()
+    }
+
+    /// Gets one entry, or `undefined` outside the matrix.
+    pub fn get(&self, row: usize, col: usize) -> Option<f64> {
+        (row < self.rows && col < self.cols).then(|| self.data[row * self.cols + col])
+    }
+
+    /// Gets the transpose.
+    pub fn transpose(&self) -> Matrix {
+        let data = (0..self.cols)
+            .flat_map(|col| (0..self.rows).map(move |row| (row, col)))
+            .map(|(row, col)| self.data[row * self.cols + col])
+            .collect();
+        Self { rows: self.cols, cols: self.rows, data }
+    }
+
+    /// Adds a matrix from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The matrix to add
+    ///
+    /// # Returns
+    ///
+    /// The sum, or an error message
+    #[wasm_bindgen(js_name = add)]
+    pub fn add_wasm(&self, other: &Matrix) -> Result<Matrix, JsValue> {
+        self.add(other).map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Subtracts a matrix from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The matrix to subtract
+    ///
+    /// # Returns
+    ///
+    /// The difference, or an error message
+    #[wasm_bindgen(js_name = subtract)]
+    pub fn subtract_wasm(&self, other: &Matrix) -> Result<Matrix, JsValue> {
+        self.subtract(other).map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Multiplies by a matrix entry by entry from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The matrix to multiply by
+    ///
+    /// # Returns
+    ///
+    /// The element-wise product, or an error message
+    #[wasm_bindgen(js_name = multiply_elements)]
+    pub fn multiply_elements_wasm(&self, other: &Matrix) -> Result<Matrix, JsValue> {
+        self.multiply_elements(other).map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Scales the matrix from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `factor` - The scale factor
+    ///
+    /// # Returns
+    ///
+    /// The scaled matrix, or an error message
+    #[wasm_bindgen(js_name = scale)]
+    pub fn scale_wasm(&self, factor: f64) -> Result<Matrix, JsValue> {
+        self.scale(factor).map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Multiplies by a matrix from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The right-hand matrix
+    ///
+    /// # Returns
+    ///
+    /// The matrix product, or an error message
+    #[wasm_bindgen(js_name = multiply)]
+    pub fn multiply_wasm(&self, other: &Matrix) -> Result<Matrix, JsValue> {
+        self.multiply(other).map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Multiplies a vector from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `vector` - The vector, as a column
+    ///
+    /// # Returns
+    ///
+    /// The product, or an error message
+    #[wasm_bindgen(js_name = multiply_vector)]
+    pub fn multiply_vector_wasm(&self, vector: &Vector) -> Result<Vector, JsValue> {
+        self.multiply_vector(vector).map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Computes the determinant from JavaScript.
+    ///
+    /// # Returns
+    ///
+    /// The determinant, or an error message
+    #[wasm_bindgen(js_name = determinant)]
+    pub fn determinant_wasm(&self) -> Result<f64, JsValue> {
+        self.determinant().map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Computes the inverse from JavaScript.
+    ///
+    /// # Returns
+    ///
+    /// The inverse, or an error message
+    #[wasm_bindgen(js_name = inverse)]
+    pub fn inverse_wasm(&self) -> Result<Matrix, JsValue> {
+        self.inverse().map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Computes the rank.
+    pub fn rank(&self) -> usize {
+        let mut rows: Vec<Vec<f64>> = self.data.chunks(self.cols.max(1)).map(<[f64]>::to_vec).collect();
+        let tolerance = self.tolerance(self.rows.max(self.cols));
+        let mut rank = 0;
+        for col in 0..self.cols {
+            let Some(pivot) = (rank..self.rows).max_by(|&a, &b| rows[a][col].abs().total_cmp(&rows[b][col].abs())) else {
+                break;
+            };
+            if rows[pivot][col].abs() <= tolerance {
+                continue;
+            }
+            rows.swap(rank, pivot);
+            let (upper, lower) = rows.split_at_mut(rank + 1);
+            let pivot_row = &upper[rank];
+            for row in lower {
+                let factor = row[col] / pivot_row[col];
+                for (entry, above) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                    *entry -= factor * above;
+                }
+            }
+            rank += 1;
+        }
+        rank
+    }
+
+    /// Solves `A x = b` from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `b` - The right-hand side
+    ///
+    /// # Returns
+    ///
+    /// The solution `x`, or an error message
+    #[wasm_bindgen(js_name = solve)]
+    pub fn solve_wasm(&self, b: &Vector) -> Result<Vector, JsValue> {
+        self.solve(b).map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Formats the matrix as nested lists (e.g. `"[[1, 2], [3, 4]]"`).
+    pub fn format(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Matrix {
+    /// Creates a matrix from its shape and entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - The number of rows
+    /// * `cols` - The number of columns
+    /// * `data` - The entries, row by row
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(matrix)` - The matrix
+    /// * `Err(error)` - An invalid input error if a dimension is zero or
+    ///   above `MAX_DIMENSION`, or the number of entries is not `rows * cols`
+    pub fn new(rows: usize, cols: usize, data: &[f64]) -> CalcResult<Self> {
+        check_dimension(rows)?;
+        check_dimension(cols)?;
+        if rows == 0 || cols == 0 || rows.checked_mul(cols) != Some(data.len()) {
+            return Err(CalculatorError::invalid_input(Some(&format!(
+                "a {}×{} matrix cannot hold {} entries",
+                rows,
+                cols,
+                data.len()
+            ))));
+        }
+        Ok(Self { rows, cols, data: data.to_vec() })
+    }
+
+    /// Creates a matrix from its rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - The rows, which must all have the same length
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(matrix)` - The matrix
+    /// * `Err(error)` - An invalid input error if there are no entries or
+    ///   the rows differ in length
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::calculator::linalg::Matrix;
+    ///
+    /// let matrix = Matrix::from_rows(&[vec![1.0, 2.0], vec![3.0, 4.0]]).unwrap();
+    /// assert_eq!(matrix.determinant().unwrap(), -2.0);
+    /// assert!(Matrix::from_rows(&[vec![1.0, 2.0], vec![3.0]]).is_err());
+    /// ```
+    pub fn from_rows(rows: &[Vec<f64>]) -> CalcResult<Self> {
+        let cols = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != cols) {
+            return Err(CalculatorError::invalid_input(Some("matrix rows must have the same length")));
+        }
+        Self::new(rows.len(), cols, &rows.concat())
+    }
+
+    /// Creates an identity matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The number of rows and columns
+    ///
+    /// # Returns
+    ///
+    /// The `size`×`size` identity matrix
+    pub fn identity(size: usize) -> Self {
+        let data = (0..size * size).map(|i| if i % (size + 1) == 0 { 1.0 } else { 0.0 }).collect();
+        Self { rows: size, cols: size, data }
+    }
+
+    /// Creates a one-column matrix from a vector.
+    ///
+    /// # Arguments
+    ///
+    /// * `vector` - The vector
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(matrix)` - The column
+    /// * `Err(error)` - An invalid input error for an empty vector
+    pub fn column(vector: &Vector) -> CalcResult<Self> {
+        Self::new(vector.len(), 1, &vector.values)
+    }
+
+    /// Gets the entries of a one-row or one-column matrix as a vector.
+    ///
+    /// # Returns
+    ///
+    /// The vector, or `None` if the matrix has several rows and columns
+    pub fn as_vector(&self) -> Option<Vector> {
+        (self.rows == 1 || self.cols == 1).then(|| Vector { values: self.data.clone() })
+    }
+
+    /// Checks whether the matrix is square.
+    ///
+    /// # Returns
+    ///
+    /// `true` if it has as many rows as columns
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    /// Adds a matrix of the same shape.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The matrix to add
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(sum)` - The sum
+    /// * `Err(error)` - An invalid input error if the shapes differ, or an
+    ///   overflow error
+    pub fn add(&self, other: &Matrix) -> CalcResult<Matrix> {
+        self.zip(other, "add", |a, b| a + b)
+    }
+
+    /// Subtracts a matrix of the same shape.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The matrix to subtract
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(difference)` - The difference
+    /// * `Err(error)` - An invalid input error if the shapes differ, or an
+    ///   overflow error
+    pub fn subtract(&self, other: &Matrix) -> CalcResult<Matrix> {
+        self.zip(other, "subtract", |a, b| a - b)
+    }
+
+    /// Multiplies by a matrix of the same shape entry by entry (the
+    /// Hadamard product).
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The matrix to multiply by
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(product)` - The element-wise product
+    /// * `Err(error)` - An invalid input error if the shapes differ, or an
+    ///   overflow error
+    pub fn multiply_elements(&self, other: &Matrix) -> CalcResult<Matrix> {
+        self.zip(other, "multiply", |a, b| a * b)
+    }
+
+    /// Multiplies every entry by a factor.
+    ///
+    /// # Arguments
+    ///
+    /// * `factor` - The scale factor
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(matrix)` - The scaled matrix
+    /// * `Err(error)` - An overflow error
+    pub fn scale(&self, factor: f64) -> CalcResult<Matrix> {
+        let data = checked(self.data.iter().map(|value| value * factor).collect())?;
+        Ok(Self { data, ..*self })
+    }
+
+    /// Multiplies by a matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The right-hand matrix, with as many rows as this matrix
+    ///   has columns
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(product)` - The matrix product
+    /// * `Err(error)` - An invalid input error if the shapes do not fit, or
+    ///   an overflow error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::calculator::linalg::Matrix;
+    ///
+    /// let a = Matrix::from_rows(&[vec![1.0, 2.0], vec![3.0, 4.0]]).unwrap();
+    /// let b = Matrix::from_rows(&[vec![0.0, 1.0], vec![1.0, 0.0]]).unwrap();
+    /// assert_eq!(a.multiply(&b).unwrap().to_string(), "[[2, 1], [4, 3]]");
+    /// ```
+    pub fn multiply(&self, other: &Matrix) -> CalcResult<Matrix> {
+        if self.cols != other.rows {
+            return Err(CalculatorError::invalid_input(Some(&format!(
+                "cannot multiply a {} matrix by a {} matrix",
+                self.shape_string(),
+                other.shape_string()
+            ))));
+        }
+        let mut data = vec![0.0; self.rows * other.cols];
+        for row in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self.data[row * self.cols + k];
+                for col in 0..other.cols {
+                    data[row * other.cols + col] += a * other.data[k * other.cols + col];
+                }
+            }
+        }
+        Ok(Self { rows: self.rows, cols: other.cols, data: checked(data)? })
+    }
+
+    /// Multiplies a vector, taken as a column.
+    ///
+    /// # Arguments
+    ///
+    /// * `vector` - The vector, with as many components as this matrix has
+    ///   columns
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(product)` - The product
+    /// * `Err(error)` - An invalid input error if the lengths do not fit, or
+    ///   an overflow error
+    pub fn multiply_vector(&self, vector: &Vector) -> CalcResult<Vector> {
+        let product = self.multiply(&Self::column(vector)?)?;
+        Ok(Vector { values: product.data })
+    }
+
+    /// Raises a square matrix to an integer power by repeated squaring.
+    ///
+    /// # Arguments
+    ///
+    /// * `exponent` - The power; negative powers are powers of the inverse
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(power)` - The power, the identity for exponent 0
+    /// * `Err(error)` - An invalid input error if the matrix is not square,
+    ///   a singular matrix error for a negative power of a singular matrix,
+    ///   or an overflow error
+    pub fn power(&self, exponent: i32) -> CalcResult<Matrix> {
+        self.require_square("raise to a power")?;
+        let mut base = if exponent < 0 { self.inverse()? } else { self.clone() };
+        let mut remaining = exponent.unsigned_abs();
+        let mut result = Self::identity(self.rows);
+        while remaining > 0 {
+            if remaining & 1 == 1 {
+                result = result.multiply(&base)?;
+            }
+            remaining >>= 1;
+            if remaining > 0 {
+                base = base.multiply(&base)?;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Computes the determinant of a square matrix.
+    ///
+    /// # Returns
+    ///
+    /// The product of the pivots is kept as a mantissa and a power of two,
+    /// so it only overflows or underflows if the determinant itself does.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(determinant)` - The determinant, 0 for a singular matrix
+    /// * `Err(error)` - An invalid input error if the matrix is not square,
+    ///   or an overflow or underflow error
+    pub fn determinant(&self) -> CalcResult {
+        self.require_square("compute the determinant of")?;
+        let Some(lu) = self.decompose() else {
+            return Ok(0.0);
+        };
+        let (mut mantissa, mut exponent) = (lu.sign, 0);
+        for i in 0..self.rows {
+            let (pivot_mantissa, pivot_exponent) = split_binary(lu.data[i * self.rows + i]);
+            let (product, shift) = split_binary(mantissa * pivot_mantissa);
+            mantissa = product;
+            exponent += pivot_exponent + shift;
+        }
+        // Two steps, as 2^exponent alone may be out of range
+        let half = exponent / 2;
+        check_result(mantissa * 2f64.powi(half) * 2f64.powi(exponent - half), true)
+    }
+
+    /// Computes the inverse of a square matrix.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(inverse)` - The inverse
+    /// * `Err(error)` - An invalid input error if the matrix is not square,
+    ///   a singular matrix error if it has no inverse, or an overflow error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::calculator::linalg::Matrix;
+    ///
+    /// let matrix = Matrix::from_rows(&[vec![4.0, 7.0], vec![2.0, 6.0]]).unwrap();
+    /// assert_eq!(matrix.inverse().unwrap().to_string(), "[[0.6, -0.7], [-0.2, 0.4]]");
+    /// assert!(Matrix::from_rows(&[vec![1.0, 2.0], vec![2.0, 4.0]]).unwrap().inverse().is_err());
+    /// ```
+    pub fn inverse(&self) -> CalcResult<Matrix> {
+        self.require_square("invert")?;
+        let lu = self.decompose().ok_or_else(|| CalculatorError::singular_matrix(Some("it has no inverse")))?;
+        let n = self.rows;
+        let mut data = vec![0.0; n * n];
+        for col in 0..n {
+            let unit: Vec<f64> = (0..n).map(|row| if row == col { 1.0 } else { 0.0 }).collect();
+            for (row, value) in lu.solve(&unit).into_iter().enumerate() {
+                data[row * n + col] = value;
+            }
+        }
+        Ok(Self { rows: n, cols: n, data: checked(data)? })
+    }
+
+    /// Solves the linear system `A x = b` for a square matrix `A`.
+    ///
+    /// # Arguments
+    ///
+    /// * `b` - The right-hand side, with one component per row
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(x)` - The unique solution
+    /// * `Err(error)` - An invalid input error if the shapes do not fit, a
+    ///   singular matrix error if there is no unique solution, or an
+    ///   overflow error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::calculator::linalg::{Matrix, Vector};
+    ///
+    /// // x + y = 3, x - y = 1
+    /// let a = Matrix::from_rows(&[vec![1.0, 1.0], vec![1.0, -1.0]]).unwrap();
+    /// assert_eq!(a.solve(&Vector::new(&[3.0, 1.0])).unwrap().values(), vec![2.0, 1.0]);
+    /// ```
+    pub fn solve(&self, b: &Vector) -> CalcResult<Vector> {
+        self.require_square("solve a system with")?;
+        if b.len() != self.rows {
+            return Err(CalculatorError::invalid_input(Some(&format!(
+                "a {} system needs a right-hand side of length {}, not {}",
+                self.shape_string(),
+                self.rows,
+                b.len()
+            ))));
+        }
+        let lu = self
+            .decompose()
+            .ok_or_else(|| CalculatorError::singular_matrix(Some("the system has no unique solution")))?;
+        Ok(Vector { values: checked(lu.solve(&b.values))? })
+    }
+
+    /// Combines two matrices of the same shape entry by entry.
+    fn zip(&self, other: &Matrix, action: &str, f: impl Fn(f64, f64) -> f64) -> CalcResult<Matrix> {
+        if self.rows != other.rows || self.cols != other.cols {
+            return Err(CalculatorError::invalid_input(Some(&format!(
+                "cannot {} a {} matrix and a {} matrix",
+                action,
+                self.shape_string(),
+                other.shape_string()
+            ))));
+        }
+        let data = self.data.iter().zip(&other.data).map(|(a, b)| f(*a, *b)).collect();
+        Ok(Self { data: checked(data)?, ..*self })
+    }
+
+    /// Checks that the matrix is square before an operation.
+    fn require_square(&self, action: &str) -> CalcResult<()> {
+        if self.is_square() {
+            Ok(())
+        } else {
+            Err(CalculatorError::invalid_input(Some(&format!(
+                "cannot {} a {} matrix, which is not square",
+                action,
+                self.shape_string()
+            ))))
+        }
+    }
+
+    /// Describes the shape (e.g. `"2×3"`).
+    fn shape_string(&self) -> String {
+        format!("{}×{}", self.rows, self.cols)
+    }
+
+    /// Gets the size below which a pivot counts as zero.
+    fn tolerance(&self, size: usize) -> f64 {
+        let largest = self.data.iter().fold(0.0, |largest: f64, value| largest.max(value.abs()));
+        size as f64 * f64::EPSILON * largest
+    }
+
+    /// Decomposes a square matrix as `P A = L U`, or returns `None` if it
+    /// is singular.
+    fn decompose(&self) -> Option<Lu> {
+        let n = self.rows;
+        let tolerance = self.tolerance(n);
+        let mut data = self.data.clone();
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let mut sign = 1.0;
+
+        for k in 0..n {
+            let pivot = (k..n).max_by(|&a, &b| data[a * n + k].abs().total_cmp(&data[b * n + k].abs()))?;
+            if data[pivot * n + k].abs() <= tolerance {
+                return None;
+            }
+            if pivot != k {
+                for col in 0..n {
+                    data.swap(k * n + col, pivot * n + col);
+                }
+                permutation.swap(k, pivot);
+                sign = -sign;
+            }
+            for row in k + 1..n {
+                let factor = data[row * n + k] / data[k * n + k];
+                data[row * n + k] = factor;
+                for col in k + 1..n {
+                    data[row * n + col] -= factor * data[k * n + col];
+                }
+            }
+        }
+        Some(Lu { size: n, data, permutation, sign })
+    }
+}
+
+impl fmt::Display for Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows: Vec<String> = self.data.chunks(self.cols).map(|row| format!("[{}]", join(row))).collect();
+        write!(f, "[{}]", rows.join(", "))
+    }
+}
+
+/// An LU decomposition with partial pivoting, `P A = L U`.
+struct Lu {
+    /// The number of rows and columns
+    size: usize,
+    /// `U` on and above the diagonal and `L` without its unit diagonal
+    /// below it, row by row
+    data: Vec<f64>,
+    /// The original row of each row of `P A`
+    permutation: Vec<usize>,
+    /// The sign of the permutation
+    sign: f64,
+}
+
+impl Lu {
+    /// Solves `A x = b` by forward and back substitution.
+    fn solve(&self, b: &[f64]) -> Vec<f64> {
+        let n = self.size;
+        let mut x: Vec<f64> = self.permutation.iter().map(|&row| b[row]).collect();
+        for row in 0..n {
+            for col in 0..row {
+                x[row] -= self.data[row * n + col] * x[col];
+            }
+        }
+        for row in (0..n).rev() {
+            for col in row + 1..n {
+                x[row] -= self.data[row * n + col] * x[col];
+            }
+            x[row] /= self.data[row * n + row];
+        }
+        x
+    }
+}
+
+/// Creates an identity matrix from JavaScript.
+///
+/// # Arguments
+///
+/// * `size` - The number of rows and columns
+///
+/// # Returns
+///
+/// The identity matrix, or an error message for size 0 or a size above
+/// `MAX_DIMENSION`
+#[wasm_bindgen(js_name = identity_matrix)]
+pub fn identity_matrix_wasm(size: usize) -> Result<Matrix, JsValue> {
+    if size == 0 {
+        return Err(JsValue::from_str(CalculatorError::invalid_input(Some("a matrix needs at least one row")).message()));
+    }
+    check_dimension(size).map_err(|e| JsValue::from_str(e.message()))?;
+    Ok(Matrix::identity(size))
+}
+
+/// Checks that a number of rows or columns is at most `MAX_DIMENSION`.
+fn check_dimension(size: usize) -> CalcResult<()> {
+    if size > MAX_DIMENSION {
+        return Err(CalculatorError::invalid_input(Some(&format!(
+            "a matrix can have at most {} rows and columns",
+            MAX_DIMENSION
+        ))));
+    }
+    Ok(())
+}
+
+/// Checks that computed entries are finite numbers.
+fn checked(values: Vec<f64>) -> CalcResult<Vec<f64>> {
+    if values.iter().any(|value| value.is_nan()) {
+        Err(CalculatorError::not_a_number())
+    } else if values.iter().any(|value| value.is_infinite()) {
+        Err(CalculatorError::overflow())
+    } else {
+        Ok(values)
+    }
+}
+
+/// Splits a nonzero finite value into a mantissa of magnitude in [1, 2)
+/// and a power of two.
+fn split_binary(value: f64) -> (f64, i32) {
+    let exponent = value.abs().log2().floor() as i32;
+    (value / 2f64.powi(exponent), exponent)
+}
+
+/// Joins entries for display, to 15 significant digits.
+fn join(values: &[f64]) -> String {
+    values.iter().map(|value| format_significant(*value, DISPLAY_DIGITS)).collect::<Vec<_>>().join(", ")
+}
@@ -2,15 +2,47 @@
 //!
 //! This module provides the core calculator functionality including:
 //! - Basic arithmetic operations (add, subtract, multiply, divide)
+//! - Checked arithmetic reporting overflow, underflow and NaN as errors
 //! - Memory operations (store, recall, clear, add, subtract)
+//! - One-variable statistics over data sets
+//! - Two-variable statistics and regression over paired data
+//! - Exact combinatorics and probability distributions
+//! - Time value of money, amortization, NPV and IRR
+//! - Date arithmetic, business days and h:m:s durations
+//! - Complex numbers in rectangular and polar form
+//! - Vectors, matrices and linear systems
+//! - Polynomial roots and numeric equation solving
+//! - Numeric derivatives, integrals, sums and products
 //!
 //! The module is designed to be used with WebAssembly through wasm-bindgen.
 
 // Export submodules
 pub mod operations;
 pub mod memory;
+pub mod statistics;
+pub mod regression;
+pub mod combinatorics;
+pub mod probability;
+pub mod financial;
+pub mod datetime;
+pub mod complex;
+pub mod linalg;
+pub mod solver;
+pub mod calculus;
 
 // Re-export commonly used types and functions
 pub use operations::{add, subtract, multiply, divide, divide_wasm};
+pub use operations::{checked_add, checked_subtract, checked_multiply, checked_divide};
+pub use operations::{checked_add_wasm, checked_subtract_wasm, checked_multiply_wasm, checked_divide_wasm};
+pub use statistics::{Statistics, summarize, summarize_weighted, summarize_wasm, summarize_weighted_wasm};
+pub use regression::{PairedStatistics, Regression, RegressionKind, paired_statistics_wasm, regression_wasm};
+pub use combinatorics::{factorial, permutations, combinations, factorial_wasm, permutations_wasm, combinations_wasm};
+pub use probability::{Distribution, DistributionKind};
+pub use financial::{TvmWorksheet, TvmVariable, PaymentTiming, Amortization, npv, irr};
+pub use datetime::{Date, Weekday, days_between, add_duration, business_days, add_business_days, parse_duration, format_duration};
+pub use complex::Complex;
+pub use linalg::{Vector, Matrix};
+pub use solver::{polynomial_roots, find_root};
+pub use calculus::{Estimate, derivative, integrate, sum, product};
 pub use memory::{memory_store, memory_recall, memory_clear, memory_add, memory_subtract};
 pub use crate::errors::CalcResult; 
\ No newline at end of file
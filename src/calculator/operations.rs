@@ -6,6 +6,9 @@
 //! - Multiplication
 //! - Division
 //!
+//! Each operation also has a checked variant that reports overflow,
+//! underflow and NaN results as errors instead of returning them.
+//!
 //! All operations are exposed to JavaScript through WebAssembly bindings.
 
 use wasm_bindgen::prelude::*;
@@ -129,4 +132,195 @@ pub fn divide(a: f64, b: f64) -> CalcResult {
 #[wasm_bindgen(js_name = divide)]
 pub fn divide_wasm(a: f64, b: f64) -> Result<f64, JsValue> {
     divide(a, b).map_err(|e| JsValue::from_str(e.message()))
-} 
\ No newline at end of file
+}
+
+/// Checks the result of an arithmetic operation for values that cannot be
+/// represented.
+///
+/// # Arguments
+///
+/// * `result` - The raw result of the operation
+/// * `exact_is_nonzero` - Whether the exact mathematical result is non-zero,
+///   so that a zero result means it was flushed to zero
+///
+/// # Returns
+///
+/// * `Ok(result)` - The result if it is a finite, normal number or exactly zero
+/// * `Err(error)` - An overflow, underflow or NaN error
+pub(crate) fn check_result(result: f64, exact_is_nonzero: bool) -> CalcResult {
+    if result.is_nan() {
+        Err(CalculatorError::not_a_number())
+    } else if result.is_infinite() {
+        Err(CalculatorError::overflow())
+    } else if result.is_subnormal() || (result == 0.0 && exact_is_nonzero) {
+        Err(CalculatorError::underflow())
+    } else {
+        Ok(result)
+    }
+}
+
+/// Adds two numbers, reporting unrepresentable results as errors.
+///
+/// # Arguments
+///
+/// * `a` - First operand
+/// * `b` - Second operand
+///
+/// # Returns
+///
+/// * `Ok(result)` - The sum of `a` and `b`
+/// * `Err(error)` - An overflow, underflow or NaN error
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::calculator::checked_add;
+///
+/// assert_eq!(checked_add(2.0, 3.0).unwrap(), 5.0);
+/// assert!(checked_add(f64::MAX, f64::MAX).is_err());
+/// ```
+pub fn checked_add(a: f64, b: f64) -> CalcResult {
+    check_result(a + b, false)
+}
+
+/// Subtracts the second number from the first, reporting unrepresentable
+/// results as errors.
+///
+/// # Arguments
+///
+/// * `a` - First operand
+/// * `b` - Second operand
+///
+/// # Returns
+///
+/// * `Ok(result)` - The difference `a - b`
+/// * `Err(error)` - An overflow, underflow or NaN error
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::calculator::checked_subtract;
+///
+/// assert_eq!(checked_subtract(5.0, 3.0).unwrap(), 2.0);
+/// assert!(checked_subtract(-f64::MAX, f64::MAX).is_err());
+/// ```
+pub fn checked_subtract(a: f64, b: f64) -> CalcResult {
+    check_result(a - b, false)
+}
+
+/// Multiplies two numbers, reporting unrepresentable results as errors.
+///
+/// # Arguments
+///
+/// * `a` - First operand
+/// * `b` - Second operand
+///
+/// # Returns
+///
+/// * `Ok(result)` - The product of `a` and `b`
+/// * `Err(error)` - An overflow, underflow or NaN error
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::calculator::checked_multiply;
+///
+/// assert_eq!(checked_multiply(2.0, 3.0).unwrap(), 6.0);
+/// assert!(checked_multiply(1e200, 1e200).is_err());
+/// assert!(checked_multiply(1e-200, 1e-200).is_err());
+/// ```
+pub fn checked_multiply(a: f64, b: f64) -> CalcResult {
+    check_result(a * b, a != 0.0 && b != 0.0)
+}
+
+/// Divides the first number by the second, reporting division by zero and
+/// unrepresentable results as errors.
+///
+/// # Arguments
+///
+/// * `a` - Dividend (numerator)
+/// * `b` - Divisor (denominator)
+///
+/// # Returns
+///
+/// * `Ok(result)` - The quotient `a / b`
+/// * `Err(error)` - A division by zero, overflow, underflow or NaN error
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::calculator::checked_divide;
+///
+/// assert_eq!(checked_divide(6.0, 3.0).unwrap(), 2.0);
+/// assert!(checked_divide(1.0, 0.0).is_err());
+/// assert!(checked_divide(1e-300, 1e300).is_err());
+/// ```
+pub fn checked_divide(a: f64, b: f64) -> CalcResult {
+    let result = divide(a, b)?;
+    check_result(result, a != 0.0 && b.is_finite())
+}
+
+/// Adds two numbers, reporting unrepresentable results as errors.
+/// This is a WebAssembly-friendly version that returns a Result as a JsValue.
+///
+/// # Arguments
+///
+/// * `a` - First operand
+/// * `b` - Second operand
+///
+/// # Returns
+///
+/// The sum `a + b` if successful, or an error message
+#[wasm_bindgen(js_name = checked_add)]
+pub fn checked_add_wasm(a: f64, b: f64) -> Result<f64, JsValue> {
+    checked_add(a, b).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Subtracts two numbers, reporting unrepresentable results as errors.
+/// This is a WebAssembly-friendly version that returns a Result as a JsValue.
+///
+/// # Arguments
+///
+/// * `a` - First operand
+/// * `b` - Second operand
+///
+/// # Returns
+///
+/// The difference `a - b` if successful, or an error message
+#[wasm_bindgen(js_name = checked_subtract)]
+pub fn checked_subtract_wasm(a: f64, b: f64) -> Result<f64, JsValue> {
+    checked_subtract(a, b).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Multiplies two numbers, reporting unrepresentable results as errors.
+/// This is a WebAssembly-friendly version that returns a Result as a JsValue.
+///
+/// # Arguments
+///
+/// * `a` - First operand
+/// * `b` - Second operand
+///
+/// # Returns
+///
+/// The product `a * b` if successful, or an error message
+#[wasm_bindgen(js_name = checked_multiply)]
+pub fn checked_multiply_wasm(a: f64, b: f64) -> Result<f64, JsValue> {
+    checked_multiply(a, b).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Divides two numbers, reporting division by zero and unrepresentable
+/// results as errors.
+/// This is a WebAssembly-friendly version that returns a Result as a JsValue.
+///
+/// # Arguments
+///
+/// * `a` - Dividend (numerator)
+/// * `b` - Divisor (denominator)
+///
+/// # Returns
+///
+/// The quotient `a / b` if successful, or an error message
+#[wasm_bindgen(js_name = checked_divide)]
+pub fn checked_divide_wasm(a: f64, b: f64) -> Result<f64, JsValue> {
+    checked_divide(a, b).map_err(|e| JsValue::from_str(e.message()))
+}
@@ -0,0 +1,518 @@
+//! Calculator regression module.
+//!
+//! This module provides two-variable statistics over paired (x, y) data:
+//! - Covariance and Pearson correlation
+//! - Least-squares regression: linear, exponential, logarithmic, power
+//!   and polynomial
+//! - Prediction of y for a given x (`ŷ`) and of x for a given y (`x̂`)
+//!
+//! Exponential, logarithmic and power models are fitted as straight lines
+//! through transformed data (`ln y`, `ln x`), as handheld calculators do,
+//! and their r² is that of the transformed fit.
+//!
+//! All operations are exposed to JavaScript through WebAssembly bindings.
+
+use wasm_bindgen::prelude::*;
+use crate::calculator::linalg::{Matrix, Vector};
+use crate::calculator::operations::checked_divide;
+use crate::errors::{CalculatorError, CalculatorErrorType, CalcResult};
+
+/// The highest supported polynomial degree.
+pub const MAX_POLYNOMIAL_DEGREE: u32 = 10;
+
+/// Represents the kind of regression model.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RegressionKind {
+    /// `y = a + b·x`
+    Linear,
+    /// `y = a·e^(b·x)`
+    Exponential,
+    /// `y = a + b·ln(x)`
+    Logarithmic,
+    /// `y = a·x^b`
+    Power,
+    /// `y = c₀ + c₁·x + … + cₙ·xⁿ`
+    Polynomial,
+}
+
+/// Two-variable statistics of paired data.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairedStatistics {
+    /// Total weight (number of pairs, counting frequencies)
+    count: f64,
+    /// Mean of x
+    mean_x: f64,
+    /// Mean of y
+    mean_y: f64,
+    /// Sum of squared deviations of x
+    sxx: f64,
+    /// Sum of squared deviations of y
+    syy: f64,
+    /// Sum of products of deviations
+    sxy: f64,
+}
+
+impl PairedStatistics {
+    /// Computes statistics for weighted pairs.
+    ///
+    /// # Arguments
+    ///
+    /// * `xs` - The x values
+    /// * `ys` - The y values
+    /// * `weights` - How often each pair occurs
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(statistics)` - The paired statistics
+    /// * `Err(error)` - An invalid input error if the data is empty, the
+    ///   slices differ in length or a value is not finite
+    pub fn from_weighted(xs: &[f64], ys: &[f64], weights: &[f64]) -> CalcResult<Self> {
+        validate(xs, ys, weights)?;
+
+        let count: f64 = weights.iter().sum();
+        let mean_x = weighted_sum(xs, weights, |x| x) / count;
+        let mean_y = weighted_sum(ys, weights, |y| y) / count;
+
+        let mut sxx = 0.0;
+        let mut syy = 0.0;
+        let mut sxy = 0.0;
+        for i in 0..xs.len() {
+            let dx = xs[i] - mean_x;
+            let dy = ys[i] - mean_y;
+            sxx += weights[i] * dx * dx;
+            syy += weights[i] * dy * dy;
+            sxy += weights[i] * dx * dy;
+        }
+
+        Ok(Self { count, mean_x, mean_y, sxx, syy, sxy })
+    }
+
+    /// Computes statistics for pairs that each occur once.
+    ///
+    /// # Arguments
+    ///
+    /// * `xs` - The x values
+    /// * `ys` - The y values
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(statistics)` - The paired statistics
+    /// * `Err(error)` - An invalid input error if the data is invalid
+    pub fn from_pairs(xs: &[f64], ys: &[f64]) -> CalcResult<Self> {
+        Self::from_weighted(xs, ys, &vec![1.0; xs.len()])
+    }
+
+    /// Gets the sample covariance.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(covariance)` - The sample covariance (dividing by n - 1)
+    /// * `Err(error)` - An invalid input error if there is only one pair
+    pub fn sample_covariance(&self) -> CalcResult {
+        if self.count < 2.0 {
+            return Err(CalculatorError::invalid_input(Some(
+                "sample covariance needs at least two pairs",
+            )));
+        }
+        Ok(self.sxy / (self.count - 1.0))
+    }
+
+    /// Gets the Pearson correlation coefficient.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(r)` - The correlation coefficient, from -1 to 1
+    /// * `Err(error)` - An invalid input error if x or y is constant
+    pub fn correlation(&self) -> CalcResult {
+        if self.sxx == 0.0 || self.syy == 0.0 {
+            return Err(CalculatorError::invalid_input(Some(
+                "correlation is undefined when x or y is constant",
+            )));
+        }
+        Ok((self.sxy / (self.sxx * self.syy).sqrt()).clamp(-1.0, 1.0))
+    }
+}
+
+/// Getters for PairedStatistics
+#[wasm_bindgen]
+impl PairedStatistics {
+    /// Gets the number of pairs.
+    pub fn count(&self) -> f64 {
+        self.count
+    }
+
+    /// Gets the mean of x.
+    pub fn mean_x(&self) -> f64 {
+        self.mean_x
+    }
+
+    /// Gets the mean of y.
+    pub fn mean_y(&self) -> f64 {
+        self.mean_y
+    }
+
+    /// Gets the population covariance (dividing by n).
+    pub fn population_covariance(&self) -> f64 {
+        self.sxy / self.count
+    }
+
+    /// Gets the sample covariance from JavaScript.
+    ///
+    /// # Returns
+    ///
+    /// The sample covariance, or an error message if there is only one pair
+    #[wasm_bindgen(js_name = sample_covariance)]
+    pub fn sample_covariance_wasm(&self) -> Result<f64, JsValue> {
+        self.sample_covariance().map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Gets the Pearson correlation coefficient from JavaScript.
+    ///
+    /// # Returns
+    ///
+    /// The correlation coefficient, or an error message if x or y is constant
+    #[wasm_bindgen(js_name = correlation)]
+    pub fn correlation_wasm(&self) -> Result<f64, JsValue> {
+        self.correlation().map_err(|e| JsValue::from_str(e.message()))
+    }
+}
+
+/// A fitted regression model.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    /// The kind of model
+    kind: RegressionKind,
+    /// The coefficients: `[a, b]`, or `[c₀, …, cₙ]` for polynomials
+    coefficients: Vec<f64>,
+    /// The coefficient of determination
+    r_squared: f64,
+}
+
+impl Regression {
+    /// Fits a regression model to weighted pairs.
+    ///
+    /// # Arguments
+    ///
+    /// * `xs` - The x values
+    /// * `ys` - The y values
+    /// * `weights` - How often each pair occurs
+    /// * `kind` - The kind of model
+    /// * `degree` - The polynomial degree (ignored for other models)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(regression)` - The fitted model
+    /// * `Err(error)` - An invalid input error if the data is invalid, does
+    ///   not suit the model (e.g. `ln` of a non-positive value), or does not
+    ///   determine the model
+    pub fn fit_weighted(
+        xs: &[f64],
+        ys: &[f64],
+        weights: &[f64],
+        kind: RegressionKind,
+        degree: u32,
+    ) -> CalcResult<Self> {
+        validate(xs, ys, weights)?;
+
+        let log_x = matches!(kind, RegressionKind::Logarithmic | RegressionKind::Power);
+        let log_y = matches!(kind, RegressionKind::Exponential | RegressionKind::Power);
+
+        if kind == RegressionKind::Polynomial {
+            return Self::fit_polynomial(xs, ys, weights, degree);
+        }
+
+        let tx = transform(xs, log_x, "x")?;
+        let ty = transform(ys, log_y, "y")?;
+        let stats = PairedStatistics::from_weighted(&tx, &ty, weights)?;
+
+        if stats.sxx == 0.0 {
+            return Err(CalculatorError::invalid_input(Some(
+                "x values must not all be equal",
+            )));
+        }
+
+        let slope = stats.sxy / stats.sxx;
+        let intercept = stats.mean_y - slope * stats.mean_x;
+        let r_squared = if stats.syy == 0.0 {
+            1.0
+        } else {
+            stats.sxy * stats.sxy / (stats.sxx * stats.syy)
+        };
+
+        let a = if log_y { intercept.exp() } else { intercept };
+        Ok(Self { kind, coefficients: vec![a, slope], r_squared })
+    }
+
+    /// Fits a regression model to pairs that each occur once.
+    ///
+    /// # Arguments
+    ///
+    /// * `xs` - The x values
+    /// * `ys` - The y values
+    /// * `kind` - The kind of model
+    /// * `degree` - The polynomial degree (ignored for other models)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(regression)` - The fitted model
+    /// * `Err(error)` - An invalid input error if the model cannot be fitted
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::calculator::regression::{Regression, RegressionKind};
+    ///
+    /// let fit = Regression::fit(&[1.0, 2.0, 3.0], &[3.0, 5.0, 7.0], RegressionKind::Linear, 1).unwrap();
+    /// assert_eq!(fit.coefficients(), vec![1.0, 2.0]);
+    /// assert_eq!(fit.predict(10.0).unwrap(), 21.0);
+    /// assert_eq!(fit.predict_x(21.0).unwrap(), 10.0);
+    /// ```
+    pub fn fit(xs: &[f64], ys: &[f64], kind: RegressionKind, degree: u32) -> CalcResult<Self> {
+        Self::fit_weighted(xs, ys, &vec![1.0; xs.len()], kind, degree)
+    }
+
+    /// Predicts y for a given x (`ŷ`).
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The x value
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(y)` - The predicted y value
+    /// * `Err(error)` - An invalid input error if x is outside the model's domain
+    pub fn predict(&self, x: f64) -> CalcResult {
+        let c = &self.coefficients;
+        let y = match self.kind {
+            RegressionKind::Linear => c[0] + c[1] * x,
+            RegressionKind::Exponential => c[0] * (c[1] * x).exp(),
+            RegressionKind::Logarithmic => c[0] + c[1] * positive_ln(x, "x")?,
+            RegressionKind::Power => c[0] * (c[1] * positive_ln(x, "x")?).exp(),
+            RegressionKind::Polynomial => c.iter().rev().fold(0.0, |acc, coefficient| acc * x + coefficient),
+        };
+        finite(y)
+    }
+
+    /// Predicts x for a given y (`x̂`).
+    ///
+    /// # Arguments
+    ///
+    /// * `y` - The y value
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(x)` - The predicted x value
+    /// * `Err(error)` - An invalid input error if the model cannot be inverted
+    ///   at y (including polynomials of degree 2 or higher, which have no
+    ///   unique inverse)
+    pub fn predict_x(&self, y: f64) -> CalcResult {
+        let c = &self.coefficients;
+        if c.len() != 2 || c[1] == 0.0 {
+            return Err(CalculatorError::invalid_input(Some(
+                "x̂ is not defined for this model",
+            )));
+        }
+
+        let x = match self.kind {
+            RegressionKind::Linear | RegressionKind::Polynomial => (y - c[0]) / c[1],
+            RegressionKind::Exponential => positive_ln(y / c[0], "y / a")? / c[1],
+            RegressionKind::Logarithmic => ((y - c[0]) / c[1]).exp(),
+            RegressionKind::Power => (positive_ln(y / c[0], "y / a")? / c[1]).exp(),
+        };
+        finite(x)
+    }
+
+    /// Fits a polynomial by solving the weighted normal equations.
+    fn fit_polynomial(xs: &[f64], ys: &[f64], weights: &[f64], degree: u32) -> CalcResult<Self> {
+        if degree == 0 || degree > MAX_POLYNOMIAL_DEGREE {
+            return Err(CalculatorError::invalid_input(Some(
+                "polynomial degree must be between 1 and 10",
+            )));
+        }
+
+        let terms = degree as usize + 1;
+        let distinct_x = {
+            let mut sorted = xs.to_vec();
+            sorted.sort_by(f64::total_cmp);
+            sorted.dedup();
+            sorted.len()
+        };
+        if distinct_x < terms {
+            return Err(CalculatorError::invalid_input(Some(
+                "not enough distinct x values for this degree",
+            )));
+        }
+
+        // Build the normal equations (XᵀWX) c = XᵀWy in x / scale, so that
+        // the elimination's singularity test does not depend on the units of x
+        let scale = xs.iter().fold(0.0, |max: f64, x| max.max(x.abs()));
+        let mut normal = vec![0.0; terms * terms];
+        let mut rhs = vec![0.0; terms];
+        for i in 0..xs.len() {
+            let powers: Vec<f64> = (0..terms).map(|p| (xs[i] / scale).powi(p as i32)).collect();
+            for row in 0..terms {
+                for col in 0..terms {
+                    normal[row * terms + col] += weights[i] * powers[row] * powers[col];
+                }
+                rhs[row] += weights[i] * powers[row] * ys[i];
+            }
+        }
+
+        let solution = Matrix::new(terms, terms, &normal)?.solve(&Vector::new(&rhs)).map_err(|error| {
+            if error.error_type() == CalculatorErrorType::SingularMatrix {
+                CalculatorError::invalid_input(Some("data does not determine the model"))
+            } else {
+                error
+            }
+        })?;
+        let coefficients = solution
+            .values()
+            .iter()
+            .enumerate()
+            .map(|(p, c)| checked_divide(*c, scale.powi(p as i32)))
+            .collect::<CalcResult<Vec<f64>>>()?;
+        let mut fit = Self { kind: RegressionKind::Polynomial, coefficients, r_squared: 0.0 };
+
+        let stats = PairedStatistics::from_weighted(xs, ys, weights)?;
+        let mut residuals = 0.0;
+        for i in 0..xs.len() {
+            residuals += weights[i] * (ys[i] - fit.predict(xs[i])?).powi(2);
+        }
+        fit.r_squared = if stats.syy == 0.0 { 1.0 } else { (1.0 - residuals / stats.syy).max(0.0) };
+
+        Ok(fit)
+    }
+}
+
+/// Getters and JavaScript bindings for Regression
+#[wasm_bindgen]
+impl Regression {
+    /// Gets the kind of model.
+    pub fn kind(&self) -> RegressionKind {
+        self.kind
+    }
+
+    /// Gets the coefficients: `[a, b]`, or `[c₀, …, cₙ]` for polynomials.
+    pub fn coefficients(&self) -> Vec<f64> {
+        self.coefficients.clone()
+    }
+
+    /// Gets the coefficient of determination (r²).
+    pub fn r_squared(&self) -> f64 {
+        self.r_squared
+    }
+
+    /// Predicts y for a given x from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The x value
+    ///
+    /// # Returns
+    ///
+    /// The predicted y value, or an error message
+    #[wasm_bindgen(js_name = predict)]
+    pub fn predict_wasm(&self, x: f64) -> Result<f64, JsValue> {
+        self.predict(x).map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Predicts x for a given y from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `y` - The y value
+    ///
+    /// # Returns
+    ///
+    /// The predicted x value, or an error message
+    #[wasm_bindgen(js_name = predict_x)]
+    pub fn predict_x_wasm(&self, y: f64) -> Result<f64, JsValue> {
+        self.predict_x(y).map_err(|e| JsValue::from_str(e.message()))
+    }
+}
+
+/// Computes two-variable statistics for paired data.
+/// This is a WebAssembly-friendly version that takes two `Float64Array`s.
+///
+/// # Arguments
+///
+/// * `xs` - The x values
+/// * `ys` - The y values
+///
+/// # Returns
+///
+/// The paired statistics, or an error message if the data is invalid
+#[wasm_bindgen(js_name = paired_statistics)]
+pub fn paired_statistics_wasm(xs: &[f64], ys: &[f64]) -> Result<PairedStatistics, JsValue> {
+    PairedStatistics::from_pairs(xs, ys).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Fits a regression model to paired data.
+/// This is a WebAssembly-friendly version that takes two `Float64Array`s.
+///
+/// # Arguments
+///
+/// * `xs` - The x values
+/// * `ys` - The y values
+/// * `kind` - The kind of model
+/// * `degree` - The polynomial degree (ignored for other models)
+///
+/// # Returns
+///
+/// The fitted model, or an error message if it cannot be fitted
+#[wasm_bindgen(js_name = regression)]
+pub fn regression_wasm(xs: &[f64], ys: &[f64], kind: RegressionKind, degree: u32) -> Result<Regression, JsValue> {
+    Regression::fit(xs, ys, kind, degree).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Checks that paired data is non-empty, consistent and finite.
+fn validate(xs: &[f64], ys: &[f64], weights: &[f64]) -> CalcResult<()> {
+    if xs.len() != ys.len() || xs.len() != weights.len() {
+        return Err(CalculatorError::invalid_input(Some("x and y data differ in length")));
+    }
+    if xs.is_empty() {
+        return Err(CalculatorError::invalid_input(Some("data set is empty")));
+    }
+    if xs.iter().chain(ys).any(|value| !value.is_finite()) {
+        return Err(CalculatorError::invalid_input(Some("data points must be finite")));
+    }
+    if weights.iter().any(|weight| weight.is_nan() || *weight <= 0.0) {
+        return Err(CalculatorError::invalid_input(Some("frequencies must be positive")));
+    }
+    Ok(())
+}
+
+/// Sums `f(value) * weight` over the data.
+fn weighted_sum(values: &[f64], weights: &[f64], f: impl Fn(f64) -> f64) -> f64 {
+    values.iter().zip(weights).map(|(value, weight)| f(*value) * weight).sum()
+}
+
+/// Takes the natural log of every value if requested.
+fn transform(values: &[f64], log: bool, name: &str) -> CalcResult<Vec<f64>> {
+    if log {
+        values.iter().map(|value| positive_ln(*value, name)).collect()
+    } else {
+        Ok(values.to_vec())
+    }
+}
+
+/// Takes the natural log of a value that must be positive.
+fn positive_ln(value: f64, name: &str) -> CalcResult {
+    if value > 0.0 {
+        Ok(value.ln())
+    } else {
+        Err(CalculatorError::invalid_input(Some(&format!("{} must be positive for this model", name))))
+    }
+}
+
+/// Checks that a prediction is finite.
+fn finite(value: f64) -> CalcResult {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(CalculatorError::overflow())
+    }
+}
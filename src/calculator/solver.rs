@@ -0,0 +1,460 @@
+//! Calculator solver module.
+//!
+//! This module finds roots of polynomials and equations:
+//! - Roots of polynomials with real coefficients, in closed form up to
+//!   degree 4 and by the Aberth–Ehrlich iteration above that, as complex
+//!   numbers
+//! - A root of any one-variable function near a guess, by bracketing a
+//!   sign change and narrowing it with Brent's method, or by Newton's
+//!   method where the function touches zero without changing sign
+//!
+//! When no root is found, the error says why, such as the number of
+//! iterations used and the last estimate.
+//!
+//! Polynomial roots are exposed to JavaScript through WebAssembly bindings;
+//! equations are solved with `solve(x^3 - 2x = 5, x, 2)` in expressions.
+
+use std::f64::consts::PI;
+use wasm_bindgen::prelude::*;
+use crate::calculator::complex::Complex;
+use crate::errors::{CalculatorError, CalcResult};
+use crate::state::format_significant;
+
+/// The number of significant digits shown for estimates in error messages.
+const DISPLAY_DIGITS: usize = 15;
+
+/// Iteration cap for Brent's and Newton's methods.
+const MAX_ITERATIONS: usize = 200;
+/// Iteration cap for the Aberth–Ehrlich iteration.
+const MAX_ABERTH_ITERATIONS: usize = 500;
+/// The number of times the search around a guess is widened.
+const MAX_EXPANSIONS: usize = 60;
+/// The first search step, relative to the size of the guess.
+const INITIAL_STEP: f64 = 0.01;
+/// The growth of the search step each time it is widened.
+const EXPANSION: f64 = 1.6;
+/// The step of the central difference used by Newton's method, relative to
+/// the size of the estimate (about the cube root of machine epsilon).
+const DERIVATIVE_STEP: f64 = 6e-6;
+/// The relative step below which Newton's and Aberth's methods have
+/// converged.
+const STEP_TOLERANCE: f64 = 1e-12;
+/// The largest imaginary part, relative to the modulus, that is rounding
+/// noise on a real root.
+const IMAGINARY_TOLERANCE: f64 = 1e-12;
+
+/// Finds all roots of a polynomial.
+///
+/// Roots are repeated according to their multiplicity and sorted by real
+/// part, then by imaginary part. Complex roots come in conjugate pairs.
+///
+/// # Arguments
+///
+/// * `coefficients` - The coefficients, from the highest power down to the
+///   constant term, so `[1, 0, -2, -5]` is `x³ - 2x - 5`
+///
+/// # Returns
+///
+/// * `Ok(roots)` - The roots, none for a nonzero constant
+/// * `Err(error)` - An invalid input error for non-finite coefficients or
+///   the zero polynomial, or a no solution error if the iteration for a
+///   polynomial above degree 4 does not converge
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::calculator::solver::polynomial_roots;
+///
+/// // x² - 3x + 2 = (x - 1)(x - 2)
+/// let roots = polynomial_roots(&[1.0, -3.0, 2.0]).unwrap();
+/// assert_eq!(roots[0].to_string(), "1");
+/// assert_eq!(roots[1].to_string(), "2");
+///
+/// // x² + 1 has the roots ±i
+/// let roots = polynomial_roots(&[1.0, 0.0, 1.0]).unwrap();
+/// assert_eq!(roots[0].to_string(), "-i");
+/// ```
+pub fn polynomial_roots(coefficients: &[f64]) -> CalcResult<Vec<Complex>> {
+    if coefficients.iter().any(|c| !c.is_finite()) {
+        return Err(CalculatorError::invalid_input(Some("coefficients must be finite numbers")));
+    }
+    let Some(first) = coefficients.iter().position(|c| *c != 0.0) else {
+        return Err(CalculatorError::invalid_input(Some("every number is a root of the zero polynomial")));
+    };
+    let last = coefficients.iter().rposition(|c| *c != 0.0).unwrap_or(first);
+
+    // Trailing zero coefficients are roots at 0
+    let mut roots = vec![Complex::real(0.0); coefficients.len() - 1 - last];
+    let lead = coefficients[first];
+    let monic: Vec<f64> = coefficients[first..=last].iter().map(|c| c / lead).collect();
+
+    let found = match monic.as_slice() {
+        [_] => Vec::new(),
+        [_, b] => vec![Complex::real(-b)],
+        [_, b, c] => quadratic(1.0, *b, *c).to_vec(),
+        [_, b, c, d] => cubic(*b, *c, *d).to_vec(),
+        [_, b, c, d, e] => quartic(*b, *c, *d, *e).to_vec(),
+        _ => aberth(&monic)?,
+    };
+    roots.extend(found.into_iter().map(|root| polish(&monic, root)));
+    roots.sort_by(|a, b| a.re().total_cmp(&b.re()).then(a.im().total_cmp(&b.im())));
+    Ok(roots)
+}
+
+/// Finds a root of a function near a guess.
+///
+/// The search widens around the guess until the function changes sign,
+/// then narrows the bracket with Brent's method. If no sign change is
+/// found, Newton's method is tried from the guess, which finds roots where
+/// the function only touches zero, such as that of `(x - 1)²`.
+///
+/// Points where the function fails, such as outside its domain, are
+/// skipped during the search, but the function must be defined at the
+/// guess. A zero is only accepted where the function is nonzero a step
+/// away on each side, so `exp(x)` has no root where it underflows.
+///
+/// # Arguments
+///
+/// * `f` - The function
+/// * `guess` - A starting estimate of the root
+///
+/// # Returns
+///
+/// * `Ok(root)` - A point where the function is zero
+/// * `Err(error)` - An invalid input error for a non-finite guess, the
+///   error raised by the function at the guess or inside a bracket, or a
+///   no solution error describing why the search failed
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::calculator::solver::find_root;
+///
+/// let root = find_root(|x| Ok(x * x * x - 2.0 * x - 5.0), 2.0).unwrap();
+/// assert!((root - 2.0945514815423265).abs() < 1e-12);
+/// assert!(find_root(|x| Ok(x * x + 1.0), 0.0).is_err());
+/// ```
+pub fn find_root(mut f: impl FnMut(f64) -> CalcResult, guess: f64) -> CalcResult {
+    if !guess.is_finite() {
+        return Err(CalculatorError::invalid_input(Some("the guess must be a finite number")));
+    }
+    let f_guess = f(guess)?;
+    if f_guess == 0.0 && is_isolated_zero(&mut f, guess) {
+        return Ok(guess);
+    }
+
+    let mut step = INITIAL_STEP * guess.abs().max(1.0);
+    let mut previous = [(guess, f_guess); 2];
+    for _ in 0..MAX_EXPANSIONS {
+        for (side, direction) in [1.0, -1.0].into_iter().enumerate() {
+            let x = guess + direction * step;
+            let Ok(fx) = f(x) else {
+                continue;
+            };
+            if fx == 0.0 && is_isolated_zero(&mut f, x) {
+                return Ok(x);
+            }
+            let (last_x, last_fx) = previous[side];
+            if fx.signum() != last_fx.signum() && fx.is_finite() && last_fx.is_finite() {
+                return brent(&mut f, (last_x, last_fx), (x, fx));
+            }
+            previous[side] = (x, fx);
+        }
+        step *= EXPANSION;
+    }
+
+    newton(&mut f, guess, f_guess).map_err(|details| {
+        CalculatorError::no_solution(Some(&format!(
+            "no sign change was found within {} of {}, and {}",
+            estimate(step / EXPANSION),
+            estimate(guess),
+            details
+        )))
+    })
+}
+
+/// Finds all roots of a polynomial from JavaScript.
+///
+/// # Arguments
+///
+/// * `coefficients` - The coefficients, from the highest power down
+///
+/// # Returns
+///
+/// The roots, or an error message
+#[wasm_bindgen(js_name = polynomial_roots)]
+pub fn polynomial_roots_wasm(coefficients: &[f64]) -> Result<Vec<Complex>, JsValue> {
+    polynomial_roots(coefficients).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Solves `a x² + b x + c = 0` for `a ≠ 0`, avoiding cancellation.
+fn quadratic(a: f64, b: f64, c: f64) -> [Complex; 2] {
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        let re = -b / (2.0 * a);
+        let im = (-discriminant).sqrt() / (2.0 * a).abs();
+        return [Complex::new(re, -im), Complex::new(re, im)];
+    }
+    let q = -0.5 * (b + discriminant.sqrt().copysign(b));
+    if q == 0.0 {
+        return [Complex::real(0.0); 2];
+    }
+    [Complex::real(q / a), Complex::real(c / q)]
+}
+
+/// Solves `x³ + a x² + b x + c = 0` by Cardano's formula, or by the
+/// trigonometric method when there are three real roots.
+fn cubic(a: f64, b: f64, c: f64) -> [Complex; 3] {
+    // Substituting x = t - a/3 gives t³ + p t + q = 0
+    let shift = a / 3.0;
+    let p = b - a * shift;
+    let q = 2.0 * shift * shift * shift - b * shift + c;
+    let discriminant = (q / 2.0).powi(2) + (p / 3.0).powi(3);
+
+    if discriminant < 0.0 {
+        let r = (-p / 3.0).sqrt();
+        let phi = (-q / (2.0 * r * r * r)).clamp(-1.0, 1.0).acos();
+        return [0.0, 1.0, 2.0].map(|k| Complex::real(2.0 * r * ((phi - 2.0 * PI * k) / 3.0).cos() - shift));
+    }
+
+    // Take the cube root of the larger term to avoid cancellation
+    let u = (-q / 2.0 - discriminant.sqrt().copysign(q)).cbrt();
+    let v = if u == 0.0 { 0.0 } else { -p / (3.0 * u) };
+    let re = -(u + v) / 2.0 - shift;
+    let im = 3f64.sqrt() / 2.0 * (u - v).abs();
+    [Complex::real(u + v - shift), Complex::new(re, -im), Complex::new(re, im)]
+}
+
+/// Solves `x⁴ + a x³ + b x² + c x + d = 0` by Ferrari's method.
+fn quartic(a: f64, b: f64, c: f64, d: f64) -> [Complex; 4] {
+    // Substituting x = y - a/4 gives y⁴ + p y² + q y + r = 0
+    let shift = a / 4.0;
+    let p = b - 6.0 * shift * shift;
+    let q = c - 2.0 * b * shift + 8.0 * shift * shift * shift;
+    let r = d - c * shift + b * shift * shift - 3.0 * shift.powi(4);
+    let unshift = |y: Complex| y.subtract(&Complex::real(shift));
+
+    if q.abs() <= f64::EPSILON * (p.abs() + r.abs()) {
+        // A quadratic in y²
+        let [z1, z2] = quadratic(1.0, p, r);
+        let (y1, y2) = (z1.sqrt(), z2.sqrt());
+        let negate = |y: Complex| Complex::new(-y.re(), -y.im());
+        return [y1, negate(y1), y2, negate(y2)].map(unshift);
+    }
+
+    // The largest real root of the resolvent cubic, which is positive,
+    // splits the quartic into two quadratics
+    let m = cubic(p, p * p / 4.0 - r, -q * q / 8.0)
+        .iter()
+        .filter(|root| root.im() == 0.0)
+        .map(Complex::re)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let s = (2.0 * m).sqrt();
+    let [y1, y2] = quadratic(1.0, -s, p / 2.0 + m + q / (2.0 * s));
+    let [y3, y4] = quadratic(1.0, s, p / 2.0 + m - q / (2.0 * s));
+    [y1, y2, y3, y4].map(unshift)
+}
+
+/// Finds all roots of a monic polynomial by the Aberth–Ehrlich iteration.
+fn aberth(monic: &[f64]) -> CalcResult<Vec<Complex>> {
+    let degree = monic.len() - 1;
+
+    // Start on a circle enclosing all roots (Fujiwara's bound), slightly
+    // rotated so that no estimate starts on the real axis
+    let radius = monic[1..]
+        .iter()
+        .enumerate()
+        .map(|(k, c)| c.abs().powf(1.0 / (k + 1) as f64))
+        .fold(0.0, f64::max)
+        * 2.0;
+    let mut roots: Vec<Complex> = (0..degree)
+        .map(|k| Complex::from_polar(radius, 2.0 * PI * k as f64 / degree as f64 + 0.4))
+        .collect();
+
+    for _ in 0..MAX_ABERTH_ITERATIONS {
+        let mut converged = true;
+        for k in 0..degree {
+            let (value, slope) = evaluate(monic, roots[k]);
+            if value == Complex::real(0.0) {
+                continue;
+            }
+            let Ok(ratio) = value.divide(&slope) else {
+                converged = false;
+                continue;
+            };
+            let repulsion = (0..degree)
+                .filter(|&j| j != k)
+                .filter_map(|j| Complex::real(1.0).divide(&roots[k].subtract(&roots[j])).ok())
+                .fold(Complex::real(0.0), |sum, term| sum.add(&term));
+            let Ok(offset) = ratio.divide(&Complex::real(1.0).subtract(&ratio.multiply(&repulsion))) else {
+                converged = false;
+                continue;
+            };
+            roots[k] = roots[k].subtract(&offset);
+            if offset.modulus() > STEP_TOLERANCE * roots[k].modulus().max(1.0) {
+                converged = false;
+            }
+        }
+        if converged {
+            return Ok(roots);
+        }
+    }
+    Err(CalculatorError::no_solution(Some(&format!(
+        "the roots of the degree {} polynomial did not converge after {} iterations",
+        degree, MAX_ABERTH_ITERATIONS
+    ))))
+}
+
+/// Evaluates a polynomial and its derivative by Horner's scheme.
+fn evaluate(coefficients: &[f64], z: Complex) -> (Complex, Complex) {
+    let mut value = Complex::real(0.0);
+    let mut slope = Complex::real(0.0);
+    for c in coefficients {
+        slope = slope.multiply(&z).add(&value);
+        value = value.multiply(&z).add(&Complex::real(*c));
+    }
+    (value, slope)
+}
+
+/// Refines a root with a few Newton steps, keeping only steps that reduce
+/// the residual, and clears an imaginary part that is rounding noise.
+fn polish(coefficients: &[f64], mut root: Complex) -> Complex {
+    let mut residual = evaluate(coefficients, root).0.modulus();
+    for _ in 0..3 {
+        let (value, slope) = evaluate(coefficients, root);
+        let Ok(step) = value.divide(&slope) else {
+            break;
+        };
+        let next = root.subtract(&step);
+        let next_residual = evaluate(coefficients, next).0.modulus();
+        if next_residual.is_nan() || next_residual >= residual {
+            break;
+        }
+        root = next;
+        residual = next_residual;
+    }
+    if root.im().abs() <= IMAGINARY_TOLERANCE * root.modulus().max(1.0) {
+        Complex::real(root.re())
+    } else {
+        root
+    }
+}
+
+/// Narrows a bracket around a sign change by Brent's method, which
+/// combines inverse quadratic interpolation, the secant method and
+/// bisection.
+fn brent(f: &mut impl FnMut(f64) -> CalcResult, start: (f64, f64), end: (f64, f64)) -> CalcResult {
+    let (mut a, mut fa) = start;
+    let (mut b, mut fb) = end;
+    let (mut c, mut fc) = (b, fb);
+    let mut d = b - a;
+    let mut e = d;
+    let tolerance = f64::EPSILON * (end.0 - start.0).abs();
+    let bound = fa.abs().max(fb.abs());
+
+    for _ in 0..MAX_ITERATIONS {
+        if fb.signum() == fc.signum() {
+            (c, fc) = (a, fa);
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            (a, fa) = (b, fb);
+            (b, fb) = (c, fc);
+            (c, fc) = (a, fa);
+        }
+        let tol = 2.0 * f64::EPSILON * b.abs() + 0.5 * tolerance;
+        let midpoint = 0.5 * (c - b);
+        if midpoint.abs() <= tol || fb == 0.0 {
+            if fb.abs() > bound {
+                return Err(CalculatorError::no_solution(Some(&format!(
+                    "the function changes sign at {} without reaching zero",
+                    estimate(b)
+                ))));
+            }
+            return Ok(b);
+        }
+
+        if e.abs() >= tol && fa.abs() > fb.abs() {
+            // Interpolate: secant with two points, inverse quadratic with three
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0 * midpoint * s, 1.0 - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+                (s * (2.0 * midpoint * q * (q - r) - (b - a) * (r - 1.0)), (q - 1.0) * (r - 1.0) * (s - 1.0))
+            };
+            if p > 0.0 {
+                q = -q;
+            }
+            p = p.abs();
+            if 2.0 * p < (3.0 * midpoint * q - (tol * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = midpoint;
+                e = d;
+            }
+        } else {
+            d = midpoint;
+            e = d;
+        }
+
+        (a, fa) = (b, fb);
+        b += if d.abs() > tol { d } else { tol.copysign(midpoint) };
+        fb = f(b)?;
+    }
+    Err(CalculatorError::no_solution(Some(&format!(
+        "Brent's method did not converge after {} iterations; the last estimate was {}",
+        MAX_ITERATIONS,
+        estimate(b)
+    ))))
+}
+
+/// Checks that a zero of `f` at `x` is isolated, with the function nonzero
+/// (or undefined) a search step away on each side.
+fn is_isolated_zero(f: &mut impl FnMut(f64) -> CalcResult, x: f64) -> bool {
+    let offset = INITIAL_STEP * x.abs().max(1.0);
+    [x - offset, x + offset].into_iter().all(|probe| f(probe).map_or(true, |value| value != 0.0))
+}
+
+/// Formats an estimate of a root for error messages.
+fn estimate(x: f64) -> String {
+    format_significant(x, DISPLAY_DIGITS)
+}
+
+/// Runs Newton's method with a numeric derivative, describing the failure
+/// if it does not converge.
+fn newton(f: &mut impl FnMut(f64) -> CalcResult, guess: f64, f_guess: f64) -> Result<f64, String> {
+    let residual_tolerance = f64::EPSILON.sqrt() * f_guess.abs().max(1.0);
+    let (mut x, mut fx) = (guess, f_guess);
+    for _ in 0..MAX_ITERATIONS {
+        let h = DERIVATIVE_STEP * x.abs().max(1.0);
+        let (Ok(above), Ok(below)) = (f(x + h), f(x - h)) else {
+            return Err(format!("Newton's method left the function's domain near {}", estimate(x)));
+        };
+        let slope = (above - below) / (2.0 * h);
+        if slope == 0.0 || !slope.is_finite() {
+            return Err(format!("Newton's method stopped where the slope is {:e} at {}", slope, estimate(x)));
+        }
+        let step = fx / slope;
+        x -= step;
+        fx = f(x).map_err(|_| format!("Newton's method left the function's domain near {}", estimate(x)))?;
+        if fx == 0.0 && is_isolated_zero(f, x) {
+            return Ok(x);
+        }
+        if step.abs() <= STEP_TOLERANCE * x.abs().max(1.0) {
+            if fx.abs() <= residual_tolerance && fx != 0.0 {
+                return Ok(x);
+            }
+            return Err(format!("Newton's method stalled at {} with residual {:e}", estimate(x), fx));
+        }
+    }
+    Err(format!(
+        "Newton's method did not converge after {} iterations; the last estimate was {} with residual {:e}",
+        MAX_ITERATIONS,
+        estimate(x),
+        fx
+    ))
+}
@@ -0,0 +1,377 @@
+//! Calculator statistics module.
+//!
+//! This module provides one-variable statistics over a data set:
+//! - Count, sum, mean, median and mode
+//! - Population and sample variance and standard deviation
+//! - Minimum, maximum, quartiles and percentiles
+//!
+//! Data points can carry integer frequencies, so `(3, 4)` counts the value
+//! 3 four times. Quartiles and percentiles use linear interpolation between
+//! closest ranks (the method of Excel's `PERCENTILE.INC` and R's default).
+//!
+//! All operations are exposed to JavaScript through WebAssembly bindings.
+
+use wasm_bindgen::prelude::*;
+use crate::calculator::operations::checked_add;
+use crate::errors::{CalculatorError, CalcResult};
+
+/// Summary statistics of a one-variable data set.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statistics {
+    /// Distinct values in ascending order, with their frequencies
+    sorted: Vec<(f64, u32)>,
+    /// Total number of data points (sum of frequencies)
+    count: u64,
+    /// Sum of all data points
+    sum: f64,
+    /// Arithmetic mean
+    mean: f64,
+    /// Sum of squared deviations from the mean
+    squared_deviations: f64,
+}
+
+impl Statistics {
+    /// Computes statistics for data points with frequencies.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The data points
+    /// * `frequencies` - How often each data point occurs
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(statistics)` - The summary statistics
+    /// * `Err(error)` - An invalid input error if the data set is empty, the
+    ///   slices differ in length, a value is not finite, a frequency is zero
+    ///   or the frequencies of equal values add up to more than `u32::MAX`;
+    ///   an overflow error if the sum or the squared deviations overflow
+    pub fn from_weighted(values: &[f64], frequencies: &[u32]) -> CalcResult<Self> {
+        if values.len() != frequencies.len() {
+            return Err(CalculatorError::invalid_input(Some(
+                "values and frequencies differ in length",
+            )));
+        }
+        if values.is_empty() {
+            return Err(CalculatorError::invalid_input(Some("data set is empty")));
+        }
+        if values.iter().any(|value| !value.is_finite()) {
+            return Err(CalculatorError::invalid_input(Some("data points must be finite")));
+        }
+        if frequencies.contains(&0) {
+            return Err(CalculatorError::invalid_input(Some("frequencies must be positive")));
+        }
+
+        let mut pairs: Vec<(f64, u32)> = values.iter().copied().zip(frequencies.iter().copied()).collect();
+        pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut sorted: Vec<(f64, u32)> = Vec::with_capacity(pairs.len());
+        for (value, frequency) in pairs {
+            match sorted.last_mut() {
+                Some(last) if last.0 == value => {
+                    last.1 = last.1
+                        .checked_add(frequency)
+                        .ok_or_else(|| CalculatorError::invalid_input(Some("frequency is too large")))?;
+                }
+                _ => sorted.push((value, frequency)),
+            }
+        }
+
+        let count: u64 = sorted.iter().map(|(_, f)| *f as u64).sum();
+        let mut sum = 0.0;
+        for (value, frequency) in &sorted {
+            sum = checked_add(sum, value * *frequency as f64)?;
+        }
+        let mean = sum / count as f64;
+        let mut squared_deviations = 0.0;
+        for (value, frequency) in &sorted {
+            squared_deviations = checked_add(squared_deviations, (value - mean).powi(2) * *frequency as f64)?;
+        }
+
+        Ok(Self { sorted, count, sum, mean, squared_deviations })
+    }
+
+    /// Computes statistics for data points that each occur once.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The data points
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(statistics)` - The summary statistics
+    /// * `Err(error)` - An invalid input error if the data set is empty or a
+    ///   value is not finite; an overflow error if the sum or the squared
+    ///   deviations overflow
+    pub fn from_values(values: &[f64]) -> CalcResult<Self> {
+        Self::from_weighted(values, &vec![1; values.len()])
+    }
+
+    /// Gets the sample variance.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(variance)` - The sample variance (dividing by n - 1)
+    /// * `Err(error)` - An invalid input error if there is only one data point
+    pub fn sample_variance(&self) -> CalcResult {
+        if self.count < 2 {
+            return Err(CalculatorError::invalid_input(Some(
+                "sample variance needs at least two data points",
+            )));
+        }
+        Ok(self.squared_deviations / (self.count - 1) as f64)
+    }
+
+    /// Gets the sample standard deviation.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(deviation)` - The sample standard deviation
+    /// * `Err(error)` - An invalid input error if there is only one data point
+    pub fn sample_std_dev(&self) -> CalcResult {
+        self.sample_variance().map(f64::sqrt)
+    }
+
+    /// Gets a percentile of the data.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The percentile, from 0 to 100
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(value)` - The interpolated percentile
+    /// * `Err(error)` - An invalid input error if `p` is out of range
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::calculator::statistics::Statistics;
+    ///
+    /// let stats = Statistics::from_values(&[1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+    /// assert_eq!(stats.percentile(25.0).unwrap(), 2.0);
+    /// assert_eq!(stats.percentile(90.0).unwrap(), 4.6);
+    /// ```
+    pub fn percentile(&self, p: f64) -> CalcResult {
+        if !(0.0..=100.0).contains(&p) {
+            return Err(CalculatorError::invalid_input(Some(
+                "percentile must be between 0 and 100",
+            )));
+        }
+
+        let position = (self.count - 1) as f64 * p / 100.0;
+        let lower = position.floor();
+        let fraction = position - lower;
+        let below = self.nth(lower as u64);
+
+        if fraction == 0.0 {
+            Ok(below)
+        } else {
+            let above = self.nth(lower as u64 + 1);
+            Ok(below + fraction * (above - below))
+        }
+    }
+
+    /// Gets the k-th smallest data point (0-based), counting frequencies.
+    fn nth(&self, k: u64) -> f64 {
+        let mut seen = 0;
+        for (value, frequency) in &self.sorted {
+            seen += *frequency as u64;
+            if k < seen {
+                return *value;
+            }
+        }
+        self.max()
+    }
+}
+
+/// Getters for Statistics
+#[wasm_bindgen]
+impl Statistics {
+    /// Gets the number of data points.
+    pub fn count(&self) -> f64 {
+        self.count as f64
+    }
+
+    /// Gets the sum of the data points.
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    /// Gets the arithmetic mean.
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Gets the median.
+    pub fn median(&self) -> f64 {
+        self.quantile(0.5)
+    }
+
+    /// Gets the modes: every value that occurs most often.
+    ///
+    /// If all distinct values occur equally often (and there is more than
+    /// one), the data set has no mode and this is empty.
+    pub fn modes(&self) -> Vec<f64> {
+        let highest = self.sorted.iter().map(|(_, f)| *f).max().unwrap_or(0);
+        let modes: Vec<f64> = self
+            .sorted
+            .iter()
+            .filter(|(_, f)| *f == highest)
+            .map(|(v, _)| *v)
+            .collect();
+
+        if modes.len() > 1 && modes.len() == self.sorted.len() {
+            Vec::new()
+        } else {
+            modes
+        }
+    }
+
+    /// Gets the population variance (dividing by n).
+    pub fn population_variance(&self) -> f64 {
+        self.squared_deviations / self.count as f64
+    }
+
+    /// Gets the population standard deviation.
+    pub fn population_std_dev(&self) -> f64 {
+        self.population_variance().sqrt()
+    }
+
+    /// Gets the smallest data point.
+    pub fn min(&self) -> f64 {
+        self.sorted.first().map(|(v, _)| *v).unwrap_or(f64::NAN)
+    }
+
+    /// Gets the largest data point.
+    pub fn max(&self) -> f64 {
+        self.sorted.last().map(|(v, _)| *v).unwrap_or(f64::NAN)
+    }
+
+    /// Gets the first quartile (25th percentile).
+    pub fn q1(&self) -> f64 {
+        self.quantile(0.25)
+    }
+
+    /// Gets the third quartile (75th percentile).
+    pub fn q3(&self) -> f64 {
+        self.quantile(0.75)
+    }
+
+    /// Gets the sample variance from JavaScript.
+    ///
+    /// # Returns
+    ///
+    /// The sample variance, or an error message if there is only one data point
+    #[wasm_bindgen(js_name = sample_variance)]
+    pub fn sample_variance_wasm(&self) -> Result<f64, JsValue> {
+        self.sample_variance().map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Gets the sample standard deviation from JavaScript.
+    ///
+    /// # Returns
+    ///
+    /// The sample standard deviation, or an error message if there is only
+    /// one data point
+    #[wasm_bindgen(js_name = sample_std_dev)]
+    pub fn sample_std_dev_wasm(&self) -> Result<f64, JsValue> {
+        self.sample_std_dev().map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Gets a percentile of the data from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The percentile, from 0 to 100
+    ///
+    /// # Returns
+    ///
+    /// The interpolated percentile, or an error message if `p` is out of range
+    #[wasm_bindgen(js_name = percentile)]
+    pub fn percentile_wasm(&self, p: f64) -> Result<f64, JsValue> {
+        self.percentile(p).map_err(|e| JsValue::from_str(e.message()))
+    }
+}
+
+impl Statistics {
+    /// Gets a quantile for a fraction that is known to be in range.
+    fn quantile(&self, fraction: f64) -> f64 {
+        self.percentile(fraction * 100.0).unwrap_or(f64::NAN)
+    }
+}
+
+/// Computes summary statistics for a batch of data points.
+///
+/// # Arguments
+///
+/// * `values` - The data points
+///
+/// # Returns
+///
+/// * `Ok(statistics)` - The summary statistics
+/// * `Err(error)` - An invalid input error if the data set is empty or a
+///   value is not finite
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::calculator::summarize;
+///
+/// let stats = summarize(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]).unwrap();
+/// assert_eq!(stats.mean(), 5.0);
+/// assert_eq!(stats.population_std_dev(), 2.0);
+/// assert_eq!(stats.modes(), vec![4.0]);
+///
+/// assert!(summarize(&[]).is_err());
+/// ```
+pub fn summarize(values: &[f64]) -> CalcResult<Statistics> {
+    Statistics::from_values(values)
+}
+
+/// Computes summary statistics for a batch of data points with frequencies.
+///
+/// # Arguments
+///
+/// * `values` - The data points
+/// * `frequencies` - How often each data point occurs
+///
+/// # Returns
+///
+/// * `Ok(statistics)` - The summary statistics
+/// * `Err(error)` - An invalid input error if the data is invalid
+pub fn summarize_weighted(values: &[f64], frequencies: &[u32]) -> CalcResult<Statistics> {
+    Statistics::from_weighted(values, frequencies)
+}
+
+/// Computes summary statistics for a batch of data points.
+/// This is a WebAssembly-friendly version that takes a `Float64Array`.
+///
+/// # Arguments
+///
+/// * `values` - The data points
+///
+/// # Returns
+///
+/// The summary statistics, or an error message if the data set is invalid
+#[wasm_bindgen(js_name = summarize)]
+pub fn summarize_wasm(values: &[f64]) -> Result<Statistics, JsValue> {
+    summarize(values).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Computes summary statistics for a batch of data points with frequencies.
+/// This is a WebAssembly-friendly version that takes a `Float64Array` and
+/// a `Uint32Array`.
+///
+/// # Arguments
+///
+/// * `values` - The data points
+/// * `frequencies` - How often each data point occurs
+///
+/// # Returns
+///
+/// The summary statistics, or an error message if the data set is invalid
+#[wasm_bindgen(js_name = summarize_weighted)]
+pub fn summarize_weighted_wasm(values: &[f64], frequencies: &[u32]) -> Result<Statistics, JsValue> {
+    summarize_weighted(values, frequencies).map_err(|e| JsValue::from_str(e.message()))
+}
@@ -6,8 +6,12 @@
 
 use wasm_bindgen::prelude::*;
 use std::fmt;
+use crate::i18n::{self, Language};
 
 /// Represents the different types of errors that can occur in calculator operations.
+///
+/// The discriminants are the values JavaScript sees, so new error types are
+/// only ever appended. The stable error codes are given by `code()`.
 #[wasm_bindgen]
 #[derive(Debug, Clone, PartialEq)]
 pub enum CalculatorErrorType {
@@ -21,6 +25,138 @@ pub enum CalculatorErrorType {
     Underflow,
     /// General calculation error
     CalculationError,
+    /// No solution exists (e.g. an equation or IRR without a root)
+    NoSolution,
+    /// Units of different dimensions were combined (e.g. converting km to kg)
+    IncompatibleUnits,
+    /// A matrix has no inverse (e.g. inverting or solving with a zero determinant)
+    SingularMatrix,
+    /// A computation reached its step or iteration limit (e.g. a sum of too many terms)
+    IterationLimit,
+}
+
+/// Represents how serious an error is, so the UI can choose how to present it.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorSeverity {
+    /// The user can correct the input and try again
+    Warning,
+    /// The calculation failed
+    Error,
+}
+
+impl CalculatorErrorType {
+    /// All error types, in code order.
+    pub const ALL: [CalculatorErrorType; 9] = [
+        CalculatorErrorType::DivisionByZero,
+        CalculatorErrorType::InvalidInput,
+        CalculatorErrorType::Overflow,
+        CalculatorErrorType::Underflow,
+        CalculatorErrorType::CalculationError,
+        CalculatorErrorType::NoSolution,
+        CalculatorErrorType::IncompatibleUnits,
+        CalculatorErrorType::SingularMatrix,
+        CalculatorErrorType::IterationLimit,
+    ];
+
+    /// Gets the stable numeric code of this error type.
+    ///
+    /// New error types must get a new code; existing codes are never
+    /// reused or renumbered.
+    ///
+    /// # Returns
+    ///
+    /// The numeric error code
+    pub fn code(&self) -> u16 {
+        match self {
+            CalculatorErrorType::DivisionByZero => 1,
+            CalculatorErrorType::InvalidInput => 2,
+            CalculatorErrorType::Overflow => 3,
+            CalculatorErrorType::Underflow => 4,
+            CalculatorErrorType::CalculationError => 5,
+            CalculatorErrorType::NoSolution => 6,
+            CalculatorErrorType::IncompatibleUnits => 7,
+            CalculatorErrorType::SingularMatrix => 8,
+            CalculatorErrorType::IterationLimit => 9,
+        }
+    }
+
+    /// Gets the stable string code of this error type.
+    ///
+    /// # Returns
+    ///
+    /// The string error code (e.g. `"division_by_zero"`)
+    pub fn name(&self) -> &'static str {
+        match self {
+            CalculatorErrorType::DivisionByZero => "division_by_zero",
+            CalculatorErrorType::InvalidInput => "invalid_input",
+            CalculatorErrorType::Overflow => "overflow",
+            CalculatorErrorType::Underflow => "underflow",
+            CalculatorErrorType::CalculationError => "calculation_error",
+            CalculatorErrorType::NoSolution => "no_solution",
+            CalculatorErrorType::IncompatibleUnits => "incompatible_units",
+            CalculatorErrorType::SingularMatrix => "singular_matrix",
+            CalculatorErrorType::IterationLimit => "iteration_limit",
+        }
+    }
+
+    /// Gets the default (English) message for this error type.
+    ///
+    /// # Returns
+    ///
+    /// The message used when no details are available
+    pub fn default_message(&self) -> &'static str {
+        i18n::translate(Language::English, &i18n::error_key(self)).unwrap_or(self.name())
+    }
+
+    /// Gets the message for this error type in the current language.
+    ///
+    /// # Returns
+    ///
+    /// The localized message
+    pub fn localized_message(&self) -> String {
+        i18n::error_message(self)
+    }
+
+    /// Gets the severity of this error type.
+    ///
+    /// # Returns
+    ///
+    /// The error severity
+    pub fn severity(&self) -> ErrorSeverity {
+        match self {
+            CalculatorErrorType::InvalidInput
+            | CalculatorErrorType::Underflow
+            | CalculatorErrorType::IncompatibleUnits => ErrorSeverity::Warning,
+            _ => ErrorSeverity::Error,
+        }
+    }
+
+    /// Looks up an error type by its numeric code.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The numeric error code
+    ///
+    /// # Returns
+    ///
+    /// The error type, or `None` if the code is unknown
+    pub fn from_code(code: u16) -> Option<Self> {
+        Self::ALL.iter().find(|error_type| error_type.code() == code).cloned()
+    }
+
+    /// Looks up an error type by its string code.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The string error code
+    ///
+    /// # Returns
+    ///
+    /// The error type, or `None` if the name is unknown
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().find(|error_type| error_type.name() == name).cloned()
+    }
 }
 
 /// Represents an error that occurred during a calculator operation.
@@ -50,16 +186,51 @@ impl CalculatorError {
         }
     }
 
+    /// Creates an error with the message for its type in the current language.
+    ///
+    /// # Arguments
+    ///
+    /// * `error_type` - The type of error that occurred
+    ///
+    /// # Returns
+    ///
+    /// A new `CalculatorError` instance
+    pub fn from_type(error_type: CalculatorErrorType) -> Self {
+        let message = error_type.localized_message();
+        Self::new(error_type, message)
+    }
+
     /// Creates a division by zero error.
     ///
     /// # Returns
     ///
     /// A new `CalculatorError` instance with the `DivisionByZero` error type
     pub fn division_by_zero() -> Self {
-        Self::new(
-            CalculatorErrorType::DivisionByZero,
-            "Division by zero is not allowed",
-        )
+        Self::from_type(CalculatorErrorType::DivisionByZero)
+    }
+
+    /// Creates an error of the given type, with details after the message
+    /// for its type.
+    ///
+    /// Details are written in English, so they are left out when another
+    /// language is selected rather than mixing two languages in one message.
+    ///
+    /// # Arguments
+    ///
+    /// * `error_type` - The type of error that occurred
+    /// * `details` - Optional details about the error
+    ///
+    /// # Returns
+    ///
+    /// A new `CalculatorError` instance
+    pub fn with_details(error_type: CalculatorErrorType, details: Option<&str>) -> Self {
+        let message = match details {
+            Some(details) if i18n::language() == Language::English => {
+                format!("{}: {}", error_type.localized_message(), details)
+            }
+            _ => error_type.localized_message(),
+        };
+        Self::new(error_type, message)
     }
 
     /// Creates an invalid input error.
@@ -72,11 +243,60 @@ impl CalculatorError {
     ///
     /// A new `CalculatorError` instance with the `InvalidInput` error type
     pub fn invalid_input(details: Option<&str>) -> Self {
-        let message = match details {
-            Some(details) => format!("Invalid input: {}", details),
-            None => "Invalid input".to_string(),
-        };
-        Self::new(CalculatorErrorType::InvalidInput, message)
+        Self::with_details(CalculatorErrorType::InvalidInput, details)
+    }
+
+    /// Creates an error for a problem that has no solution.
+    ///
+    /// # Arguments
+    ///
+    /// * `details` - Optional details about the problem
+    ///
+    /// # Returns
+    ///
+    /// A new `CalculatorError` instance with the `NoSolution` error type
+    pub fn no_solution(details: Option<&str>) -> Self {
+        Self::with_details(CalculatorErrorType::NoSolution, details)
+    }
+
+    /// Creates an error for units whose dimensions do not match.
+    ///
+    /// # Arguments
+    ///
+    /// * `details` - Optional details about the units involved
+    ///
+    /// # Returns
+    ///
+    /// A new `CalculatorError` instance with the `IncompatibleUnits` error type
+    pub fn incompatible_units(details: Option<&str>) -> Self {
+        Self::with_details(CalculatorErrorType::IncompatibleUnits, details)
+    }
+
+    /// Creates an error for a matrix that has no inverse.
+    ///
+    /// # Arguments
+    ///
+    /// * `details` - Optional details about the operation
+    ///
+    /// # Returns
+    ///
+    /// A new `CalculatorError` instance with the `SingularMatrix` error type
+    pub fn singular_matrix(details: Option<&str>) -> Self {
+        Self::with_details(CalculatorErrorType::SingularMatrix, details)
+    }
+
+    /// Creates an error for a computation that reached its step or
+    /// iteration limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `details` - Optional details about the limit
+    ///
+    /// # Returns
+    ///
+    /// A new `CalculatorError` instance with the `IterationLimit` error type
+    pub fn iteration_limit(details: Option<&str>) -> Self {
+        Self::with_details(CalculatorErrorType::IterationLimit, details)
     }
 
     /// Creates an overflow error.
@@ -85,10 +305,7 @@ impl CalculatorError {
     ///
     /// A new `CalculatorError` instance with the `Overflow` error type
     pub fn overflow() -> Self {
-        Self::new(
-            CalculatorErrorType::Overflow,
-            "Result is too large to represent",
-        )
+        Self::from_type(CalculatorErrorType::Overflow)
     }
 
     /// Creates an underflow error.
@@ -97,9 +314,18 @@ impl CalculatorError {
     ///
     /// A new `CalculatorError` instance with the `Underflow` error type
     pub fn underflow() -> Self {
+        Self::from_type(CalculatorErrorType::Underflow)
+    }
+
+    /// Creates an error for a result that is not a number (NaN).
+    ///
+    /// # Returns
+    ///
+    /// A new `CalculatorError` instance with the `CalculationError` error type
+    pub fn not_a_number() -> Self {
         Self::new(
-            CalculatorErrorType::Underflow,
-            "Result is too small to represent",
+            CalculatorErrorType::CalculationError,
+            i18n::message(i18n::NOT_A_NUMBER),
         )
     }
 
@@ -121,6 +347,66 @@ impl CalculatorError {
         &self.message
     }
 
+    /// Gets the stable numeric code of the error type.
+    ///
+    /// # Returns
+    ///
+    /// The numeric error code
+    pub fn code(&self) -> u16 {
+        self.error_type.code()
+    }
+
+    /// Converts the error to a coded string for logs and JavaScript.
+    ///
+    /// The string has the form `E003 overflow: <message>`, which
+    /// `from_code_string` parses back into the same error.
+    ///
+    /// # Returns
+    ///
+    /// The coded string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::errors::CalculatorError;
+    ///
+    /// let error = CalculatorError::invalid_input(Some("abc"));
+    /// let coded = error.to_code_string();
+    /// assert_eq!(coded, "E002 invalid_input: Invalid input: abc");
+    ///
+    /// let parsed = CalculatorError::from_code_string(&coded).unwrap();
+    /// assert_eq!(parsed.error_type(), error.error_type());
+    /// assert_eq!(parsed.message(), error.message());
+    /// ```
+    pub fn to_code_string(&self) -> String {
+        format!(
+            "E{:03} {}: {}",
+            self.code(),
+            self.error_type.name(),
+            self.message
+        )
+    }
+
+    /// Parses a coded string produced by `to_code_string`.
+    ///
+    /// The numeric code determines the error type; the string code is
+    /// informational only.
+    ///
+    /// # Arguments
+    ///
+    /// * `coded` - The coded string
+    ///
+    /// # Returns
+    ///
+    /// The error, or `None` if the string is not a coded error
+    pub fn from_code_string(coded: &str) -> Option<Self> {
+        let rest = coded.strip_prefix('E')?;
+        let (code, rest) = rest.split_once(' ')?;
+        let (_, message) = rest.split_once(": ")?;
+        let error_type = CalculatorErrorType::from_code(code.parse().ok()?)?;
+        Some(Self::new(error_type, message))
+    }
+
     /// Converts the error to a JavaScript-friendly string.
     ///
     /// # Returns
@@ -146,7 +432,9 @@ pub type CalcResult<T = f64> = Result<T, CalculatorError>;
 
 /// Converts a string error to a CalculatorError.
 ///
-/// This function is used to convert legacy string errors to the new error type.
+/// Coded strings produced by `CalculatorError::to_code_string` are parsed
+/// by their code. Legacy message strings are still recognized by their
+/// message prefix in any bundled language.
 ///
 /// # Arguments
 ///
@@ -156,19 +444,33 @@ pub type CalcResult<T = f64> = Result<T, CalculatorError>;
 ///
 /// A `CalculatorError` instance
 pub fn string_to_calculator_error(error_message: &str) -> CalculatorError {
-    match error_message {
-        "Division by zero is not allowed" => CalculatorError::division_by_zero(),
-        _ if error_message.starts_with("Invalid input") => {
-            CalculatorError::invalid_input(Some(&error_message[14..]))
-        }
-        _ => CalculatorError::new(
+    if let Some(error) = CalculatorError::from_code_string(error_message) {
+        return error;
+    }
+
+    let legacy = CalculatorErrorType::ALL.iter().find(|error_type| {
+        Language::ALL.iter().any(|language| {
+            let prefix = i18n::message_in(*language, &i18n::error_key(error_type));
+            error_message
+                .strip_prefix(prefix.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(": "))
+        })
+    });
+
+    match legacy {
+        Some(error_type) => CalculatorError::new(error_type.clone(), error_message),
+        None => CalculatorError::new(
             CalculatorErrorType::CalculationError,
             error_message.to_string(),
         ),
     }
 }
 
-/// Converts a CalculatorError to a JavaScript-friendly string.
+/// Converts a CalculatorError to a JavaScript-friendly string in the
+/// current language.
+///
+/// As with `CalculatorError::with_details`, the English message is only
+/// appended when English is selected.
 ///
 /// # Arguments
 ///
@@ -181,10 +483,95 @@ pub fn string_to_calculator_error(error_message: &str) -> CalculatorError {
 #[wasm_bindgen]
 pub fn error_to_js_string_by_type(error_type: CalculatorErrorType, message: &str) -> String {
     match error_type {
-        CalculatorErrorType::DivisionByZero => "Division by zero is not allowed".to_string(),
-        CalculatorErrorType::InvalidInput => format!("Invalid input: {}", message),
-        CalculatorErrorType::Overflow => "Result is too large to represent".to_string(),
-        CalculatorErrorType::Underflow => "Result is too small to represent".to_string(),
-        CalculatorErrorType::CalculationError => format!("Calculation error: {}", message),
+        CalculatorErrorType::InvalidInput
+        | CalculatorErrorType::CalculationError
+        | CalculatorErrorType::NoSolution
+        | CalculatorErrorType::IncompatibleUnits
+        | CalculatorErrorType::SingularMatrix
+        | CalculatorErrorType::IterationLimit
+            if i18n::language() == Language::English =>
+        {
+            format!("{}: {}", error_type.localized_message(), message)
+        }
+        _ => error_type.localized_message(),
+    }
+}
+
+/// Describes one entry of the error catalogue.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorInfo {
+    /// The error type
+    error_type: CalculatorErrorType,
+}
+
+/// Getters for ErrorInfo
+#[wasm_bindgen]
+impl ErrorInfo {
+    /// Gets the error type.
+    pub fn error_type(&self) -> CalculatorErrorType {
+        self.error_type.clone()
+    }
+
+    /// Gets the stable numeric code.
+    pub fn code(&self) -> u16 {
+        self.error_type.code()
+    }
+
+    /// Gets the stable string code.
+    pub fn name(&self) -> String {
+        self.error_type.name().to_string()
+    }
+
+    /// Gets the default (English) message.
+    pub fn default_message(&self) -> String {
+        self.error_type.default_message().to_string()
     }
-} 
\ No newline at end of file
+
+    /// Gets the message in the current language.
+    pub fn message(&self) -> String {
+        self.error_type.localized_message()
+    }
+
+    /// Gets the severity.
+    pub fn severity(&self) -> ErrorSeverity {
+        self.error_type.severity()
+    }
+}
+
+/// Lists every error the calculator can report.
+///
+/// # Returns
+///
+/// One entry per error type, in code order
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::errors::error_catalogue;
+///
+/// let catalogue = error_catalogue();
+/// assert_eq!(catalogue[0].code(), 1);
+/// assert_eq!(catalogue[0].name(), "division_by_zero");
+/// ```
+#[wasm_bindgen]
+pub fn error_catalogue() -> Vec<ErrorInfo> {
+    CalculatorErrorType::ALL
+        .iter()
+        .map(|error_type| ErrorInfo { error_type: error_type.clone() })
+        .collect()
+}
+
+/// Looks up an error type by its stable numeric code.
+///
+/// # Arguments
+///
+/// * `code` - The numeric error code
+///
+/// # Returns
+///
+/// The error type, or `undefined` if the code is unknown
+#[wasm_bindgen]
+pub fn error_type_from_code(code: u16) -> Option<CalculatorErrorType> {
+    CalculatorErrorType::from_code(code)
+}
@@ -0,0 +1,311 @@
+//! Compiled expressions for fast repeated evaluation.
+//!
+//! Plotting, tables of values and batch evaluation compute the same
+//! expression for many values of one variable. Compiling it once:
+//! - Resolves names, functions and argument counts up front, so an unknown
+//!   name is reported when compiling rather than at every value
+//! - Folds the parts that do not mention the variable, so `sin(pi / 6) * x`
+//!   multiplies `x` by a stored 0.5
+//! - Turns the rest into instructions for a small stack machine, with no
+//!   tree walking, name lookups or units bookkeeping per value
+//!
+//! The results, including errors, are the same as those of
+//! `Expr::evaluate` with the variable bound in a `Context`. Calls that bind
+//! a variable of their own, such as `integral(t^2, t, 0, x)`, and
+//! expressions with units or matrices are evaluated as trees.
+
+use wasm_bindgen::prelude::*;
+use super::evaluator::{binds_variable, equation_error, Context, MATRIX_FUNCTIONS};
+use super::functions::{constant, lookup, Function};
+use super::parser::{parse, BinaryOp, Expr};
+use crate::calculator::operations::{check_result, checked_add, checked_divide, checked_multiply, checked_subtract};
+use crate::errors::{CalculatorError, CalcResult};
+use crate::units;
+
+/// One instruction of a compiled expression.
+#[derive(Debug, Clone)]
+enum Instruction {
+    /// Pushes a number
+    Number(f64),
+    /// Pushes the value of the variable
+    Variable,
+    /// Negates the top of the stack
+    Negate,
+    /// Replaces the top two values with the result of an operator
+    Binary(BinaryOp),
+    /// Replaces the given number of values with the result of a function
+    Call(&'static Function, usize),
+    /// Pushes the value of an expression evaluated as a tree
+    Evaluate(Expr),
+}
+
+/// Represents an expression in one variable compiled for repeated
+/// evaluation.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct CompiledExpression {
+    /// The name of the variable
+    variable: String,
+    /// The expression, as printed
+    expression: String,
+    /// The instructions, in postfix order
+    code: Vec<Instruction>,
+}
+
+/// Constructor, getters and evaluation for CompiledExpression
+#[wasm_bindgen]
+impl CompiledExpression {
+    /// Compiles an expression from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The expression text
+    /// * `variable` - The name of the variable
+    ///
+    /// # Returns
+    ///
+    /// The compiled expression, or an error message
+    #[wasm_bindgen(constructor)]
+    pub fn new_wasm(input: &str, variable: &str) -> Result<CompiledExpression, JsValue> {
+        Self::new(input, variable).map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Gets the name of the variable.
+    #[wasm_bindgen(getter)]
+    pub fn variable(&self) -> String {
+        self.variable.clone()
+    }
+
+    /// Gets the expression as printed.
+    #[wasm_bindgen(getter)]
+    pub fn expression(&self) -> String {
+        self.expression.clone()
+    }
+
+    /// Evaluates the expression from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The value of the variable
+    ///
+    /// # Returns
+    ///
+    /// The value of the expression, or an error message
+    #[wasm_bindgen(js_name = eval)]
+    pub fn eval_wasm(&self, x: f64) -> Result<f64, JsValue> {
+        self.eval(x).map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Evaluates the expression for each of many values of the variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `xs` - The values of the variable
+    ///
+    /// # Returns
+    ///
+    /// The value of the expression at each of `xs`, NaN where it raises an
+    /// error
+    pub fn eval_many(&self, xs: &[f64]) -> Vec<f64> {
+        let mut stack = Vec::new();
+        xs.iter().map(|&x| self.run(x, &mut stack).unwrap_or(f64::NAN)).collect()
+    }
+}
+
+impl CompiledExpression {
+    /// Parses and compiles an expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The expression text
+    /// * `variable` - The name of the variable
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(compiled)` - The compiled expression
+    /// * `Err(error)` - A syntax error, or an error as for `Expr::compile`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::expression::CompiledExpression;
+    ///
+    /// let square = CompiledExpression::new("x^2 + 1", "x").unwrap();
+    /// assert_eq!(square.eval(3.0).unwrap(), 10.0);
+    /// assert_eq!(square.eval_many(&[0.0, 1.0, 2.0]), vec![1.0, 2.0, 5.0]);
+    /// ```
+    pub fn new(input: &str, variable: &str) -> CalcResult<Self> {
+        parse(input)?.compile(variable)
+    }
+
+    /// Evaluates the expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The value of the variable
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(value)` - The value of the expression
+    /// * `Err(error)` - The error `Expr::evaluate` raises for this value
+    pub fn eval(&self, x: f64) -> CalcResult {
+        self.run(x, &mut Vec::new())
+    }
+
+    /// Runs the instructions on a stack, which is left empty for reuse.
+    fn run(&self, x: f64, stack: &mut Vec<f64>) -> CalcResult {
+        stack.clear();
+        for instruction in &self.code {
+            let value = match instruction {
+                Instruction::Number(value) => *value,
+                Instruction::Variable => x,
+                Instruction::Negate => -pop(stack),
+                Instruction::Binary(op) => {
+                    let b = pop(stack);
+                    let a = pop(stack);
+                    match op {
+                        BinaryOp::Add => checked_add(a, b)?,
+                        BinaryOp::Subtract => checked_subtract(a, b)?,
+                        BinaryOp::Multiply => checked_multiply(a, b)?,
+                        BinaryOp::Divide => checked_divide(a, b)?,
+                        BinaryOp::Power => check_result(a.powf(b), a != 0.0)?,
+                    }
+                }
+                Instruction::Call(function, count) => {
+                    let start = stack.len() - count;
+                    let value = check_result((function.apply)(&stack[start..])?, false)?;
+                    stack.truncate(start);
+                    value
+                }
+                Instruction::Evaluate(expr) => {
+                    let mut context = Context::new();
+                    context.set(&self.variable, x);
+                    expr.evaluate(&context)?
+                }
+            };
+            stack.push(value);
+        }
+        Ok(pop(stack))
+    }
+}
+
+impl Expr {
+    /// Compiles the expression for repeated evaluation with different
+    /// values of one variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `variable` - The name of the variable
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(compiled)` - The compiled expression
+    /// * `Err(error)` - An invalid input error for an unknown name, a wrong
+    ///   argument count or an equation outside `solve`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::expression::parse;
+    ///
+    /// let compiled = parse("1 / x").unwrap().compile("x").unwrap();
+    /// assert_eq!(compiled.eval(4.0).unwrap(), 0.25);
+    /// assert!(compiled.eval(0.0).is_err());
+    /// assert!(parse("x + y").unwrap().compile("x").is_err());
+    /// ```
+    pub fn compile(&self, variable: &str) -> CalcResult<CompiledExpression> {
+        let mut code = Vec::new();
+        if self.is_compilable(variable) {
+            self.emit(variable, &mut code)?;
+        } else {
+            code.push(Instruction::Evaluate(self.clone()));
+        }
+        Ok(CompiledExpression { variable: variable.to_string(), expression: self.to_string(), code })
+    }
+
+    /// Checks whether the expression is made only of plain numbers, so it
+    /// can be run as instructions; units, conversions and matrices are
+    /// left to the tree evaluator.
+    fn is_compilable(&self, variable: &str) -> bool {
+        match self {
+            Expr::Number(_) | Expr::Equation(_, _) => true,
+            // Unknown names are reported by `emit`
+            Expr::Variable(name) => name == variable || constant(name).is_some() || units::lookup(name).is_err(),
+            Expr::Negate(operand) | Expr::Factorial(operand) => operand.is_compilable(variable),
+            Expr::Binary(_, left, right) => left.is_compilable(variable) && right.is_compilable(variable),
+            Expr::Call(name, arguments) if binds_variable(name, arguments) => true,
+            Expr::Call(name, _) if MATRIX_FUNCTIONS.contains(&name.as_str()) => false,
+            Expr::Call(_, arguments) => arguments.iter().all(|argument| argument.is_compilable(variable)),
+            Expr::Convert(_, _) | Expr::Matrix(_) => false,
+        }
+    }
+
+    /// Appends the instructions for the expression, folding parts that do
+    /// not mention the variable into numbers.
+    fn emit(&self, variable: &str, code: &mut Vec<Instruction>) -> CalcResult<()> {
+        if !matches!(self, Expr::Number(_)) && !self.depends_on(variable) {
+            // A part that fails is left to fail at each evaluation
+            if let Ok(value) = self.evaluate(&Context::new()) {
+                code.push(Instruction::Number(value));
+                return Ok(());
+            }
+        }
+
+        match self {
+            Expr::Number(value) => code.push(Instruction::Number(*value)),
+            Expr::Variable(name) if name == variable => code.push(Instruction::Variable),
+            Expr::Variable(name) => match constant(name) {
+                Some(value) => code.push(Instruction::Number(value)),
+                None => {
+                    return Err(CalculatorError::invalid_input(Some(&format!("unknown variable '{}'", name))))
+                }
+            },
+            Expr::Negate(operand) => {
+                operand.emit(variable, code)?;
+                code.push(Instruction::Negate);
+            }
+            Expr::Binary(op, left, right) => {
+                left.emit(variable, code)?;
+                right.emit(variable, code)?;
+                code.push(Instruction::Binary(*op));
+            }
+            Expr::Factorial(operand) => {
+                operand.emit(variable, code)?;
+                code.push(Instruction::Call(function("fact", 1)?, 1));
+            }
+            Expr::Call(name, arguments) if binds_variable(name, arguments) => {
+                code.push(Instruction::Evaluate(self.clone()));
+            }
+            Expr::Call(name, arguments) => {
+                let function = function(name, arguments.len())?;
+                for argument in arguments {
+                    argument.emit(variable, code)?;
+                }
+                code.push(Instruction::Call(function, arguments.len()));
+            }
+            Expr::Equation(_, _) => return Err(equation_error()),
+            // Ruled out by `is_compilable`
+            Expr::Convert(_, _) | Expr::Matrix(_) => code.push(Instruction::Evaluate(self.clone())),
+        }
+        Ok(())
+    }
+}
+
+/// Looks up a built-in function and checks its argument count.
+fn function(name: &str, count: usize) -> CalcResult<&'static Function> {
+    let function = lookup(name)
+        .ok_or_else(|| CalculatorError::invalid_input(Some(&format!("unknown function '{}'", name))))?;
+    if count < function.min_args || count > function.max_args {
+        return Err(CalculatorError::invalid_input(Some(&format!(
+            "wrong number of arguments for '{}'",
+            name
+        ))));
+    }
+    Ok(function)
+}
+
+/// Takes the top value off the stack, which the compiler guarantees is
+/// there.
+fn pop(stack: &mut Vec<f64>) -> f64 {
+    stack.pop().unwrap_or(f64::NAN)
+}
@@ -0,0 +1,788 @@
+//! Evaluator for calculator expressions.
+//!
+//! This module computes the value of an expression tree:
+//! - Variables are looked up in a `Context`, then among the constants, then
+//!   among the units, so `5 km` is a quantity of kilometres
+//! - Arithmetic uses the checked operations, so overflow, underflow and
+//!   division by zero are reported as errors
+//! - Units are carried through arithmetic, and adding quantities of
+//!   different dimensions is an error
+//! - Functions take plain numbers; angles are passed in radians, and `abs`,
+//!   `sqrt`, `cbrt`, `min` and `max` also accept other units
+//! - Function results that are not finite numbers are reported as errors
+//! - In complex mode, `i` and `j` are the imaginary unit, and functions
+//!   that have no real value at an argument, such as `sqrt(-4)`, give
+//!   their principal complex value instead of an error
+//! - Matrix literals are matrices of plain numbers; `+` and `-` act entry by
+//!   entry, `*` is the matrix product or scales by a number, `/` divides by
+//!   a number and `^` takes whole powers of square matrices, and `det`,
+//!   `inv`, `transpose`, `rank`, `dot`, `cross`, `solve`, `norm` and
+//!   `hadamard` take matrices as arguments
+//! - `solve(x^3 - 2x = 5, x, 2)` finds the value of a variable that makes
+//!   an equation true, starting from an optional guess
+//! - `derivative(x^2, x, 3)`, `integral(x^2, x, 0, 1)`, `sum(k^2, k, 1, 100)`
+//!   and `product(k, k, 1, 5)` evaluate their first argument for many
+//!   values of the variable named second; nested calls share one budget of
+//!   evaluations, so their work cannot multiply unchecked
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use super::functions::{constant, lookup};
+use super::parser::{BinaryOp, Expr};
+use crate::calculator::complex::Complex;
+use crate::calculator::linalg::Matrix;
+use crate::calculator::calculus::{derivative, integrate, product, sum};
+use crate::calculator::solver::find_root;
+use crate::calculator::operations::check_result;
+use crate::errors::{CalculatorError, CalcResult};
+use crate::units::{self, Dimension, Quantity};
+
+/// The number of evaluations shared by nested `solve`, `derivative`,
+/// `integral`, `sum` and `product` calls.
+const MAX_EVALUATIONS: u64 = 10_000_000;
+
+/// Variable bindings for evaluating expressions.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Context {
+    /// Variable values by name
+    variables: HashMap<String, f64>,
+    /// Whether complex numbers are enabled
+    complex_mode: bool,
+    /// The evaluations left to the outermost call that binds a variable,
+    /// shared with the calls nested inside it
+    budget: Option<Rc<Cell<u64>>>,
+}
+
+impl Context {
+    /// Creates a context without variables.
+    ///
+    /// # Returns
+    ///
+    /// An empty context
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Binds a variable, replacing any previous value.
+    ///
+    /// Variables shadow constants of the same name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The variable name
+    /// * `value` - The value
+    pub fn set(&mut self, name: &str, value: f64) {
+        self.variables.insert(name.to_string(), value);
+    }
+
+    /// Gets the value of a variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The variable name
+    ///
+    /// # Returns
+    ///
+    /// The value, or `None` if the variable is not bound
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.variables.get(name).copied()
+    }
+
+    /// Enables or disables complex numbers.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether `i` is the imaginary unit and functions may
+    ///   return complex values
+    pub fn set_complex_mode(&mut self, enabled: bool) {
+        self.complex_mode = enabled;
+    }
+
+    /// Checks whether complex numbers are enabled.
+    ///
+    /// # Returns
+    ///
+    /// `true` in complex mode
+    pub fn complex_mode(&self) -> bool {
+        self.complex_mode
+    }
+}
+
+impl Expr {
+    /// Evaluates the expression to a plain number.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The variable bindings
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(value)` - The value of the expression
+    /// * `Err(error)` - An invalid input error for unknown names, wrong
+    ///   argument counts or a complex result, an incompatible units error if
+    ///   the result has units, or the error raised by an operation or function
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::expression::{parse, Context};
+    ///
+    /// let mut context = Context::new();
+    /// context.set("x", 3.0);
+    /// assert_eq!(parse("x^2 + 1").unwrap().evaluate(&context).unwrap(), 10.0);
+    /// ```
+    pub fn evaluate(&self, context: &Context) -> CalcResult {
+        if context.complex_mode() {
+            let value = self.evaluate_complex(context)?;
+            if !value.is_real() {
+                return Err(CalculatorError::invalid_input(Some(&format!(
+                    "the result {} is not a real number",
+                    value
+                ))));
+            }
+            return Ok(value.re());
+        }
+
+        let quantity = self.evaluate_quantity(context)?;
+        if !quantity.is_number() {
+            return Err(CalculatorError::incompatible_units(Some(&format!(
+                "the result {} is not a plain number",
+                quantity
+            ))));
+        }
+        Ok(quantity.si_value())
+    }
+
+    /// Evaluates the expression to a quantity with units.
+    ///
+    /// Unless the expression ends in a conversion, the result's units are
+    /// simplified for display.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The variable bindings
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(quantity)` - The value of the expression
+    /// * `Err(error)` - An invalid input error for unknown names or wrong
+    ///   argument counts, an incompatible units error for mismatched
+    ///   dimensions, or the error raised by an operation or function
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::expression::{parse, Context};
+    ///
+    /// let speed = parse("5 km / 20 min").unwrap().evaluate_quantity(&Context::new()).unwrap();
+    /// assert_eq!(speed.to_string(), "15 km/h");
+    /// ```
+    pub fn evaluate_quantity(&self, context: &Context) -> CalcResult<Quantity> {
+        let quantity = self.quantity(context)?;
+        Ok(match self {
+            Expr::Convert(_, _) => quantity,
+            _ => quantity.simplify(),
+        })
+    }
+
+    /// Evaluates the expression to a complex number.
+    ///
+    /// Outside complex mode, this is the real value of the expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The variable bindings and mode
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(value)` - The value of the expression
+    /// * `Err(error)` - An invalid input error for unknown names, wrong
+    ///   argument counts or functions that do not take complex arguments,
+    ///   an incompatible units error for units other than angles, or the
+    ///   error raised by an operation or function
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::expression::{parse, Context};
+    ///
+    /// let mut context = Context::new();
+    /// context.set_complex_mode(true);
+    /// let root = parse("sqrt(-4)").unwrap().evaluate_complex(&context).unwrap();
+    /// assert_eq!(root.to_string(), "2i");
+    /// ```
+    pub fn evaluate_complex(&self, context: &Context) -> CalcResult<Complex> {
+        if !context.complex_mode() {
+            return self.evaluate(context).map(Complex::real);
+        }
+        self.complex(context)
+    }
+
+    /// Evaluates the expression to a matrix.
+    ///
+    /// A number is a 1×1 matrix, and a list such as `[1, 2]` is a column.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The variable bindings
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(matrix)` - The value of the expression
+    /// * `Err(error)` - An invalid input error for unknown names, wrong
+    ///   argument counts or mismatched shapes, an incompatible units error
+    ///   for entries with units, a singular matrix error, or the error
+    ///   raised by an operation or function
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::expression::{parse, Context};
+    ///
+    /// let product = parse("[[1, 2], [3, 4]] * [1, 1]").unwrap().evaluate_matrix(&Context::new()).unwrap();
+    /// assert_eq!(product.to_string(), "[[3], [7]]");
+    /// ```
+    pub fn evaluate_matrix(&self, context: &Context) -> CalcResult<Matrix> {
+        self.value(context)?.into_matrix()
+    }
+
+    /// Computes the complex value of a node in complex mode.
+    pub(super) fn complex(&self, context: &Context) -> CalcResult<Complex> {
+        let value = match self {
+            Expr::Number(value) => Complex::real(*value),
+            Expr::Variable(name) => match context.get(name) {
+                Some(value) => Complex::real(value),
+                None if name == "i" || name == "j" => Complex::I,
+                None => Complex::real(without_units(&resolve(name, context)?, "complex numbers")?),
+            },
+            Expr::Call(name, arguments) if binds_variable(name, arguments) => {
+                Complex::real(call_binding(name, arguments, context)?)
+            }
+            // These are errors before their operands are looked at
+            Expr::Convert(_, _) | Expr::Matrix(_) | Expr::Equation(_, _) => return self.apply_complex(Vec::new()),
+            _ => {
+                let operands = self
+                    .operands()
+                    .into_iter()
+                    .map(|operand| operand.complex(context))
+                    .collect::<CalcResult<Vec<Complex>>>()?;
+                return self.apply_complex(operands);
+            }
+        };
+        value.checked()
+    }
+
+    /// Computes the complex value of a node that is not a leaf from the
+    /// values of its `operands`, in complex mode.
+    pub(super) fn apply_complex(&self, operands: Vec<Complex>) -> CalcResult<Complex> {
+        let value = match (self, operands.as_slice()) {
+            (Expr::Negate(_), [operand]) => Complex::real(0.0).subtract(operand),
+            (Expr::Binary(op, _, _), [a, b]) => match op {
+                BinaryOp::Add => a.add(b),
+                BinaryOp::Subtract => a.subtract(b),
+                BinaryOp::Multiply => a.multiply(b),
+                BinaryOp::Divide => a.divide(b)?,
+                BinaryOp::Power => complex_power(a, b)?,
+            },
+            (Expr::Factorial(_), [operand]) => call_complex("fact", &[*operand])?,
+            (Expr::Call(name, _), values) => call_complex(name, values)?,
+            (Expr::Convert(_, _), _) => {
+                return Err(CalculatorError::incompatible_units(Some(
+                    "unit conversions are not available in complex mode",
+                )))
+            }
+            (Expr::Matrix(_), _) => {
+                return Err(CalculatorError::invalid_input(Some("matrices are not available in complex mode")))
+            }
+            (Expr::Equation(_, _), _) => return Err(equation_error()),
+            _ => return Err(operand_error(self)),
+        };
+        value.checked()
+    }
+
+    /// Computes the unsimplified quantity of a node, which must not be a
+    /// matrix.
+    fn quantity(&self, context: &Context) -> CalcResult<Quantity> {
+        self.value(context)?.into_quantity()
+    }
+
+    /// Computes the value of a node, which is a quantity or a matrix.
+    pub(super) fn value(&self, context: &Context) -> CalcResult<Value> {
+        Ok(match self {
+            Expr::Number(value) => Value::Quantity(Quantity::number(*value)),
+            Expr::Variable(name) => Value::Quantity(resolve(name, context)?),
+            Expr::Call(name, arguments) if binds_variable(name, arguments) => {
+                Value::Quantity(Quantity::number(call_binding(name, arguments, context)?))
+            }
+            Expr::Equation(_, _) => return Err(equation_error()),
+            _ => {
+                let operands = self
+                    .operands()
+                    .into_iter()
+                    .map(|operand| operand.value(context))
+                    .collect::<CalcResult<Vec<Value>>>()?;
+                return self.apply(operands);
+            }
+        })
+    }
+
+    /// Computes the value of a node that is not a leaf from the values of
+    /// its `operands`.
+    pub(super) fn apply(&self, operands: Vec<Value>) -> CalcResult<Value> {
+        let mut operands = operands.into_iter();
+        let mut next = || operands.next().ok_or_else(|| operand_error(self));
+        Ok(match self {
+            Expr::Negate(_) => match next()? {
+                Value::Quantity(quantity) => Value::Quantity(quantity.negate()),
+                Value::Matrix(matrix) => Value::Matrix(matrix.scale(-1.0)?),
+            },
+            Expr::Binary(op, _, _) => {
+                let left = next()?;
+                binary(*op, left, next()?)?
+            }
+            Expr::Factorial(_) => Value::Quantity(call("fact", &[next()?.into_quantity()?])?),
+            Expr::Call(name, _) => {
+                let values: Vec<Value> = operands.collect();
+                if MATRIX_FUNCTIONS.contains(&name.as_str()) {
+                    return call_matrix(name, &values);
+                }
+                let quantities = values
+                    .into_iter()
+                    .map(|value| match value {
+                        Value::Quantity(quantity) => Ok(quantity),
+                        Value::Matrix(_) => Err(CalculatorError::invalid_input(Some(&format!(
+                            "'{}' does not accept a matrix",
+                            name
+                        )))),
+                    })
+                    .collect::<CalcResult<Vec<Quantity>>>()?;
+                Value::Quantity(call(name, &quantities)?)
+            }
+            Expr::Convert(_, target) => Value::Quantity(next()?.into_quantity()?.convert_to(&target.units()?)?),
+            Expr::Matrix(rows) => {
+                let rows = rows
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .map(|_| without_units(&next()?.into_quantity()?, "matrix entries"))
+                            .collect::<CalcResult<Vec<f64>>>()
+                    })
+                    .collect::<CalcResult<Vec<Vec<f64>>>>()?;
+                Value::Matrix(Matrix::from_rows(&rows)?)
+            }
+            Expr::Equation(_, _) => return Err(equation_error()),
+            Expr::Number(_) | Expr::Variable(_) => return Err(operand_error(self)),
+        })
+    }
+
+    /// Lists the operands whose values `apply` and `apply_complex` take:
+    /// none for a leaf, and the entries of a matrix row by row.
+    pub(super) fn operands(&self) -> Vec<&Expr> {
+        match self {
+            Expr::Negate(operand) | Expr::Factorial(operand) | Expr::Convert(operand, _) => vec![operand],
+            Expr::Binary(_, left, right) => vec![left, right],
+            Expr::Call(name, arguments) if !binds_variable(name, arguments) => arguments.iter().collect(),
+            Expr::Matrix(rows) => rows.iter().flatten().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Computes the units of a conversion target such as `km/h` or `m^2`.
+    fn units(&self) -> CalcResult<Quantity> {
+        match self {
+            Expr::Variable(name) => units::lookup(name).map(|unit| Quantity::new(1.0, unit)),
+            Expr::Binary(BinaryOp::Multiply, left, right) => left.units()?.multiply(&right.units()?),
+            Expr::Binary(BinaryOp::Divide, left, right) => left.units()?.divide(&right.units()?),
+            Expr::Binary(BinaryOp::Power, base, exponent) => match exponent.as_ref() {
+                Expr::Number(_) | Expr::Negate(_) => base.units()?.power(&exponent.quantity(&Context::new())?),
+                _ => Err(conversion_target_error()),
+            },
+            _ => Err(conversion_target_error()),
+        }
+    }
+}
+
+/// The value of an expression node.
+#[derive(Clone)]
+pub(super) enum Value {
+    /// A number, possibly with units
+    Quantity(Quantity),
+    /// A matrix of plain numbers
+    Matrix(Matrix),
+}
+
+/// The functions that take matrices as arguments.
+pub(super) const MATRIX_FUNCTIONS: [&str; 9] = ["det", "inv", "transpose", "rank", "dot", "cross", "solve", "norm", "hadamard"];
+
+/// Resolves a name to a variable, a constant or a unit.
+fn resolve(name: &str, context: &Context) -> CalcResult<Quantity> {
+    if let Some(value) = context.get(name).or_else(|| constant(name)) {
+        return Ok(Quantity::number(value));
+    }
+    units::lookup(name)
+        .map(|unit| Quantity::new(1.0, unit))
+        .map_err(|_| CalculatorError::invalid_input(Some(&format!("unknown variable '{}'", name))))
+}
+
+/// Checks whether a call binds a variable named by its second argument,
+/// as in `sum(k^2, k, 1, 100)`. A call to `solve` does so for an equation,
+/// as in `solve(x^2 = 2, x)`, but not for a linear system, as in
+/// `solve(A, b)`.
+pub(super) fn binds_variable(name: &str, arguments: &[Expr]) -> bool {
+    match name {
+        "solve" => {
+            matches!(arguments.first(), Some(Expr::Equation(_, _))) || matches!(arguments.get(1), Some(Expr::Variable(_)))
+        }
+        "derivative" | "integral" | "sum" | "product" => true,
+        _ => false,
+    }
+}
+
+/// Calls a function that evaluates its first argument for many values of
+/// a variable:
+/// - `solve(equation, x, guess)` solves for `x`, starting from the guess or
+///   0; an expression without `=` is solved for zero
+/// - `derivative(f, x, a)` differentiates at `x = a`
+/// - `integral(f, x, a, b)` integrates from `x = a` to `x = b`
+/// - `sum(f, k, m, n)` and `product(f, k, m, n)` run over `k = m, …, n`
+fn call_binding(name: &str, arguments: &[Expr], context: &Context) -> CalcResult {
+    let Some(Expr::Variable(variable)) = arguments.get(1) else {
+        return Err(CalculatorError::invalid_input(Some(&format!(
+            "'{}' expects a variable name as its second argument",
+            name
+        ))));
+    };
+    let what = format!("the arguments of '{}'", name);
+    let limits = arguments[2..]
+        .iter()
+        .map(|limit| without_units(&limit.quantity(context)?, &what))
+        .collect::<CalcResult<Vec<f64>>>()?;
+
+    let mut bound = context.clone();
+    let budget = Rc::clone(bound.budget.get_or_insert_with(|| Rc::new(Cell::new(MAX_EVALUATIONS))));
+    let at = |x: f64| -> CalcResult {
+        if budget.get() == 0 {
+            return Err(CalculatorError::iteration_limit(Some(&format!(
+                "'{}' needs more than {} evaluations, counting nested calls",
+                name,
+                MAX_EVALUATIONS
+            ))));
+        }
+        budget.set(budget.get() - 1);
+        bound.set(variable, x);
+        match &arguments[0] {
+            Expr::Equation(left, right) if name == "solve" => {
+                Ok(left.quantity(&bound)?.subtract(&right.quantity(&bound)?)?.si_value())
+            }
+            body => plain_number(name, &body.quantity(&bound)?),
+        }
+    };
+
+    match (name, limits.as_slice()) {
+        ("solve", []) => find_root(at, 0.0),
+        ("solve", [guess]) => find_root(at, *guess),
+        ("derivative", [point]) => Ok(derivative(at, *point)?.value()),
+        ("integral", [from, to]) => Ok(integrate(at, *from, *to)?.value()),
+        ("sum", [from, to]) => sum(at, *from, *to),
+        ("product", [from, to]) => product(at, *from, *to),
+        _ => Err(CalculatorError::invalid_input(Some(&format!(
+            "wrong number of arguments for '{}'",
+            name
+        )))),
+    }
+}
+
+/// Builds the error for an equation outside `solve`.
+pub(super) fn equation_error() -> CalculatorError {
+    CalculatorError::invalid_input(Some("an equation can only be used in solve(equation, variable, guess)"))
+}
+
+/// Builds the error for applying a node to the wrong number of operand
+/// values.
+fn operand_error(expr: &Expr) -> CalculatorError {
+    CalculatorError::invalid_input(Some(&format!("'{}' was given the wrong number of operands", expr)))
+}
+
+/// Builds the error for a conversion target that is not made of units.
+fn conversion_target_error() -> CalculatorError {
+    CalculatorError::invalid_input(Some("a conversion must be followed by units, such as 'to km/h'"))
+}
+
+/// Calls a built-in function after checking its argument count.
+///
+/// `abs`, `sqrt`, `cbrt`, `min` and `max` keep the units of their
+/// arguments. Other functions take plain numbers, with angles in radians.
+fn call(name: &str, arguments: &[Quantity]) -> CalcResult<Quantity> {
+    let function = lookup(name)
+        .ok_or_else(|| CalculatorError::invalid_input(Some(&format!("unknown function '{}'", name))))?;
+
+    if arguments.len() < function.min_args || arguments.len() > function.max_args {
+        return Err(CalculatorError::invalid_input(Some(&format!(
+            "wrong number of arguments for '{}'",
+            name
+        ))));
+    }
+
+    if arguments.iter().any(|argument| !argument.is_number()) {
+        match name {
+            "abs" => return Ok(arguments[0].abs()),
+            "sqrt" => return arguments[0].power(&Quantity::number(0.5)),
+            "cbrt" => return arguments[0].power(&Quantity::number(1.0 / 3.0)),
+            "min" | "max" => return extreme(name, arguments),
+            _ => {}
+        }
+    }
+
+    let values = arguments
+        .iter()
+        .map(|argument| plain_number(name, argument))
+        .collect::<CalcResult<Vec<f64>>>()?;
+    Ok(Quantity::number(check_result((function.apply)(&values)?, false)?))
+}
+
+/// Finds the smallest or largest of quantities with the same dimension.
+fn extreme(name: &str, arguments: &[Quantity]) -> CalcResult<Quantity> {
+    let mut best = arguments[0].clone();
+    for argument in &arguments[1..] {
+        // Comparing through subtraction also checks the dimensions
+        let difference = argument.subtract(&best)?.si_value();
+        if (name == "min" && difference < 0.0) || (name == "max" && difference > 0.0) {
+            best = argument.clone();
+        }
+    }
+    Ok(best)
+}
+
+/// Converts a function argument to a plain number, taking angles in radians.
+fn plain_number(name: &str, argument: &Quantity) -> CalcResult {
+    let dimension = argument.dimension()?;
+    if dimension == Dimension::NONE || dimension == Dimension::ANGLE {
+        Ok(argument.si_value())
+    } else {
+        Err(CalculatorError::incompatible_units(Some(&format!(
+            "'{}' expects a plain number, not {}",
+            name, argument
+        ))))
+    }
+}
+
+/// Converts a quantity to a number for values that cannot carry units,
+/// such as complex numbers and matrix entries, taking angles in radians.
+fn without_units(quantity: &Quantity, what: &str) -> CalcResult {
+    let dimension = quantity.dimension()?;
+    if dimension == Dimension::NONE || dimension == Dimension::ANGLE {
+        Ok(quantity.si_value())
+    } else {
+        Err(CalculatorError::incompatible_units(Some(&format!(
+            "{} cannot have units such as {}",
+            what,
+            quantity.unit()
+        ))))
+    }
+}
+
+/// Applies a binary operator to quantities or matrices.
+///
+/// Matrices add and subtract entry by entry, multiply as a matrix product,
+/// scale by numbers and take whole powers.
+fn binary(op: BinaryOp, a: Value, b: Value) -> CalcResult<Value> {
+    Ok(match (op, a, b) {
+        (op, Value::Quantity(a), Value::Quantity(b)) => Value::Quantity(match op {
+            BinaryOp::Add => a.add(&b),
+            BinaryOp::Subtract => a.subtract(&b),
+            BinaryOp::Multiply => a.multiply(&b),
+            BinaryOp::Divide => a.divide(&b),
+            BinaryOp::Power => a.power(&b),
+        }?),
+        (BinaryOp::Add, Value::Matrix(a), Value::Matrix(b)) => Value::Matrix(a.add(&b)?),
+        (BinaryOp::Subtract, Value::Matrix(a), Value::Matrix(b)) => Value::Matrix(a.subtract(&b)?),
+        (BinaryOp::Multiply, Value::Matrix(a), Value::Matrix(b)) => Value::Matrix(a.multiply(&b)?),
+        (BinaryOp::Multiply, Value::Quantity(factor), Value::Matrix(matrix))
+        | (BinaryOp::Multiply, Value::Matrix(matrix), Value::Quantity(factor)) => {
+            Value::Matrix(matrix.scale(without_units(&factor, "matrix entries")?)?)
+        }
+        (BinaryOp::Divide, Value::Matrix(matrix), Value::Quantity(divisor)) => {
+            let divisor = without_units(&divisor, "matrix entries")?;
+            if divisor == 0.0 {
+                return Err(CalculatorError::division_by_zero());
+            }
+            Value::Matrix(matrix.scale(1.0 / divisor)?)
+        }
+        (BinaryOp::Power, Value::Matrix(matrix), Value::Quantity(exponent)) => {
+            let exponent = without_units(&exponent, "matrix powers")?;
+            if exponent.fract() != 0.0 || exponent.abs() > i32::MAX as f64 {
+                return Err(CalculatorError::invalid_input(Some("a matrix can only be raised to a whole power")));
+            }
+            Value::Matrix(matrix.power(exponent as i32)?)
+        }
+        (op, a, b) => {
+            return Err(CalculatorError::invalid_input(Some(&format!(
+                "'{}' does not apply to {} and {}",
+                symbol(op),
+                a.kind(),
+                b.kind()
+            ))))
+        }
+    })
+}
+
+/// Gets the symbol of an operator for error messages.
+fn symbol(op: BinaryOp) -> &'static str {
+    match op {
+        BinaryOp::Add => "+",
+        BinaryOp::Subtract => "-",
+        BinaryOp::Multiply => "*",
+        BinaryOp::Divide => "/",
+        BinaryOp::Power => "^",
+    }
+}
+
+impl Value {
+    /// Describes the kind of value for error messages.
+    fn kind(&self) -> &'static str {
+        match self {
+            Value::Quantity(_) => "a number",
+            Value::Matrix(_) => "a matrix",
+        }
+    }
+
+    /// Converts the value to a quantity, which it must be.
+    fn into_quantity(self) -> CalcResult<Quantity> {
+        match self {
+            Value::Quantity(quantity) => Ok(quantity),
+            Value::Matrix(matrix) => Err(CalculatorError::invalid_input(Some(&format!(
+                "the matrix {} is not a number",
+                matrix
+            )))),
+        }
+    }
+
+    /// Converts the value to a matrix, taking a number as a 1×1 matrix.
+    fn into_matrix(self) -> CalcResult<Matrix> {
+        match self {
+            Value::Matrix(matrix) => Ok(matrix),
+            Value::Quantity(quantity) => Matrix::new(1, 1, &[without_units(&quantity, "matrix entries")?]),
+        }
+    }
+}
+
+/// Calls a function that takes matrices after checking its argument count.
+///
+/// `dot`, `cross` and `norm` take vectors, written as a single row or
+/// column, and `cross` and `solve` give a column.
+fn call_matrix(name: &str, arguments: &[Value]) -> CalcResult<Value> {
+    let expected = match name {
+        "dot" | "cross" | "solve" | "hadamard" => 2,
+        _ => 1,
+    };
+    if arguments.len() != expected {
+        return Err(CalculatorError::invalid_input(Some(&format!(
+            "wrong number of arguments for '{}'",
+            name
+        ))));
+    }
+
+    let matrices = arguments
+        .iter()
+        .map(|argument| argument.clone().into_matrix())
+        .collect::<CalcResult<Vec<Matrix>>>()?;
+    let vector = |matrix: &Matrix| {
+        matrix.as_vector().ok_or_else(|| {
+            CalculatorError::invalid_input(Some(&format!("'{}' expects a vector, not the matrix {}", name, matrix)))
+        })
+    };
+
+    let number = |value: f64| Value::Quantity(Quantity::number(value));
+    Ok(match (name, matrices.as_slice()) {
+        ("det", [a]) => number(a.determinant()?),
+        ("inv", [a]) => Value::Matrix(a.inverse()?),
+        ("transpose", [a]) => Value::Matrix(a.transpose()),
+        ("rank", [a]) => number(a.rank() as f64),
+        ("norm", [a]) => number(vector(a)?.norm()),
+        ("dot", [a, b]) => number(vector(a)?.dot(&vector(b)?)?),
+        ("cross", [a, b]) => Value::Matrix(Matrix::column(&vector(a)?.cross(&vector(b)?)?)?),
+        ("solve", [a, b]) => Value::Matrix(Matrix::column(&a.solve(&vector(b)?)?)?),
+        // hadamard
+        _ => Value::Matrix(matrices[0].multiply_elements(&matrices[1])?),
+    })
+}
+
+/// Raises to a power in complex mode, keeping the real power where it is
+/// defined, so `(-8)^(1/3)` is the principal root but `2^0.5` is unchanged.
+fn complex_power(base: &Complex, exponent: &Complex) -> CalcResult<Complex> {
+    if base.is_real() && exponent.is_real() {
+        if let Ok(power) = Quantity::number(base.re()).power(&Quantity::number(exponent.re())) {
+            return Ok(Complex::real(power.si_value()));
+        }
+    }
+    base.power(exponent)
+}
+
+/// Calls a built-in function in complex mode.
+///
+/// `re`, `im`, `conj`, `arg`, `abs` and `polar` take complex numbers.
+/// Other functions are computed on real numbers where they have a real
+/// value, so `cbrt(-8)` is -2, and otherwise give their principal complex
+/// value, so `sqrt(-4)` is 2i.
+fn call_complex(name: &str, arguments: &[Complex]) -> CalcResult<Complex> {
+    match (name, arguments) {
+        ("re", [z]) => return Ok(Complex::real(z.re())),
+        ("im", [z]) => return Ok(Complex::real(z.im())),
+        ("conj", [z]) => return Ok(z.conjugate()),
+        ("arg", [z]) => return Ok(Complex::real(z.argument())),
+        ("abs", [z]) => return Ok(Complex::real(z.modulus())),
+        ("polar", [modulus, argument]) if modulus.is_real() && argument.is_real() => {
+            return Ok(Complex::from_polar(modulus.re(), argument.re()))
+        }
+        ("polar", [_, _]) => {
+            return Err(CalculatorError::invalid_input(Some("the modulus and argument must be real")))
+        }
+        ("re" | "im" | "conj" | "arg" | "polar", _) => {
+            return Err(CalculatorError::invalid_input(Some(&format!(
+                "wrong number of arguments for '{}'",
+                name
+            ))))
+        }
+        _ => {}
+    }
+
+    if arguments.iter().all(Complex::is_real) {
+        let real: Vec<Quantity> = arguments.iter().map(|z| Quantity::number(z.re())).collect();
+        match call(name, &real) {
+            Ok(value) => return Ok(Complex::real(value.si_value())),
+            Err(error) if complex_function(name, arguments).is_none() => return Err(error),
+            Err(_) => {}
+        }
+    }
+
+    complex_function(name, arguments).unwrap_or_else(|| {
+        Err(CalculatorError::invalid_input(Some(&format!(
+            "'{}' does not accept complex arguments",
+            name
+        ))))
+    })
+}
+
+/// Computes the principal complex value of a function, or `None` if the
+/// function has no complex version for these arguments.
+fn complex_function(name: &str, arguments: &[Complex]) -> Option<CalcResult<Complex>> {
+    Some(match (name, arguments) {
+        ("sqrt", [z]) => Ok(z.sqrt()),
+        ("cbrt", [z]) => Ok(z.cbrt()),
+        ("exp", [z]) => Ok(z.exp()),
+        ("ln", [z]) => Ok(z.ln()),
+        ("log", [z]) => z.log(&Complex::real(10.0)),
+        ("log", [z, base]) => z.log(base),
+        ("sin", [z]) => Ok(z.sin()),
+        ("cos", [z]) => Ok(z.cos()),
+        ("tan", [z]) => z.tan(),
+        ("asin", [z]) => Ok(z.asin()),
+        ("acos", [z]) => Ok(z.acos()),
+        ("atan", [z]) => z.atan(),
+        ("sinh", [z]) => Ok(z.sinh()),
+        ("cosh", [z]) => Ok(z.cosh()),
+        ("tanh", [z]) => z.tanh(),
+        _ => return None,
+    })
+}
@@ -0,0 +1,144 @@
+//! Built-in functions and constants for expressions.
+//!
+//! This module provides the registry that expressions call into:
+//! - Elementary functions (`sqrt`, `ln`, `sin`, ...), with angles in radians
+//! - Combinatorics (`nCr`, `nPr`, `fact`)
+//! - Probability distributions, named `<family>_pdf`, `<family>_cdf` and
+//!   `<family>_inv` with the point or probability first, then the
+//!   distribution's parameters (`normal_cdf(x, mean, sd)`)
+//! - Dates as day numbers counted from 1970-01-01, so subtracting two
+//!   dates gives the days between them (`date(2024, 12, 25) - date(2024, 1, 1)`),
+//!   with `weekday`, `add_months`, `workdays` and `add_workdays`; any
+//!   arguments after the first two of the business-day functions are holidays
+//! - Constants (`pi`, `π`, `e`)
+
+use crate::calculator::combinatorics;
+use crate::calculator::datetime::{self, Date};
+use crate::calculator::probability::Distribution;
+use crate::errors::{CalculatorError, CalcResult};
+
+/// A built-in function.
+#[derive(Debug)]
+pub struct Function {
+    /// The name used in expressions
+    pub name: &'static str,
+    /// The smallest number of arguments accepted
+    pub min_args: usize,
+    /// The largest number of arguments accepted
+    pub max_args: usize,
+    /// Computes the result from the evaluated arguments
+    pub apply: fn(&[f64]) -> CalcResult,
+}
+
+/// Built-in constants.
+pub const CONSTANTS: &[(&str, f64)] = &[
+    ("pi", std::f64::consts::PI),
+    ("π", std::f64::consts::PI),
+    ("e", std::f64::consts::E),
+];
+
+/// Built-in functions.
+pub const FUNCTIONS: &[Function] = &[
+    Function { name: "abs", min_args: 1, max_args: 1, apply: |a| Ok(a[0].abs()) },
+    Function { name: "sqrt", min_args: 1, max_args: 1, apply: |a| Ok(a[0].sqrt()) },
+    Function { name: "cbrt", min_args: 1, max_args: 1, apply: |a| Ok(a[0].cbrt()) },
+    Function { name: "exp", min_args: 1, max_args: 1, apply: |a| Ok(a[0].exp()) },
+    Function { name: "ln", min_args: 1, max_args: 1, apply: |a| Ok(a[0].ln()) },
+    Function { name: "log", min_args: 1, max_args: 2, apply: |a| Ok(a[0].log(*a.get(1).unwrap_or(&10.0))) },
+    Function { name: "sin", min_args: 1, max_args: 1, apply: |a| Ok(a[0].sin()) },
+    Function { name: "cos", min_args: 1, max_args: 1, apply: |a| Ok(a[0].cos()) },
+    Function { name: "tan", min_args: 1, max_args: 1, apply: |a| Ok(a[0].tan()) },
+    Function { name: "asin", min_args: 1, max_args: 1, apply: |a| Ok(a[0].asin()) },
+    Function { name: "acos", min_args: 1, max_args: 1, apply: |a| Ok(a[0].acos()) },
+    Function { name: "atan", min_args: 1, max_args: 1, apply: |a| Ok(a[0].atan()) },
+    Function { name: "sinh", min_args: 1, max_args: 1, apply: |a| Ok(a[0].sinh()) },
+    Function { name: "cosh", min_args: 1, max_args: 1, apply: |a| Ok(a[0].cosh()) },
+    Function { name: "tanh", min_args: 1, max_args: 1, apply: |a| Ok(a[0].tanh()) },
+    Function { name: "floor", min_args: 1, max_args: 1, apply: |a| Ok(a[0].floor()) },
+    Function { name: "ceil", min_args: 1, max_args: 1, apply: |a| Ok(a[0].ceil()) },
+    Function { name: "round", min_args: 1, max_args: 1, apply: |a| Ok(a[0].round()) },
+    Function { name: "min", min_args: 1, max_args: usize::MAX, apply: |a| Ok(a.iter().copied().fold(f64::INFINITY, f64::min)) },
+    Function { name: "max", min_args: 1, max_args: usize::MAX, apply: |a| Ok(a.iter().copied().fold(f64::NEG_INFINITY, f64::max)) },
+    Function { name: "fact", min_args: 1, max_args: 1, apply: |a| exact(combinatorics::factorial(a[0])?) },
+    Function { name: "nPr", min_args: 2, max_args: 2, apply: |a| exact(combinatorics::permutations(a[0], a[1])?) },
+    Function { name: "nCr", min_args: 2, max_args: 2, apply: |a| exact(combinatorics::combinations(a[0], a[1])?) },
+    Function { name: "normal_pdf", min_args: 3, max_args: 3, apply: |a| Distribution::normal(a[1], a[2])?.pdf(a[0]) },
+    Function { name: "normal_cdf", min_args: 3, max_args: 3, apply: |a| Distribution::normal(a[1], a[2])?.cdf(a[0]) },
+    Function { name: "normal_inv", min_args: 3, max_args: 3, apply: |a| Distribution::normal(a[1], a[2])?.inverse_cdf(a[0]) },
+    Function { name: "binomial_pdf", min_args: 3, max_args: 3, apply: |a| Distribution::binomial(a[1], a[2])?.pdf(a[0]) },
+    Function { name: "binomial_cdf", min_args: 3, max_args: 3, apply: |a| Distribution::binomial(a[1], a[2])?.cdf(a[0]) },
+    Function { name: "binomial_inv", min_args: 3, max_args: 3, apply: |a| Distribution::binomial(a[1], a[2])?.inverse_cdf(a[0]) },
+    Function { name: "poisson_pdf", min_args: 2, max_args: 2, apply: |a| Distribution::poisson(a[1])?.pdf(a[0]) },
+    Function { name: "poisson_cdf", min_args: 2, max_args: 2, apply: |a| Distribution::poisson(a[1])?.cdf(a[0]) },
+    Function { name: "poisson_inv", min_args: 2, max_args: 2, apply: |a| Distribution::poisson(a[1])?.inverse_cdf(a[0]) },
+    Function { name: "t_pdf", min_args: 2, max_args: 2, apply: |a| Distribution::student_t(a[1])?.pdf(a[0]) },
+    Function { name: "t_cdf", min_args: 2, max_args: 2, apply: |a| Distribution::student_t(a[1])?.cdf(a[0]) },
+    Function { name: "t_inv", min_args: 2, max_args: 2, apply: |a| Distribution::student_t(a[1])?.inverse_cdf(a[0]) },
+    Function { name: "chi2_pdf", min_args: 2, max_args: 2, apply: |a| Distribution::chi_square(a[1])?.pdf(a[0]) },
+    Function { name: "chi2_cdf", min_args: 2, max_args: 2, apply: |a| Distribution::chi_square(a[1])?.cdf(a[0]) },
+    Function { name: "chi2_inv", min_args: 2, max_args: 2, apply: |a| Distribution::chi_square(a[1])?.inverse_cdf(a[0]) },
+    Function { name: "f_pdf", min_args: 3, max_args: 3, apply: |a| Distribution::f(a[1], a[2])?.pdf(a[0]) },
+    Function { name: "f_cdf", min_args: 3, max_args: 3, apply: |a| Distribution::f(a[1], a[2])?.cdf(a[0]) },
+    Function { name: "f_inv", min_args: 3, max_args: 3, apply: |a| Distribution::f(a[1], a[2])?.inverse_cdf(a[0]) },
+    Function { name: "date", min_args: 3, max_args: 3, apply: |a| {
+        let date = Date::new(whole(a[0], "year")? as i32, whole(a[1], "month")? as u32, whole(a[2], "day")? as u32)?;
+        Ok(date.day_number() as f64)
+    } },
+    Function { name: "weekday", min_args: 1, max_args: 1, apply: |a| Ok(day(a[0])?.weekday() as u8 as f64) },
+    Function { name: "add_months", min_args: 2, max_args: 2, apply: |a| Ok(day(a[0])?.add_months(whole(a[1], "months")?)?.day_number() as f64) },
+    Function { name: "workdays", min_args: 2, max_args: usize::MAX, apply: |a| {
+        let holidays = a[2..].iter().map(|&h| day(h).map(|d| d.day_number())).collect::<CalcResult<Vec<_>>>()?;
+        Ok(datetime::count_business_days(day(a[0])?.day_number(), day(a[1])?.day_number(), &holidays) as f64)
+    } },
+    Function { name: "add_workdays", min_args: 2, max_args: usize::MAX, apply: |a| {
+        let holidays = a[2..].iter().map(|&h| day(h).map(|d| d.day_number())).collect::<CalcResult<Vec<_>>>()?;
+        Ok(datetime::offset_business_days(day(a[0])?.day_number(), whole(a[1], "business days")?, &holidays)? as f64)
+    } },
+];
+
+/// Looks up a built-in function by name.
+///
+/// # Arguments
+///
+/// * `name` - The function name, which is case-sensitive
+///
+/// # Returns
+///
+/// The function, or `None` if there is no built-in function by that name
+pub fn lookup(name: &str) -> Option<&'static Function> {
+    FUNCTIONS.iter().find(|function| function.name == name)
+}
+
+/// Looks up a built-in constant by name.
+///
+/// # Arguments
+///
+/// * `name` - The constant name
+///
+/// # Returns
+///
+/// The constant's value, or `None` if there is no constant by that name
+pub fn constant(name: &str) -> Option<f64> {
+    CONSTANTS.iter().find(|(constant, _)| *constant == name).map(|(_, value)| *value)
+}
+
+/// Converts an exact decimal result to the nearest `f64`.
+fn exact(digits: String) -> CalcResult {
+    match digits.parse::<f64>() {
+        Ok(value) if value.is_finite() => Ok(value),
+        _ => Err(CalculatorError::overflow()),
+    }
+}
+
+/// Checks that an argument is a whole number.
+fn whole(value: f64, name: &str) -> CalcResult<i64> {
+    if value.fract() != 0.0 || value.abs() > i32::MAX as f64 {
+        return Err(CalculatorError::invalid_input(Some(&format!("{} must be a whole number", name))));
+    }
+    Ok(value as i64)
+}
+
+/// Converts a day number argument to a date.
+fn day(value: f64) -> CalcResult<Date> {
+    Date::from_day_number(whole(value, "date")?)
+}
@@ -0,0 +1,188 @@
+//! Tokenizer for calculator expressions.
+//!
+//! This module splits expression text into tokens:
+//! - Numbers, with optional fraction and exponent (`1.5e-3`)
+//! - Durations written as `h:m:s` or `h:m`, which become seconds
+//! - Identifiers for variables, constants, functions and units (`x`, `pi`,
+//!   `nCr`, `°C`, `m²`)
+//! - Operators and punctuation, including the `×`, `÷` and `−` signs, the
+//!   `∠` of polar complex numbers, the brackets of matrix literals and the
+//!   `=` of equations
+
+use crate::calculator::datetime::parse_duration;
+use crate::errors::{CalculatorError, CalcResult};
+
+/// Represents a token of an expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    /// A numeric literal
+    Number(f64),
+    /// A variable, constant or function name
+    Identifier(String),
+    /// `+`
+    Plus,
+    /// `-` or `−`
+    Minus,
+    /// `*` or `×`
+    Star,
+    /// `/` or `÷`
+    Slash,
+    /// `^`
+    Caret,
+    /// `!`
+    Bang,
+    /// `(`
+    LeftParen,
+    /// `)`
+    RightParen,
+    /// `[`
+    LeftBracket,
+    /// `]`
+    RightBracket,
+    /// `,`
+    Comma,
+    /// `∠`
+    Angle,
+    /// `=`
+    Equals,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number(value) => write!(f, "{}", value),
+            Token::Identifier(name) => write!(f, "{}", name),
+            Token::Plus => write!(f, "+"),
+            Token::Minus => write!(f, "-"),
+            Token::Star => write!(f, "*"),
+            Token::Slash => write!(f, "/"),
+            Token::Caret => write!(f, "^"),
+            Token::Bang => write!(f, "!"),
+            Token::LeftParen => write!(f, "("),
+            Token::RightParen => write!(f, ")"),
+            Token::LeftBracket => write!(f, "["),
+            Token::RightBracket => write!(f, "]"),
+            Token::Comma => write!(f, ","),
+            Token::Angle => write!(f, "∠"),
+            Token::Equals => write!(f, "="),
+        }
+    }
+}
+
+/// Splits an expression into tokens.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+///
+/// # Returns
+///
+/// * `Ok(tokens)` - The tokens, each with its character position
+/// * `Err(error)` - An invalid input error naming the first character that
+///   cannot start a token
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::expression::lexer::{tokenize, Token};
+///
+/// let tokens: Vec<Token> = tokenize("2 × pi").unwrap().into_iter().map(|(t, _)| t).collect();
+/// assert_eq!(tokens, vec![Token::Number(2.0), Token::Star, Token::Identifier("pi".to_string())]);
+/// ```
+pub fn tokenize(input: &str) -> CalcResult<Vec<(Token, usize)>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        if c.is_ascii_digit() || (c == '.' && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit())) {
+            let (value, end) = read_number(&chars, i)?;
+            tokens.push((Token::Number(value), start));
+            i = end;
+            continue;
+        }
+
+        if c.is_alphabetic() || c == '_' || c == '°' {
+            i += 1;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let name: String = chars[start..i].iter().collect();
+            tokens.push((Token::Identifier(name), start));
+            continue;
+        }
+
+        let token = match c {
+            '+' => Token::Plus,
+            '-' | '−' => Token::Minus,
+            '*' | '×' => Token::Star,
+            '/' | '÷' => Token::Slash,
+            '^' => Token::Caret,
+            '!' => Token::Bang,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            '[' => Token::LeftBracket,
+            ']' => Token::RightBracket,
+            ',' => Token::Comma,
+            '∠' => Token::Angle,
+            '=' => Token::Equals,
+            _ => {
+                return Err(CalculatorError::invalid_input(Some(&format!(
+                    "unexpected '{}' at position {}",
+                    c,
+                    start + 1
+                ))))
+            }
+        };
+        tokens.push((token, start));
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+/// Reads a number starting at `start`.
+///
+/// An `e` only starts an exponent when digits follow it, so `2e` is the
+/// number 2 followed by the constant `e`. Digits followed by `:` and more
+/// digits are read as a duration in seconds.
+fn read_number(chars: &[char], start: usize) -> CalcResult<(f64, usize)> {
+    let mut i = start;
+    while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+        i += 1;
+    }
+
+    if chars.get(i) == Some(&':') && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit()) {
+        while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.' || chars[i] == ':') {
+            i += 1;
+        }
+        let text: String = chars[start..i].iter().collect();
+        return parse_duration(&text).map(|seconds| (seconds, i));
+    }
+
+    if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+        let mut j = i + 1;
+        if j < chars.len() && (chars[j] == '+' || chars[j] == '-') {
+            j += 1;
+        }
+        if j < chars.len() && chars[j].is_ascii_digit() {
+            i = j;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+        }
+    }
+
+    let text: String = chars[start..i].iter().collect();
+    text.parse::<f64>()
+        .map(|value| (value, i))
+        .map_err(|_| CalculatorError::invalid_input(Some(&text)))
+}
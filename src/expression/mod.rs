@@ -0,0 +1,365 @@
+//! Expression module for the Rust WASM Calculator.
+//!
+//! This module evaluates typed-in expressions such as `2 * (3 + 4)`:
+//! - Tokenizing and parsing into an expression tree
+//! - Evaluating with variables bound in a `Context`
+//! - A registry of built-in functions and constants
+//! - Quantities with units, such as `5 km / 20 min to mph`
+//! - Complex numbers in complex mode, such as `(3 + 4i) * 2∠30°`
+//! - Matrix literals, such as `inv([[1, 2], [3, 4]]) * [5, 6]`
+//! - Symbolic derivatives and simplification, printed back as text
+//! - Step-by-step evaluation traces for explanation mode
+//! - Compiled expressions for evaluating one expression at many values
+//!
+//! The module is designed to be used with WebAssembly through wasm-bindgen.
+
+// Export submodules
+pub mod lexer;
+pub mod parser;
+pub mod functions;
+pub mod evaluator;
+pub mod symbolic;
+pub mod trace;
+pub mod compiled;
+
+use wasm_bindgen::prelude::*;
+use crate::calculator::complex::Complex;
+use crate::calculator::linalg::Matrix;
+use crate::errors::CalcResult;
+use crate::units::Quantity;
+
+// Re-export commonly used types and functions
+pub use parser::{parse, BinaryOp, Expr};
+pub use evaluator::Context;
+pub use symbolic::DerivativeStep;
+pub use trace::TraceStep;
+pub use compiled::CompiledExpression;
+
+/// Evaluates an expression without variables.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+///
+/// # Returns
+///
+/// * `Ok(value)` - The value of the expression
+/// * `Err(error)` - A syntax error, an unknown name, or the error raised by
+///   an operation or function
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::expression::evaluate;
+///
+/// assert_eq!(evaluate("2 * (3 + 4)").unwrap(), 14.0);
+/// assert_eq!(evaluate("nCr(5, 2) + 3!").unwrap(), 16.0);
+/// assert!(evaluate("1 / 0").is_err());
+/// ```
+pub fn evaluate(input: &str) -> CalcResult {
+    parse(input)?.evaluate(&Context::new())
+}
+
+/// Evaluates an expression without variables.
+/// This is a WebAssembly-friendly version that returns a string error.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+///
+/// # Returns
+///
+/// The value of the expression, or an error message
+#[wasm_bindgen(js_name = evaluate)]
+pub fn evaluate_wasm(input: &str) -> Result<f64, JsValue> {
+    evaluate(input).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Evaluates an expression whose result may have units.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+///
+/// # Returns
+///
+/// * `Ok(quantity)` - The value of the expression, with simplified units
+///   unless the expression ends in a conversion
+/// * `Err(error)` - A syntax error, an unknown name, mismatched dimensions,
+///   or the error raised by an operation or function
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::expression::evaluate_quantity;
+///
+/// assert_eq!(evaluate_quantity("3 m * 4 m").unwrap().to_string(), "12 m²");
+/// assert_eq!(evaluate_quantity("100 °C to °F").unwrap().to_string(), "212 °F");
+/// ```
+pub fn evaluate_quantity(input: &str) -> CalcResult<Quantity> {
+    parse(input)?.evaluate_quantity(&Context::new())
+}
+
+/// Evaluates an expression whose result may have units.
+/// This is a WebAssembly-friendly version that returns a string error.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+///
+/// # Returns
+///
+/// The quantity, or an error message
+#[wasm_bindgen(js_name = evaluate_quantity)]
+pub fn evaluate_quantity_wasm(input: &str) -> Result<Quantity, JsValue> {
+    evaluate_quantity(input).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Evaluates an expression in complex mode.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+///
+/// # Returns
+///
+/// * `Ok(value)` - The complex value of the expression
+/// * `Err(error)` - A syntax error, an unknown name, or the error raised by
+///   an operation or function
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::expression::evaluate_complex;
+///
+/// assert_eq!(evaluate_complex("sqrt(-4)").unwrap().to_string(), "2i");
+/// assert_eq!(evaluate_complex("(1 + 2i) * (3 - i)").unwrap().to_string(), "5 + 5i");
+/// ```
+pub fn evaluate_complex(input: &str) -> CalcResult<Complex> {
+    let mut context = Context::new();
+    context.set_complex_mode(true);
+    parse(input)?.evaluate_complex(&context)
+}
+
+/// Evaluates an expression in complex mode.
+/// This is a WebAssembly-friendly version that returns a string error.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+///
+/// # Returns
+///
+/// The complex value, or an error message
+#[wasm_bindgen(js_name = evaluate_complex)]
+pub fn evaluate_complex_wasm(input: &str) -> Result<Complex, JsValue> {
+    evaluate_complex(input).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Evaluates an expression whose result may be a matrix.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+///
+/// # Returns
+///
+/// * `Ok(matrix)` - The value of the expression, with a number as a 1×1
+///   matrix
+/// * `Err(error)` - A syntax error, an unknown name, mismatched shapes, a
+///   singular matrix, or the error raised by an operation or function
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::expression::evaluate_matrix;
+///
+/// assert_eq!(evaluate_matrix("transpose([[1, 2], [3, 4]])").unwrap().to_string(), "[[1, 3], [2, 4]]");
+/// assert_eq!(evaluate_matrix("solve([[2, 0], [0, 4]], [2, 2])").unwrap().to_string(), "[[1], [0.5]]");
+/// assert!(evaluate_matrix("inv([[1, 2], [2, 4]])").is_err());
+/// ```
+pub fn evaluate_matrix(input: &str) -> CalcResult<Matrix> {
+    parse(input)?.evaluate_matrix(&Context::new())
+}
+
+/// Evaluates an expression whose result may be a matrix.
+/// This is a WebAssembly-friendly version that returns a string error.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+///
+/// # Returns
+///
+/// The matrix, or an error message
+#[wasm_bindgen(js_name = evaluate_matrix)]
+pub fn evaluate_matrix_wasm(input: &str) -> Result<Matrix, JsValue> {
+    evaluate_matrix(input).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Evaluates an expression step by step, for explanation mode.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+///
+/// # Returns
+///
+/// * `Ok(steps)` - The operations in the order they are carried out, each
+///   with its operands' values and its result
+/// * `Err(error)` - A syntax error, an unknown name, or the error raised by
+///   an operation or function
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::expression::trace;
+///
+/// let steps = trace("2 + 4 * 2").unwrap();
+/// assert_eq!(steps[0].calculation(), "4 * 2");
+/// assert_eq!(steps[0].result(), "8");
+/// assert_eq!(steps[1].calculation(), "2 + 8");
+/// assert_eq!(steps[1].result(), "10");
+/// ```
+pub fn trace(input: &str) -> CalcResult<Vec<TraceStep>> {
+    parse(input)?.trace(&Context::new())
+}
+
+/// Evaluates an expression step by step, for explanation mode.
+/// This is a WebAssembly-friendly version that returns a string error.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+///
+/// # Returns
+///
+/// The steps, or an error message
+#[wasm_bindgen(js_name = trace)]
+pub fn trace_wasm(input: &str) -> Result<Vec<TraceStep>, JsValue> {
+    trace(input).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Differentiates an expression symbolically.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+/// * `variable` - The variable to differentiate with respect to
+///
+/// # Returns
+///
+/// * `Ok(text)` - The simplified derivative as expression text
+/// * `Err(error)` - A syntax error, or an invalid input error for a part
+///   of the expression that has no derivative rule
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::expression::differentiate;
+///
+/// assert_eq!(differentiate("x^2 + 3x", "x").unwrap(), "2 * x + 3");
+/// assert_eq!(differentiate("ln(x)", "x").unwrap(), "1 / x");
+/// assert!(differentiate("floor(x)", "x").is_err());
+/// ```
+pub fn differentiate(input: &str, variable: &str) -> CalcResult<String> {
+    Ok(parse(input)?.derivative(variable)?.to_string())
+}
+
+/// Differentiates an expression symbolically.
+/// This is a WebAssembly-friendly version that returns a string error.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+/// * `variable` - The variable to differentiate with respect to
+///
+/// # Returns
+///
+/// The derivative as expression text, or an error message
+#[wasm_bindgen(js_name = differentiate)]
+pub fn differentiate_wasm(input: &str, variable: &str) -> Result<String, JsValue> {
+    differentiate(input, variable).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Lists the steps of a symbolic derivative, for explanation mode.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+/// * `variable` - The variable to differentiate with respect to
+///
+/// # Returns
+///
+/// * `Ok(steps)` - The rule applied to each sub-expression, from the whole
+///   expression inwards
+/// * `Err(error)` - A syntax error, or an invalid input error for a part
+///   of the expression that has no derivative rule
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::expression::derivative_steps;
+///
+/// let steps = derivative_steps("x^2 + x", "x").unwrap();
+/// assert_eq!(steps[0].rule(), "sum rule");
+/// assert_eq!(steps[0].result(), "2 * x + 1");
+/// assert_eq!(steps.len(), 4);
+/// ```
+pub fn derivative_steps(input: &str, variable: &str) -> CalcResult<Vec<DerivativeStep>> {
+    parse(input)?.derivative_steps(variable)
+}
+
+/// Lists the steps of a symbolic derivative, for explanation mode.
+/// This is a WebAssembly-friendly version that returns a string error.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+/// * `variable` - The variable to differentiate with respect to
+///
+/// # Returns
+///
+/// The steps, or an error message
+#[wasm_bindgen(js_name = derivative_steps)]
+pub fn derivative_steps_wasm(input: &str, variable: &str) -> Result<Vec<DerivativeStep>, JsValue> {
+    derivative_steps(input, variable).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Simplifies an expression and prints it back as text.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+///
+/// # Returns
+///
+/// * `Ok(text)` - The simplified expression text
+/// * `Err(error)` - A syntax error
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::expression::simplify;
+///
+/// assert_eq!(simplify("x + x + 2 * 3").unwrap(), "2 * x + 6");
+/// assert_eq!(simplify("a * b^2 * a / 1").unwrap(), "a^2 * b^2");
+/// ```
+pub fn simplify(input: &str) -> CalcResult<String> {
+    Ok(parse(input)?.simplify().to_string())
+}
+
+/// Simplifies an expression and prints it back as text.
+/// This is a WebAssembly-friendly version that returns a string error.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+///
+/// # Returns
+///
+/// The simplified expression text, or an error message
+#[wasm_bindgen(js_name = simplify)]
+pub fn simplify_wasm(input: &str) -> Result<String, JsValue> {
+    simplify(input).map_err(|e| JsValue::from_str(e.message()))
+}
@@ -0,0 +1,566 @@
+//! Parser for calculator expressions.
+//!
+//! This module turns tokens into an abstract syntax tree. From lowest to
+//! highest precedence:
+//! - Unit conversion with `to` or `in` (`5 km to mi`), only at the top level
+//! - `+` and `-`
+//! - `*` and `/`
+//! - Polar complex numbers with `∠`, so `2 * 5∠30°` is `2 * (5∠30°)`
+//! - Unary minus and plus
+//! - Implicit multiplication of a following name or parenthesis, so
+//!   `5 km / 20 min` is `(5 km) / (20 min)`
+//! - `^`, which is right-associative, so `2^3^2` is `2^9`
+//! - Postfix factorial `!`
+//!
+//! Operands include matrix literals: `[[1, 2], [3, 4]]` lists rows, and a
+//! single list such as `[1, 2, 3]` is a column vector. A function argument
+//! may be an equation, as in `solve(x^3 - 2x = 5, x)`.
+//!
+//! As in standard notation, `-2^2` is `-(2^2)` and `2^-1` is allowed.
+//! `in` is also the inch, so it only starts a conversion when a unit name
+//! follows it: `12 in to cm` converts inches, `5 ft in m` converts to metres.
+//!
+//! Parentheses, calls, brackets, signs, powers and factorials may nest at
+//! most 200 deep, and the tree may be at most 400 levels tall, which allows
+//! a chain of a few hundred terms such as a pasted column of numbers. Larger
+//! input is an error rather than a stack overflow in the parser or in the
+//! functions that walk the tree.
+
+use std::fmt;
+use super::lexer::{tokenize, Token};
+use crate::errors::{CalculatorError, CalcResult};
+use crate::state::format_significant;
+
+/// The number of significant digits of numbers in printed expressions.
+const DISPLAY_DIGITS: usize = 15;
+/// The deepest nesting of parentheses, calls, brackets, signs, powers and
+/// factorials, which keeps parsing within a small stack.
+const MAX_NESTING: usize = 200;
+/// The tallest expression tree, which keeps evaluation, printing and
+/// differentiation within a small stack.
+const MAX_HEIGHT: usize = 400;
+
+/// Represents a binary operator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Power,
+}
+
+/// Represents a node of an expression tree.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// A numeric literal
+    Number(f64),
+    /// A variable or constant
+    Variable(String),
+    /// Unary minus
+    Negate(Box<Expr>),
+    /// A binary operation
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    /// Postfix factorial
+    Factorial(Box<Expr>),
+    /// A function call
+    Call(String, Vec<Expr>),
+    /// A conversion of the first expression to the units of the second
+    Convert(Box<Expr>, Box<Expr>),
+    /// A matrix literal, row by row
+    Matrix(Vec<Vec<Expr>>),
+    /// An equation between two sides, as a function argument
+    Equation(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Gets the binding strength of the node's outermost operation, from
+    /// 0 for conversions and equations to 6 for operands that never need
+    /// parentheses.
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Convert(..) | Expr::Equation(..) => 0,
+            Expr::Binary(BinaryOp::Add | BinaryOp::Subtract, ..) => 1,
+            Expr::Binary(BinaryOp::Multiply | BinaryOp::Divide, ..) => 2,
+            Expr::Negate(_) => 3,
+            Expr::Number(value) if value.is_sign_negative() => 3,
+            Expr::Binary(BinaryOp::Power, ..) => 4,
+            Expr::Factorial(_) => 5,
+            Expr::Number(_) | Expr::Variable(_) | Expr::Call(..) | Expr::Matrix(_) => 6,
+        }
+    }
+
+    /// Writes the node, in parentheses if it binds more loosely than
+    /// `minimum`, or if it is signed and `after_operator` is set.
+    fn write_operand(&self, f: &mut fmt::Formatter<'_>, minimum: u8, after_operator: bool) -> fmt::Result {
+        let precedence = self.precedence();
+        if precedence < minimum || (after_operator && precedence == 3) {
+            write!(f, "({})", self)
+        } else {
+            write!(f, "{}", self)
+        }
+    }
+}
+
+/// Prints an expression as infix text that parses back to the same value,
+/// with only the parentheses that precedence requires.
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Number(value) => write!(f, "{}", format_significant(*value, DISPLAY_DIGITS)),
+            Expr::Variable(name) => write!(f, "{}", name),
+            Expr::Negate(operand) => {
+                write!(f, "-")?;
+                // `-2 * x` parses as `(-2) * x`, which has the same value
+                operand.write_operand(f, 2, true)
+            }
+            Expr::Binary(op, left, right) => {
+                let (symbol, level) = match op {
+                    BinaryOp::Add => (" + ", 1),
+                    BinaryOp::Subtract => (" - ", 1),
+                    BinaryOp::Multiply => (" * ", 2),
+                    BinaryOp::Divide => (" / ", 2),
+                    BinaryOp::Power => ("^", 4),
+                };
+                if *op == BinaryOp::Power {
+                    // Right-associative, with a signed exponent allowed
+                    left.write_operand(f, 5, false)?;
+                    write!(f, "{}", symbol)?;
+                    return right.write_operand(f, 3, false);
+                }
+                left.write_operand(f, level, false)?;
+                write!(f, "{}", symbol)?;
+                let minimum = if matches!(op, BinaryOp::Subtract | BinaryOp::Divide) { level + 1 } else { level };
+                right.write_operand(f, minimum, true)
+            }
+            Expr::Factorial(operand) => {
+                operand.write_operand(f, 5, false)?;
+                write!(f, "!")
+            }
+            Expr::Call(name, arguments) => {
+                write!(f, "{}(", name)?;
+                for (i, argument) in arguments.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", argument)?;
+                }
+                write!(f, ")")
+            }
+            Expr::Convert(value, target) => write!(f, "{} to {}", value, target),
+            Expr::Matrix(rows) => {
+                write!(f, "[")?;
+                for (i, row) in rows.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "[")?;
+                    for (j, entry) in row.iter().enumerate() {
+                        if j > 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{}", entry)?;
+                    }
+                    write!(f, "]")?;
+                }
+                write!(f, "]")
+            }
+            Expr::Equation(left, right) => write!(f, "{} = {}", left, right),
+        }
+    }
+}
+
+/// Parses an expression.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+///
+/// # Returns
+///
+/// * `Ok(expr)` - The expression tree
+/// * `Err(error)` - An invalid input error describing the first syntax error
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::expression::parser::{parse, BinaryOp, Expr};
+///
+/// let expr = parse("1 + x").unwrap();
+/// assert_eq!(
+///     expr,
+///     Expr::Binary(BinaryOp::Add, Box::new(Expr::Number(1.0)), Box::new(Expr::Variable("x".to_string())))
+/// );
+/// assert!(parse("1 +").is_err());
+/// ```
+pub fn parse(input: &str) -> CalcResult<Expr> {
+    let mut parser = Parser { tokens: tokenize(input)?, position: 0, depth: 0, height: 0 };
+    let mut expr = parser.expression()?;
+    if parser.at_conversion() {
+        parser.position += 1;
+        let height = parser.height;
+        let target = parser.expression()?;
+        parser.grow(height.max(parser.height));
+        expr = Expr::Convert(Box::new(expr), Box::new(target));
+    }
+    if parser.height > MAX_HEIGHT {
+        return Err(too_long());
+    }
+    match parser.peek() {
+        None => Ok(expr),
+        Some(_) => Err(parser.unexpected()),
+    }
+}
+
+/// Builds the error for an expression tree taller than `MAX_HEIGHT`.
+fn too_long() -> CalculatorError {
+    CalculatorError::invalid_input(Some("expression is too long"))
+}
+
+/// A recursive-descent parser over a token list.
+struct Parser {
+    /// The tokens with their character positions
+    tokens: Vec<(Token, usize)>,
+    /// The index of the next token
+    position: usize,
+    /// The current nesting depth
+    depth: usize,
+    /// The height of the expression parsed last
+    height: usize,
+}
+
+impl Parser {
+    /// Parses a sum or difference.
+    fn expression(&mut self) -> CalcResult<Expr> {
+        let mut left = self.term()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Plus) => BinaryOp::Add,
+                Some(Token::Minus) => BinaryOp::Subtract,
+                _ => return Ok(left),
+            };
+            self.position += 1;
+            let height = self.height;
+            let right = self.term()?;
+            self.grow(height.max(self.height));
+            if self.height > MAX_HEIGHT {
+                return Err(too_long());
+            }
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    /// Parses a product or quotient.
+    fn term(&mut self) -> CalcResult<Expr> {
+        let mut left = self.polar()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Star) => BinaryOp::Multiply,
+                Some(Token::Slash) => BinaryOp::Divide,
+                _ => return Ok(left),
+            };
+            self.position += 1;
+            let height = self.height;
+            let right = self.polar()?;
+            self.grow(height.max(self.height));
+            if self.height > MAX_HEIGHT {
+                return Err(too_long());
+            }
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    /// Parses a modulus and argument such as `5∠30°` into a call to `polar`.
+    fn polar(&mut self) -> CalcResult<Expr> {
+        let modulus = self.unary()?;
+        if self.peek() == Some(&Token::Angle) {
+            self.position += 1;
+            let height = self.height;
+            let argument = self.unary()?;
+            self.grow(height.max(self.height));
+            return Ok(Expr::Call("polar".to_string(), vec![modulus, argument]));
+        }
+        Ok(modulus)
+    }
+
+    /// Parses a signed operand.
+    fn unary(&mut self) -> CalcResult<Expr> {
+        match self.peek() {
+            Some(Token::Minus) => {
+                self.position += 1;
+                self.descend()?;
+                let operand = self.unary()?;
+                self.depth -= 1;
+                self.grow(self.height);
+                Ok(Expr::Negate(Box::new(operand)))
+            }
+            Some(Token::Plus) => {
+                self.position += 1;
+                self.descend()?;
+                let operand = self.unary()?;
+                self.depth -= 1;
+                Ok(operand)
+            }
+            _ => self.implicit_product(),
+        }
+    }
+
+    /// Parses powers written side by side, such as `5 km` or `2 pi r`.
+    fn implicit_product(&mut self) -> CalcResult<Expr> {
+        let mut left = self.power()?;
+        while self.at_implicit_factor() {
+            let height = self.height;
+            let right = self.power()?;
+            self.grow(height.max(self.height));
+            if self.height > MAX_HEIGHT {
+                return Err(too_long());
+            }
+            left = Expr::Binary(BinaryOp::Multiply, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    /// Parses a power, whose exponent may itself be signed.
+    fn power(&mut self) -> CalcResult<Expr> {
+        let base = self.postfix()?;
+        if self.peek() == Some(&Token::Caret) {
+            self.position += 1;
+            self.descend()?;
+            let height = self.height;
+            let exponent = self.exponent()?;
+            self.depth -= 1;
+            self.grow(height.max(self.height));
+            return Ok(Expr::Binary(BinaryOp::Power, Box::new(base), Box::new(exponent)));
+        }
+        Ok(base)
+    }
+
+    /// Parses a signed exponent, which does not take implicit products, so
+    /// `2^3 m` is `(2^3) m`.
+    fn exponent(&mut self) -> CalcResult<Expr> {
+        match self.peek() {
+            Some(Token::Minus) => {
+                self.position += 1;
+                self.descend()?;
+                let operand = self.exponent()?;
+                self.depth -= 1;
+                self.grow(self.height);
+                Ok(Expr::Negate(Box::new(operand)))
+            }
+            Some(Token::Plus) => {
+                self.position += 1;
+                self.descend()?;
+                let operand = self.exponent()?;
+                self.depth -= 1;
+                Ok(operand)
+            }
+            _ => self.power(),
+        }
+    }
+
+    /// Parses an operand followed by any number of factorial signs.
+    fn postfix(&mut self) -> CalcResult<Expr> {
+        let depth = self.depth;
+        let mut expr = self.primary()?;
+        while self.peek() == Some(&Token::Bang) {
+            self.position += 1;
+            self.descend()?;
+            self.grow(self.height);
+            expr = Expr::Factorial(Box::new(expr));
+        }
+        self.depth = depth;
+        Ok(expr)
+    }
+
+    /// Parses a number, variable, function call, matrix literal or
+    /// parenthesized expression.
+    fn primary(&mut self) -> CalcResult<Expr> {
+        match self.peek().cloned() {
+            Some(Token::Number(value)) => {
+                self.position += 1;
+                self.height = 1;
+                Ok(Expr::Number(value))
+            }
+            Some(Token::Identifier(name)) => {
+                self.position += 1;
+                if self.peek() == Some(&Token::LeftParen) {
+                    self.position += 1;
+                    self.descend()?;
+                    let arguments = self.arguments()?;
+                    self.depth -= 1;
+                    self.grow(self.height);
+                    Ok(Expr::Call(name, arguments))
+                } else {
+                    self.height = 1;
+                    Ok(Expr::Variable(name))
+                }
+            }
+            Some(Token::LeftParen) => {
+                self.position += 1;
+                self.descend()?;
+                let expr = self.expression()?;
+                self.expect(Token::RightParen)?;
+                self.depth -= 1;
+                Ok(expr)
+            }
+            Some(Token::LeftBracket) => {
+                self.position += 1;
+                self.descend()?;
+                let matrix = self.matrix()?;
+                self.depth -= 1;
+                self.grow(self.height);
+                Ok(matrix)
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    /// Parses a matrix literal after the opening bracket: a list of
+    /// bracketed rows of equal length, or a list of entries that forms a
+    /// column vector.
+    fn matrix(&mut self) -> CalcResult<Expr> {
+        if self.peek() != Some(&Token::LeftBracket) {
+            let entries = self.list()?;
+            return Ok(Expr::Matrix(entries.into_iter().map(|entry| vec![entry]).collect()));
+        }
+
+        let mut rows = Vec::new();
+        let mut height = 0;
+        loop {
+            self.expect(Token::LeftBracket)?;
+            self.descend()?;
+            let row = self.list()?;
+            self.depth -= 1;
+            height = height.max(self.height);
+            if rows.first().is_some_and(|first: &Vec<Expr>| first.len() != row.len()) {
+                return Err(CalculatorError::invalid_input(Some("matrix rows must have the same length")));
+            }
+            rows.push(row);
+            match self.peek() {
+                Some(Token::Comma) => self.position += 1,
+                _ => {
+                    self.expect(Token::RightBracket)?;
+                    self.height = height;
+                    return Ok(Expr::Matrix(rows));
+                }
+            }
+        }
+    }
+
+    /// Parses a non-empty comma-separated list up to its closing bracket,
+    /// leaving the height of its tallest entry.
+    fn list(&mut self) -> CalcResult<Vec<Expr>> {
+        let mut entries = Vec::new();
+        let mut height = 0;
+        loop {
+            entries.push(self.expression()?);
+            height = height.max(self.height);
+            match self.peek() {
+                Some(Token::Comma) => self.position += 1,
+                _ => {
+                    self.expect(Token::RightBracket)?;
+                    self.height = height;
+                    return Ok(entries);
+                }
+            }
+        }
+    }
+
+    /// Parses a comma-separated argument list after the opening
+    /// parenthesis, leaving the height of its tallest argument.
+    fn arguments(&mut self) -> CalcResult<Vec<Expr>> {
+        let mut arguments = Vec::new();
+        self.height = 0;
+        if self.peek() == Some(&Token::RightParen) {
+            self.position += 1;
+            return Ok(arguments);
+        }
+
+        let mut height = 0;
+        loop {
+            let mut argument = self.expression()?;
+            if self.peek() == Some(&Token::Equals) {
+                self.position += 1;
+                let left = self.height;
+                argument = Expr::Equation(Box::new(argument), Box::new(self.expression()?));
+                self.grow(left.max(self.height));
+            }
+            arguments.push(argument);
+            height = height.max(self.height);
+            match self.peek() {
+                Some(Token::Comma) => self.position += 1,
+                _ => {
+                    self.expect(Token::RightParen)?;
+                    self.height = height;
+                    return Ok(arguments);
+                }
+            }
+        }
+    }
+
+    /// Enters one more level of nesting.
+    fn descend(&mut self) -> CalcResult<()> {
+        if self.depth >= MAX_NESTING {
+            return Err(CalculatorError::invalid_input(Some("expression is nested too deeply")));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Records a new node above children whose tallest has the given
+    /// height. Only chains can grow the tree without nesting, so the height
+    /// is checked there and once more for the whole expression.
+    fn grow(&mut self, height: usize) {
+        self.height = height + 1;
+    }
+
+    /// Consumes the given token or reports what was found instead.
+    fn expect(&mut self, expected: Token) -> CalcResult<()> {
+        if self.peek() == Some(&expected) {
+            self.position += 1;
+            Ok(())
+        } else if self.peek().is_none() {
+            Err(CalculatorError::invalid_input(Some(&format!("missing '{}'", expected))))
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    /// Checks whether the next token starts an implicit factor.
+    fn at_implicit_factor(&self) -> bool {
+        match self.peek() {
+            Some(Token::Identifier(_)) => !self.at_conversion(),
+            Some(Token::LeftParen) | Some(Token::LeftBracket) => true,
+            _ => false,
+        }
+    }
+
+    /// Checks whether the next token is a `to` or `in` conversion keyword.
+    fn at_conversion(&self) -> bool {
+        match self.peek() {
+            Some(Token::Identifier(name)) if name == "to" => true,
+            Some(Token::Identifier(name)) if name == "in" => matches!(
+                self.tokens.get(self.position + 1),
+                Some((Token::Identifier(next), _)) if next != "to"
+            ),
+            _ => false,
+        }
+    }
+
+    /// Gets the next token without consuming it.
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    /// Builds an error for the next token.
+    fn unexpected(&self) -> CalculatorError {
+        match self.tokens.get(self.position) {
+            Some((token, at)) => CalculatorError::invalid_input(Some(&format!(
+                "unexpected '{}' at position {}",
+                token,
+                at + 1
+            ))),
+            None => CalculatorError::invalid_input(Some("unexpected end of expression")),
+        }
+    }
+}
@@ -0,0 +1,554 @@
+//! Symbolic algebra on expression trees.
+//!
+//! This module rewrites an `Expr` rather than evaluating it:
+//! - Derivatives with respect to a variable, by the sum, product,
+//!   quotient, power and chain rules
+//! - A record of the rule applied at each step, for explanations
+//! - Simplification by constant folding, identity elimination (`x + 0`,
+//!   `1 * x`, `x^1`) and collection of like terms and factors, so
+//!   `2x + 3x` is `5 * x` and `x * x` is `x^2`
+//!
+//! Only arithmetic is folded: `1 / 3` and `sin(2)` are kept exact, and
+//! division is only folded when the quotient is a whole number. Functions
+//! of the variable that have no closed-form derivative, such as `floor`,
+//! raise an invalid input error.
+
+use wasm_bindgen::prelude::*;
+use super::parser::{BinaryOp, Expr};
+use crate::calculator::operations::{checked_add, checked_multiply};
+use crate::errors::{CalculatorError, CalcResult};
+
+/// The number of rewriting passes after which simplification stops.
+const MAX_PASSES: usize = 10;
+/// The largest exponent to which a power of numbers is folded.
+const MAX_FOLDED_EXPONENT: f64 = 64.0;
+
+/// Represents one step of a derivative: the rule applied to a
+/// sub-expression and the derivative it gave.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DerivativeStep {
+    /// The sub-expression being differentiated
+    expression: String,
+    /// The rule applied to it
+    rule: String,
+    /// Its simplified derivative
+    result: String,
+}
+
+/// Getters for DerivativeStep
+#[wasm_bindgen]
+impl DerivativeStep {
+    /// Gets the sub-expression being differentiated.
+    #[wasm_bindgen(getter)]
+    pub fn expression(&self) -> String {
+        self.expression.clone()
+    }
+
+    /// Gets the rule applied to the sub-expression.
+    #[wasm_bindgen(getter)]
+    pub fn rule(&self) -> String {
+        self.rule.clone()
+    }
+
+    /// Gets the simplified derivative of the sub-expression.
+    #[wasm_bindgen(getter)]
+    pub fn result(&self) -> String {
+        self.result.clone()
+    }
+}
+
+impl Expr {
+    /// Differentiates the expression with respect to a variable.
+    ///
+    /// Other variables and named constants are treated as constants.
+    ///
+    /// # Arguments
+    ///
+    /// * `variable` - The name of the variable
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(expr)` - The simplified derivative
+    /// * `Err(error)` - An invalid input error for a function, factorial,
+    ///   conversion, matrix or equation that depends on the variable and
+    ///   has no derivative rule
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::expression::parse;
+    ///
+    /// let derivative = parse("x^3 - 2x").unwrap().derivative("x").unwrap();
+    /// assert_eq!(derivative.to_string(), "3 * x^2 - 2");
+    /// let derivative = parse("x sin(x)").unwrap().derivative("x").unwrap();
+    /// assert_eq!(derivative.to_string(), "sin(x) + x * cos(x)");
+    /// ```
+    pub fn derivative(&self, variable: &str) -> CalcResult<Expr> {
+        Ok(self.differentiate(variable, &mut None)?.simplify())
+    }
+
+    /// Differentiates the expression with respect to a variable, recording
+    /// the rule applied at each step.
+    ///
+    /// Steps are listed from the whole expression inwards; sub-expressions
+    /// that do not depend on the variable are not listed.
+    ///
+    /// # Arguments
+    ///
+    /// * `variable` - The name of the variable
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(steps)` - The steps, the first of which has the derivative of
+    ///   the whole expression as its result
+    /// * `Err(error)` - An invalid input error as for `derivative`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::expression::parse;
+    ///
+    /// let steps = parse("sin(x^2)").unwrap().derivative_steps("x").unwrap();
+    /// assert_eq!(steps[0].rule(), "chain rule: d/du sin(u) = cos(u)");
+    /// assert_eq!(steps[0].result(), "2 * x * cos(x^2)");
+    /// assert_eq!(steps[1].rule(), "power rule");
+    /// ```
+    pub fn derivative_steps(&self, variable: &str) -> CalcResult<Vec<DerivativeStep>> {
+        let mut steps = Some(Vec::new());
+        self.differentiate(variable, &mut steps)?;
+        Ok(steps.unwrap_or_default())
+    }
+
+    /// Simplifies the expression by constant folding, identity elimination
+    /// and collection of like terms and factors.
+    ///
+    /// # Returns
+    ///
+    /// An expression with the same value wherever the original is defined
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::expression::parse;
+    ///
+    /// assert_eq!(parse("2x + 3x - x * 1").unwrap().simplify().to_string(), "4 * x");
+    /// assert_eq!(parse("x * x^2 / 1 + 0").unwrap().simplify().to_string(), "x^3");
+    /// assert_eq!(parse("(2 + 3) * y - 6 / 2").unwrap().simplify().to_string(), "5 * y - 3");
+    /// ```
+    pub fn simplify(&self) -> Expr {
+        let mut current = self.clone();
+        for _ in 0..MAX_PASSES {
+            let next = current.simplify_once();
+            if next == current {
+                break;
+            }
+            current = next;
+        }
+        current
+    }
+
+    /// Checks whether the expression mentions a variable.
+    pub(super) fn depends_on(&self, variable: &str) -> bool {
+        match self {
+            Expr::Number(_) => false,
+            Expr::Variable(name) => name == variable,
+            Expr::Negate(operand) | Expr::Factorial(operand) => operand.depends_on(variable),
+            Expr::Binary(_, left, right) | Expr::Convert(left, right) | Expr::Equation(left, right) => {
+                left.depends_on(variable) || right.depends_on(variable)
+            }
+            Expr::Call(_, arguments) => arguments.iter().any(|argument| argument.depends_on(variable)),
+            Expr::Matrix(rows) => rows.iter().flatten().any(|entry| entry.depends_on(variable)),
+        }
+    }
+
+    /// Differentiates the expression without simplifying, appending a step
+    /// for each sub-expression that depends on the variable when steps are
+    /// being recorded.
+    fn differentiate(&self, variable: &str, steps: &mut Option<Vec<DerivativeStep>>) -> CalcResult<Expr> {
+        let is_whole = steps.as_ref().is_some_and(|steps| steps.is_empty());
+        if !self.depends_on(variable) {
+            if is_whole {
+                record(steps, self, "constant".to_string(), &number(0.0));
+            }
+            return Ok(number(0.0));
+        }
+        // Reserve the step so that it comes before those of the operands
+        let index = steps.as_ref().map(|steps| steps.len());
+        if let Some(steps) = steps.as_mut() {
+            steps.push(DerivativeStep { expression: self.to_string(), rule: String::new(), result: String::new() });
+        }
+
+        let (rule, result) = match self {
+            Expr::Variable(_) => ("variable".to_string(), number(1.0)),
+            Expr::Negate(operand) => ("negation".to_string(), negate(operand.differentiate(variable, steps)?)),
+            Expr::Binary(op, left, right) => self.differentiate_binary(*op, left, right, variable, steps)?,
+            Expr::Call(name, arguments) => {
+                let inner = match arguments.as_slice() {
+                    [inner] => inner,
+                    [inner, base] if name == "log" && !base.depends_on(variable) => inner,
+                    _ => return Err(no_rule(self)),
+                };
+                let u = Expr::Variable("u".to_string());
+                let outer = outer_derivative(name, &u, arguments.get(1)).ok_or_else(|| no_rule(self))?;
+                let rule = format!("chain rule: d/du {}(u) = {}", name, outer.simplify());
+                let outer = outer_derivative(name, inner, arguments.get(1)).ok_or_else(|| no_rule(self))?;
+                (rule, multiply(outer, inner.differentiate(variable, steps)?))
+            }
+            _ => return Err(no_rule(self)),
+        };
+
+        if let (Some(steps), Some(index)) = (steps.as_mut(), index) {
+            steps[index].rule = rule;
+            steps[index].result = result.simplify().to_string();
+        }
+        Ok(result)
+    }
+
+    /// Differentiates a binary operation, giving the rule applied and the
+    /// unsimplified derivative.
+    fn differentiate_binary(
+        &self,
+        op: BinaryOp,
+        left: &Expr,
+        right: &Expr,
+        variable: &str,
+        steps: &mut Option<Vec<DerivativeStep>>,
+    ) -> CalcResult<(String, Expr)> {
+        let (left_varies, right_varies) = (left.depends_on(variable), right.depends_on(variable));
+        let (rule, result) = match op {
+            BinaryOp::Add => ("sum rule", add(left.differentiate(variable, steps)?, right.differentiate(variable, steps)?)),
+            BinaryOp::Subtract => (
+                "difference rule",
+                subtract(left.differentiate(variable, steps)?, right.differentiate(variable, steps)?),
+            ),
+            BinaryOp::Multiply if !left_varies => {
+                ("constant multiple rule", multiply(left.clone(), right.differentiate(variable, steps)?))
+            }
+            BinaryOp::Multiply if !right_varies => {
+                ("constant multiple rule", multiply(left.differentiate(variable, steps)?, right.clone()))
+            }
+            BinaryOp::Multiply => {
+                let (du, dv) = (left.differentiate(variable, steps)?, right.differentiate(variable, steps)?);
+                ("product rule", add(multiply(du, right.clone()), multiply(left.clone(), dv)))
+            }
+            BinaryOp::Divide if !right_varies => {
+                ("constant multiple rule", divide(left.differentiate(variable, steps)?, right.clone()))
+            }
+            BinaryOp::Divide => {
+                let (du, dv) = (left.differentiate(variable, steps)?, right.differentiate(variable, steps)?);
+                let numerator = subtract(multiply(du, right.clone()), multiply(left.clone(), dv));
+                ("quotient rule", divide(numerator, power(right.clone(), number(2.0))))
+            }
+            BinaryOp::Power if !right_varies => {
+                // n u^(n - 1) u'
+                let lowered = power(left.clone(), subtract(right.clone(), number(1.0)));
+                ("power rule", multiply(multiply(right.clone(), lowered), left.differentiate(variable, steps)?))
+            }
+            BinaryOp::Power if !left_varies => {
+                // a^v ln(a) v', where ln(e) is 1
+                let mut result = multiply(self.clone(), right.differentiate(variable, steps)?);
+                if *left != Expr::Variable("e".to_string()) {
+                    result = multiply(result, call("ln", left.clone()));
+                }
+                ("exponential rule", result)
+            }
+            BinaryOp::Power => {
+                // u^v (v' ln(u) + v u' / u)
+                let (du, dv) = (left.differentiate(variable, steps)?, right.differentiate(variable, steps)?);
+                let inner = add(multiply(dv, call("ln", left.clone())), divide(multiply(right.clone(), du), left.clone()));
+                ("general power rule", multiply(self.clone(), inner))
+            }
+        };
+        Ok((rule.to_string(), result))
+    }
+
+    /// Simplifies the operands and then the node itself.
+    fn simplify_once(&self) -> Expr {
+        match self {
+            Expr::Number(value) => number(*value),
+            Expr::Variable(_) => self.clone(),
+            Expr::Negate(_) | Expr::Binary(BinaryOp::Add | BinaryOp::Subtract, ..) => {
+                let mut terms = Vec::new();
+                collect_terms(self, 1.0, &mut terms);
+                build_sum(terms)
+            }
+            Expr::Binary(BinaryOp::Multiply, ..) => {
+                let mut factors = Vec::new();
+                let mut coefficient = 1.0;
+                collect_factors(self, &mut coefficient, &mut factors);
+                build_product(coefficient, factors)
+            }
+            Expr::Binary(BinaryOp::Divide, numerator, denominator) => {
+                simplify_quotient(numerator.simplify_once(), denominator.simplify_once())
+            }
+            Expr::Binary(BinaryOp::Power, base, exponent) => simplify_power(base.simplify_once(), exponent.simplify_once()),
+            Expr::Factorial(operand) => Expr::Factorial(Box::new(operand.simplify_once())),
+            Expr::Call(name, arguments) => {
+                let arguments: Vec<Expr> = arguments.iter().map(Expr::simplify_once).collect();
+                match (name.as_str(), arguments.as_slice()) {
+                    ("ln", [Expr::Variable(constant)]) if constant == "e" => number(1.0),
+                    ("ln" | "log", [Expr::Number(value)]) if *value == 1.0 => number(0.0),
+                    ("exp", [Expr::Number(value)]) if *value == 0.0 => number(1.0),
+                    _ => Expr::Call(name.clone(), arguments),
+                }
+            }
+            Expr::Convert(value, target) => Expr::Convert(Box::new(value.simplify_once()), target.clone()),
+            Expr::Matrix(rows) => Expr::Matrix(
+                rows.iter().map(|row| row.iter().map(Expr::simplify_once).collect()).collect(),
+            ),
+            Expr::Equation(left, right) => {
+                Expr::Equation(Box::new(left.simplify_once()), Box::new(right.simplify_once()))
+            }
+        }
+    }
+}
+
+/// Gets the derivative of a function of one argument at `u`, or `None` if
+/// the function has no derivative rule. `base` is the second argument of
+/// `log`.
+fn outer_derivative(name: &str, u: &Expr, base: Option<&Expr>) -> Option<Expr> {
+    let u = u.clone();
+    let derivative = match name {
+        "sin" => call("cos", u),
+        "cos" => negate(call("sin", u)),
+        "tan" => divide(number(1.0), power(call("cos", u), number(2.0))),
+        "asin" => divide(number(1.0), call("sqrt", subtract(number(1.0), power(u, number(2.0))))),
+        "acos" => negate(divide(number(1.0), call("sqrt", subtract(number(1.0), power(u, number(2.0)))))),
+        "atan" => divide(number(1.0), add(number(1.0), power(u, number(2.0)))),
+        "sinh" => call("cosh", u),
+        "cosh" => call("sinh", u),
+        "tanh" => divide(number(1.0), power(call("cosh", u), number(2.0))),
+        "exp" => call("exp", u),
+        "ln" => divide(number(1.0), u),
+        "log" => {
+            let base = base.cloned().unwrap_or(number(10.0));
+            divide(number(1.0), multiply(u, call("ln", base)))
+        }
+        "sqrt" => divide(number(1.0), multiply(number(2.0), call("sqrt", u))),
+        "cbrt" => divide(number(1.0), multiply(number(3.0), power(call("cbrt", u), number(2.0)))),
+        "abs" => divide(u.clone(), call("abs", u)),
+        _ => return None,
+    };
+    Some(derivative)
+}
+
+/// Records a step for a sub-expression whose operands are not
+/// differentiated.
+fn record(steps: &mut Option<Vec<DerivativeStep>>, expr: &Expr, rule: String, result: &Expr) {
+    if let Some(steps) = steps.as_mut() {
+        steps.push(DerivativeStep { expression: expr.to_string(), rule, result: result.to_string() });
+    }
+}
+
+/// Builds the error for a sub-expression that cannot be differentiated.
+fn no_rule(expr: &Expr) -> CalculatorError {
+    CalculatorError::invalid_input(Some(&format!("'{}' cannot be differentiated symbolically", expr)))
+}
+
+/// Adds the terms of a sum, each multiplied by `sign`, merging terms that
+/// differ only in their numeric coefficient unless adding the coefficients
+/// overflows or underflows.
+fn collect_terms(expr: &Expr, sign: f64, terms: &mut Vec<(f64, Option<Expr>)>) {
+    match expr {
+        Expr::Binary(BinaryOp::Add, left, right) => {
+            collect_terms(left, sign, terms);
+            collect_terms(right, sign, terms);
+        }
+        Expr::Binary(BinaryOp::Subtract, left, right) => {
+            collect_terms(left, sign, terms);
+            collect_terms(right, -sign, terms);
+        }
+        Expr::Negate(operand) => collect_terms(operand, -sign, terms),
+        _ => {
+            let simplified = expr.simplify_once();
+            if matches!(simplified, Expr::Negate(_) | Expr::Binary(BinaryOp::Add | BinaryOp::Subtract, ..)) {
+                return collect_terms(&simplified, sign, terms);
+            }
+            let (coefficient, rest) = split_coefficient(simplified);
+            let coefficient = sign * coefficient;
+            let merged = terms
+                .iter_mut()
+                .filter(|(_, other)| *other == rest)
+                .find_map(|term| checked_add(term.0, coefficient).ok().map(|sum| term.0 = sum));
+            if merged.is_none() {
+                terms.push((coefficient, rest));
+            }
+        }
+    }
+}
+
+/// Splits a simplified term into its numeric coefficient and the rest,
+/// which is `None` for a number.
+fn split_coefficient(term: Expr) -> (f64, Option<Expr>) {
+    match term {
+        Expr::Number(value) => (value, None),
+        Expr::Binary(BinaryOp::Multiply, left, right) => match *left {
+            Expr::Number(value) => (value, Some(*right)),
+            left => (1.0, Some(multiply(left, *right))),
+        },
+        term => (1.0, Some(term)),
+    }
+}
+
+/// Rebuilds a sum from its terms, with the constant terms last, added
+/// together where that neither overflows nor underflows.
+fn build_sum(terms: Vec<(f64, Option<Expr>)>) -> Expr {
+    let (constants, terms): (Vec<_>, Vec<_>) = terms.into_iter().partition(|(_, rest)| rest.is_none());
+    let mut folded: Vec<f64> = Vec::new();
+    for (constant, _) in constants {
+        match folded.last_mut().map(|last| checked_add(*last, constant).map(|sum| *last = sum)) {
+            Some(Ok(())) => {}
+            _ => folded.push(constant),
+        }
+    }
+    let mut ordered: Vec<(f64, Option<Expr>)> = terms.into_iter().filter(|(coefficient, _)| *coefficient != 0.0).collect();
+    ordered.extend(folded.into_iter().filter(|constant| *constant != 0.0).map(|constant| (constant, None)));
+
+    let mut sum: Option<Expr> = None;
+    for (coefficient, rest) in ordered {
+        sum = Some(match sum {
+            None => scale(coefficient, rest),
+            Some(sum) if coefficient < 0.0 => subtract(sum, scale(-coefficient, rest)),
+            Some(sum) => add(sum, scale(coefficient, rest)),
+        });
+    }
+    sum.unwrap_or(number(0.0))
+}
+
+/// Multiplies a term by its coefficient, leaving out a coefficient of 1.
+fn scale(coefficient: f64, rest: Option<Expr>) -> Expr {
+    match rest {
+        None => number(coefficient),
+        Some(rest) if coefficient == 1.0 => rest,
+        Some(rest) if coefficient == -1.0 => negate(rest),
+        Some(rest) => multiply(number(coefficient), rest),
+    }
+}
+
+/// Multiplies out the factors of a product into a numeric coefficient and
+/// a list of bases with numeric exponents, merging equal bases. A number
+/// that would overflow or underflow the coefficient is kept as a factor.
+fn collect_factors(expr: &Expr, coefficient: &mut f64, factors: &mut Vec<(Expr, f64)>) {
+    if let Expr::Binary(BinaryOp::Multiply, left, right) = expr {
+        collect_factors(left, coefficient, factors);
+        collect_factors(right, coefficient, factors);
+        return;
+    }
+    let (base, exponent) = match expr.simplify_once() {
+        Expr::Number(value) => match checked_multiply(*coefficient, value) {
+            Ok(product) => {
+                *coefficient = product;
+                return;
+            }
+            Err(_) => (number(value), 1.0),
+        },
+        Expr::Negate(operand) => {
+            *coefficient = -*coefficient;
+            return collect_factors(&operand, coefficient, factors);
+        }
+        simplified @ Expr::Binary(BinaryOp::Multiply, ..) => return collect_factors(&simplified, coefficient, factors),
+        Expr::Binary(BinaryOp::Power, base, exponent) => match *exponent {
+            Expr::Number(exponent) => (*base, exponent),
+            exponent => (power(*base, exponent), 1.0),
+        },
+        simplified => (simplified, 1.0),
+    };
+    match factors.iter_mut().find(|(other, _)| *other == base) {
+        Some(factor) => factor.1 += exponent,
+        None => factors.push((base, exponent)),
+    }
+}
+
+/// Rebuilds a product from its coefficient and factors, with the
+/// coefficient first.
+fn build_product(coefficient: f64, factors: Vec<(Expr, f64)>) -> Expr {
+    if coefficient == 0.0 {
+        return number(0.0);
+    }
+    // Variables come before the functions and powers they multiply
+    let mut factors = factors;
+    factors.sort_by_key(|(base, _)| !matches!(base, Expr::Variable(_)));
+    let product = factors
+        .into_iter()
+        .filter(|(_, exponent)| *exponent != 0.0)
+        .map(|(base, exponent)| if exponent == 1.0 { base } else { power(base, number(exponent)) })
+        .reduce(multiply);
+    scale(coefficient, product)
+}
+
+/// Simplifies a quotient of simplified operands.
+fn simplify_quotient(numerator: Expr, denominator: Expr) -> Expr {
+    match (&numerator, &denominator) {
+        (Expr::Negate(operand), _) => negate(simplify_quotient((**operand).clone(), denominator)),
+        (Expr::Number(a), _) if *a < 0.0 => negate(simplify_quotient(number(-a), denominator)),
+        (Expr::Number(a), Expr::Number(b)) if *b != 0.0 && (a / b).fract() == 0.0 => number(a / b),
+        (_, Expr::Number(b)) if *b == 1.0 => numerator,
+        (Expr::Number(a), _) if *a == 0.0 => number(0.0),
+        _ if numerator == denominator => number(1.0),
+        _ => divide(numerator, denominator),
+    }
+}
+
+/// Simplifies a power of simplified operands.
+fn simplify_power(base: Expr, exponent: Expr) -> Expr {
+    match (&base, &exponent) {
+        (Expr::Number(a), Expr::Number(n)) if n.fract() == 0.0 && n.abs() <= MAX_FOLDED_EXPONENT => {
+            let value = a.powf(*n);
+            if value.is_finite() && value.fract() == 0.0 {
+                number(value)
+            } else {
+                power(base, exponent)
+            }
+        }
+        (_, Expr::Number(n)) if *n == 0.0 => number(1.0),
+        (_, Expr::Number(n)) if *n == 1.0 => base,
+        (Expr::Number(a), _) if *a == 1.0 => number(1.0),
+        // (u^m)^n is u^(mn) for whole n
+        (Expr::Binary(BinaryOp::Power, inner, m), Expr::Number(n)) if n.fract() == 0.0 => match **m {
+            Expr::Number(m) => power((**inner).clone(), number(m * n)),
+            _ => power(base, exponent),
+        },
+        _ => power(base, exponent),
+    }
+}
+
+/// Builds a number, without a negative zero.
+fn number(value: f64) -> Expr {
+    Expr::Number(value + 0.0)
+}
+
+/// Builds a negation.
+fn negate(operand: Expr) -> Expr {
+    Expr::Negate(Box::new(operand))
+}
+
+/// Builds a sum.
+fn add(left: Expr, right: Expr) -> Expr {
+    Expr::Binary(BinaryOp::Add, Box::new(left), Box::new(right))
+}
+
+/// Builds a difference.
+fn subtract(left: Expr, right: Expr) -> Expr {
+    Expr::Binary(BinaryOp::Subtract, Box::new(left), Box::new(right))
+}
+
+/// Builds a product.
+fn multiply(left: Expr, right: Expr) -> Expr {
+    Expr::Binary(BinaryOp::Multiply, Box::new(left), Box::new(right))
+}
+
+/// Builds a quotient.
+fn divide(left: Expr, right: Expr) -> Expr {
+    Expr::Binary(BinaryOp::Divide, Box::new(left), Box::new(right))
+}
+
+/// Builds a power.
+fn power(base: Expr, exponent: Expr) -> Expr {
+    Expr::Binary(BinaryOp::Power, Box::new(base), Box::new(exponent))
+}
+
+/// Builds a call of a function of one argument.
+fn call(name: &str, argument: Expr) -> Expr {
+    Expr::Call(name.to_string(), vec![argument])
+}
@@ -0,0 +1,315 @@
+//! Step-by-step evaluation traces.
+//!
+//! This module records how a result is reached, for explanation mode:
+//! - Each operation, function call, variable and constant of an
+//!   expression, innermost first
+//! - The operation with its operands' values filled in, such as `4 * 2`
+//!   for `(1 + 3) * 2`
+//! - The intermediate value, as text and as a number
+//!
+//! Keypad calculations on `CalculatorState` record the same steps, so the
+//! interface can explain both without redoing any arithmetic itself.
+//! Numbers and quantities as written, such as `-3` and `5 km`, are not
+//! steps of their own, and the body of a call such as
+//! `integral(x^2, x, 0, 1)` is a single step.
+
+use wasm_bindgen::prelude::*;
+use super::evaluator::{binds_variable, equation_error, Context, Value};
+use super::functions::constant;
+use super::parser::{BinaryOp, Expr};
+use crate::calculator::complex::Complex;
+use crate::errors::CalcResult;
+use crate::state::format_significant;
+
+/// The number of significant digits of values in traces.
+const DISPLAY_DIGITS: usize = 15;
+
+/// Represents one step of an evaluation: an operation applied to values
+/// that are already known.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceStep {
+    /// The sub-expression as written
+    expression: String,
+    /// The operation with its operands' values
+    calculation: String,
+    /// The rule applied, such as `multiplication` or `function sin`
+    rule: String,
+    /// The value as display text, with units
+    result: String,
+    /// The value as a number
+    value: f64,
+}
+
+/// Getters for TraceStep
+#[wasm_bindgen]
+impl TraceStep {
+    /// Gets the sub-expression as written.
+    #[wasm_bindgen(getter)]
+    pub fn expression(&self) -> String {
+        self.expression.clone()
+    }
+
+    /// Gets the operation with its operands' values, such as `4 * 2`.
+    #[wasm_bindgen(getter)]
+    pub fn calculation(&self) -> String {
+        self.calculation.clone()
+    }
+
+    /// Gets the rule applied: `variable`, `constant`, `negation`,
+    /// `addition`, `subtraction`, `multiplication`, `division`, `power`,
+    /// `factorial`, `conversion`, `matrix`, or `function` and the
+    /// function's name.
+    #[wasm_bindgen(getter)]
+    pub fn rule(&self) -> String {
+        self.rule.clone()
+    }
+
+    /// Gets the value as display text, with units.
+    #[wasm_bindgen(getter)]
+    pub fn result(&self) -> String {
+        self.result.clone()
+    }
+
+    /// Gets the value as a number: in its displayed units for a quantity,
+    /// and NaN for a matrix or a complex number that is not real.
+    #[wasm_bindgen(getter)]
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+}
+
+impl TraceStep {
+    /// Creates the step of a keypad operation on two numbers.
+    ///
+    /// # Arguments
+    ///
+    /// * `op` - The operator
+    /// * `a` - The first operand
+    /// * `b` - The second operand
+    /// * `value` - The result
+    ///
+    /// # Returns
+    ///
+    /// The step, whose expression and calculation are both `a op b`
+    pub(crate) fn operation(op: BinaryOp, a: f64, b: f64, value: f64) -> Self {
+        let text = Expr::Binary(op, Box::new(Expr::Number(a)), Box::new(Expr::Number(b))).to_string();
+        TraceStep {
+            expression: text.clone(),
+            calculation: text,
+            rule: rule(op).to_string(),
+            result: format_significant(value, DISPLAY_DIGITS),
+            value,
+        }
+    }
+}
+
+impl Expr {
+    /// Evaluates the expression step by step.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The variable bindings and mode
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(steps)` - The steps in the order they are carried out; the
+    ///   last has the value of the whole expression, unless it is a plain
+    ///   number
+    /// * `Err(error)` - The first error raised, as for `evaluate`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::expression::{parse, Context};
+    ///
+    /// let steps = parse("(1 + 3) * 2").unwrap().trace(&Context::new()).unwrap();
+    /// assert_eq!(steps[0].calculation(), "1 + 3");
+    /// assert_eq!(steps[1].rule(), "multiplication");
+    /// assert_eq!(steps[1].calculation(), "4 * 2");
+    /// assert_eq!(steps[1].value(), 8.0);
+    /// ```
+    pub fn trace(&self, context: &Context) -> CalcResult<Vec<TraceStep>> {
+        let mut steps = Vec::new();
+        self.trace_node(context, &mut steps)?;
+        Ok(steps)
+    }
+
+    /// Traces the operands of a node and then the node itself, giving an
+    /// expression that stands for the node's value in its parent's
+    /// calculation, and the value itself.
+    ///
+    /// Each node's value is computed from its operands' traced values, so
+    /// no sub-expression is evaluated twice.
+    fn trace_node(&self, context: &Context, steps: &mut Vec<TraceStep>) -> CalcResult<(Expr, Traced)> {
+        if let Some(traced) = self.trace_leaf(context, steps) {
+            return traced;
+        }
+        let mut stand_ins = Vec::new();
+        let mut values = Vec::new();
+        for operand in self.operands() {
+            let (stand_in, value) = operand.trace_node(context, steps)?;
+            stand_ins.push(stand_in);
+            values.push(value);
+        }
+        let value = Traced::apply(self, values, context)?;
+        self.record(self.node_rule(), self.with_operands(stand_ins), value, steps)
+    }
+
+    /// Traces a node whose operands are not traced: a number, variable,
+    /// constant, unit, quantity as written or call that binds a variable.
+    ///
+    /// # Returns
+    ///
+    /// The node's stand-in and value, or `None` if its operands are traced
+    fn trace_leaf(&self, context: &Context, steps: &mut Vec<TraceStep>) -> Option<CalcResult<(Expr, Traced)>> {
+        let rule = match self {
+            Expr::Number(_) => return Some(Traced::leaf(self, context).map(|value| (self.clone(), value))),
+            Expr::Negate(operand) if matches!(**operand, Expr::Number(_)) => {
+                return Some(Traced::leaf(self, context).map(|value| (self.clone(), value)))
+            }
+            Expr::Variable(name) if context.get(name).is_some() => "variable".to_string(),
+            Expr::Variable(name) if constant(name).is_some() || (context.complex_mode() && (name == "i" || name == "j")) => {
+                "constant".to_string()
+            }
+            // Units are part of the quantity they follow, as in `5 km`
+            Expr::Variable(_) => return Some(Traced::leaf(self, context).map(|value| (self.clone(), value))),
+            Expr::Binary(BinaryOp::Multiply, left, right) if is_quantity_literal(left, right, context) => {
+                return Some(Traced::leaf(self, context).map(|value| {
+                    let (result, _, _) = value.describe(self);
+                    (Expr::Variable(format!("({})", result)), value)
+                }))
+            }
+            Expr::Call(name, arguments) if binds_variable(name, arguments) => format!("function {}", name),
+            Expr::Equation(_, _) => return Some(Err(equation_error())),
+            _ => return None,
+        };
+        Some(Traced::leaf(self, context).and_then(|value| self.record(rule, self.clone(), value, steps)))
+    }
+
+    /// Records the step of a node, giving its stand-in and value.
+    fn record(&self, rule: String, calculation: Expr, value: Traced, steps: &mut Vec<TraceStep>) -> CalcResult<(Expr, Traced)> {
+        let (result, number, displayed) = value.describe(self);
+        // A plain number is printed as one; anything else is written
+        // verbatim, in parentheses if it is more than one token
+        let stand_in = match number {
+            Some(number) => Expr::Number(number),
+            None if result.contains(' ') && !result.starts_with('[') => Expr::Variable(format!("({})", result)),
+            None => Expr::Variable(result.clone()),
+        };
+        steps.push(TraceStep {
+            expression: self.to_string(),
+            calculation: calculation.to_string(),
+            rule,
+            result,
+            value: number.unwrap_or(displayed),
+        });
+        Ok((stand_in, value))
+    }
+
+    /// Gets the rule of a node whose operands are traced.
+    fn node_rule(&self) -> String {
+        match self {
+            Expr::Negate(_) => "negation".to_string(),
+            Expr::Binary(op, _, _) => rule(*op).to_string(),
+            Expr::Factorial(_) => "factorial".to_string(),
+            Expr::Call(name, _) => format!("function {}", name),
+            Expr::Convert(_, _) => "conversion".to_string(),
+            _ => "matrix".to_string(),
+        }
+    }
+
+    /// Writes a node with its operands replaced, in the order `operands`
+    /// gives them, such as `4 * 2` for `(1 + 3) * 2`.
+    fn with_operands(&self, stand_ins: Vec<Expr>) -> Expr {
+        let mut stand_ins = stand_ins.into_iter();
+        let mut next = |operand: &Expr| stand_ins.next().unwrap_or_else(|| operand.clone());
+        match self {
+            Expr::Negate(operand) => Expr::Negate(Box::new(next(operand))),
+            Expr::Binary(op, left, right) => Expr::Binary(*op, Box::new(next(left)), Box::new(next(right))),
+            Expr::Factorial(operand) => Expr::Factorial(Box::new(next(operand))),
+            Expr::Call(name, arguments) => Expr::Call(name.clone(), arguments.iter().map(&mut next).collect()),
+            Expr::Convert(operand, target) => Expr::Convert(Box::new(next(operand)), target.clone()),
+            Expr::Matrix(rows) => {
+                Expr::Matrix(rows.iter().map(|row| row.iter().map(&mut next).collect()).collect())
+            }
+            _ => self.clone(),
+        }
+    }
+}
+
+/// The value of a traced node: a quantity or matrix, or a complex number
+/// in complex mode.
+enum Traced {
+    Real(Value),
+    Complex(Complex),
+}
+
+impl Traced {
+    /// Evaluates a node whose operands are not traced.
+    fn leaf(expr: &Expr, context: &Context) -> CalcResult<Traced> {
+        if context.complex_mode() {
+            Ok(Traced::Complex(expr.complex(context)?))
+        } else {
+            Ok(Traced::Real(expr.value(context)?))
+        }
+    }
+
+    /// Computes the value of a node from its operands' values, which are
+    /// all of the kind the mode gives.
+    fn apply(expr: &Expr, operands: Vec<Traced>, context: &Context) -> CalcResult<Traced> {
+        if context.complex_mode() {
+            let operands = operands.into_iter().filter_map(|operand| match operand {
+                Traced::Complex(value) => Some(value),
+                Traced::Real(_) => None,
+            });
+            Ok(Traced::Complex(expr.apply_complex(operands.collect())?))
+        } else {
+            let operands = operands.into_iter().filter_map(|operand| match operand {
+                Traced::Real(value) => Some(value),
+                Traced::Complex(_) => None,
+            });
+            Ok(Traced::Real(expr.apply(operands.collect())?))
+        }
+    }
+
+    /// Describes the value of a node as display text, as a number if it
+    /// is a plain real number, and as the number shown otherwise: a
+    /// quantity in its displayed units, or NaN.
+    fn describe(&self, expr: &Expr) -> (String, Option<f64>, f64) {
+        match self {
+            Traced::Complex(value) => (value.to_string(), Some(value.re()).filter(|_| value.is_real()), f64::NAN),
+            Traced::Real(Value::Quantity(quantity)) => {
+                let quantity = match expr {
+                    Expr::Convert(_, _) => quantity.clone(),
+                    _ => quantity.simplify(),
+                };
+                (quantity.to_string(), Some(quantity.si_value()).filter(|_| quantity.is_number()), quantity.value())
+            }
+            Traced::Real(Value::Matrix(matrix)) => (matrix.to_string(), None, f64::NAN),
+        }
+    }
+}
+
+/// Checks whether a product is a number followed by a unit, such as
+/// `5 km`, which is written as one quantity rather than traced.
+fn is_quantity_literal(left: &Expr, right: &Expr, context: &Context) -> bool {
+    let is_number = matches!(left, Expr::Number(_))
+        || matches!(left, Expr::Negate(operand) if matches!(**operand, Expr::Number(_)));
+    let is_unit = match right {
+        Expr::Variable(name) => context.get(name).is_none() && constant(name).is_none() && !context.complex_mode(),
+        _ => false,
+    };
+    is_number && is_unit
+}
+
+/// Gets the name of the rule for a binary operator.
+fn rule(op: BinaryOp) -> &'static str {
+    match op {
+        BinaryOp::Add => "addition",
+        BinaryOp::Subtract => "subtraction",
+        BinaryOp::Multiply => "multiplication",
+        BinaryOp::Divide => "division",
+        BinaryOp::Power => "power",
+    }
+}
@@ -0,0 +1,301 @@
+//! Message catalogue for localized error and UI text.
+//!
+//! This module provides translations for the messages shown by the
+//! calculator, selected by a global language setting:
+//! - Languages are selected by tag (`"de"`, `"es-MX"`, ...)
+//! - Messages are looked up by stable string keys (`"error.overflow"`)
+//! - Missing translations fall back to English
+//!
+//! Error messages are keyed by the string code of their
+//! `CalculatorErrorType` (`"error.<code>"`). Details passed to error
+//! constructors, such as the offending input, are written in English and
+//! only shown when English is selected.
+
+use wasm_bindgen::prelude::*;
+use crate::errors::CalculatorErrorType;
+
+/// Represents a language with a bundled message catalogue.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Language {
+    English,
+    Spanish,
+    German,
+    French,
+    Hindi,
+}
+
+impl Language {
+    /// All bundled languages.
+    pub const ALL: [Language; 5] = [
+        Language::English,
+        Language::Spanish,
+        Language::German,
+        Language::French,
+        Language::Hindi,
+    ];
+
+    /// Gets the language tag.
+    ///
+    /// # Returns
+    ///
+    /// The two-letter language tag (e.g. `"de"`)
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+            Language::German => "de",
+            Language::French => "fr",
+            Language::Hindi => "hi",
+        }
+    }
+
+    /// Looks up a bundled language by tag.
+    ///
+    /// Only the primary subtag is used, so `"de-AT"` and `"es_MX"` select
+    /// German and Spanish.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - A language tag
+    ///
+    /// # Returns
+    ///
+    /// The language, or `None` if no catalogue is bundled for it
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.split(['-', '_']).next().unwrap_or("").to_ascii_lowercase();
+        Self::ALL.iter().find(|language| language.tag() == primary).copied()
+    }
+
+    /// Gets the message catalogue for this language.
+    fn catalogue(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Language::English => ENGLISH,
+            Language::Spanish => SPANISH,
+            Language::German => GERMAN,
+            Language::French => FRENCH,
+            Language::Hindi => HINDI,
+        }
+    }
+}
+
+/// The label shown in front of error messages on the display.
+pub const ERROR_LABEL: &str = "ui.error";
+/// The message for a result that is not a number.
+pub const NOT_A_NUMBER: &str = "error.not_a_number";
+/// The message for a locale whose separators are equal.
+pub const SEPARATORS_MUST_DIFFER: &str = "locale.separators_must_differ";
+/// The message for a locale whose separators are digits or signs.
+pub const SEPARATORS_NOT_DIGITS: &str = "locale.separators_not_digits";
+
+const ENGLISH: &[(&str, &str)] = &[
+    (ERROR_LABEL, "Error"),
+    ("error.division_by_zero", "Division by zero is not allowed"),
+    ("error.invalid_input", "Invalid input"),
+    ("error.overflow", "Result is too large to represent"),
+    ("error.underflow", "Result is too small to represent"),
+    ("error.calculation_error", "Calculation error"),
+    ("error.no_solution", "No solution exists"),
+    ("error.incompatible_units", "Units are not compatible"),
+    ("error.singular_matrix", "Matrix is singular"),
+    ("error.iteration_limit", "Iteration limit reached"),
+    (NOT_A_NUMBER, "Result is not a number"),
+    (SEPARATORS_MUST_DIFFER, "decimal and grouping separators must differ"),
+    (SEPARATORS_NOT_DIGITS, "separators cannot be digits or signs"),
+];
+
+const SPANISH: &[(&str, &str)] = &[
+    (ERROR_LABEL, "Error"),
+    ("error.division_by_zero", "No se permite dividir entre cero"),
+    ("error.invalid_input", "Entrada no válida"),
+    ("error.overflow", "El resultado es demasiado grande para representarlo"),
+    ("error.underflow", "El resultado es demasiado pequeño para representarlo"),
+    ("error.calculation_error", "Error de cálculo"),
+    ("error.no_solution", "No existe solución"),
+    ("error.incompatible_units", "Las unidades no son compatibles"),
+    ("error.singular_matrix", "La matriz es singular"),
+    ("error.iteration_limit", "Se alcanzó el límite de iteraciones"),
+    (NOT_A_NUMBER, "El resultado no es un número"),
+    (SEPARATORS_MUST_DIFFER, "los separadores decimal y de miles deben ser distintos"),
+    (SEPARATORS_NOT_DIGITS, "los separadores no pueden ser dígitos ni signos"),
+];
+
+const GERMAN: &[(&str, &str)] = &[
+    (ERROR_LABEL, "Fehler"),
+    ("error.division_by_zero", "Division durch null ist nicht erlaubt"),
+    ("error.invalid_input", "Ungültige Eingabe"),
+    ("error.overflow", "Das Ergebnis ist zu groß für die Darstellung"),
+    ("error.underflow", "Das Ergebnis ist zu klein für die Darstellung"),
+    ("error.calculation_error", "Rechenfehler"),
+    ("error.no_solution", "Es gibt keine Lösung"),
+    ("error.incompatible_units", "Die Einheiten sind nicht kompatibel"),
+    ("error.singular_matrix", "Die Matrix ist singulär"),
+    ("error.iteration_limit", "Iterationsgrenze erreicht"),
+    (NOT_A_NUMBER, "Das Ergebnis ist keine Zahl"),
+    (SEPARATORS_MUST_DIFFER, "Dezimal- und Gruppierungstrennzeichen müssen sich unterscheiden"),
+    (SEPARATORS_NOT_DIGITS, "Trennzeichen dürfen keine Ziffern oder Vorzeichen sein"),
+];
+
+const FRENCH: &[(&str, &str)] = &[
+    (ERROR_LABEL, "Erreur"),
+    ("error.division_by_zero", "La division par zéro n'est pas autorisée"),
+    ("error.invalid_input", "Entrée non valide"),
+    ("error.overflow", "Le résultat est trop grand pour être représenté"),
+    ("error.underflow", "Le résultat est trop petit pour être représenté"),
+    ("error.calculation_error", "Erreur de calcul"),
+    ("error.no_solution", "Il n'existe aucune solution"),
+    ("error.incompatible_units", "Les unités ne sont pas compatibles"),
+    ("error.singular_matrix", "La matrice est singulière"),
+    ("error.iteration_limit", "Limite d'itérations atteinte"),
+    (NOT_A_NUMBER, "Le résultat n'est pas un nombre"),
+    (SEPARATORS_MUST_DIFFER, "les séparateurs décimal et de groupe doivent être différents"),
+    (SEPARATORS_NOT_DIGITS, "les séparateurs ne peuvent pas être des chiffres ou des signes"),
+];
+
+const HINDI: &[(&str, &str)] = &[
+    (ERROR_LABEL, "त्रुटि"),
+    ("error.division_by_zero", "शून्य से भाग देने की अनुमति नहीं है"),
+    ("error.invalid_input", "अमान्य इनपुट"),
+    ("error.overflow", "परिणाम दर्शाने के लिए बहुत बड़ा है"),
+    ("error.underflow", "परिणाम दर्शाने के लिए बहुत छोटा है"),
+    ("error.calculation_error", "गणना त्रुटि"),
+    ("error.no_solution", "कोई हल मौजूद नहीं है"),
+    ("error.incompatible_units", "इकाइयाँ संगत नहीं हैं"),
+    ("error.singular_matrix", "आव्यूह व्युत्क्रमणीय नहीं है"),
+    ("error.iteration_limit", "पुनरावृत्ति सीमा पूरी हो गई"),
+    (NOT_A_NUMBER, "परिणाम कोई संख्या नहीं है"),
+    (SEPARATORS_MUST_DIFFER, "दशमलव और समूह विभाजक अलग होने चाहिए"),
+    (SEPARATORS_NOT_DIGITS, "विभाजक अंक या चिह्न नहीं हो सकते"),
+];
+
+// The language used for messages
+thread_local! {
+    static LANGUAGE: std::cell::Cell<Language> = const { std::cell::Cell::new(Language::English) };
+}
+
+/// Lists every message key in the catalogue.
+///
+/// # Returns
+///
+/// The keys of the English catalogue, which is complete by definition
+pub fn message_keys() -> Vec<&'static str> {
+    ENGLISH.iter().map(|(key, _)| *key).collect()
+}
+
+/// Looks up a message in one language's catalogue, without fallback.
+///
+/// # Arguments
+///
+/// * `language` - The language to look in
+/// * `key` - The message key
+///
+/// # Returns
+///
+/// The translation, or `None` if the catalogue has no entry for the key
+pub fn translate(language: Language, key: &str) -> Option<&'static str> {
+    language
+        .catalogue()
+        .iter()
+        .find(|(entry, _)| *entry == key)
+        .map(|(_, text)| *text)
+}
+
+/// Gets a message in the given language.
+///
+/// Falls back to English if the language has no translation, and to the
+/// key itself if the key is unknown.
+///
+/// # Arguments
+///
+/// * `language` - The language to use
+/// * `key` - The message key
+///
+/// # Returns
+///
+/// The message text
+pub fn message_in(language: Language, key: &str) -> String {
+    translate(language, key)
+        .or_else(|| translate(Language::English, key))
+        .unwrap_or(key)
+        .to_string()
+}
+
+/// Gets the message key for an error type.
+///
+/// # Arguments
+///
+/// * `error_type` - The error type
+///
+/// # Returns
+///
+/// The key (`"error.<code>"`)
+pub fn error_key(error_type: &CalculatorErrorType) -> String {
+    format!("error.{}", error_type.name())
+}
+
+/// Gets the message for an error type in the current language.
+///
+/// # Arguments
+///
+/// * `error_type` - The error type
+///
+/// # Returns
+///
+/// The localized message
+pub fn error_message(error_type: &CalculatorErrorType) -> String {
+    message(&error_key(error_type))
+}
+
+/// Gets a message in the current language.
+///
+/// # Arguments
+///
+/// * `key` - The message key
+///
+/// # Returns
+///
+/// The message text
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::i18n::{message, set_language};
+///
+/// set_language("de-AT");
+/// assert_eq!(message("ui.error"), "Fehler");
+///
+/// set_language("en");
+/// assert_eq!(message("ui.error"), "Error");
+/// ```
+#[wasm_bindgen]
+pub fn message(key: &str) -> String {
+    message_in(language(), key)
+}
+
+/// Selects the language used for messages.
+///
+/// Unknown languages fall back to English.
+///
+/// # Arguments
+///
+/// * `tag` - A language tag such as `"fr"` or `"es-MX"`
+///
+/// # Returns
+///
+/// The language that was selected
+#[wasm_bindgen]
+pub fn set_language(tag: &str) -> Language {
+    let language = Language::from_tag(tag).unwrap_or(Language::English);
+    LANGUAGE.with(|current| current.set(language));
+    language
+}
+
+/// Gets the language used for messages.
+///
+/// # Returns
+///
+/// The current language
+#[wasm_bindgen]
+pub fn language() -> Language {
+    LANGUAGE.with(|current| current.get())
+}
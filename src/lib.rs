@@ -11,6 +11,10 @@
 //! - Memory functions
 //! - State management for calculator operations
 //! - Error handling with proper JavaScript integration
+//! - Localized error and UI messages
+//! - Expression evaluation with built-in functions
+//! - Unit conversion with dimensional analysis
+//! - Function plotting with adaptive sampling, and tables of values
 //!
 //! # Examples
 //!
@@ -28,6 +32,10 @@ use wasm_bindgen::prelude::*;
 pub mod calculator;
 pub mod state;
 pub mod errors;
+pub mod i18n;
+pub mod expression;
+pub mod units;
+pub mod plot;
 
 /// Returns a friendly greeting message.
 ///
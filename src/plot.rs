@@ -0,0 +1,561 @@
+//! Plot module for the Rust WASM Calculator.
+//!
+//! This module samples an expression in one variable over a range, ready to
+//! be drawn as lines:
+//! - An even grid of samples, refined where the curve bends or rises
+//!   steeply
+//! - Breaks at discontinuities, so an asymptote such as that of `tan(x)`
+//!   or a step of `floor(x)` is not joined by a vertical line
+//! - Points where the expression is undefined, such as `sqrt(x)` for
+//!   negative `x`, marked rather than aborting the plot; values too small
+//!   to represent, such as `exp(-x^2)` far from 0, are 0 rather than gaps
+//! - A suggested vertical range that ignores the far ends of asymptotes
+//!
+//! A y value of NaN means the line is broken there; the front end starts a
+//! new line after it.
+//!
+//! It also tabulates an expression at evenly spaced values, as a table of
+//! values screen does. A row where the expression cannot be evaluated, such
+//! as `1 / x` at `x = 0`, holds the error instead of a value, and the rest
+//! of the table is unaffected. A value too small to represent is 0, as in
+//! a plot.
+
+use wasm_bindgen::prelude::*;
+use crate::errors::{CalculatorError, CalculatorErrorType, CalcResult};
+use crate::expression::{parse, CompiledExpression, Expr};
+use crate::state::format_significant;
+
+/// The number of intervals of the initial, even grid.
+const INITIAL_INTERVALS: usize = 200;
+/// The number of times an interval may be halved.
+const MAX_DEPTH: u32 = 10;
+/// The number of points after which no interval is refined further.
+const MAX_POINTS: usize = 5000;
+/// How far, relative to the vertical range, the middle of an interval may
+/// stray from a straight line before the interval is halved.
+const FLATNESS: f64 = 0.001;
+/// How far, relative to the vertical range, a curve may rise in one half
+/// of a fully refined interval before it is taken to be a step.
+const JUMP: f64 = 0.05;
+/// The share of the rise of a fully refined interval that one half must
+/// hold for it to be a step; a smooth curve splits it more evenly.
+const JUMP_SHARE: f64 = 0.9;
+/// The fraction of the samples at each end ignored when choosing the
+/// vertical range.
+const OUTLIER_FRACTION: f64 = 0.05;
+/// The largest number of rows of a table of values.
+const MAX_ROWS: u32 = 10_000;
+/// The number of significant digits of numbers in CSV text.
+const DISPLAY_DIGITS: usize = 15;
+
+/// Represents the samples of a plotted expression.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plot {
+    /// The x values, in increasing order
+    xs: Vec<f64>,
+    /// The y values, NaN where the line is broken
+    ys: Vec<f64>,
+    /// The x values at which the expression could not be evaluated
+    undefined: Vec<f64>,
+    /// The bottom of the suggested vertical range
+    y_min: f64,
+    /// The top of the suggested vertical range
+    y_max: f64,
+}
+
+/// Getters for Plot
+#[wasm_bindgen]
+impl Plot {
+    /// Gets the x values, in increasing order.
+    #[wasm_bindgen(getter)]
+    pub fn xs(&self) -> Vec<f64> {
+        self.xs.clone()
+    }
+
+    /// Gets the y values, which are NaN where the line is broken.
+    #[wasm_bindgen(getter)]
+    pub fn ys(&self) -> Vec<f64> {
+        self.ys.clone()
+    }
+
+    /// Gets the x values at which the expression could not be evaluated,
+    /// such as the negative values of `x` in `sqrt(x)`.
+    #[wasm_bindgen(getter)]
+    pub fn undefined(&self) -> Vec<f64> {
+        self.undefined.clone()
+    }
+
+    /// Gets the bottom of the suggested vertical range.
+    #[wasm_bindgen(getter)]
+    pub fn y_min(&self) -> f64 {
+        self.y_min
+    }
+
+    /// Gets the top of the suggested vertical range.
+    #[wasm_bindgen(getter)]
+    pub fn y_max(&self) -> f64 {
+        self.y_max
+    }
+}
+
+impl Plot {
+    /// Splits the samples into the lines to draw.
+    ///
+    /// # Returns
+    ///
+    /// The `(x, y)` points of each unbroken line, from left to right
+    pub fn segments(&self) -> Vec<Vec<(f64, f64)>> {
+        let mut segments = Vec::new();
+        let mut current = Vec::new();
+        for (&x, &y) in self.xs.iter().zip(&self.ys) {
+            if y.is_nan() {
+                if !current.is_empty() {
+                    segments.push(std::mem::take(&mut current));
+                }
+            } else {
+                current.push((x, y));
+            }
+        }
+        if !current.is_empty() {
+            segments.push(current);
+        }
+        segments
+    }
+}
+
+/// Samples an expression in one variable over a range.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+/// * `variable` - The name of the variable
+/// * `from` - The left end of the range
+/// * `to` - The right end of the range
+///
+/// # Returns
+///
+/// * `Ok(plot)` - The samples, with breaks at discontinuities and at
+///   points where the expression is undefined
+/// * `Err(error)` - A syntax error, an invalid input error for an unknown
+///   name or a range that is empty or not finite, or the error at the middle
+///   of the range if the expression is undefined everywhere
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::plot::plot;
+///
+/// let plot = plot("1 / x", "x", -1.0, 1.0).unwrap();
+/// // The two branches of the hyperbola are separate lines
+/// assert_eq!(plot.segments().len(), 2);
+/// ```
+pub fn plot(input: &str, variable: &str, from: f64, to: f64) -> CalcResult<Plot> {
+    plot_expression(&parse(input)?, variable, from, to)
+}
+
+/// Samples an expression in one variable over a range.
+/// This is a WebAssembly-friendly version that returns a string error.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+/// * `variable` - The name of the variable
+/// * `from` - The left end of the range
+/// * `to` - The right end of the range
+///
+/// # Returns
+///
+/// The samples, or an error message
+#[wasm_bindgen(js_name = plot)]
+pub fn plot_wasm(input: &str, variable: &str, from: f64, to: f64) -> Result<Plot, JsValue> {
+    plot(input, variable, from, to).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Samples a parsed expression in one variable over a range.
+///
+/// # Arguments
+///
+/// * `expr` - The expression
+/// * `variable` - The name of the variable
+/// * `from` - The left end of the range
+/// * `to` - The right end of the range
+///
+/// # Returns
+///
+/// * `Ok(plot)` - The samples, as for `plot`
+/// * `Err(error)` - An error as for `plot`
+pub fn plot_expression(expr: &Expr, variable: &str, from: f64, to: f64) -> CalcResult<Plot> {
+    if !from.is_finite() || !to.is_finite() || from >= to {
+        return Err(CalculatorError::invalid_input(Some("the range to plot must be finite and increasing")));
+    }
+    let mut sampler = Sampler {
+        compiled: expr.compile(variable)?,
+        y_min: 0.0,
+        y_max: 0.0,
+        xs: Vec::new(),
+        ys: Vec::new(),
+        undefined: Vec::new(),
+    };
+
+    let step = (to - from) / INITIAL_INTERVALS as f64;
+    let grid: Vec<(f64, f64)> = (0..=INITIAL_INTERVALS)
+        .map(|i| {
+            let x = if i == INITIAL_INTERVALS { to } else { from + step * i as f64 };
+            (x, sampler.evaluate(x))
+        })
+        .collect();
+    if grid.iter().all(|(_, y)| y.is_nan()) {
+        return Err(sampler.compiled.eval(0.5 * (from + to)).err().unwrap_or_else(CalculatorError::not_a_number));
+    }
+
+    (sampler.y_min, sampler.y_max) = vertical_range(&grid);
+    sampler.push(grid[0].0, grid[0].1);
+    for pair in grid.windows(2) {
+        let ((a, fa), (b, fb)) = (pair[0], pair[1]);
+        sampler.refine(a, fa, b, fb, 0);
+        sampler.push(b, fb);
+    }
+    Ok(Plot { xs: sampler.xs, ys: sampler.ys, undefined: sampler.undefined, y_min: sampler.y_min, y_max: sampler.y_max })
+}
+
+/// Evaluates an expression at the samples of a plot.
+struct Sampler {
+    /// The expression, compiled
+    compiled: CompiledExpression,
+    /// The bottom of the suggested vertical range
+    y_min: f64,
+    /// The top of the suggested vertical range
+    y_max: f64,
+    /// The x values of the plot so far
+    xs: Vec<f64>,
+    /// The y values of the plot so far
+    ys: Vec<f64>,
+    /// The x values at which the expression could not be evaluated
+    undefined: Vec<f64>,
+}
+
+impl Sampler {
+    /// Evaluates the expression, giving NaN where it is undefined.
+    fn evaluate(&self, x: f64) -> f64 {
+        flushing_underflow(&self.compiled, x).unwrap_or(f64::NAN)
+    }
+
+    /// Adds a sample, noting it if the expression is undefined there.
+    fn push(&mut self, x: f64, y: f64) {
+        self.xs.push(x);
+        self.ys.push(y);
+        if y.is_nan() {
+            self.undefined.push(x);
+        }
+    }
+
+    /// Adds samples strictly between `a` and `b` wherever a straight line
+    /// between the ends is not close to the curve, and a break if the
+    /// interval holds a discontinuity.
+    fn refine(&mut self, a: f64, fa: f64, b: f64, fb: f64, depth: u32) {
+        let m = 0.5 * (a + b);
+        let fm = self.evaluate(m);
+        let bends = match (fa.is_nan(), fm.is_nan(), fb.is_nan()) {
+            (false, false, false) => {
+                let hidden = fa.min(fm).min(fb) > self.y_max || fa.max(fm).max(fb) < self.y_min;
+                !hidden && (fm - 0.5 * (fa + fb)).abs() > FLATNESS * (self.y_max - self.y_min)
+            }
+            (true, true, true) => false,
+            // Find the edge of the domain
+            _ => true,
+        };
+        if !bends {
+            return;
+        }
+
+        if depth >= MAX_DEPTH || self.xs.len() >= MAX_POINTS {
+            if self.is_break(fa, fm, fb) {
+                // A break that is not an undefined point
+                self.xs.push(m);
+                self.ys.push(f64::NAN);
+            } else {
+                self.push(m, fm);
+            }
+            return;
+        }
+        self.refine(a, fa, m, fm, depth + 1);
+        self.push(m, fm);
+        self.refine(m, fm, b, fb, depth + 1);
+    }
+
+    /// Checks whether a fully refined interval holds a discontinuity: a
+    /// step, where one half holds nearly all of a large rise, or a pole,
+    /// where the ends leave the vertical range on opposite sides.
+    fn is_break(&self, fa: f64, fm: f64, fb: f64) -> bool {
+        if fa.is_nan() || fm.is_nan() || fb.is_nan() {
+            return false;
+        }
+        let (above, below) = (fa.min(fb) > self.y_max, fa.max(fb) < self.y_min);
+        if above || below {
+            // Out of sight, where a steep curve is left unbroken
+            return false;
+        }
+        let (left, right) = ((fm - fa).abs(), (fb - fm).abs());
+        let is_step = left.max(right) > JUMP * (self.y_max - self.y_min) && left.max(right) > JUMP_SHARE * (left + right);
+        let is_pole = (fa > self.y_max && fb < self.y_min) || (fa < self.y_min && fb > self.y_max);
+        is_step || is_pole
+    }
+}
+
+/// Evaluates a compiled expression, taking values that underflow as 0.
+fn flushing_underflow(compiled: &CompiledExpression, x: f64) -> CalcResult {
+    match compiled.eval(x) {
+        Err(error) if error.error_type() == CalculatorErrorType::Underflow => Ok(0.0),
+        Ok(value) if value.is_subnormal() => Ok(0.0),
+        result => result,
+    }
+}
+
+/// Chooses a vertical range that holds the samples, except for the far
+/// ends of asymptotes.
+fn vertical_range(grid: &[(f64, f64)]) -> (f64, f64) {
+    let mut ys: Vec<f64> = grid.iter().map(|(_, y)| *y).filter(|y| !y.is_nan()).collect();
+    ys.sort_by(f64::total_cmp);
+    let cut = (OUTLIER_FRACTION * ys.len() as f64) as usize;
+    let (low, high) = (ys[cut], ys[ys.len() - 1 - cut]);
+    // Samples beyond the core range by more than its height are outliers
+    let core = high - low;
+    let bottom = ys[0].max(low - core);
+    let top = ys[ys.len() - 1].min(high + core);
+
+    let margin = if top > bottom { 0.05 * (top - bottom) } else { 0.1 * top.abs().max(1.0) };
+    (bottom - margin, top + margin)
+}
+
+/// Represents one row of a table of values.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct TableRow {
+    /// The value of the variable
+    x: f64,
+    /// The value of the expression, NaN if it could not be evaluated
+    value: f64,
+    /// The error raised evaluating the expression, if any
+    error: Option<CalculatorError>,
+}
+
+/// Getters for TableRow
+#[wasm_bindgen]
+impl TableRow {
+    /// Gets the value of the variable.
+    #[wasm_bindgen(getter)]
+    pub fn x(&self) -> f64 {
+        self.x
+    }
+
+    /// Gets the value of the expression, which is NaN if it could not be
+    /// evaluated.
+    #[wasm_bindgen(getter)]
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Gets the code of the error raised evaluating the expression.
+    ///
+    /// # Returns
+    ///
+    /// The error code, or `None` if the row has a value.
+    #[wasm_bindgen(getter)]
+    pub fn error_code(&self) -> Option<u16> {
+        self.error.as_ref().map(CalculatorError::code)
+    }
+
+    /// Gets the message of the error raised evaluating the expression.
+    ///
+    /// # Returns
+    ///
+    /// The error message, or `None` if the row has a value.
+    #[wasm_bindgen(getter)]
+    pub fn error_message(&self) -> Option<String> {
+        self.error.as_ref().map(|error| error.message().to_string())
+    }
+}
+
+impl TableRow {
+    /// Gets the error raised evaluating the expression, if any.
+    pub fn error(&self) -> Option<&CalculatorError> {
+        self.error.as_ref()
+    }
+}
+
+/// Represents a table of values of an expression in one variable.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct ValueTable {
+    /// The name of the variable
+    variable: String,
+    /// The expression, as printed
+    expression: String,
+    /// The rows, in the order the variable was stepped
+    rows: Vec<TableRow>,
+}
+
+/// Getters for ValueTable
+#[wasm_bindgen]
+impl ValueTable {
+    /// Gets the name of the variable, the heading of the first column.
+    #[wasm_bindgen(getter)]
+    pub fn variable(&self) -> String {
+        self.variable.clone()
+    }
+
+    /// Gets the expression as printed, the heading of the second column.
+    #[wasm_bindgen(getter)]
+    pub fn expression(&self) -> String {
+        self.expression.clone()
+    }
+
+    /// Gets the rows, in the order the variable was stepped.
+    #[wasm_bindgen(getter)]
+    pub fn rows(&self) -> Vec<TableRow> {
+        self.rows.clone()
+    }
+
+    /// Writes the table as CSV text.
+    ///
+    /// The header row names the variable, the expression and an `error`
+    /// column. Numbers have up to 15 significant digits and a `.` decimal
+    /// point whatever the locale; a row with an error has an empty value
+    /// and the error message.
+    ///
+    /// # Returns
+    ///
+    /// The CSV text, with a line break after each row
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!("{},{},error\n", csv_field(&self.variable), csv_field(&self.expression));
+        for row in &self.rows {
+            let value = match row.error {
+                Some(_) => String::new(),
+                None => format_significant(row.value, DISPLAY_DIGITS),
+            };
+            let error = row.error.as_ref().map(|error| csv_field(error.message())).unwrap_or_default();
+            csv.push_str(&format!("{},{},{}\n", format_significant(row.x, DISPLAY_DIGITS), value, error));
+        }
+        csv
+    }
+}
+
+/// Tabulates an expression in one variable at evenly spaced values.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+/// * `variable` - The name of the variable
+/// * `start` - The value of the variable in the first row
+/// * `step` - The difference between the values of consecutive rows
+/// * `count` - The number of rows
+///
+/// # Returns
+///
+/// * `Ok(table)` - The table, with any error raised in a row recorded in
+///   that row
+/// * `Err(error)` - A syntax error, or an invalid input error for an unknown
+///   name, a start or step that is not finite, a zero step, or more than
+///   10,000 rows
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::plot::table;
+///
+/// let table = table("1 / x", "x", -1.0, 1.0, 3).unwrap();
+/// let rows = table.rows();
+/// assert_eq!(rows[0].value(), -1.0);
+/// // Only the row at x = 0 fails
+/// assert!(rows[1].error().is_some());
+/// assert_eq!(rows[2].value(), 1.0);
+/// assert_eq!(table.to_csv().lines().next(), Some("x,1 / x,error"));
+/// ```
+pub fn table(input: &str, variable: &str, start: f64, step: f64, count: u32) -> CalcResult<ValueTable> {
+    table_expression(&parse(input)?, variable, start, step, count)
+}
+
+/// Tabulates an expression in one variable at evenly spaced values.
+/// This is a WebAssembly-friendly version that returns a string error.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+/// * `variable` - The name of the variable
+/// * `start` - The value of the variable in the first row
+/// * `step` - The difference between the values of consecutive rows
+/// * `count` - The number of rows
+///
+/// # Returns
+///
+/// The table, or an error message
+#[wasm_bindgen(js_name = table)]
+pub fn table_wasm(input: &str, variable: &str, start: f64, step: f64, count: u32) -> Result<ValueTable, JsValue> {
+    table(input, variable, start, step, count).map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Tabulates an expression in one variable and writes the table as CSV
+/// text, as for `ValueTable::to_csv`.
+///
+/// # Arguments
+///
+/// * `input` - The expression text
+/// * `variable` - The name of the variable
+/// * `start` - The value of the variable in the first row
+/// * `step` - The difference between the values of consecutive rows
+/// * `count` - The number of rows
+///
+/// # Returns
+///
+/// The CSV text, or an error message
+#[wasm_bindgen(js_name = table_csv)]
+pub fn table_csv_wasm(input: &str, variable: &str, start: f64, step: f64, count: u32) -> Result<String, JsValue> {
+    table_wasm(input, variable, start, step, count).map(|table| table.to_csv())
+}
+
+/// Tabulates a parsed expression in one variable at evenly spaced values.
+///
+/// # Arguments
+///
+/// * `expr` - The expression
+/// * `variable` - The name of the variable
+/// * `start` - The value of the variable in the first row
+/// * `step` - The difference between the values of consecutive rows
+/// * `count` - The number of rows
+///
+/// # Returns
+///
+/// * `Ok(table)` - The table, as for `table`
+/// * `Err(error)` - An error as for `table`
+pub fn table_expression(expr: &Expr, variable: &str, start: f64, step: f64, count: u32) -> CalcResult<ValueTable> {
+    if !start.is_finite() || !step.is_finite() || step == 0.0 {
+        return Err(CalculatorError::invalid_input(Some("the start and step of a table must be finite, and the step not zero")));
+    }
+    if count > MAX_ROWS {
+        return Err(CalculatorError::invalid_input(Some(&format!("a table has at most {} rows", MAX_ROWS))));
+    }
+
+    let compiled = expr.compile(variable)?;
+    let rows = (0..count)
+        .map(|i| {
+            // Multiplying rather than adding keeps rounding errors from
+            // building up down the table
+            let x = start + step * i as f64;
+            match flushing_underflow(&compiled, x) {
+                Ok(value) => TableRow { x, value, error: None },
+                Err(error) => TableRow { x, value: f64::NAN, error: Some(error) },
+            }
+        })
+        .collect();
+    Ok(ValueTable { variable: variable.to_string(), expression: compiled.expression(), rows })
+}
+
+/// Quotes a CSV field if it holds a comma, a quote or a line break.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
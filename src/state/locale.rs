@@ -0,0 +1,487 @@
+//! Locale settings for number display and input.
+//!
+//! This module provides locale-aware number handling for the calculator:
+//! - Decimal and grouping separators (e.g. `1,234.5` vs `1.234,5`)
+//! - Grouping styles (thousands or Indian lakh/crore)
+//! - Native digit scripts (e.g. Devanagari or Arabic-Indic digits)
+//!
+//! The calculator always keeps values in a canonical form (ASCII digits,
+//! `.` as the decimal point, no grouping). A `Locale` is only applied when
+//! rendering a value for display and when parsing text typed by the user.
+
+use wasm_bindgen::prelude::*;
+use crate::errors::{CalculatorError, CalcResult};
+use crate::i18n::{self, SEPARATORS_MUST_DIFFER, SEPARATORS_NOT_DIGITS};
+
+/// Represents how the integer part of a number is grouped.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GroupingStyle {
+    /// No grouping (`1234567`)
+    None,
+    /// Groups of three digits (`1,234,567`)
+    Thousands,
+    /// Indian lakh/crore grouping (`12,34,567`)
+    Indian,
+}
+
+/// Represents the script used to render digits.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DigitScript {
+    /// Western digits (0-9)
+    Latin,
+    /// Arabic-Indic digits (٠-٩)
+    ArabicIndic,
+    /// Extended Arabic-Indic digits used for Persian and Urdu (۰-۹)
+    ExtendedArabicIndic,
+    /// Devanagari digits (०-९)
+    Devanagari,
+    /// Bengali digits (০-৯)
+    Bengali,
+}
+
+impl DigitScript {
+    /// All supported digit scripts.
+    const ALL: [DigitScript; 5] = [
+        DigitScript::Latin,
+        DigitScript::ArabicIndic,
+        DigitScript::ExtendedArabicIndic,
+        DigitScript::Devanagari,
+        DigitScript::Bengali,
+    ];
+
+    /// Gets the character used for zero in this script.
+    fn zero(self) -> char {
+        match self {
+            DigitScript::Latin => '0',
+            DigitScript::ArabicIndic => '\u{0660}',
+            DigitScript::ExtendedArabicIndic => '\u{06F0}',
+            DigitScript::Devanagari => '\u{0966}',
+            DigitScript::Bengali => '\u{09E6}',
+        }
+    }
+
+    /// Converts an ASCII digit to this script.
+    fn render(self, digit: char) -> char {
+        match digit.to_digit(10) {
+            Some(value) => char::from_u32(self.zero() as u32 + value).unwrap_or(digit),
+            None => digit,
+        }
+    }
+
+    /// Gets the numeric value of a digit written in any supported script.
+    fn value_of(c: char) -> Option<u32> {
+        Self::ALL.iter().find_map(|script| {
+            let offset = (c as u32).checked_sub(script.zero() as u32)?;
+            (offset < 10).then_some(offset)
+        })
+    }
+}
+
+/// Represents the locale settings used to display and parse numbers.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Locale {
+    /// The character used as the decimal separator
+    decimal_separator: char,
+    /// The character used to separate digit groups
+    grouping_separator: char,
+    /// How the integer part is grouped
+    grouping_style: GroupingStyle,
+    /// The script used to render digits
+    digit_script: DigitScript,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::en_us()
+    }
+}
+
+impl Locale {
+    /// Creates a new locale, validating the separators.
+    ///
+    /// # Arguments
+    ///
+    /// * `decimal_separator` - The character used as the decimal separator
+    /// * `grouping_separator` - The character used to separate digit groups
+    /// * `grouping_style` - How the integer part is grouped
+    /// * `digit_script` - The script used to render digits
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(locale)` - The new locale
+    /// * `Err(error)` - An invalid input error if the separators are equal,
+    ///   are digits, or are sign characters
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::state::{DigitScript, GroupingStyle, Locale};
+    ///
+    /// let locale = Locale::try_new(',', '.', GroupingStyle::Thousands, DigitScript::Latin);
+    /// assert!(locale.is_ok());
+    ///
+    /// let invalid = Locale::try_new(',', ',', GroupingStyle::Thousands, DigitScript::Latin);
+    /// assert!(invalid.is_err());
+    /// ```
+    pub fn try_new(
+        decimal_separator: char,
+        grouping_separator: char,
+        grouping_style: GroupingStyle,
+        digit_script: DigitScript,
+    ) -> CalcResult<Self> {
+        if decimal_separator == grouping_separator {
+            return Err(CalculatorError::invalid_input(Some(
+                &i18n::message(SEPARATORS_MUST_DIFFER),
+            )));
+        }
+
+        for separator in [decimal_separator, grouping_separator] {
+            if DigitScript::value_of(separator).is_some() || is_sign(separator) {
+                return Err(CalculatorError::invalid_input(Some(
+                    &i18n::message(SEPARATORS_NOT_DIGITS),
+                )));
+            }
+        }
+
+        Ok(Self {
+            decimal_separator,
+            grouping_separator,
+            grouping_style,
+            digit_script,
+        })
+    }
+
+    /// Renders a canonical number string using this locale.
+    ///
+    /// Canonical strings use ASCII digits, an optional leading `-`, `.` as
+    /// the decimal point and an optional `e` exponent, which is rendered as
+    /// `E` (`"6.022e23"` becomes `6.022E23`). Partially typed values such as
+    /// `"12."` or `"6.022e"` are kept intact. Strings that are not numbers
+    /// (such as error messages) are returned unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `canonical` - The canonical number string
+    /// * `group` - Whether to apply digit grouping to the integer part
+    ///
+    /// # Returns
+    ///
+    /// The localized string
+    pub fn localize(&self, canonical: &str, group: bool) -> String {
+        if let Some((mantissa, exponent)) = canonical.split_once('e') {
+            let digits = exponent.trim_start_matches('-');
+            if !digits.chars().all(|c| c.is_ascii_digit()) {
+                return canonical.to_string();
+            }
+
+            let mut result = self.localize(mantissa, group);
+            result.push('E');
+            if exponent.starts_with('-') {
+                result.push('-');
+            }
+            result.extend(digits.chars().map(|c| self.digit_script.render(c)));
+            return result;
+        }
+
+        let (sign, unsigned) = match canonical.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => ("", canonical),
+        };
+
+        if unsigned.is_empty() || !unsigned.chars().all(|c| c.is_ascii_digit() || c == '.') {
+            return canonical.to_string();
+        }
+
+        let (integer, fraction) = match unsigned.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (unsigned, None),
+        };
+
+        let mut result = String::from(sign);
+        if group {
+            result.push_str(&self.group_digits(integer));
+        } else {
+            result.extend(integer.chars().map(|c| self.digit_script.render(c)));
+        }
+
+        if let Some(fraction) = fraction {
+            result.push(self.decimal_separator);
+            result.extend(fraction.chars().map(|c| self.digit_script.render(c)));
+        }
+
+        result
+    }
+
+    /// Parses a number written in this locale into its canonical string form.
+    ///
+    /// Digits from any supported script are accepted, as is an exponent
+    /// written with `e` or `E`. Grouping separators are ignored; when the
+    /// grouping separator is a space, any kind of space is accepted.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The localized text typed by the user
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(canonical)` - The canonical number string
+    /// * `Err(error)` - An invalid input error if the text is not a number
+    pub fn to_canonical(&self, input: &str) -> CalcResult<String> {
+        let trimmed = input.trim();
+        let mut canonical = String::with_capacity(trimmed.len());
+
+        for (index, c) in trimmed.chars().enumerate() {
+            if let Some(value) = DigitScript::value_of(c) {
+                canonical.push(char::from_digit(value, 10).unwrap_or('0'));
+            } else if c == self.decimal_separator {
+                canonical.push('.');
+            } else if c == self.grouping_separator
+                || (self.grouping_separator.is_whitespace() && c.is_whitespace())
+            {
+                continue;
+            } else if (c == 'e' || c == 'E') && !canonical.contains('e') {
+                canonical.push('e');
+            } else if is_sign(c) && (index == 0 || canonical.ends_with('e')) {
+                if c != '+' {
+                    canonical.push('-');
+                }
+            } else {
+                return Err(CalculatorError::invalid_input(Some(trimmed)));
+            }
+        }
+
+        if canonical.parse::<f64>().is_err() {
+            return Err(CalculatorError::invalid_input(Some(trimmed)));
+        }
+
+        Ok(canonical)
+    }
+
+    /// Parses a number written in this locale.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The localized text typed by the user
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(value)` - The parsed value
+    /// * `Err(error)` - An invalid input error if the text is not a number
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::state::Locale;
+    ///
+    /// let german = Locale::de_de();
+    /// assert_eq!(german.parse(" 1.234,5").unwrap(), 1234.5);
+    /// assert!(german.parse("1,2,3").is_err());
+    /// ```
+    pub fn parse(&self, input: &str) -> CalcResult {
+        self.to_canonical(input)?
+            .parse::<f64>()
+            .map_err(|_| CalculatorError::invalid_input(Some(input)))
+    }
+
+    /// Inserts grouping separators into a string of ASCII digits.
+    fn group_digits(&self, integer: &str) -> String {
+        let digits: Vec<char> = integer.chars().map(|c| self.digit_script.render(c)).collect();
+        let len = digits.len();
+        let mut result = String::with_capacity(len * 2);
+
+        for (index, digit) in digits.iter().enumerate() {
+            let remaining = len - index;
+            let boundary = match self.grouping_style {
+                GroupingStyle::None => false,
+                GroupingStyle::Thousands => remaining.is_multiple_of(3),
+                GroupingStyle::Indian => {
+                    remaining == 3 || (remaining > 3 && (remaining - 3).is_multiple_of(2))
+                }
+            };
+
+            if index > 0 && boundary {
+                result.push(self.grouping_separator);
+            }
+            result.push(*digit);
+        }
+
+        result
+    }
+}
+
+/// Constructors and getters for Locale
+#[wasm_bindgen]
+impl Locale {
+    /// Creates a new locale from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `decimal_separator` - The character used as the decimal separator
+    /// * `grouping_separator` - The character used to separate digit groups
+    /// * `grouping_style` - How the integer part is grouped
+    /// * `digit_script` - The script used to render digits
+    ///
+    /// # Returns
+    ///
+    /// The new locale, or an error message if the separators are invalid
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        decimal_separator: char,
+        grouping_separator: char,
+        grouping_style: GroupingStyle,
+        digit_script: DigitScript,
+    ) -> Result<Locale, JsValue> {
+        Self::try_new(decimal_separator, grouping_separator, grouping_style, digit_script)
+            .map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// US English: `1,234,567.5`
+    pub fn en_us() -> Locale {
+        Self {
+            decimal_separator: '.',
+            grouping_separator: ',',
+            grouping_style: GroupingStyle::Thousands,
+            digit_script: DigitScript::Latin,
+        }
+    }
+
+    /// German: `1.234.567,5`
+    pub fn de_de() -> Locale {
+        Self {
+            decimal_separator: ',',
+            grouping_separator: '.',
+            grouping_style: GroupingStyle::Thousands,
+            digit_script: DigitScript::Latin,
+        }
+    }
+
+    /// French: `1 234 567,5` (narrow no-break space)
+    pub fn fr_fr() -> Locale {
+        Self {
+            decimal_separator: ',',
+            grouping_separator: '\u{202F}',
+            grouping_style: GroupingStyle::Thousands,
+            digit_script: DigitScript::Latin,
+        }
+    }
+
+    /// Indian English: `12,34,567.5`
+    pub fn en_in() -> Locale {
+        Self {
+            decimal_separator: '.',
+            grouping_separator: ',',
+            grouping_style: GroupingStyle::Indian,
+            digit_script: DigitScript::Latin,
+        }
+    }
+
+    /// Hindi: `१२,३४,५६७.५`
+    pub fn hi_in() -> Locale {
+        Self {
+            decimal_separator: '.',
+            grouping_separator: ',',
+            grouping_style: GroupingStyle::Indian,
+            digit_script: DigitScript::Devanagari,
+        }
+    }
+
+    /// Gets the decimal separator.
+    pub fn decimal_separator(&self) -> char {
+        self.decimal_separator
+    }
+
+    /// Gets the grouping separator.
+    pub fn grouping_separator(&self) -> char {
+        self.grouping_separator
+    }
+
+    /// Gets the grouping style.
+    pub fn grouping_style(&self) -> GroupingStyle {
+        self.grouping_style
+    }
+
+    /// Gets the digit script.
+    pub fn digit_script(&self) -> DigitScript {
+        self.digit_script
+    }
+
+    /// Formats a number for display using this locale, with digit grouping.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to format
+    ///
+    /// # Returns
+    ///
+    /// The localized string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::state::Locale;
+    ///
+    /// assert_eq!(Locale::de_de().format_number(1234.5), "1.234,5");
+    /// assert_eq!(Locale::en_in().format_number(1234567.0), "12,34,567");
+    /// ```
+    pub fn format_number(&self, value: f64) -> String {
+        self.localize(&format_canonical(value), true)
+    }
+
+    /// Parses a number written in this locale from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The localized text
+    ///
+    /// # Returns
+    ///
+    /// The parsed value, or an error message if the text is not a number
+    #[wasm_bindgen(js_name = parse)]
+    pub fn parse_wasm(&self, input: &str) -> Result<f64, JsValue> {
+        self.parse(input).map_err(|e| JsValue::from_str(e.message()))
+    }
+}
+
+/// Formats a value in canonical form, avoiding unnecessary decimal places.
+///
+/// # Arguments
+///
+/// * `value` - The value to format
+///
+/// # Returns
+///
+/// The canonical string (`"8"` rather than `"8.0"`)
+pub fn format_canonical(value: f64) -> String {
+    if value == (value as i64) as f64 {
+        format!("{}", value as i64)
+    } else {
+        format!("{}", value)
+    }
+}
+
+/// Formats a value in canonical form, rounded to a number of significant
+/// digits to hide floating-point noise such as 0.6000000000000001.
+///
+/// # Arguments
+///
+/// * `value` - The value to format
+/// * `digits` - The number of significant digits
+///
+/// # Returns
+///
+/// The canonical string of the rounded value
+pub(crate) fn format_significant(value: f64, digits: usize) -> String {
+    if !value.is_finite() || value == 0.0 {
+        return format_canonical(value);
+    }
+    let rounded: f64 = format!("{:.*e}", digits.max(1) - 1, value).parse().unwrap_or(value);
+    format_canonical(rounded)
+}
+
+/// Checks whether a character is a sign that may prefix a number.
+fn is_sign(c: char) -> bool {
+    matches!(c, '-' | '+' | '\u{2212}')
+}
@@ -6,10 +6,16 @@
 //! The module is organized into:
 //! - Type definitions (Operation enum, CalculatorState struct)
 //! - State operations (methods for manipulating calculator state)
+//! - Locale settings (number display and input parsing)
+//! - Statistics mode (data entry for one-variable statistics)
 
 // Export submodules
 pub mod types;
 pub mod operations;
+pub mod locale;
+pub mod statistics;
 
 // Re-export commonly used types and functions
-pub use types::{Operation, CalculatorState}; 
\ No newline at end of file
+pub use types::{Operation, CalculatorMode, CalculatorState, ErrorRecovery, DEFAULT_MAX_DIGITS};
+pub use locale::{Locale, GroupingStyle, DigitScript, format_canonical};
+pub(crate) use locale::format_significant; 
\ No newline at end of file
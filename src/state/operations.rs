@@ -8,8 +8,12 @@
 //! - Calculating results
 
 use wasm_bindgen::prelude::*;
-use super::types::{CalculatorState, Operation};
+use super::types::{CalculatorState, ErrorRecovery, Operation};
+use super::locale::format_canonical;
+use crate::calculator::{checked_add, checked_subtract, checked_multiply, checked_divide};
 use crate::errors::CalculatorError;
+use crate::expression::{BinaryOp, TraceStep};
+use crate::i18n::{self, ERROR_LABEL};
 
 #[wasm_bindgen]
 impl CalculatorState {
@@ -23,6 +27,9 @@ impl CalculatorState {
         self.clear_on_next_input = false;
         self.last_pressed_operation = false;
         self.error_state = false;
+        self.last_error = None;
+        self.input_truncated = false;
+        self.trace.clear();
     }
 
     /// Clears the current entry without resetting the entire calculation.
@@ -30,15 +37,25 @@ impl CalculatorState {
         self.display_value = "0".to_string();
         self.clear_on_next_input = false;
         self.error_state = false;
+        self.last_error = None;
+        self.input_truncated = false;
     }
 
     /// Handles input of a digit.
     ///
+    /// Digits beyond the maximum digit count are ignored and flagged
+    /// through `input_truncated`. During exponent entry the digit is added
+    /// to the exponent instead, as long as the value stays within the range
+    /// of `f64`.
+    ///
+    /// In the error state, the digit starts a fresh calculation if error
+    /// recovery is set to `Input`; otherwise it is ignored.
+    ///
     /// # Arguments
     ///
     /// * `digit` - The digit to input (0-9)
     pub fn input_digit(&mut self, digit: u8) {
-        if self.error_state {
+        if self.error_state && !self.recover_on_input() {
             return;
         }
 
@@ -49,38 +66,175 @@ impl CalculatorState {
         if self.clear_on_next_input {
             self.display_value = digit.to_string();
             self.clear_on_next_input = false;
+        } else if self.display_value.contains('e') {
+            if !self.push_exponent_digit(digit) {
+                self.input_truncated = true;
+                return;
+            }
         } else if self.display_value == "0" {
             self.display_value = digit.to_string();
+        } else if count_digits(&self.display_value) >= self.max_digits as usize {
+            self.input_truncated = true;
+            return;
         } else {
             self.display_value.push_str(&digit.to_string());
         }
 
+        self.input_truncated = false;
         self.last_pressed_operation = false;
     }
 
     /// Handles input of a decimal point.
+    ///
+    /// The point is stored canonically as `.` and rendered with the
+    /// locale's decimal separator.
     pub fn input_decimal(&mut self) {
-        if self.error_state {
+        if self.error_state && !self.recover_on_input() {
             return;
         }
 
         if self.clear_on_next_input {
             self.display_value = "0.".to_string();
             self.clear_on_next_input = false;
-        } else if !self.display_value.contains('.') {
+        } else if !self.display_value.contains('.') && !self.display_value.contains('e') {
             self.display_value.push('.');
         }
 
         self.last_pressed_operation = false;
     }
 
+    /// Handles the exponent key (EE/EXP).
+    ///
+    /// Starts entry of a power-of-ten exponent for the current mantissa, so
+    /// `6.022 EE 23` enters 6.022×10²³. Starting an exponent on a fresh
+    /// entry uses a mantissa of 1.
+    pub fn input_exponent(&mut self) {
+        if self.error_state && !self.recover_on_input() {
+            return;
+        }
+
+        if self.clear_on_next_input || self.display_value == "0" {
+            self.display_value = "1e".to_string();
+            self.clear_on_next_input = false;
+        } else if !self.display_value.contains('e') {
+            if self.display_value.ends_with('.') {
+                self.display_value.pop();
+            }
+            self.display_value.push('e');
+        }
+
+        self.last_pressed_operation = false;
+    }
+
+    /// Toggles the sign of the exponent being entered.
+    ///
+    /// This does nothing outside exponent entry, or if the new exponent
+    /// would take the value out of the range of `f64` (which is flagged
+    /// through `input_truncated`).
+    pub fn toggle_exponent_sign(&mut self) {
+        if self.error_state || self.clear_on_next_input {
+            return;
+        }
+
+        let Some((mantissa, exponent)) = self.display_value.split_once('e') else {
+            return;
+        };
+
+        let candidate = match exponent.strip_prefix('-') {
+            Some(digits) => format!("{}e{}", mantissa, digits),
+            None => format!("{}e-{}", mantissa, exponent),
+        };
+
+        if is_in_range(&candidate) {
+            self.display_value = candidate;
+            self.input_truncated = false;
+        } else {
+            self.input_truncated = true;
+        }
+    }
+
+    /// Replaces the current entry with a number typed in the active locale.
+    ///
+    /// This accepts localized text such as `1.234,5` for German users. The
+    /// value is stored in canonical form; invalid text puts the calculator
+    /// into the error state.
+    ///
+    /// Fractional digits beyond the maximum digit count are dropped. If the
+    /// integer part alone is too long, the entry is left unchanged. Both
+    /// cases are flagged through `input_truncated`.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The localized number text
+    pub fn input_value(&mut self, text: &str) {
+        if self.error_state && !self.recover_on_input() {
+            return;
+        }
+
+        match self.locale.to_canonical(text) {
+            Ok(canonical) if !is_in_range(&canonical) => {
+                self.set_error(range_error(&canonical));
+            }
+            Ok(canonical) => {
+                let (mantissa, exponent) = match canonical.find('e') {
+                    Some(index) => canonical.split_at(index),
+                    None => (canonical.as_str(), ""),
+                };
+                let mut mantissa = mantissa.to_string();
+                let max_digits = self.max_digits as usize;
+                self.input_truncated = count_digits(&mantissa) > max_digits;
+
+                if self.input_truncated {
+                    let integer_digits = count_digits(mantissa.split('.').next().unwrap_or(""));
+                    if integer_digits > max_digits {
+                        return;
+                    }
+                    while count_digits(&mantissa) > max_digits {
+                        mantissa.pop();
+                    }
+                    if mantissa.ends_with('.') {
+                        mantissa.pop();
+                    }
+                }
+
+                self.display_value = format!("{}{}", mantissa, exponent);
+                self.clear_on_next_input = false;
+                self.last_pressed_operation = false;
+            }
+            Err(error) => self.set_error(error),
+        }
+    }
+
+    /// Checks whether the current entry loses precision when stored as `f64`.
+    ///
+    /// Values such as `9007199254740993` cannot be represented exactly and
+    /// would be rounded silently once used in a calculation.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the entered value cannot be represented exactly.
+    pub fn precision_warning(&self) -> bool {
+        match parse_entry(&self.display_value) {
+            Ok(value) if value.is_finite() => {
+                decimal_parts(&self.display_value) != decimal_parts(&format!("{:e}", value))
+            }
+            Ok(_) => true,
+            Err(_) => false,
+        }
+    }
+
     /// Toggles the sign of the current display value.
+    ///
+    /// During exponent entry this toggles the sign of the exponent, as on a
+    /// handheld scientific calculator.
     pub fn toggle_sign(&mut self) {
         if self.error_state {
             return;
         }
 
-        if self.display_value != "0" {
+        if !self.clear_on_next_input && self.display_value.contains('e') {
+            self.toggle_exponent_sign();
+        } else if self.display_value != "0" {
             if self.display_value.starts_with('-') {
                 self.display_value = self.display_value[1..].to_string();
             } else {
@@ -90,8 +244,14 @@ impl CalculatorState {
     }
 
     /// Handles backspace/delete functionality.
+    ///
+    /// In the error state, backspace clears the error unless error
+    /// recovery is set to `ClearOnly`.
     pub fn backspace(&mut self) {
         if self.error_state {
+            if self.error_recovery != ErrorRecovery::ClearOnly {
+                self.clear();
+            }
             return;
         }
 
@@ -120,19 +280,20 @@ impl CalculatorState {
         // If we already have a pending operation, calculate the result first
         if self.first_operand.is_some() && !self.last_pressed_operation {
             self.calculate();
+            // Keep the error the calculation raised
+            if self.error_state {
+                return;
+            }
         }
 
-        match self.display_value.parse::<f64>() {
+        match parse_entry(&self.display_value) {
             Ok(value) => {
                 self.first_operand = Some(value);
                 self.current_operation = operation;
                 self.clear_on_next_input = true;
                 self.last_pressed_operation = true;
             }
-            Err(_) => {
-                self.error_state = true;
-                self.display_value = "Error".to_string();
-            }
+            Err(_) => self.set_error(CalculatorError::invalid_input(None)),
         }
     }
 
@@ -143,51 +304,29 @@ impl CalculatorState {
         }
 
         if let Some(first) = self.first_operand {
-            match self.display_value.parse::<f64>() {
+            match parse_entry(&self.display_value) {
                 Ok(second) => {
-                    let result = match self.current_operation {
-                        Operation::Add => Ok(first + second),
-                        Operation::Subtract => Ok(first - second),
-                        Operation::Multiply => Ok(first * second),
-                        Operation::Divide => {
-                            if second == 0.0 {
-                                Err(CalculatorError::division_by_zero())
-                            } else {
-                                Ok(first / second)
-                            }
-                        }
-                        Operation::None => Ok(second),
+                    let (result, op) = match self.current_operation {
+                        Operation::Add => (checked_add(first, second), Some(BinaryOp::Add)),
+                        Operation::Subtract => (checked_subtract(first, second), Some(BinaryOp::Subtract)),
+                        Operation::Multiply => (checked_multiply(first, second), Some(BinaryOp::Multiply)),
+                        Operation::Divide => (checked_divide(first, second), Some(BinaryOp::Divide)),
+                        Operation::None => (Ok(second), None),
                     };
 
                     match result {
                         Ok(value) => {
-                            // Check for overflow/underflow
-                            if value.is_infinite() {
-                                self.error_state = true;
-                                self.display_value = "Error: Overflow".to_string();
-                            } else if value.is_nan() {
-                                self.error_state = true;
-                                self.display_value = "Error: Invalid operation".to_string();
-                            } else {
-                                // Format the result to avoid unnecessary decimal places
-                                if value == (value as i64) as f64 {
-                                    self.display_value = format!("{}", value as i64);
-                                } else {
-                                    self.display_value = format!("{}", value);
-                                }
-                                self.first_operand = Some(value);
+                            if let Some(op) = op {
+                                self.trace.push(TraceStep::operation(op, first, second, value));
                             }
+                            // Format the result to avoid unnecessary decimal places
+                            self.display_value = format_canonical(value);
+                            self.first_operand = Some(value);
                         }
-                        Err(error) => {
-                            self.error_state = true;
-                            self.display_value = format!("Error: {}", error.message());
-                        }
+                        Err(error) => self.set_error(error),
                     }
                 }
-                Err(_) => {
-                    self.error_state = true;
-                    self.display_value = "Error: Invalid input".to_string();
-                }
+                Err(_) => self.set_error(CalculatorError::invalid_input(None)),
             }
         }
 
@@ -195,4 +334,127 @@ impl CalculatorState {
         self.clear_on_next_input = true;
         self.last_pressed_operation = false;
     }
-} 
\ No newline at end of file
+}
+
+impl CalculatorState {
+    /// Puts the calculator into the error state.
+    ///
+    /// The display shows the error message after a localized label.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The error that occurred
+    pub(crate) fn set_error(&mut self, error: CalculatorError) {
+        self.error_state = true;
+        self.display_value = format!("{}: {}", i18n::message(ERROR_LABEL), error.message());
+        self.last_error = Some(error);
+    }
+
+    /// Recovers from the error state before number input, if allowed.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the calculator was reset and the input should proceed.
+    fn recover_on_input(&mut self) -> bool {
+        if self.error_recovery == ErrorRecovery::Input {
+            self.clear();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Appends a digit to the exponent being entered.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the exponent is already at its maximum length or the new
+    /// value would be out of the range of `f64`.
+    fn push_exponent_digit(&mut self, digit: u8) -> bool {
+        let exponent = self.display_value.split('e').nth(1).unwrap_or("");
+        let exponent_digits = exponent.trim_start_matches('-');
+
+        if exponent_digits.len() >= MAX_EXPONENT_DIGITS {
+            return false;
+        }
+
+        let mut candidate = self.display_value.clone();
+        if exponent_digits == "0" {
+            candidate.pop();
+        }
+        candidate.push_str(&digit.to_string());
+
+        if !is_in_range(&candidate) {
+            return false;
+        }
+
+        self.display_value = candidate;
+        true
+    }
+}
+
+/// The maximum number of digits in an entered exponent.
+const MAX_EXPONENT_DIGITS: usize = 3;
+
+/// Parses a canonical entry, treating an exponent with no digits yet
+/// (`"6.022e"` or `"6.022e-"`) as a zero exponent.
+pub(crate) fn parse_entry(canonical: &str) -> Result<f64, std::num::ParseFloatError> {
+    canonical
+        .trim_end_matches('-')
+        .trim_end_matches('e')
+        .parse::<f64>()
+}
+
+/// Checks whether a canonical entry fits in `f64` without overflowing to
+/// infinity or underflowing to zero.
+fn is_in_range(canonical: &str) -> bool {
+    let mantissa = canonical.split('e').next().unwrap_or("");
+    let mantissa_is_zero = mantissa.parse::<f64>().map(|m| m == 0.0).unwrap_or(false);
+
+    match parse_entry(canonical) {
+        Ok(value) => value.is_finite() && (value != 0.0 || mantissa_is_zero),
+        Err(_) => false,
+    }
+}
+
+/// Builds the error for a canonical entry that is out of range: an
+/// underflow if a nonzero number rounds to zero, and an overflow otherwise.
+fn range_error(canonical: &str) -> CalculatorError {
+    if parse_entry(canonical) == Ok(0.0) {
+        CalculatorError::underflow()
+    } else {
+        CalculatorError::overflow()
+    }
+}
+
+/// Counts the significant digits entered for a number's mantissa,
+/// ignoring leading zeros (`"0.025"` has two digits, `"2.50"` three).
+fn count_digits(canonical: &str) -> usize {
+    let mantissa = canonical.split('e').next().unwrap_or("");
+    mantissa
+        .chars()
+        .filter(char::is_ascii_digit)
+        .skip_while(|&digit| digit == '0')
+        .count()
+}
+
+/// Splits a canonical or scientific number string into its significant
+/// digits and decimal exponent, so that equal decimal values compare equal
+/// (`"007.50"`, `"7.5"` and `"75e-1"` all become `("75", 1)`).
+fn decimal_parts(canonical: &str) -> (String, i64) {
+    let unsigned = canonical.trim_start_matches('-');
+    let (mantissa, exponent) = unsigned.split_once('e').unwrap_or((unsigned, "0"));
+    let exponent = exponent.parse::<i64>().unwrap_or(0);
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+    let digits = format!("{}{}", integer, fraction);
+    let leading_zeros = digits.len() - digits.trim_start_matches('0').len();
+    let significant = digits.trim_start_matches('0').trim_end_matches('0');
+
+    if significant.is_empty() {
+        return ("0".to_string(), 0);
+    }
+
+    let point = integer.len() as i64 - leading_zeros as i64 + exponent;
+    (significant.to_string(), point)
+}
@@ -0,0 +1,202 @@
+//! Statistics mode for the calculator state.
+//!
+//! This module provides data entry for one- and two-variable statistics:
+//! - Adding the current entry as a data point, optionally with a frequency
+//! - Entering (x, y) pairs by storing x before adding y
+//! - Removing the last data point and clearing the data set
+//! - Computing summary statistics and regressions over the entered data
+
+use wasm_bindgen::prelude::*;
+use super::operations::parse_entry;
+use super::types::{CalculatorMode, CalculatorState, DataPoint};
+use crate::calculator::regression::{PairedStatistics, Regression, RegressionKind};
+use crate::calculator::statistics::Statistics;
+use crate::errors::{CalculatorError, CalcResult};
+
+#[wasm_bindgen]
+impl CalculatorState {
+    /// Adds the current entry to the data set once.
+    ///
+    /// This only works in statistics mode. The entry is cleared for the next
+    /// data point.
+    pub fn stats_add(&mut self) {
+        self.stats_add_with_frequency(1);
+    }
+
+    /// Stores the current entry as the x value of a pair.
+    ///
+    /// This only works in statistics mode. The next entry added with
+    /// `stats_add` becomes the y value of the pair.
+    pub fn stats_enter_x(&mut self) {
+        if self.error_state || self.mode != CalculatorMode::Statistics {
+            return;
+        }
+
+        match parse_entry(&self.display_value) {
+            Ok(value) if value.is_finite() => {
+                self.stats_pending_x = Some(value);
+                self.clear_on_next_input = true;
+                self.last_pressed_operation = false;
+            }
+            _ => self.set_error(CalculatorError::invalid_input(None)),
+        }
+    }
+
+    /// Gets the x value stored for the pair being entered.
+    ///
+    /// # Returns
+    ///
+    /// The stored x value, or `None` if no pair is being entered
+    pub fn stats_pending_x(&self) -> Option<f64> {
+        self.stats_pending_x
+    }
+
+    /// Adds the current entry to the data set with a frequency.
+    ///
+    /// This only works in statistics mode. If an x value was stored with
+    /// `stats_enter_x`, the entry is its y value and the pair is added.
+    /// Invalid entries and zero frequencies put the calculator into the
+    /// error state.
+    ///
+    /// # Arguments
+    ///
+    /// * `frequency` - How often the data point occurs
+    pub fn stats_add_with_frequency(&mut self, frequency: u32) {
+        if self.error_state || self.mode != CalculatorMode::Statistics {
+            return;
+        }
+
+        if frequency == 0 {
+            self.set_error(CalculatorError::invalid_input(Some("frequencies must be positive")));
+            return;
+        }
+
+        match parse_entry(&self.display_value) {
+            Ok(value) if value.is_finite() => {
+                let point = match self.stats_pending_x.take() {
+                    Some(x) => DataPoint { x, y: Some(value), frequency },
+                    None => DataPoint { x: value, y: None, frequency },
+                };
+                self.stats_data.push(point);
+                self.clear_on_next_input = true;
+                self.last_pressed_operation = false;
+            }
+            _ => self.set_error(CalculatorError::invalid_input(None)),
+        }
+    }
+
+    /// Removes the most recently added data point.
+    pub fn stats_remove_last(&mut self) {
+        self.stats_data.pop();
+    }
+
+    /// Removes all data points.
+    pub fn stats_clear(&mut self) {
+        self.stats_data.clear();
+        self.stats_pending_x = None;
+    }
+
+    /// Gets the number of data points entered, counting frequencies.
+    ///
+    /// # Returns
+    ///
+    /// The number of data points.
+    pub fn stats_count(&self) -> f64 {
+        self.stats_data.iter().map(|point| point.frequency as f64).sum()
+    }
+
+    /// Computes summary statistics over the entered data from JavaScript.
+    ///
+    /// # Returns
+    ///
+    /// The summary statistics, or an error message if no data was entered
+    #[wasm_bindgen(js_name = statistics)]
+    pub fn statistics_wasm(&self) -> Result<Statistics, JsValue> {
+        self.statistics().map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Computes two-variable statistics over the entered pairs from JavaScript.
+    ///
+    /// # Returns
+    ///
+    /// The paired statistics, or an error message if no pairs were entered
+    #[wasm_bindgen(js_name = paired_statistics)]
+    pub fn paired_statistics_wasm(&self) -> Result<PairedStatistics, JsValue> {
+        self.paired_statistics().map_err(|e| JsValue::from_str(e.message()))
+    }
+
+    /// Fits a regression model to the entered pairs from JavaScript.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of model
+    /// * `degree` - The polynomial degree (ignored for other models)
+    ///
+    /// # Returns
+    ///
+    /// The fitted model, or an error message if it cannot be fitted
+    #[wasm_bindgen(js_name = regression)]
+    pub fn regression_wasm(&self, kind: RegressionKind, degree: u32) -> Result<Regression, JsValue> {
+        self.regression(kind, degree).map_err(|e| JsValue::from_str(e.message()))
+    }
+}
+
+impl CalculatorState {
+    /// Computes summary statistics over the entered data.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(statistics)` - The summary statistics
+    /// * `Err(error)` - An invalid input error if no data was entered
+    pub fn statistics(&self) -> CalcResult<Statistics> {
+        let (values, frequencies): (Vec<f64>, Vec<u32>) =
+            self.stats_data.iter().map(|point| (point.x, point.frequency)).unzip();
+        Statistics::from_weighted(&values, &frequencies)
+    }
+
+    /// Computes two-variable statistics over the entered pairs.
+    ///
+    /// Data points entered without a y value are left out.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(statistics)` - The paired statistics
+    /// * `Err(error)` - An invalid input error if no pairs were entered
+    pub fn paired_statistics(&self) -> CalcResult<PairedStatistics> {
+        let (xs, ys, weights) = self.pairs();
+        PairedStatistics::from_weighted(&xs, &ys, &weights)
+    }
+
+    /// Fits a regression model to the entered pairs.
+    ///
+    /// Data points entered without a y value are left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of model
+    /// * `degree` - The polynomial degree (ignored for other models)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(regression)` - The fitted model
+    /// * `Err(error)` - An invalid input error if the model cannot be fitted
+    pub fn regression(&self, kind: RegressionKind, degree: u32) -> CalcResult<Regression> {
+        let (xs, ys, weights) = self.pairs();
+        Regression::fit_weighted(&xs, &ys, &weights, kind, degree)
+    }
+
+    /// Splits the entered pairs into x values, y values and weights.
+    fn pairs(&self) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let mut xs = Vec::new();
+        let mut ys = Vec::new();
+        let mut weights = Vec::new();
+        for point in &self.stats_data {
+            if let Some(y) = point.y {
+                xs.push(point.x);
+                ys.push(y);
+                weights.push(point.frequency as f64);
+            }
+        }
+        (xs, ys, weights)
+    }
+}
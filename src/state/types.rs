@@ -5,6 +5,9 @@
 //! - CalculatorState struct for tracking the calculator's current state
 
 use wasm_bindgen::prelude::*;
+use super::locale::Locale;
+use crate::errors::{CalculatorError, CalculatorErrorType};
+use crate::expression::TraceStep;
 
 /// Represents the current operation being performed.
 #[wasm_bindgen]
@@ -17,8 +20,53 @@ pub enum Operation {
     Divide,
 }
 
+/// Represents the mode the calculator is in.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CalculatorMode {
+    /// Standard arithmetic
+    Standard,
+    /// Statistics data entry
+    Statistics,
+}
+
+/// Represents which inputs recover the calculator from an error state.
+///
+/// `clear` and `clear_entry` always recover from an error.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorRecovery {
+    /// Only `clear` and `clear_entry` recover from an error
+    ClearOnly,
+    /// Backspace also clears the error
+    Backspace,
+    /// Backspace clears the error, and number input starts a fresh calculation
+    Input,
+}
+
+/// The default maximum number of digits that can be entered for one number.
+///
+/// `f64` holds 15 to 17 significant decimal digits, so longer entries
+/// would be rounded silently.
+pub const DEFAULT_MAX_DIGITS: u8 = 16;
+
+/// A data point entered in statistics mode.
+///
+/// Points entered as (x, y) pairs carry a y value; one-variable statistics
+/// use the x values of all points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct DataPoint {
+    /// The value, or the x value of a pair
+    pub(crate) x: f64,
+    /// The y value of a pair
+    pub(crate) y: Option<f64>,
+    /// How often the data point occurs
+    pub(crate) frequency: u32,
+}
+
 /// Represents the current state of the calculator.
 #[wasm_bindgen]
+#[derive(Debug)]
 pub struct CalculatorState {
     /// The current display value
     pub(crate) display_value: String,
@@ -32,6 +80,26 @@ pub struct CalculatorState {
     pub(crate) last_pressed_operation: bool,
     /// Whether there is an error state
     pub(crate) error_state: bool,
+    /// The error that put the calculator into the error state
+    pub(crate) last_error: Option<CalculatorError>,
+    /// Which inputs recover from the error state
+    pub(crate) error_recovery: ErrorRecovery,
+    /// The locale used to display and parse numbers
+    pub(crate) locale: Locale,
+    /// Whether digit grouping is applied to the rendered display
+    pub(crate) digit_grouping: bool,
+    /// The maximum number of digits that can be entered for one number
+    pub(crate) max_digits: u8,
+    /// Whether the last input was cut short by the digit limit
+    pub(crate) input_truncated: bool,
+    /// The operations carried out since the last clear
+    pub(crate) trace: Vec<TraceStep>,
+    /// The mode the calculator is in
+    pub(crate) mode: CalculatorMode,
+    /// Data points entered in statistics mode
+    pub(crate) stats_data: Vec<DataPoint>,
+    /// The x value of a pair whose y value is being entered
+    pub(crate) stats_pending_x: Option<f64>,
 }
 
 impl Default for CalculatorState {
@@ -57,16 +125,224 @@ impl CalculatorState {
             clear_on_next_input: false,
             last_pressed_operation: false,
             error_state: false,
+            last_error: None,
+            error_recovery: ErrorRecovery::ClearOnly,
+            locale: Locale::default(),
+            digit_grouping: false,
+            max_digits: DEFAULT_MAX_DIGITS,
+            input_truncated: false,
+            trace: Vec::new(),
+            mode: CalculatorMode::Standard,
+            stats_data: Vec::new(),
+            stats_pending_x: None,
         }
     }
 
-    /// Gets the current display value.
+    /// Gets the current display value, rendered with the active locale.
     ///
     /// # Returns
     ///
     /// The current display value as a string.
     #[wasm_bindgen]
     pub fn display_value(&self) -> String {
+        self.locale.localize(&self.display_value, self.digit_grouping)
+    }
+
+    /// Gets the raw display value in canonical form.
+    ///
+    /// The raw value uses ASCII digits, `.` as the decimal point and no
+    /// grouping, regardless of the locale and grouping settings.
+    ///
+    /// # Returns
+    ///
+    /// The raw display value as a string.
+    #[wasm_bindgen]
+    pub fn raw_display_value(&self) -> String {
         self.display_value.clone()
     }
-} 
\ No newline at end of file
+
+    /// Gets whether digit grouping is applied to the rendered display.
+    ///
+    /// # Returns
+    ///
+    /// `true` if grouping is enabled.
+    #[wasm_bindgen]
+    pub fn digit_grouping(&self) -> bool {
+        self.digit_grouping
+    }
+
+    /// Enables or disables digit grouping in the rendered display.
+    ///
+    /// Grouping follows the locale's grouping style and is applied while
+    /// typing as well as to results. It only affects rendering; editing
+    /// and parsing always work on the raw value.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to group digits
+    #[wasm_bindgen]
+    pub fn set_digit_grouping(&mut self, enabled: bool) {
+        self.digit_grouping = enabled;
+    }
+
+    /// Gets the maximum number of digits that can be entered for one number.
+    ///
+    /// # Returns
+    ///
+    /// The digit limit.
+    #[wasm_bindgen]
+    pub fn max_digits(&self) -> u8 {
+        self.max_digits
+    }
+
+    /// Sets the maximum number of digits that can be entered for one number.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The digit limit (at least 1)
+    #[wasm_bindgen]
+    pub fn set_max_digits(&mut self, count: u8) {
+        self.max_digits = count.max(1);
+    }
+
+    /// Gets whether the last input was cut short by the digit limit.
+    ///
+    /// # Returns
+    ///
+    /// `true` if digits were dropped from the last input.
+    #[wasm_bindgen]
+    pub fn input_truncated(&self) -> bool {
+        self.input_truncated
+    }
+
+    /// Gets whether the calculator is in the error state.
+    ///
+    /// # Returns
+    ///
+    /// `true` if an error occurred and has not been recovered from.
+    #[wasm_bindgen]
+    pub fn is_error(&self) -> bool {
+        self.error_state
+    }
+
+    /// Gets the type of the error that put the calculator into the error state.
+    ///
+    /// # Returns
+    ///
+    /// The error type, or `None` if there is no error.
+    #[wasm_bindgen]
+    pub fn last_error_type(&self) -> Option<CalculatorErrorType> {
+        self.last_error.as_ref().map(CalculatorError::error_type)
+    }
+
+    /// Gets the stable numeric code of the error that put the calculator
+    /// into the error state.
+    ///
+    /// # Returns
+    ///
+    /// The error code, or `None` if there is no error.
+    #[wasm_bindgen]
+    pub fn last_error_code(&self) -> Option<u16> {
+        self.last_error.as_ref().map(CalculatorError::code)
+    }
+
+    /// Gets the message of the error that put the calculator into the error state.
+    ///
+    /// # Returns
+    ///
+    /// The error message, or `None` if there is no error.
+    #[wasm_bindgen]
+    pub fn last_error_message(&self) -> Option<String> {
+        self.last_error.as_ref().map(|error| error.message().to_string())
+    }
+
+    /// Gets the operations carried out since the last clear, for
+    /// explanation mode.
+    ///
+    /// Each `=` or chained operator that combines two numbers adds a step,
+    /// such as `4 * 2` giving 8.
+    ///
+    /// # Returns
+    ///
+    /// The steps, oldest first.
+    #[wasm_bindgen]
+    pub fn trace(&self) -> Vec<TraceStep> {
+        self.trace.clone()
+    }
+
+    /// Gets which inputs recover from the error state.
+    ///
+    /// # Returns
+    ///
+    /// The error recovery setting.
+    #[wasm_bindgen]
+    pub fn error_recovery(&self) -> ErrorRecovery {
+        self.error_recovery
+    }
+
+    /// Sets which inputs recover from the error state.
+    ///
+    /// # Arguments
+    ///
+    /// * `recovery` - The error recovery setting
+    #[wasm_bindgen]
+    pub fn set_error_recovery(&mut self, recovery: ErrorRecovery) {
+        self.error_recovery = recovery;
+    }
+
+    /// Gets the mode the calculator is in.
+    ///
+    /// # Returns
+    ///
+    /// The current mode.
+    #[wasm_bindgen]
+    pub fn mode(&self) -> CalculatorMode {
+        self.mode
+    }
+
+    /// Sets the mode the calculator is in.
+    ///
+    /// Data entered in statistics mode is kept when switching modes.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The new mode
+    #[wasm_bindgen]
+    pub fn set_mode(&mut self, mode: CalculatorMode) {
+        self.mode = mode;
+    }
+
+    /// Gets the locale used to display and parse numbers.
+    ///
+    /// # Returns
+    ///
+    /// A copy of the active locale.
+    #[wasm_bindgen]
+    pub fn locale(&self) -> Locale {
+        self.locale.clone()
+    }
+
+    /// Sets the locale used to display and parse numbers.
+    ///
+    /// The stored value is not affected; only its rendering changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `locale` - The new locale
+    #[wasm_bindgen]
+    pub fn set_locale(&mut self, locale: Locale) {
+        self.locale = locale;
+    }
+}
+
+/// Error details for CalculatorState
+impl CalculatorState {
+    /// Gets the error that put the calculator into the error state.
+    ///
+    /// # Returns
+    ///
+    /// A copy of the error, or `None` if there is no error.
+    pub fn last_error(&self) -> Option<CalculatorError> {
+        self.last_error.clone()
+    }
+}
@@ -0,0 +1,571 @@
+//! The catalogue of supported units.
+//!
+//! This module lists every unit the converter understands, grouped into
+//! categories for the UI. Units are found by symbol (`km`), alias (`kph`)
+//! or name in the singular or plural (`kilometre`, `Feet`). Units marked as
+//! prefixable also accept SI prefixes (`Gm`, `mbar`), and data units accept
+//! IEC prefixes as well (`PiB`). Currencies come from the loaded exchange
+//! rates and are found by their ISO 4217 code (`EUR`) or name (`euro`).
+
+use std::f64::consts::PI;
+use wasm_bindgen::prelude::*;
+use super::currency::{currency_name, with_rates, RateTable};
+use super::dimension::Dimension;
+use crate::errors::{CalculatorError, CalcResult};
+
+/// Represents a group of units that measure the same kind of quantity.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnitCategory {
+    Length,
+    Mass,
+    Time,
+    Temperature,
+    Area,
+    Volume,
+    Speed,
+    Pressure,
+    Energy,
+    Power,
+    DataSize,
+    Angle,
+    Currency,
+}
+
+impl UnitCategory {
+    /// All categories, in catalogue order.
+    pub const ALL: [UnitCategory; 13] = [
+        UnitCategory::Length,
+        UnitCategory::Mass,
+        UnitCategory::Time,
+        UnitCategory::Temperature,
+        UnitCategory::Area,
+        UnitCategory::Volume,
+        UnitCategory::Speed,
+        UnitCategory::Pressure,
+        UnitCategory::Energy,
+        UnitCategory::Power,
+        UnitCategory::DataSize,
+        UnitCategory::Angle,
+        UnitCategory::Currency,
+    ];
+
+    /// Gets the stable string code of this category.
+    ///
+    /// # Returns
+    ///
+    /// The string code (e.g. `"data_size"`)
+    pub fn name(&self) -> &'static str {
+        match self {
+            UnitCategory::Length => "length",
+            UnitCategory::Mass => "mass",
+            UnitCategory::Time => "time",
+            UnitCategory::Temperature => "temperature",
+            UnitCategory::Area => "area",
+            UnitCategory::Volume => "volume",
+            UnitCategory::Speed => "speed",
+            UnitCategory::Pressure => "pressure",
+            UnitCategory::Energy => "energy",
+            UnitCategory::Power => "power",
+            UnitCategory::DataSize => "data_size",
+            UnitCategory::Angle => "angle",
+            UnitCategory::Currency => "currency",
+        }
+    }
+
+    /// Gets the dimension shared by every unit in this category.
+    ///
+    /// # Returns
+    ///
+    /// The dimension
+    pub fn dimension(&self) -> Dimension {
+        match self {
+            UnitCategory::Length => Dimension::LENGTH,
+            UnitCategory::Mass => Dimension::MASS,
+            UnitCategory::Time => Dimension::TIME,
+            UnitCategory::Temperature => Dimension::TEMPERATURE,
+            UnitCategory::Area => Dimension::AREA,
+            UnitCategory::Volume => Dimension::VOLUME,
+            UnitCategory::Speed => Dimension::SPEED,
+            UnitCategory::Pressure => Dimension::PRESSURE,
+            UnitCategory::Energy => Dimension::ENERGY,
+            UnitCategory::Power => Dimension::POWER,
+            UnitCategory::DataSize => Dimension::INFORMATION,
+            UnitCategory::Angle => Dimension::ANGLE,
+            UnitCategory::Currency => Dimension::CURRENCY,
+        }
+    }
+}
+
+/// Represents which prefixes a unit accepts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Prefixes {
+    /// No prefixes
+    None,
+    /// SI prefixes (`k`, `M`, `m`, `µ`, ...)
+    Decimal,
+    /// SI and IEC prefixes (`Ki`, `Mi`, ...)
+    DecimalAndBinary,
+}
+
+/// A catalogue entry.
+pub struct UnitDefinition {
+    /// The symbol (e.g. `"km"`)
+    pub symbol: &'static str,
+    /// The singular name
+    pub name: &'static str,
+    /// The plural name
+    pub plural: &'static str,
+    /// Other accepted spellings
+    pub aliases: &'static [&'static str],
+    /// The category
+    pub category: UnitCategory,
+    /// The size of one unit in the category's SI unit
+    pub factor: f64,
+    /// The SI value of the unit's zero point, for temperature scales
+    pub offset: f64,
+    /// The prefixes the symbol accepts
+    pub prefixes: Prefixes,
+}
+
+/// Builds a catalogue entry without an offset.
+const fn unit(
+    symbol: &'static str,
+    name: &'static str,
+    plural: &'static str,
+    aliases: &'static [&'static str],
+    category: UnitCategory,
+    factor: f64,
+    prefixes: Prefixes,
+) -> UnitDefinition {
+    UnitDefinition { symbol, name, plural, aliases, category, factor, offset: 0.0, prefixes }
+}
+
+use Prefixes::{Decimal, DecimalAndBinary, None as Plain};
+use UnitCategory::*;
+
+/// Every unit in the catalogue, grouped by category. The first unit of each
+/// category is its SI unit.
+pub const UNITS: &[UnitDefinition] = &[
+    unit("m", "metre", "metres", &["meter", "meters"], Length, 1.0, Decimal),
+    unit("km", "kilometre", "kilometres", &["kilometer", "kilometers"], Length, 1e3, Plain),
+    unit("cm", "centimetre", "centimetres", &["centimeter", "centimeters"], Length, 1e-2, Plain),
+    unit("mm", "millimetre", "millimetres", &["millimeter", "millimeters"], Length, 1e-3, Plain),
+    unit("µm", "micrometre", "micrometres", &["um", "micron"], Length, 1e-6, Plain),
+    unit("nm", "nanometre", "nanometres", &["nanometer", "nanometers"], Length, 1e-9, Plain),
+    unit("in", "inch", "inches", &["\""], Length, 0.0254, Plain),
+    unit("ft", "foot", "feet", &["'"], Length, 0.3048, Plain),
+    unit("yd", "yard", "yards", &[], Length, 0.9144, Plain),
+    unit("mi", "mile", "miles", &[], Length, 1609.344, Plain),
+    unit("nmi", "nautical mile", "nautical miles", &["NM"], Length, 1852.0, Plain),
+    unit("au", "astronomical unit", "astronomical units", &["AU"], Length, 149_597_870_700.0, Plain),
+    unit("ly", "light-year", "light-years", &[], Length, 9_460_730_472_580_800.0, Plain),
+    unit("kg", "kilogram", "kilograms", &[], Mass, 1.0, Plain),
+    unit("g", "gram", "grams", &[], Mass, 1e-3, Decimal),
+    unit("mg", "milligram", "milligrams", &[], Mass, 1e-6, Plain),
+    unit("µg", "microgram", "micrograms", &["ug", "mcg"], Mass, 1e-9, Plain),
+    unit("t", "tonne", "tonnes", &["metric ton"], Mass, 1e3, Plain),
+    unit("lb", "pound", "pounds", &["lbs"], Mass, 0.45359237, Plain),
+    unit("oz", "ounce", "ounces", &[], Mass, 0.028349523125, Plain),
+    unit("st", "stone", "stones", &[], Mass, 6.35029318, Plain),
+    unit("ton", "short ton", "short tons", &[], Mass, 907.18474, Plain),
+    unit("s", "second", "seconds", &["sec"], Time, 1.0, Decimal),
+    unit("ms", "millisecond", "milliseconds", &[], Time, 1e-3, Plain),
+    unit("µs", "microsecond", "microseconds", &["us"], Time, 1e-6, Plain),
+    unit("ns", "nanosecond", "nanoseconds", &[], Time, 1e-9, Plain),
+    unit("min", "minute", "minutes", &[], Time, 60.0, Plain),
+    unit("h", "hour", "hours", &["hr"], Time, 3600.0, Plain),
+    unit("d", "day", "days", &[], Time, 86_400.0, Plain),
+    unit("wk", "week", "weeks", &[], Time, 604_800.0, Plain),
+    unit("yr", "year", "years", &[], Time, 31_557_600.0, Plain),
+    unit("K", "kelvin", "kelvins", &[], Temperature, 1.0, Decimal),
+    UnitDefinition {
+        symbol: "°C",
+        name: "degree Celsius",
+        plural: "degrees Celsius",
+        aliases: &["C", "degC", "celsius"],
+        category: Temperature,
+        factor: 1.0,
+        offset: 273.15,
+        prefixes: Plain,
+    },
+    UnitDefinition {
+        symbol: "°F",
+        name: "degree Fahrenheit",
+        plural: "degrees Fahrenheit",
+        aliases: &["F", "degF", "fahrenheit"],
+        category: Temperature,
+        factor: 5.0 / 9.0,
+        offset: 459.67 * 5.0 / 9.0,
+        prefixes: Plain,
+    },
+    unit("°R", "degree Rankine", "degrees Rankine", &["R", "degR", "rankine"], Temperature, 5.0 / 9.0, Plain),
+    unit("m²", "square metre", "square metres", &["m2", "m^2", "sq m"], Area, 1.0, Plain),
+    unit("km²", "square kilometre", "square kilometres", &["km2", "km^2"], Area, 1e6, Plain),
+    unit("cm²", "square centimetre", "square centimetres", &["cm2", "cm^2"], Area, 1e-4, Plain),
+    unit("mm²", "square millimetre", "square millimetres", &["mm2", "mm^2"], Area, 1e-6, Plain),
+    unit("ha", "hectare", "hectares", &[], Area, 1e4, Plain),
+    unit("acre", "acre", "acres", &["ac"], Area, 4046.8564224, Plain),
+    unit("ft²", "square foot", "square feet", &["ft2", "ft^2", "sq ft"], Area, 0.09290304, Plain),
+    unit("in²", "square inch", "square inches", &["in2", "in^2", "sq in"], Area, 6.4516e-4, Plain),
+    unit("yd²", "square yard", "square yards", &["yd2", "yd^2"], Area, 0.83612736, Plain),
+    unit("mi²", "square mile", "square miles", &["mi2", "mi^2"], Area, 2_589_988.110336, Plain),
+    unit("m³", "cubic metre", "cubic metres", &["m3", "m^3"], Volume, 1.0, Plain),
+    unit("cm³", "cubic centimetre", "cubic centimetres", &["cm3", "cm^3", "cc"], Volume, 1e-6, Plain),
+    unit("L", "litre", "litres", &["l", "liter", "liters"], Volume, 1e-3, Decimal),
+    unit("mL", "millilitre", "millilitres", &["ml", "milliliter", "milliliters"], Volume, 1e-6, Plain),
+    unit("gal", "US gallon", "US gallons", &["gallon", "gallons"], Volume, 3.785411784e-3, Plain),
+    unit("qt", "US quart", "US quarts", &["quart", "quarts"], Volume, 9.46352946e-4, Plain),
+    unit("pt", "US pint", "US pints", &["pint", "pints"], Volume, 4.73176473e-4, Plain),
+    unit("cup", "US cup", "US cups", &["cups"], Volume, 2.365882365e-4, Plain),
+    unit("floz", "US fluid ounce", "US fluid ounces", &["fl oz", "fl_oz"], Volume, 2.95735295625e-5, Plain),
+    unit("tbsp", "tablespoon", "tablespoons", &[], Volume, 1.478676478125e-5, Plain),
+    unit("tsp", "teaspoon", "teaspoons", &[], Volume, 4.92892159375e-6, Plain),
+    unit("ft³", "cubic foot", "cubic feet", &["ft3", "ft^3"], Volume, 0.028316846592, Plain),
+    unit("in³", "cubic inch", "cubic inches", &["in3", "in^3"], Volume, 1.6387064e-5, Plain),
+    unit("gal_imp", "imperial gallon", "imperial gallons", &[], Volume, 4.54609e-3, Plain),
+    unit("m/s", "metre per second", "metres per second", &["mps"], Speed, 1.0, Plain),
+    unit("km/h", "kilometre per hour", "kilometres per hour", &["kph", "kmh"], Speed, 1.0 / 3.6, Plain),
+    unit("mph", "mile per hour", "miles per hour", &["mi/h"], Speed, 0.44704, Plain),
+    unit("kn", "knot", "knots", &["kt"], Speed, 1852.0 / 3600.0, Plain),
+    unit("ft/s", "foot per second", "feet per second", &["fps"], Speed, 0.3048, Plain),
+    unit("Pa", "pascal", "pascals", &[], Pressure, 1.0, Decimal),
+    unit("kPa", "kilopascal", "kilopascals", &[], Pressure, 1e3, Plain),
+    unit("MPa", "megapascal", "megapascals", &[], Pressure, 1e6, Plain),
+    unit("hPa", "hectopascal", "hectopascals", &[], Pressure, 1e2, Plain),
+    unit("bar", "bar", "bars", &[], Pressure, 1e5, Decimal),
+    unit("atm", "standard atmosphere", "standard atmospheres", &[], Pressure, 101_325.0, Plain),
+    unit("psi", "pound per square inch", "pounds per square inch", &[], Pressure, 6894.757293168361, Plain),
+    unit("mmHg", "millimetre of mercury", "millimetres of mercury", &[], Pressure, 133.322387415, Plain),
+    unit("Torr", "torr", "torr", &["torr"], Pressure, 101_325.0 / 760.0, Plain),
+    unit("inHg", "inch of mercury", "inches of mercury", &[], Pressure, 3386.389, Plain),
+    unit("J", "joule", "joules", &[], Energy, 1.0, Decimal),
+    unit("kJ", "kilojoule", "kilojoules", &[], Energy, 1e3, Plain),
+    unit("MJ", "megajoule", "megajoules", &[], Energy, 1e6, Plain),
+    unit("cal", "calorie", "calories", &[], Energy, 4.184, Decimal),
+    unit("kcal", "kilocalorie", "kilocalories", &["Cal"], Energy, 4184.0, Plain),
+    unit("Wh", "watt-hour", "watt-hours", &["W·h"], Energy, 3600.0, Decimal),
+    unit("kWh", "kilowatt-hour", "kilowatt-hours", &["kW·h"], Energy, 3.6e6, Plain),
+    unit("eV", "electronvolt", "electronvolts", &[], Energy, 1.602176634e-19, Decimal),
+    unit("BTU", "British thermal unit", "British thermal units", &["Btu"], Energy, 1055.05585262, Plain),
+    unit("W", "watt", "watts", &[], Power, 1.0, Decimal),
+    unit("kW", "kilowatt", "kilowatts", &[], Power, 1e3, Plain),
+    unit("MW", "megawatt", "megawatts", &[], Power, 1e6, Plain),
+    unit("hp", "horsepower", "horsepower", &[], Power, 745.69987158227, Plain),
+    unit("BTU/h", "British thermal unit per hour", "British thermal units per hour", &["Btu/h"], Power, 0.29307107017222, Plain),
+    unit("bit", "bit", "bits", &["b"], DataSize, 1.0, DecimalAndBinary),
+    unit("B", "byte", "bytes", &[], DataSize, 8.0, DecimalAndBinary),
+    unit("kB", "kilobyte", "kilobytes", &[], DataSize, 8e3, Plain),
+    unit("MB", "megabyte", "megabytes", &[], DataSize, 8e6, Plain),
+    unit("GB", "gigabyte", "gigabytes", &[], DataSize, 8e9, Plain),
+    unit("TB", "terabyte", "terabytes", &[], DataSize, 8e12, Plain),
+    unit("KiB", "kibibyte", "kibibytes", &[], DataSize, 8.0 * 1024.0, Plain),
+    unit("MiB", "mebibyte", "mebibytes", &[], DataSize, 8.0 * 1_048_576.0, Plain),
+    unit("GiB", "gibibyte", "gibibytes", &[], DataSize, 8.0 * 1_073_741_824.0, Plain),
+    unit("TiB", "tebibyte", "tebibytes", &[], DataSize, 8.0 * 1_099_511_627_776.0, Plain),
+    unit("kbit", "kilobit", "kilobits", &[], DataSize, 1e3, Plain),
+    unit("Mbit", "megabit", "megabits", &[], DataSize, 1e6, Plain),
+    unit("Gbit", "gigabit", "gigabits", &[], DataSize, 1e9, Plain),
+    unit("rad", "radian", "radians", &[], Angle, 1.0, Decimal),
+    unit("deg", "degree", "degrees", &["°"], Angle, PI / 180.0, Plain),
+    unit("grad", "gradian", "gradians", &["gon"], Angle, PI / 200.0, Plain),
+    unit("turn", "turn", "turns", &["rev"], Angle, 2.0 * PI, Plain),
+    unit("arcmin", "arcminute", "arcminutes", &["′"], Angle, PI / 10_800.0, Plain),
+    unit("arcsec", "arcsecond", "arcseconds", &["″"], Angle, PI / 648_000.0, Plain),
+];
+
+/// SI prefixes, with two-letter prefixes first so `da` wins over `d`.
+pub const DECIMAL_PREFIXES: &[(&str, &str, f64)] = &[
+    ("da", "deca", 1e1),
+    ("Q", "quetta", 1e30),
+    ("R", "ronna", 1e27),
+    ("Y", "yotta", 1e24),
+    ("Z", "zetta", 1e21),
+    ("E", "exa", 1e18),
+    ("P", "peta", 1e15),
+    ("T", "tera", 1e12),
+    ("G", "giga", 1e9),
+    ("M", "mega", 1e6),
+    ("k", "kilo", 1e3),
+    ("h", "hecto", 1e2),
+    ("d", "deci", 1e-1),
+    ("c", "centi", 1e-2),
+    ("m", "milli", 1e-3),
+    ("µ", "micro", 1e-6),
+    ("u", "micro", 1e-6),
+    ("n", "nano", 1e-9),
+    ("p", "pico", 1e-12),
+    ("f", "femto", 1e-15),
+    ("a", "atto", 1e-18),
+    ("z", "zepto", 1e-21),
+    ("y", "yocto", 1e-24),
+    ("r", "ronto", 1e-27),
+    ("q", "quecto", 1e-30),
+];
+
+/// IEC binary prefixes.
+pub const BINARY_PREFIXES: &[(&str, &str, f64)] = &[
+    ("Ki", "kibi", 1024.0),
+    ("Mi", "mebi", 1_048_576.0),
+    ("Gi", "gibi", 1_073_741_824.0),
+    ("Ti", "tebi", 1_099_511_627_776.0),
+    ("Pi", "pebi", 1_125_899_906_842_624.0),
+    ("Ei", "exbi", 1_152_921_504_606_846_976.0),
+    ("Zi", "zebi", 1_180_591_620_717_411_303_424.0),
+    ("Yi", "yobi", 1_208_925_819_614_629_174_706_176.0),
+];
+
+/// Represents a unit found in the catalogue, possibly with a prefix.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unit {
+    /// The symbol, including any prefix
+    symbol: String,
+    /// The singular name
+    name: String,
+    /// The plural name
+    plural: String,
+    /// The category
+    category: UnitCategory,
+    /// The size of one unit in the category's SI unit
+    factor: f64,
+    /// The SI value of the unit's zero point
+    offset: f64,
+}
+
+/// Getters for Unit
+#[wasm_bindgen]
+impl Unit {
+    /// Gets the symbol.
+    pub fn symbol(&self) -> String {
+        self.symbol.clone()
+    }
+
+    /// Gets the singular name.
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    /// Gets the plural name.
+    pub fn plural(&self) -> String {
+        self.plural.clone()
+    }
+
+    /// Gets the category.
+    pub fn category(&self) -> UnitCategory {
+        self.category
+    }
+
+    /// Gets the size of one unit in the category's SI unit.
+    pub fn factor(&self) -> f64 {
+        self.factor
+    }
+
+    /// Gets the SI value of the unit's zero point (non-zero only for
+    /// temperature scales).
+    pub fn offset(&self) -> f64 {
+        self.offset
+    }
+}
+
+impl Unit {
+    /// Creates a unit from a catalogue entry and a prefix.
+    fn from_definition(definition: &UnitDefinition, prefix: Option<&(&str, &str, f64)>) -> Self {
+        let (prefix_symbol, prefix_name, scale) = prefix.copied().unwrap_or(("", "", 1.0));
+        Self {
+            symbol: format!("{}{}", prefix_symbol, definition.symbol),
+            name: format!("{}{}", prefix_name, definition.name),
+            plural: format!("{}{}", prefix_name, definition.plural),
+            category: definition.category,
+            factor: definition.factor * scale,
+            offset: definition.offset,
+        }
+    }
+
+    /// Creates a currency unit from a rate table, measured in the table's
+    /// base currency.
+    fn from_currency(table: &RateTable, code: &str) -> Option<Self> {
+        let rate = table.rate(code).ok()?;
+        Some(Self {
+            symbol: code.to_string(),
+            name: currency_name(code).to_string(),
+            plural: currency_name(code).to_string(),
+            category: UnitCategory::Currency,
+            factor: 1.0 / rate,
+            offset: 0.0,
+        })
+    }
+
+    /// Gets the dimension.
+    ///
+    /// # Returns
+    ///
+    /// The dimension of the unit's category
+    pub fn dimension(&self) -> Dimension {
+        self.category.dimension()
+    }
+
+    /// Converts a value in this unit to the category's SI unit.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value in this unit
+    ///
+    /// # Returns
+    ///
+    /// The value in the SI unit
+    pub fn to_si(&self, value: f64) -> f64 {
+        value * self.factor + self.offset
+    }
+
+    /// Converts a value in the category's SI unit to this unit.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value in the SI unit
+    ///
+    /// # Returns
+    ///
+    /// The value in this unit
+    pub fn from_si(&self, value: f64) -> f64 {
+        (value - self.offset) / self.factor
+    }
+}
+
+/// Looks up a unit by symbol, alias or name.
+///
+/// Symbols and aliases are case-sensitive, since `mm` and `Mm` differ;
+/// names are not.
+///
+/// # Arguments
+///
+/// * `text` - The unit text
+///
+/// # Returns
+///
+/// * `Ok(unit)` - The unit
+/// * `Err(error)` - An invalid input error if the unit is unknown
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::units::catalogue::{lookup, UnitCategory};
+///
+/// assert_eq!(lookup("feet").unwrap().symbol(), "ft");
+/// assert_eq!(lookup("GiB").unwrap().category(), UnitCategory::DataSize);
+/// assert!(lookup("furlongs").is_err());
+/// ```
+pub fn lookup(text: &str) -> CalcResult<Unit> {
+    let text = text.trim();
+
+    if let Some(definition) = UNITS
+        .iter()
+        .find(|unit| unit.symbol == text || unit.aliases.contains(&text))
+    {
+        return Ok(Unit::from_definition(definition, None));
+    }
+
+    if let Some(definition) = UNITS.iter().find(|unit| {
+        unit.name.eq_ignore_ascii_case(text) || unit.plural.eq_ignore_ascii_case(text)
+    }) {
+        return Ok(Unit::from_definition(definition, None));
+    }
+
+    let currency = with_rates(|table| {
+        let table = table?;
+        let code = table
+            .codes()
+            .into_iter()
+            .find(|code| *code == text || currency_name(code).eq_ignore_ascii_case(text))?;
+        Unit::from_currency(table, code)
+    });
+    if let Some(unit) = currency {
+        return Ok(unit);
+    }
+
+    for definition in UNITS.iter().filter(|unit| unit.prefixes != Prefixes::None) {
+        let Some(stem) = text.strip_suffix(definition.symbol) else {
+            continue;
+        };
+        let binary: &[_] = match definition.prefixes {
+            Prefixes::DecimalAndBinary => BINARY_PREFIXES,
+            _ => &[],
+        };
+        if let Some(prefix) = DECIMAL_PREFIXES.iter().chain(binary).find(|(symbol, _, _)| *symbol == stem) {
+            return Ok(Unit::from_definition(definition, Some(prefix)));
+        }
+    }
+
+    Err(CalculatorError::invalid_input(Some(&format!("unknown unit '{}'", text))))
+}
+
+/// Lists every unit in the catalogue, without generated prefixes.
+///
+/// # Returns
+///
+/// The units, grouped by category, followed by the loaded currencies
+#[wasm_bindgen]
+pub fn unit_catalogue() -> Vec<Unit> {
+    UNITS
+        .iter()
+        .map(|definition| Unit::from_definition(definition, None))
+        .chain(currencies())
+        .collect()
+}
+
+/// Lists the units of one category.
+///
+/// # Arguments
+///
+/// * `category` - The category
+///
+/// # Returns
+///
+/// The category's units, SI unit (or base currency) first
+#[wasm_bindgen]
+pub fn units_in_category(category: UnitCategory) -> Vec<Unit> {
+    if category == UnitCategory::Currency {
+        return currencies();
+    }
+    UNITS
+        .iter()
+        .filter(|definition| definition.category == category)
+        .map(|definition| Unit::from_definition(definition, None))
+        .collect()
+}
+
+/// Lists the currencies of the loaded exchange rates, base currency first.
+fn currencies() -> Vec<Unit> {
+    with_rates(|table| match table {
+        Some(table) => table.codes().into_iter().filter_map(|code| Unit::from_currency(table, code)).collect(),
+        None => Vec::new(),
+    })
+}
+
+/// Gets the string code of a category from JavaScript.
+///
+/// # Arguments
+///
+/// * `category` - The category
+///
+/// # Returns
+///
+/// The string code (e.g. `"data_size"`)
+#[wasm_bindgen]
+pub fn unit_category_name(category: UnitCategory) -> String {
+    category.name().to_string()
+}
+
+/// Looks up a unit from JavaScript.
+///
+/// # Arguments
+///
+/// * `text` - The unit's symbol, alias or name
+///
+/// # Returns
+///
+/// The unit, or an error message
+#[wasm_bindgen(js_name = lookup_unit)]
+pub fn lookup_wasm(text: &str) -> Result<Unit, JsValue> {
+    lookup(text).map_err(|e| JsValue::from_str(e.message()))
+}
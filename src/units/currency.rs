@@ -0,0 +1,760 @@
+//! Currency conversion with a user-supplied rate table.
+//!
+//! This module lets currencies be used like any other unit:
+//! - Exchange rates are loaded from JSON or CSV; nothing is fetched over
+//!   the network, so tests can inject a fixed table
+//! - Rates are quoted against a base currency and converted through it
+//! - Each rate keeps the timestamp it was quoted at
+//! - Converted amounts are rounded to the currency's ISO 4217 minor units
+//!
+//! A JSON table names its base currency, an optional timestamp and the
+//! rates, each either a number or an object with its own timestamp:
+//!
+//! ```json
+//! {"base": "USD", "timestamp": "2024-03-15T16:00:00Z",
+//!  "rates": {"EUR": 0.92, "JPY": {"rate": 149.5, "timestamp": "2024-03-15T09:00:00Z"}}}
+//! ```
+//!
+//! A CSV table has one `code,rate[,timestamp]` row per currency, optionally
+//! after a header row naming the columns.
+//!
+//! All operations are exposed to JavaScript through WebAssembly bindings.
+
+use std::cell::RefCell;
+use wasm_bindgen::prelude::*;
+use crate::calculator::datetime::Date;
+use crate::errors::{CalculatorError, CalcResult};
+
+/// ISO 4217 names and minor units of common currencies. Other valid codes
+/// are accepted with two minor units.
+pub const CURRENCIES: &[(&str, &str, u32)] = &[
+    ("AED", "UAE dirham", 2),
+    ("ARS", "Argentine peso", 2),
+    ("AUD", "Australian dollar", 2),
+    ("BHD", "Bahraini dinar", 3),
+    ("BIF", "Burundian franc", 0),
+    ("BRL", "Brazilian real", 2),
+    ("CAD", "Canadian dollar", 2),
+    ("CHF", "Swiss franc", 2),
+    ("CLF", "Unidad de Fomento", 4),
+    ("CLP", "Chilean peso", 0),
+    ("CNY", "Chinese yuan", 2),
+    ("CZK", "Czech koruna", 2),
+    ("DJF", "Djiboutian franc", 0),
+    ("DKK", "Danish krone", 2),
+    ("EUR", "euro", 2),
+    ("GBP", "pound sterling", 2),
+    ("GNF", "Guinean franc", 0),
+    ("HKD", "Hong Kong dollar", 2),
+    ("HUF", "Hungarian forint", 2),
+    ("IDR", "Indonesian rupiah", 2),
+    ("ILS", "Israeli new shekel", 2),
+    ("INR", "Indian rupee", 2),
+    ("IQD", "Iraqi dinar", 3),
+    ("ISK", "Icelandic króna", 0),
+    ("JOD", "Jordanian dinar", 3),
+    ("JPY", "Japanese yen", 0),
+    ("KMF", "Comorian franc", 0),
+    ("KRW", "South Korean won", 0),
+    ("KWD", "Kuwaiti dinar", 3),
+    ("LYD", "Libyan dinar", 3),
+    ("MXN", "Mexican peso", 2),
+    ("MYR", "Malaysian ringgit", 2),
+    ("NOK", "Norwegian krone", 2),
+    ("NZD", "New Zealand dollar", 2),
+    ("OMR", "Omani rial", 3),
+    ("PHP", "Philippine peso", 2),
+    ("PLN", "Polish złoty", 2),
+    ("PYG", "Paraguayan guaraní", 0),
+    ("RWF", "Rwandan franc", 0),
+    ("SAR", "Saudi riyal", 2),
+    ("SEK", "Swedish krona", 2),
+    ("SGD", "Singapore dollar", 2),
+    ("THB", "Thai baht", 2),
+    ("TND", "Tunisian dinar", 3),
+    ("TRY", "Turkish lira", 2),
+    ("TWD", "New Taiwan dollar", 2),
+    ("UGX", "Ugandan shilling", 0),
+    ("USD", "US dollar", 2),
+    ("UYW", "Unidad previsional", 4),
+    ("VND", "Vietnamese đồng", 0),
+    ("VUV", "Vanuatu vatu", 0),
+    ("XAF", "Central African CFA franc", 0),
+    ("XOF", "West African CFA franc", 0),
+    ("XPF", "CFP franc", 0),
+    ("ZAR", "South African rand", 2),
+];
+
+/// Deepest nesting of arrays and objects accepted in a JSON rate table.
+const MAX_JSON_DEPTH: usize = 64;
+
+// The rate table used by unit lookups
+thread_local! {
+    static RATES: RefCell<Option<RateTable>> = const { RefCell::new(None) };
+}
+
+/// One exchange rate.
+#[derive(Debug, Clone, PartialEq)]
+struct Rate {
+    /// The currency code
+    code: String,
+    /// Units of this currency per unit of the base currency
+    rate: f64,
+    /// When the rate was quoted, if it differs from the table's timestamp
+    timestamp: Option<String>,
+}
+
+/// Represents exchange rates quoted against a base currency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateTable {
+    /// The base currency code
+    base: String,
+    /// When the rates were quoted
+    timestamp: Option<String>,
+    /// The rates of the other currencies
+    rates: Vec<Rate>,
+}
+
+impl RateTable {
+    /// Creates a table with only its base currency.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base currency code
+    /// * `timestamp` - When the rates were quoted (ISO 8601), if known
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(table)` - The table
+    /// * `Err(error)` - An invalid input error for a malformed code or timestamp
+    pub fn new(base: &str, timestamp: Option<&str>) -> CalcResult<Self> {
+        Ok(Self {
+            base: validate_code(base)?,
+            timestamp: timestamp.map(validate_timestamp).transpose()?,
+            rates: Vec::new(),
+        })
+    }
+
+    /// Sets the rate of a currency, replacing any previous rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The currency code
+    /// * `rate` - Units of the currency per unit of the base currency
+    /// * `timestamp` - When the rate was quoted, if it differs from the table's
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The rate was set
+    /// * `Err(error)` - An invalid input error for a malformed code or
+    ///   timestamp, a rate that is not positive, or a base rate other than 1
+    pub fn set_rate(&mut self, code: &str, rate: f64, timestamp: Option<&str>) -> CalcResult<()> {
+        let code = validate_code(code)?;
+        if !(rate.is_finite() && rate > 0.0) {
+            return Err(CalculatorError::invalid_input(Some(&format!("the rate of {} must be positive", code))));
+        }
+        if code == self.base {
+            if rate != 1.0 {
+                return Err(CalculatorError::invalid_input(Some("the base currency's rate must be 1")));
+            }
+            return Ok(());
+        }
+
+        let timestamp = timestamp.map(validate_timestamp).transpose()?;
+        self.rates.retain(|existing| existing.code != code);
+        self.rates.push(Rate { code, rate, timestamp });
+        Ok(())
+    }
+
+    /// Parses a table from JSON.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - The table, as described in the module documentation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(table)` - The table
+    /// * `Err(error)` - An invalid input error describing the problem
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::units::currency::RateTable;
+    ///
+    /// let table = RateTable::from_json(r#"{"base": "USD", "rates": {"EUR": 0.9, "GBP": 0.8}}"#).unwrap();
+    /// assert_eq!(table.convert(100.0, "EUR", "GBP").unwrap(), 88.89);
+    /// ```
+    pub fn from_json(json: &str) -> CalcResult<Self> {
+        let invalid = |detail: &str| CalculatorError::invalid_input(Some(&format!("rate table: {}", detail)));
+        let root = Json::parse(json)?;
+        let base = root.field("base").and_then(Json::as_str).ok_or_else(|| invalid("missing \"base\""))?;
+        let timestamp = match root.field("timestamp") {
+            Some(value) => Some(value.as_str().ok_or_else(|| invalid("\"timestamp\" must be a string"))?),
+            None => None,
+        };
+        let Some(Json::Object(rates)) = root.field("rates") else {
+            return Err(invalid("missing \"rates\" object"));
+        };
+
+        let mut table = Self::new(base, timestamp)?;
+        for (code, value) in rates {
+            let (rate, timestamp) = match value {
+                Json::Number(rate) => (*rate, None),
+                Json::Object(_) => (
+                    value.field("rate").and_then(Json::as_number).ok_or_else(|| invalid("missing \"rate\""))?,
+                    value.field("timestamp").and_then(Json::as_str),
+                ),
+                _ => return Err(invalid(&format!("the rate of {} must be a number", code))),
+            };
+            table.set_rate(code, rate, timestamp)?;
+        }
+        Ok(table)
+    }
+
+    /// Parses a table from CSV rows of `code,rate[,timestamp]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base currency code
+    /// * `csv` - The rows, optionally after a header row
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(table)` - The table
+    /// * `Err(error)` - An invalid input error naming the first bad row
+    pub fn from_csv(base: &str, csv: &str) -> CalcResult<Self> {
+        let mut table = Self::new(base, None)?;
+        let mut first_row = true;
+        for (index, line) in csv.lines().enumerate() {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            if line.trim().is_empty() {
+                continue;
+            }
+            let is_first_row = std::mem::replace(&mut first_row, false);
+            let rate = fields.get(1).and_then(|rate| rate.parse::<f64>().ok());
+            match (fields.len(), rate) {
+                // Only the first row may be a header, naming its columns
+                // rather than a currency and a rate
+                (2..=3, None) if is_first_row && is_header(&fields) => continue,
+                (2..=3, Some(rate)) => table.set_rate(fields[0], rate, fields.get(2).copied())?,
+                _ => {
+                    return Err(CalculatorError::invalid_input(Some(&format!(
+                        "rate table: row {} is not code,rate[,timestamp]",
+                        index + 1
+                    ))))
+                }
+            }
+        }
+        Ok(table)
+    }
+
+    /// Gets the base currency code.
+    ///
+    /// # Returns
+    ///
+    /// The base currency code
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    /// Lists the currency codes in the table, base currency first.
+    ///
+    /// # Returns
+    ///
+    /// The codes
+    pub fn codes(&self) -> Vec<&str> {
+        std::iter::once(self.base.as_str())
+            .chain(self.rates.iter().map(|rate| rate.code.as_str()))
+            .collect()
+    }
+
+    /// Gets the rate of a currency.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The currency code
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(rate)` - Units of the currency per unit of the base currency
+    /// * `Err(error)` - An invalid input error if the table has no rate for it
+    pub fn rate(&self, code: &str) -> CalcResult<f64> {
+        if code == self.base {
+            return Ok(1.0);
+        }
+        self.find(code).map(|rate| rate.rate)
+    }
+
+    /// Gets when a currency's rate was quoted.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The currency code
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(timestamp)` - The rate's timestamp, or the table's, if known
+    /// * `Err(error)` - An invalid input error if the table has no rate for it
+    pub fn timestamp(&self, code: &str) -> CalcResult<Option<&str>> {
+        if code == self.base {
+            return Ok(self.timestamp.as_deref());
+        }
+        let rate = self.find(code)?;
+        Ok(rate.timestamp.as_deref().or(self.timestamp.as_deref()))
+    }
+
+    /// Gets the exchange rate between two currencies, through the base.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The currency converted from
+    /// * `to` - The currency converted to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(rate)` - Units of `to` per unit of `from`
+    /// * `Err(error)` - An invalid input error if either rate is missing
+    pub fn cross_rate(&self, from: &str, to: &str) -> CalcResult<f64> {
+        Ok(self.rate(to)? / self.rate(from)?)
+    }
+
+    /// Converts an amount, rounding to the target currency's minor units.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The amount in `from`
+    /// * `from` - The currency converted from
+    /// * `to` - The currency converted to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(amount)` - The amount in `to`
+    /// * `Err(error)` - An invalid input error if either rate is missing
+    pub fn convert(&self, amount: f64, from: &str, to: &str) -> CalcResult<f64> {
+        Ok(round_to_minor_units(amount * self.cross_rate(from, to)?, to))
+    }
+
+    /// Finds the rate of a currency other than the base.
+    fn find(&self, code: &str) -> CalcResult<&Rate> {
+        self.rates
+            .iter()
+            .find(|rate| rate.code == code)
+            .ok_or_else(|| CalculatorError::invalid_input(Some(&format!("no exchange rate for {}", code))))
+    }
+}
+
+/// Installs a rate table for currency units, replacing any previous table.
+///
+/// # Arguments
+///
+/// * `table` - The rate table
+pub fn install_rates(table: RateTable) {
+    RATES.with(|rates| *rates.borrow_mut() = Some(table));
+}
+
+/// Runs a function with the installed rate table.
+///
+/// # Arguments
+///
+/// * `f` - The function, given the table if one is installed
+///
+/// # Returns
+///
+/// The function's result
+pub fn with_rates<T>(f: impl FnOnce(Option<&RateTable>) -> T) -> T {
+    RATES.with(|rates| f(rates.borrow().as_ref()))
+}
+
+/// Gets the ISO 4217 name of a currency.
+///
+/// # Arguments
+///
+/// * `code` - The currency code
+///
+/// # Returns
+///
+/// The name, or the code itself for currencies not in `CURRENCIES`
+pub fn currency_name(code: &str) -> &str {
+    CURRENCIES.iter().find(|(known, _, _)| *known == code).map_or(code, |(_, name, _)| name)
+}
+
+/// Gets the number of ISO 4217 minor units (decimal places) of a currency.
+///
+/// # Arguments
+///
+/// * `code` - The currency code
+///
+/// # Returns
+///
+/// The number of decimal places, 2 for currencies not in `CURRENCIES`
+pub fn minor_units(code: &str) -> u32 {
+    CURRENCIES.iter().find(|(known, _, _)| *known == code).map_or(2, |(_, _, minor)| *minor)
+}
+
+/// Rounds an amount to a currency's minor units, half away from zero.
+///
+/// The amount is first rounded to 15 significant digits, so 1.005 rounds
+/// to 1.01 even though the nearest `f64` is slightly below it, and noise
+/// from converting through the base currency cannot tip a half.
+///
+/// # Arguments
+///
+/// * `amount` - The amount
+/// * `code` - The currency code
+///
+/// # Returns
+///
+/// The rounded amount
+pub fn round_to_minor_units(amount: f64, code: &str) -> f64 {
+    round_decimal(amount, minor_units(code) as usize)
+}
+
+/// Loads exchange rates from JSON from JavaScript.
+///
+/// # Arguments
+///
+/// * `json` - The rate table
+///
+/// # Returns
+///
+/// Nothing, or an error message
+#[wasm_bindgen(js_name = load_rates)]
+pub fn load_rates_wasm(json: &str) -> Result<(), JsValue> {
+    RateTable::from_json(json)
+        .map(install_rates)
+        .map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Loads exchange rates from CSV from JavaScript.
+///
+/// # Arguments
+///
+/// * `base` - The base currency code
+/// * `csv` - The `code,rate[,timestamp]` rows
+///
+/// # Returns
+///
+/// Nothing, or an error message
+#[wasm_bindgen(js_name = load_rates_csv)]
+pub fn load_rates_csv_wasm(base: &str, csv: &str) -> Result<(), JsValue> {
+    RateTable::from_csv(base, csv)
+        .map(install_rates)
+        .map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Removes the installed exchange rates.
+#[wasm_bindgen]
+pub fn clear_rates() {
+    RATES.with(|rates| *rates.borrow_mut() = None);
+}
+
+/// Gets when a currency's rate was quoted from JavaScript.
+///
+/// # Arguments
+///
+/// * `code` - The currency code
+///
+/// # Returns
+///
+/// The timestamp, `undefined` if unknown, or an error message
+#[wasm_bindgen(js_name = rate_timestamp)]
+pub fn rate_timestamp_wasm(code: &str) -> Result<Option<String>, JsValue> {
+    with_rates(|table| match table {
+        Some(table) => table.timestamp(code).map(|timestamp| timestamp.map(str::to_string)),
+        None => Err(no_rates()),
+    })
+    .map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Converts an amount between currencies from JavaScript.
+///
+/// # Arguments
+///
+/// * `amount` - The amount in `from`
+/// * `from` - The currency converted from
+/// * `to` - The currency converted to
+///
+/// # Returns
+///
+/// The amount in `to`, rounded to its minor units, or an error message
+#[wasm_bindgen(js_name = convert_currency)]
+pub fn convert_currency_wasm(amount: f64, from: &str, to: &str) -> Result<f64, JsValue> {
+    with_rates(|table| table.ok_or_else(no_rates)?.convert(amount, from, to))
+        .map_err(|e| JsValue::from_str(e.message()))
+}
+
+/// Builds the error for using currencies before loading rates.
+pub(crate) fn no_rates() -> CalculatorError {
+    CalculatorError::invalid_input(Some("no exchange rates are loaded"))
+}
+
+/// Checks that a currency code is three capital letters.
+fn validate_code(code: &str) -> CalcResult<String> {
+    let code = code.trim();
+    if code.len() == 3 && code.chars().all(|c| c.is_ascii_uppercase()) {
+        Ok(code.to_string())
+    } else {
+        Err(CalculatorError::invalid_input(Some(&format!("{} is not an ISO 4217 currency code", code))))
+    }
+}
+
+/// Checks whether CSV fields name columns: text other than a currency code,
+/// then non-numeric text.
+fn is_header(fields: &[&str]) -> bool {
+    let is_text = |field: &str| field.chars().any(char::is_alphabetic) && field.parse::<f64>().is_err();
+    validate_code(fields[0]).is_err() && is_text(fields[0]) && is_text(fields[1])
+}
+
+/// Checks that a timestamp starts with a valid ISO 8601 date.
+fn validate_timestamp(timestamp: &str) -> CalcResult<String> {
+    let timestamp = timestamp.trim();
+    match timestamp.get(..10) {
+        Some(date) if Date::parse(date).is_ok() && timestamp[10..].chars().all(|c| !c.is_whitespace()) => {
+            Ok(timestamp.to_string())
+        }
+        _ => Err(CalculatorError::invalid_input(Some(&format!("{} is not an ISO 8601 timestamp", timestamp)))),
+    }
+}
+
+/// Rounds a value to decimal places, half away from zero, using its
+/// decimal representation to 15 significant digits.
+fn round_decimal(value: f64, places: usize) -> f64 {
+    if !value.is_finite() {
+        return value;
+    }
+    let significant: f64 = format!("{:.14e}", value.abs()).parse().unwrap_or(value.abs());
+    let text = format!("{}", significant);
+    let (integer, fraction) = text.split_once('.').unwrap_or((&text, ""));
+    if fraction.len() <= places {
+        return value;
+    }
+
+    let kept = format!("{}{}", integer, &fraction[..places]);
+    let mut digits: Vec<u8> = kept.bytes().map(|b| b - b'0').collect();
+    if fraction.as_bytes()[places] >= b'5' {
+        let mut i = digits.len();
+        loop {
+            if i == 0 {
+                digits.insert(0, 1);
+                break;
+            }
+            i -= 1;
+            if digits[i] == 9 {
+                digits[i] = 0;
+            } else {
+                digits[i] += 1;
+                break;
+            }
+        }
+    }
+
+    let split = digits.len() - places;
+    let digits: String = digits.iter().map(|d| char::from(b'0' + d)).collect();
+    let rounded: f64 = format!("{}.{}", &digits[..split], &digits[split..]).parse().unwrap_or(value);
+    rounded.copysign(value)
+}
+
+/// A parsed JSON value, enough to read rate tables.
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Parses a complete JSON document.
+    fn parse(text: &str) -> CalcResult<Json> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut position = 0;
+        let value = parse_json_value(&chars, &mut position, 0)?;
+        skip_whitespace(&chars, &mut position);
+        if position < chars.len() {
+            return Err(json_error(position));
+        }
+        Ok(value)
+    }
+
+    /// Gets a field of an object.
+    fn field(&self, name: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(key, _)| key == name).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Gets the value of a string.
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Gets the value of a number.
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            Json::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// Parses the JSON value at `position`, inside `depth` arrays and objects.
+fn parse_json_value(chars: &[char], position: &mut usize, depth: usize) -> CalcResult<Json> {
+    skip_whitespace(chars, position);
+    if matches!(chars.get(*position), Some('{' | '[')) && depth >= MAX_JSON_DEPTH {
+        return Err(CalculatorError::invalid_input(Some("rate table: JSON is nested too deeply")));
+    }
+    match chars.get(*position) {
+        Some('{') => {
+            *position += 1;
+            let mut fields = Vec::new();
+            skip_whitespace(chars, position);
+            if chars.get(*position) == Some(&'}') {
+                *position += 1;
+                return Ok(Json::Object(fields));
+            }
+            loop {
+                skip_whitespace(chars, position);
+                let Json::String(key) = parse_json_value(chars, position, depth + 1)? else {
+                    return Err(json_error(*position));
+                };
+                skip_whitespace(chars, position);
+                expect_char(chars, position, ':')?;
+                fields.push((key, parse_json_value(chars, position, depth + 1)?));
+                skip_whitespace(chars, position);
+                match chars.get(*position) {
+                    Some(',') => *position += 1,
+                    Some('}') => {
+                        *position += 1;
+                        return Ok(Json::Object(fields));
+                    }
+                    _ => return Err(json_error(*position)),
+                }
+            }
+        }
+        Some('[') => {
+            *position += 1;
+            let mut items = Vec::new();
+            skip_whitespace(chars, position);
+            if chars.get(*position) == Some(&']') {
+                *position += 1;
+                return Ok(Json::Array(items));
+            }
+            loop {
+                items.push(parse_json_value(chars, position, depth + 1)?);
+                skip_whitespace(chars, position);
+                match chars.get(*position) {
+                    Some(',') => *position += 1,
+                    Some(']') => {
+                        *position += 1;
+                        return Ok(Json::Array(items));
+                    }
+                    _ => return Err(json_error(*position)),
+                }
+            }
+        }
+        Some('"') => {
+            *position += 1;
+            let mut text = String::new();
+            loop {
+                match chars.get(*position) {
+                    Some('"') => {
+                        *position += 1;
+                        return Ok(Json::String(text));
+                    }
+                    Some('\\') => {
+                        let escaped = match chars.get(*position + 1) {
+                            Some('n') => '\n',
+                            Some('t') => '\t',
+                            Some('r') => '\r',
+                            Some('b') => '\u{8}',
+                            Some('f') => '\u{c}',
+                            Some('u') => {
+                                let mut code = hex_escape(chars, *position).ok_or_else(|| json_error(*position))?;
+                                *position += 4;
+                                // A high surrogate joins the low surrogate escaped after it
+                                if (0xd800..0xdc00).contains(&code) {
+                                    let low = hex_escape(chars, *position + 2).filter(|low| (0xdc00..0xe000).contains(low));
+                                    if let Some(low) = low {
+                                        code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                                        *position += 6;
+                                    }
+                                }
+                                char::from_u32(code).unwrap_or('\u{fffd}')
+                            }
+                            Some(c @ ('"' | '\\' | '/')) => *c,
+                            _ => return Err(json_error(*position)),
+                        };
+                        text.push(escaped);
+                        *position += 2;
+                    }
+                    Some(c) => {
+                        text.push(*c);
+                        *position += 1;
+                    }
+                    None => return Err(json_error(*position)),
+                }
+            }
+        }
+        Some(c) if *c == '-' || c.is_ascii_digit() => {
+            let start = *position;
+            while chars
+                .get(*position)
+                .is_some_and(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+            {
+                *position += 1;
+            }
+            let text: String = chars[start..*position].iter().collect();
+            text.parse().map(Json::Number).map_err(|_| json_error(start))
+        }
+        _ => {
+            for (word, value) in [("true", Json::Bool(true)), ("false", Json::Bool(false)), ("null", Json::Null)] {
+                let end = *position + word.len();
+                if chars.get(*position..end).is_some_and(|found| found.iter().copied().eq(word.chars())) {
+                    *position = end;
+                    return Ok(value);
+                }
+            }
+            Err(json_error(*position))
+        }
+    }
+}
+
+/// Reads the four hex digits of a `\u` escape starting at `position`.
+fn hex_escape(chars: &[char], position: usize) -> Option<u32> {
+    if chars.get(position..position + 2)? != ['\\', 'u'] {
+        return None;
+    }
+    chars
+        .get(position + 2..position + 6)?
+        .iter()
+        .try_fold(0, |code, c| Some(code * 16 + c.to_digit(16)?))
+}
+
+/// Skips whitespace at `position`.
+fn skip_whitespace(chars: &[char], position: &mut usize) {
+    while chars.get(*position).is_some_and(|c| c.is_whitespace()) {
+        *position += 1;
+    }
+}
+
+/// Consumes an expected character.
+fn expect_char(chars: &[char], position: &mut usize, expected: char) -> CalcResult<()> {
+    if chars.get(*position) == Some(&expected) {
+        *position += 1;
+        Ok(())
+    } else {
+        Err(json_error(*position))
+    }
+}
+
+/// Builds the error for malformed JSON.
+fn json_error(position: usize) -> CalculatorError {
+    CalculatorError::invalid_input(Some(&format!("rate table: invalid JSON at position {}", position + 1)))
+}
@@ -0,0 +1,114 @@
+//! Physical dimensions for unit analysis.
+//!
+//! A dimension is a product of powers of the base quantities (length, mass,
+//! time, temperature, information, angle and currency). Two units can be converted
+//! into each other only if their dimensions are equal.
+
+/// The number of base quantities.
+pub const BASE_COUNT: usize = 7;
+
+/// Represents a product of powers of the base quantities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Dimension {
+    /// The exponents of length, mass, time, temperature, information, angle
+    /// and currency
+    exponents: [i8; BASE_COUNT],
+}
+
+impl Dimension {
+    /// A dimensionless quantity
+    pub const NONE: Dimension = Dimension::new([0, 0, 0, 0, 0, 0, 0]);
+    /// Length (metre)
+    pub const LENGTH: Dimension = Dimension::new([1, 0, 0, 0, 0, 0, 0]);
+    /// Mass (kilogram)
+    pub const MASS: Dimension = Dimension::new([0, 1, 0, 0, 0, 0, 0]);
+    /// Time (second)
+    pub const TIME: Dimension = Dimension::new([0, 0, 1, 0, 0, 0, 0]);
+    /// Temperature (kelvin)
+    pub const TEMPERATURE: Dimension = Dimension::new([0, 0, 0, 1, 0, 0, 0]);
+    /// Information (bit)
+    pub const INFORMATION: Dimension = Dimension::new([0, 0, 0, 0, 1, 0, 0]);
+    /// Plane angle (radian)
+    pub const ANGLE: Dimension = Dimension::new([0, 0, 0, 0, 0, 1, 0]);
+    /// Money (the base currency of the loaded exchange rates)
+    pub const CURRENCY: Dimension = Dimension::new([0, 0, 0, 0, 0, 0, 1]);
+    /// Area (square metre)
+    pub const AREA: Dimension = Dimension::new([2, 0, 0, 0, 0, 0, 0]);
+    /// Volume (cubic metre)
+    pub const VOLUME: Dimension = Dimension::new([3, 0, 0, 0, 0, 0, 0]);
+    /// Speed (metre per second)
+    pub const SPEED: Dimension = Dimension::new([1, 0, -1, 0, 0, 0, 0]);
+    /// Pressure (pascal)
+    pub const PRESSURE: Dimension = Dimension::new([-1, 1, -2, 0, 0, 0, 0]);
+    /// Energy (joule)
+    pub const ENERGY: Dimension = Dimension::new([2, 1, -2, 0, 0, 0, 0]);
+    /// Power (watt)
+    pub const POWER: Dimension = Dimension::new([2, 1, -3, 0, 0, 0, 0]);
+
+    /// Creates a dimension from the exponents of the base quantities.
+    ///
+    /// # Arguments
+    ///
+    /// * `exponents` - The exponents of length, mass, time, temperature,
+    ///   information, angle and currency
+    ///
+    /// # Returns
+    ///
+    /// The dimension
+    pub const fn new(exponents: [i8; BASE_COUNT]) -> Self {
+        Self { exponents }
+    }
+
+    /// Gets the exponents of the base quantities.
+    ///
+    /// # Returns
+    ///
+    /// The exponents of length, mass, time, temperature, information, angle
+    /// and currency
+    pub fn exponents(&self) -> [i8; BASE_COUNT] {
+        self.exponents
+    }
+
+    /// Checks whether the dimension is dimensionless.
+    ///
+    /// # Returns
+    ///
+    /// `true` if every exponent is zero
+    pub fn is_dimensionless(&self) -> bool {
+        self.exponents.iter().all(|exponent| *exponent == 0)
+    }
+
+    /// Multiplies two dimensions by adding their exponents.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other dimension
+    ///
+    /// # Returns
+    ///
+    /// The product, or `None` if an exponent is out of range
+    pub fn multiply(&self, other: &Dimension) -> Option<Dimension> {
+        let mut exponents = self.exponents;
+        for (exponent, other) in exponents.iter_mut().zip(other.exponents) {
+            *exponent = exponent.checked_add(other)?;
+        }
+        Some(Self::new(exponents))
+    }
+
+    /// Raises a dimension to an integer power.
+    ///
+    /// # Arguments
+    ///
+    /// * `power` - The power
+    ///
+    /// # Returns
+    ///
+    /// The dimension raised to `power`, or `None` if an exponent is out of range
+    pub fn power(&self, power: i8) -> Option<Dimension> {
+        let mut exponents = self.exponents;
+        for exponent in exponents.iter_mut() {
+            *exponent = exponent.checked_mul(power)?;
+        }
+        Some(Self::new(exponents))
+    }
+}
@@ -0,0 +1,94 @@
+//! Units module for the Rust WASM Calculator.
+//!
+//! This module converts values between units of measurement:
+//! - A catalogue of units for length, mass, time, temperature, area,
+//!   volume, speed, pressure, energy, power, data size and angle
+//! - Currencies, converted with a user-supplied exchange rate table
+//! - SI prefixes on metric units and IEC prefixes on data units
+//! - Dimensional analysis, so only units of the same dimension convert
+//! - Temperature scales with offsets (°C, °F)
+//! - Quantities that carry units through arithmetic
+//!
+//! All operations are exposed to JavaScript through WebAssembly bindings.
+
+// Export submodules
+pub mod dimension;
+pub mod catalogue;
+pub mod quantity;
+pub mod currency;
+
+use wasm_bindgen::prelude::*;
+use crate::calculator::operations::check_result;
+use crate::errors::{CalculatorError, CalcResult};
+
+// Re-export commonly used types and functions
+pub use dimension::Dimension;
+pub use catalogue::{lookup, unit_catalogue, units_in_category, Unit, UnitCategory};
+pub use quantity::Quantity;
+pub use currency::{install_rates, RateTable};
+
+/// Converts a value from one unit to another.
+///
+/// Amounts converted to a currency are rounded to its minor units.
+///
+/// # Arguments
+///
+/// * `value` - The value in the `from` unit
+/// * `from` - The unit's symbol, alias or name
+/// * `to` - The target unit's symbol, alias or name
+///
+/// # Returns
+///
+/// * `Ok(value)` - The value in the `to` unit
+/// * `Err(error)` - An invalid input error for an unknown unit, an
+///   incompatible units error if the dimensions differ, or an overflow or
+///   underflow error
+///
+/// # Examples
+///
+/// ```
+/// use rust_wasm_calc::units::convert;
+///
+/// assert!((convert(100.0, "°C", "°F").unwrap() - 212.0).abs() < 1e-9);
+/// assert!((convert(1.0, "mi", "km").unwrap() - 1.609344).abs() < 1e-12);
+/// assert!(convert(1.0, "km", "kg").is_err());
+/// ```
+pub fn convert(value: f64, from: &str, to: &str) -> CalcResult {
+    let from = lookup(from)?;
+    let to = lookup(to)?;
+
+    if from.dimension() != to.dimension() {
+        return Err(CalculatorError::incompatible_units(Some(&format!(
+            "cannot convert {} ({}) to {} ({})",
+            from.symbol(),
+            from.category().name(),
+            to.symbol(),
+            to.category().name()
+        ))));
+    }
+
+    let result = to.from_si(from.to_si(value));
+    // A zero point shift can legitimately give zero, as in 273.15 K to °C
+    let scales_only = from.offset() == 0.0 && to.offset() == 0.0;
+    let result = check_result(result, scales_only && value != 0.0)?;
+    if to.category() == UnitCategory::Currency {
+        return Ok(currency::round_to_minor_units(result, &to.symbol()));
+    }
+    Ok(result)
+}
+
+/// Converts a value from one unit to another from JavaScript.
+///
+/// # Arguments
+///
+/// * `value` - The value in the `from` unit
+/// * `from` - The unit's symbol, alias or name
+/// * `to` - The target unit's symbol, alias or name
+///
+/// # Returns
+///
+/// The converted value, or an error message
+#[wasm_bindgen(js_name = convert)]
+pub fn convert_wasm(value: f64, from: &str, to: &str) -> Result<f64, JsValue> {
+    convert(value, from, to).map_err(|e| JsValue::from_str(e.message()))
+}
@@ -0,0 +1,552 @@
+//! Quantities: values that carry units through arithmetic.
+//!
+//! A quantity stores its value in SI units together with the units it is
+//! displayed in, such as km·min⁻¹. Multiplying and dividing combine the
+//! units, merging units of the same dimension into the first one seen, so
+//! `3 m * 4 km` is 12000 m². Adding and subtracting require equal
+//! dimensions. `simplify` then picks a readable unit for display, so
+//! `5 km / 20 min` shows as 15 km/h.
+//!
+//! Temperature scales with an offset (°C, °F) are treated as plain scales
+//! in arithmetic, so they may only be scaled, added and converted, not
+//! combined with other units.
+//!
+//! A quantity of a single currency is shown with that currency's ISO 4217
+//! minor units, so `10 USD to JPY` shows whole yen. Amounts too large to
+//! hold minor units are shown in scientific notation instead.
+
+use std::fmt;
+use wasm_bindgen::prelude::*;
+use super::catalogue::{lookup, units_in_category, Unit, UnitCategory, UNITS};
+use super::currency::{minor_units, round_to_minor_units};
+use super::dimension::Dimension;
+use crate::calculator::operations::{check_result, checked_add, checked_divide, checked_multiply, checked_subtract};
+use crate::errors::{CalculatorError, CalcResult};
+use crate::state::{format_canonical, Locale};
+
+/// The number of significant digits shown, which hides rounding noise
+/// from unit factors such as 15.000000000000002 km/h.
+const DISPLAY_DIGITS: usize = 15;
+
+/// The magnitude from which currency amounts are shown in scientific
+/// notation, since an `f64` no longer holds their minor units.
+const MINOR_UNITS_LIMIT: f64 = 1e15;
+
+/// Represents a value with units.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quantity {
+    /// The value in SI units, ignoring temperature offsets
+    si: f64,
+    /// The display units with their powers
+    units: Vec<(Unit, i8)>,
+}
+
+/// Getters for Quantity
+#[wasm_bindgen]
+impl Quantity {
+    /// Gets the value in the display units, rounded to the minor units for
+    /// an amount of a single currency.
+    pub fn value(&self) -> f64 {
+        let value = self
+            .units
+            .iter()
+            .fold(self.si, |value, (unit, power)| value / unit.factor().powi(*power as i32));
+        match self.currency() {
+            Some(code) => round_to_minor_units(value, &code),
+            None => value,
+        }
+    }
+
+    /// Gets the value in SI units.
+    pub fn si_value(&self) -> f64 {
+        self.si
+    }
+
+    /// Gets the display units (e.g. `"km/h"`), empty for a plain number.
+    pub fn unit(&self) -> String {
+        unit_string(&self.units)
+    }
+
+    /// Formats the quantity for display using a locale.
+    ///
+    /// # Arguments
+    ///
+    /// * `locale` - The locale for the number
+    ///
+    /// # Returns
+    ///
+    /// The number followed by the units (e.g. `"1.234,5 km"`)
+    pub fn format(&self, locale: &Locale) -> String {
+        self.join(locale.localize(&self.number_string(), true))
+    }
+}
+
+impl Quantity {
+    /// Creates a plain number.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The number
+    ///
+    /// # Returns
+    ///
+    /// A dimensionless quantity
+    pub fn number(value: f64) -> Self {
+        Self { si: value, units: Vec::new() }
+    }
+
+    /// Creates a quantity of a unit.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value in `unit`
+    /// * `unit` - The unit
+    ///
+    /// # Returns
+    ///
+    /// The quantity
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_wasm_calc::units::{lookup, Quantity};
+    ///
+    /// let distance = Quantity::new(5.0, lookup("km").unwrap());
+    /// let time = Quantity::new(20.0, lookup("min").unwrap());
+    /// assert_eq!(distance.divide(&time).unwrap().simplify().to_string(), "15 km/h");
+    /// ```
+    pub fn new(value: f64, unit: Unit) -> Self {
+        Self { si: value * unit.factor(), units: vec![(unit, 1)] }
+    }
+
+    /// Gets the dimension.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(dimension)` - The product of the units' dimensions
+    /// * `Err(error)` - An invalid input error if an exponent is out of range
+    pub fn dimension(&self) -> CalcResult<Dimension> {
+        self.units.iter().try_fold(Dimension::NONE, |dimension, (unit, power)| {
+            unit.dimension()
+                .power(*power)
+                .and_then(|unit_dimension| dimension.multiply(&unit_dimension))
+                .ok_or_else(|| CalculatorError::invalid_input(Some("unit exponent is too large")))
+        })
+    }
+
+    /// Checks whether the quantity is a plain number.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the quantity has no units
+    pub fn is_number(&self) -> bool {
+        self.units.is_empty()
+    }
+
+    /// Negates the quantity.
+    ///
+    /// # Returns
+    ///
+    /// The quantity with the opposite sign
+    pub fn negate(&self) -> Self {
+        Self { si: -self.si, units: self.units.clone() }
+    }
+
+    /// Takes the absolute value of the quantity.
+    ///
+    /// # Returns
+    ///
+    /// The quantity without its sign
+    pub fn abs(&self) -> Self {
+        Self { si: self.si.abs(), units: self.units.clone() }
+    }
+
+    /// Adds two quantities of the same dimension.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The quantity to add
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(sum)` - The sum, in this quantity's units
+    /// * `Err(error)` - An incompatible units error if the dimensions differ,
+    ///   or an overflow error
+    pub fn add(&self, other: &Quantity) -> CalcResult<Self> {
+        self.check_same_dimension(other, "add")?;
+        Ok(Self { si: checked_add(self.si, other.si)?, units: self.common_units(other) })
+    }
+
+    /// Subtracts a quantity of the same dimension.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The quantity to subtract
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(difference)` - The difference, in this quantity's units
+    /// * `Err(error)` - An incompatible units error if the dimensions differ,
+    ///   or an overflow error
+    pub fn subtract(&self, other: &Quantity) -> CalcResult<Self> {
+        self.check_same_dimension(other, "subtract")?;
+        Ok(Self { si: checked_subtract(self.si, other.si)?, units: self.common_units(other) })
+    }
+
+    /// Multiplies two quantities, combining their units.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The quantity to multiply by
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(product)` - The product
+    /// * `Err(error)` - An incompatible units error if a temperature offset
+    ///   unit would be combined with other units, or an overflow or
+    ///   underflow error
+    pub fn multiply(&self, other: &Quantity) -> CalcResult<Self> {
+        let units = combine(&self.units, &other.units, 1)?;
+        Ok(Self { si: checked_multiply(self.si, other.si)?, units })
+    }
+
+    /// Divides by a quantity, combining the units.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The quantity to divide by
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(quotient)` - The quotient
+    /// * `Err(error)` - A division by zero error, an incompatible units error
+    ///   if a temperature offset unit would be combined with other units, or
+    ///   an overflow or underflow error
+    pub fn divide(&self, other: &Quantity) -> CalcResult<Self> {
+        let units = combine(&self.units, &other.units, -1)?;
+        Ok(Self { si: checked_divide(self.si, other.si)?, units })
+    }
+
+    /// Raises the quantity to a power.
+    ///
+    /// The exponent must be a plain number, and with units it must give
+    /// whole powers, so `(4 m²)^0.5` is 2 m but `(4 m)^0.5` is an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `exponent` - The exponent
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(power)` - The quantity raised to `exponent`
+    /// * `Err(error)` - An incompatible units error if the exponent has units
+    ///   or gives fractional powers, or an overflow, underflow or NaN error
+    pub fn power(&self, exponent: &Quantity) -> CalcResult<Self> {
+        if !exponent.is_number() {
+            return Err(CalculatorError::incompatible_units(Some(&format!(
+                "the exponent {} must be a plain number",
+                exponent
+            ))));
+        }
+        let exponent = exponent.si;
+
+        let mut units = Vec::with_capacity(self.units.len());
+        for (unit, power) in self.units.iter().map(expand_power) {
+            let scaled = power as f64 * exponent;
+            if scaled.fract() != 0.0 || scaled.abs() > i8::MAX as f64 {
+                return Err(CalculatorError::incompatible_units(Some(&format!(
+                    "cannot raise {} to the power {}",
+                    unit_string(&self.units),
+                    exponent
+                ))));
+            }
+            units.push((unit, scaled as i8));
+        }
+        check_offset_units(&units)?;
+
+        Ok(Self { si: check_result(self.si.powf(exponent), self.si != 0.0)?, units })
+    }
+
+    /// Converts the quantity to other units of the same dimension.
+    ///
+    /// Converting between lone temperature units applies their offsets, so
+    /// 100 °C converts to 212 °F.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - A quantity whose units are the target units; its value
+    ///   is ignored
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(quantity)` - The quantity in the target units
+    /// * `Err(error)` - An incompatible units error if the dimensions differ
+    pub fn convert_to(&self, target: &Quantity) -> CalcResult<Self> {
+        if self.dimension()? != target.dimension()? {
+            return Err(CalculatorError::incompatible_units(Some(&format!(
+                "cannot convert {} to {}",
+                self.describe(),
+                target.describe()
+            ))));
+        }
+
+        let si = match (self.lone_unit(), target.lone_unit()) {
+            (Some(from), Some(to)) if from.offset() != 0.0 || to.offset() != 0.0 => {
+                to.from_si(from.to_si(self.value())) * to.factor()
+            }
+            _ => self.si,
+        };
+        Ok(Self { si: check_result(si, false)?, units: target.units.clone() })
+    }
+
+    /// Chooses readable units for display.
+    ///
+    /// Units that cancel out give a plain number. Units that spell a
+    /// catalogue unit use it (`m·m` is `m²`, `kW·h` is `kWh`). Otherwise, if
+    /// the dimension has a category, the category's unit sharing the most
+    /// units with the quantity is used, falling back to the SI unit, so
+    /// `km/min` becomes `km/h` and `J/s` becomes `W`.
+    ///
+    /// # Returns
+    ///
+    /// The same quantity with simplified units
+    pub fn simplify(&self) -> Self {
+        if self.units.is_empty() || (self.units.len() == 1 && self.units[0].1 == 1) {
+            return self.clone();
+        }
+        let Ok(dimension) = self.dimension() else {
+            return self.clone();
+        };
+        if dimension.is_dimensionless() {
+            return Self::number(self.si);
+        }
+
+        let spelled = lookup(&unit_string(&self.units)).ok().or_else(|| {
+            UNITS
+                .iter()
+                .find(|definition| definition.aliases.iter().any(|alias| same_units(alias, &self.units)))
+                .and_then(|definition| lookup(definition.symbol).ok())
+        });
+        if let Some(unit) = spelled.filter(|unit| unit.dimension() == dimension) {
+            return Self { si: self.si, units: vec![(unit, 1)] };
+        }
+
+        let Some(category) = UnitCategory::ALL.iter().find(|category| category.dimension() == dimension) else {
+            return self.clone();
+        };
+        let mut best: Option<(usize, Unit)> = None;
+        for unit in units_in_category(*category).into_iter().filter(|unit| unit.offset() == 0.0) {
+            let score = self.shared_units(&unit.symbol());
+            if best.as_ref().is_none_or(|(best_score, _)| score > *best_score) {
+                best = Some((score, unit));
+            }
+        }
+        match best {
+            Some((_, unit)) => Self { si: self.si, units: vec![(unit, 1)] },
+            None => self.clone(),
+        }
+    }
+
+    /// Writes the value in canonical form, with a lone currency's minor
+    /// units unless the amount is too large to hold them.
+    fn number_string(&self) -> String {
+        match self.currency() {
+            Some(_) if self.value().abs() >= MINOR_UNITS_LIMIT => format!("{:e}", round_for_display(self.value())),
+            Some(code) => format!("{:.*}", minor_units(&code) as usize, self.value()),
+            None => format_canonical(round_for_display(self.value())),
+        }
+    }
+
+    /// Gets the currency code if the quantity is an amount of one currency.
+    fn currency(&self) -> Option<String> {
+        self.lone_unit()
+            .filter(|unit| unit.category() == UnitCategory::Currency)
+            .map(Unit::symbol)
+    }
+
+    /// Joins a formatted number with the units.
+    fn join(&self, number: String) -> String {
+        if self.units.is_empty() {
+            number
+        } else {
+            format!("{} {}", number, unit_string(&self.units))
+        }
+    }
+
+    /// Describes the units for error messages.
+    fn describe(&self) -> String {
+        if self.units.is_empty() {
+            "a plain number".to_string()
+        } else {
+            unit_string(&self.units)
+        }
+    }
+
+    /// Gets the only unit if the quantity has exactly one, to the first power.
+    fn lone_unit(&self) -> Option<&Unit> {
+        match self.units.as_slice() {
+            [(unit, 1)] => Some(unit),
+            _ => None,
+        }
+    }
+
+    /// Counts how many of this quantity's units, split into their parts
+    /// (`km/h` into `km` and `h`), appear in a catalogue unit's symbol or
+    /// aliases.
+    fn shared_units(&self, symbol: &str) -> usize {
+        let Some(definition) = UNITS.iter().find(|definition| definition.symbol == symbol) else {
+            return 0;
+        };
+        let spellings: Vec<&str> = std::iter::once(definition.symbol)
+            .chain(definition.aliases.iter().copied())
+            .flat_map(|spelling| spelling.split(['/', '·']))
+            .collect();
+        self.units
+            .iter()
+            .flat_map(|(unit, _)| unit.symbol().split(['/', '·']).map(str::to_string).collect::<Vec<_>>())
+            .filter(|part| spellings.contains(&part.as_str()))
+            .count()
+    }
+
+    /// Checks that two quantities can be added or subtracted.
+    fn check_same_dimension(&self, other: &Quantity, action: &str) -> CalcResult<()> {
+        if self.dimension()? != other.dimension()? {
+            return Err(CalculatorError::incompatible_units(Some(&format!(
+                "cannot {} {} and {}",
+                action,
+                self.describe(),
+                other.describe()
+            ))));
+        }
+        Ok(())
+    }
+
+    /// Gets the units for a sum, preferring this quantity's.
+    fn common_units(&self, other: &Quantity) -> Vec<(Unit, i8)> {
+        if self.units.is_empty() {
+            other.units.clone()
+        } else {
+            self.units.clone()
+        }
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.join(self.number_string()))
+    }
+}
+
+/// Multiplies unit lists, raising the second list's powers by `sign`.
+fn combine(units: &[(Unit, i8)], other: &[(Unit, i8)], sign: i8) -> CalcResult<Vec<(Unit, i8)>> {
+    let too_large = || CalculatorError::invalid_input(Some("unit exponent is too large"));
+    let mut combined = units.to_vec();
+    for (unit, power) in other {
+        let power = power.checked_mul(sign).ok_or_else(too_large)?;
+        match combined.iter_mut().find(|(existing, _)| existing.dimension() == unit.dimension()) {
+            Some((_, existing)) => *existing = existing.checked_add(power).ok_or_else(too_large)?,
+            None => combined.push((unit.clone(), power)),
+        }
+    }
+    combined.retain(|(_, power)| *power != 0);
+    check_offset_units(&combined)?;
+    Ok(combined)
+}
+
+/// Writes a squared or cubed unit such as `m²` as a power of its stem, so
+/// roots of it keep whole powers.
+fn expand_power((unit, power): &(Unit, i8)) -> (Unit, i8) {
+    let symbol = unit.symbol();
+    let stem_power = if symbol.ends_with('²') {
+        2
+    } else if symbol.ends_with('³') {
+        3
+    } else {
+        return (unit.clone(), *power);
+    };
+
+    let stem = lookup(&symbol[..symbol.len() - '²'.len_utf8()]).ok().filter(|stem| {
+        stem.dimension().power(stem_power) == Some(unit.dimension())
+            && (stem.factor().powi(stem_power as i32) / unit.factor() - 1.0).abs() < 1e-12
+    });
+    match (stem, power.checked_mul(stem_power)) {
+        (Some(stem), Some(power)) => (stem, power),
+        _ => (unit.clone(), *power),
+    }
+}
+
+/// Rejects temperature offset units that are combined with other units.
+fn check_offset_units(units: &[(Unit, i8)]) -> CalcResult<()> {
+    let lone = matches!(units, [(_, 1)]);
+    match units.iter().find(|(unit, _)| unit.offset() != 0.0) {
+        Some((unit, _)) if !lone => Err(CalculatorError::incompatible_units(Some(&format!(
+            "{} cannot be combined with other units; convert to K first",
+            unit.symbol()
+        )))),
+        _ => Ok(()),
+    }
+}
+
+/// Checks whether a spelling such as `kW·h` names exactly these units.
+fn same_units(spelling: &str, units: &[(Unit, i8)]) -> bool {
+    let parts: Vec<&str> = spelling.split('·').collect();
+    parts.len() == units.len()
+        && units.iter().all(|(unit, power)| *power == 1 && parts.contains(&unit.symbol().as_str()))
+}
+
+/// Renders a unit list such as `kg·m/s²`.
+fn unit_string(units: &[(Unit, i8)]) -> String {
+    let render = |unit: &Unit, power: i8| {
+        let symbol = unit.symbol();
+        let symbol = if symbol.contains('/') && (power != 1 || units.len() > 1) {
+            format!("({})", symbol)
+        } else {
+            symbol
+        };
+        if power == 1 {
+            symbol
+        } else {
+            format!("{}{}", symbol, superscript(power))
+        }
+    };
+
+    let numerator: Vec<String> = units.iter().filter(|(_, p)| *p > 0).map(|(u, p)| render(u, *p)).collect();
+    let denominator: Vec<String> = units.iter().filter(|(_, p)| *p < 0).map(|(u, p)| render(u, -*p)).collect();
+
+    match (numerator.is_empty(), denominator.len()) {
+        (_, 0) => numerator.join("·"),
+        (true, _) => units.iter().map(|(u, p)| render(u, *p)).collect::<Vec<_>>().join("·"),
+        (false, 1) => format!("{}/{}", numerator.join("·"), denominator[0]),
+        (false, _) => format!("{}/({})", numerator.join("·"), denominator.join("·")),
+    }
+}
+
+/// Writes an integer in superscript digits.
+fn superscript(value: i8) -> String {
+    value
+        .to_string()
+        .chars()
+        .map(|c| match c {
+            '-' => '⁻',
+            '0' => '⁰',
+            '1' => '¹',
+            '2' => '²',
+            '3' => '³',
+            '4' => '⁴',
+            '5' => '⁵',
+            '6' => '⁶',
+            '7' => '⁷',
+            '8' => '⁸',
+            _ => '⁹',
+        })
+        .collect()
+}
+
+/// Rounds a value to the displayed number of significant digits.
+fn round_for_display(value: f64) -> f64 {
+    if !value.is_finite() || value == 0.0 {
+        return value;
+    }
+    format!("{:.*e}", DISPLAY_DIGITS - 1, value).parse().unwrap_or(value)
+}
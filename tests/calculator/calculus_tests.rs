@@ -0,0 +1,119 @@
+//! Tests for numeric calculus.
+//!
+//! This module contains unit tests for the calculus module:
+//! - Derivatives at a point and their error estimates
+//! - Adaptive integration, including endpoint singularities
+//! - Sums and products over ranges, and their limits
+
+use rust_wasm_calc::calculator::calculus::{derivative, integrate, product, sum};
+use rust_wasm_calc::errors::{CalculatorError, CalculatorErrorType};
+
+/// Tests for derivatives.
+#[cfg(test)]
+mod derivative_tests {
+    use super::*;
+
+    #[test]
+    fn test_derivatives() {
+        let slope = derivative(|x| Ok(x.exp()), 1.0).unwrap();
+        assert!((slope.value() - std::f64::consts::E).abs() < 1e-11);
+        assert!(slope.error() < 1e-9);
+        let slope = derivative(|x| Ok(x.powi(3)), -2.0).unwrap();
+        assert!((slope.value() - 12.0).abs() < 1e-10);
+        let slope = derivative(|x| Ok(x.ln()), 1000.0).unwrap();
+        assert!((slope.value() - 0.001).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_derivative_near_domain_edge() {
+        // The first step would leave the domain of √x, so it is shrunk
+        let slope = derivative(
+            |x| if x < 0.0 { Err(CalculatorError::not_a_number()) } else { Ok(x.sqrt()) },
+            0.01,
+        )
+        .unwrap();
+        assert!((slope.value() - 5.0).abs() < 1e-8, "{:?}", slope);
+    }
+
+    #[test]
+    fn test_derivative_errors() {
+        assert_eq!(derivative(Ok, f64::NAN).unwrap_err().error_type(), CalculatorErrorType::InvalidInput);
+        let error = derivative(|_| Err(CalculatorError::not_a_number()), 1.0).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::CalculationError);
+    }
+}
+
+/// Tests for integrals.
+#[cfg(test)]
+mod integral_tests {
+    use super::*;
+
+    #[test]
+    fn test_integrals() {
+        let area = integrate(|x| Ok(x.sin()), 0.0, std::f64::consts::PI).unwrap();
+        assert!((area.value() - 2.0).abs() < 1e-12);
+        assert!(area.error() < 1e-9);
+        let area = integrate(|x| Ok((-x * x).exp()), -10.0, 10.0).unwrap();
+        assert!((area.value() - std::f64::consts::PI.sqrt()).abs() < 1e-10);
+        // Reversed limits change the sign
+        let area = integrate(|x| Ok(x * x), 3.0, 0.0).unwrap();
+        assert!((area.value() + 9.0).abs() < 1e-12);
+        assert_eq!(integrate(Ok, 2.0, 2.0).unwrap().value(), 0.0);
+    }
+
+    #[test]
+    fn test_endpoint_singularity() {
+        let area = integrate(|x| Ok(1.0 / x.sqrt()), 0.0, 1.0).unwrap();
+        assert!((area.value() - 2.0).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_integral_errors() {
+        let error = integrate(|x| Ok(1.0 / x), 0.0, 1.0).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::IterationLimit);
+        assert!(error.message().contains("500 subintervals"));
+        assert_eq!(integrate(Ok, 0.0, f64::INFINITY).unwrap_err().error_type(), CalculatorErrorType::InvalidInput);
+    }
+}
+
+/// Tests for sums and products.
+#[cfg(test)]
+mod series_tests {
+    use super::*;
+
+    #[test]
+    fn test_sums() {
+        assert_eq!(sum(Ok, 1.0, 100.0).unwrap(), 5050.0);
+        assert_eq!(sum(Ok, 5.0, 4.0).unwrap(), 0.0);
+        // Compensated summation keeps the small terms
+        let total = sum(|k| Ok(if k == 0.0 { 1e16 } else { 1.0 }), 0.0, 1000.0).unwrap();
+        assert_eq!(total, 1e16 + 1000.0);
+        let basel = sum(|k| Ok(1.0 / (k * k)), 1.0, 1_000_000.0).unwrap();
+        assert!((basel - (std::f64::consts::PI.powi(2) / 6.0 - 1e-6)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_products() {
+        assert_eq!(product(Ok, 1.0, 10.0).unwrap(), 3628800.0);
+        assert_eq!(product(Ok, 1.0, 0.0).unwrap(), 1.0);
+        assert_eq!(product(|_| Ok(1e300), 1.0, 3.0).unwrap_err().error_type(), CalculatorErrorType::Overflow);
+    }
+
+    #[test]
+    fn test_underflowing_terms_are_zero() {
+        let term = |k: f64| if k > 2.0 { Err(CalculatorError::underflow()) } else { Ok(k) };
+        assert_eq!(sum(term, 1.0, 10.0).unwrap(), 3.0);
+        assert_eq!(product(|_| Ok(1e-310), 1.0, 2.0).unwrap(), 0.0);
+        let error = sum(|_| Err(CalculatorError::overflow()), 1.0, 2.0).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::Overflow);
+    }
+
+    #[test]
+    fn test_series_limits() {
+        let error = sum(Ok, 1.0, 1e7).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::IterationLimit);
+        assert_eq!(product(Ok, 1.0, 1e12).unwrap_err().error_type(), CalculatorErrorType::IterationLimit);
+        assert_eq!(sum(Ok, 0.5, 3.0).unwrap_err().error_type(), CalculatorErrorType::InvalidInput);
+        assert_eq!(sum(Ok, 1.0, f64::INFINITY).unwrap_err().error_type(), CalculatorErrorType::InvalidInput);
+    }
+}
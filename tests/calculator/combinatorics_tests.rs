@@ -0,0 +1,69 @@
+//! Tests for exact combinatorics.
+//!
+//! This module contains unit tests for the counting functions:
+//! - Factorials, including results beyond `f64` precision
+//! - Permutations and combinations
+//! - Argument validation
+
+use rust_wasm_calc::calculator::{combinations, factorial, permutations};
+
+/// Tests for factorials.
+#[cfg(test)]
+mod factorial_tests {
+    use super::*;
+
+    #[test]
+    fn test_small_factorials() {
+        assert_eq!(factorial(0.0).unwrap(), "1");
+        assert_eq!(factorial(1.0).unwrap(), "1");
+        assert_eq!(factorial(10.0).unwrap(), "3628800");
+    }
+
+    #[test]
+    fn test_factorials_beyond_f64_precision() {
+        assert_eq!(factorial(20.0).unwrap(), "2432902008176640000");
+        assert_eq!(factorial(30.0).unwrap(), "265252859812191058636308480000000");
+        // 1000! has 2568 digits and ends in 249 zeros
+        let digits = factorial(1000.0).unwrap();
+        assert_eq!(digits.len(), 2568);
+        assert!(digits.starts_with("402387260077"));
+        assert!(digits.ends_with(&"0".repeat(249)));
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        assert!(factorial(-1.0).is_err());
+        assert!(factorial(2.5).is_err());
+        assert!(factorial(f64::NAN).is_err());
+        assert!(factorial(10_001.0).is_err());
+    }
+}
+
+/// Tests for permutations and combinations.
+#[cfg(test)]
+mod selection_tests {
+    use super::*;
+
+    #[test]
+    fn test_permutations() {
+        assert_eq!(permutations(5.0, 2.0).unwrap(), "20");
+        assert_eq!(permutations(5.0, 0.0).unwrap(), "1");
+        assert_eq!(permutations(5.0, 5.0).unwrap(), "120");
+        assert_eq!(permutations(3.0, 4.0).unwrap(), "0");
+    }
+
+    #[test]
+    fn test_combinations() {
+        assert_eq!(combinations(52.0, 5.0).unwrap(), "2598960");
+        assert_eq!(combinations(10.0, 0.0).unwrap(), "1");
+        assert_eq!(combinations(10.0, 10.0).unwrap(), "1");
+        assert_eq!(combinations(3.0, 4.0).unwrap(), "0");
+        assert_eq!(combinations(60.0, 30.0).unwrap(), "118264581564861424");
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        assert!(combinations(5.0, -1.0).is_err());
+        assert!(permutations(5.5, 1.0).is_err());
+    }
+}
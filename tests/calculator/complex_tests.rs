@@ -0,0 +1,171 @@
+//! Tests for complex numbers.
+//!
+//! This module contains unit tests for the complex number type:
+//! - Rectangular and polar entry and display
+//! - Arithmetic, conjugate, modulus and argument
+//! - Principal values of roots, powers, logarithms and trigonometric functions
+
+use std::f64::consts::{FRAC_PI_2, PI};
+use rust_wasm_calc::calculator::complex::Complex;
+use rust_wasm_calc::errors::CalculatorErrorType;
+
+/// Asserts that two complex numbers agree to within rounding.
+fn assert_close(actual: Complex, re: f64, im: f64) {
+    assert!(
+        (actual.re() - re).abs() < 1e-12 && (actual.im() - im).abs() < 1e-12,
+        "{} != {} + {}i",
+        actual,
+        re,
+        im
+    );
+}
+
+/// Tests for entry and display.
+#[cfg(test)]
+mod format_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rectangular() {
+        assert_eq!(Complex::parse("3+4i").unwrap(), Complex::new(3.0, 4.0));
+        assert_eq!(Complex::parse(" 3 − 4j ").unwrap(), Complex::new(3.0, -4.0));
+        assert_eq!(Complex::parse("-2.5i").unwrap(), Complex::new(0.0, -2.5));
+        assert_eq!(Complex::parse("i").unwrap(), Complex::I);
+        assert_eq!(Complex::parse("1e-3-i").unwrap(), Complex::new(0.001, -1.0));
+        assert_eq!(Complex::parse("7").unwrap(), Complex::real(7.0));
+    }
+
+    #[test]
+    fn test_parse_polar() {
+        assert_close(Complex::parse("5∠53.13010235415598°").unwrap(), 3.0, 4.0);
+        assert_close(Complex::parse("2∠3.141592653589793").unwrap(), -2.0, 0.0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for text in ["", "i3", "3+4", "3++4i", "5∠", "∠30°", "abc"] {
+            let error = Complex::parse(text).unwrap_err();
+            assert_eq!(error.error_type(), CalculatorErrorType::InvalidInput, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Complex::new(3.0, 4.0).to_string(), "3 + 4i");
+        assert_eq!(Complex::new(3.0, -1.0).to_string(), "3 - i");
+        assert_eq!(Complex::new(0.0, -2.0).to_string(), "-2i");
+        assert_eq!(Complex::new(-1.5, 0.0).to_string(), "-1.5");
+        assert_eq!(Complex::new(0.0, 0.0).to_string(), "0");
+        assert_eq!(Complex::new(0.1 + 0.2, 0.0).to_string(), "0.3");
+    }
+
+    #[test]
+    fn test_polar_display() {
+        assert_eq!(Complex::new(0.0, 2.0).format_polar(true), "2∠90°");
+        assert_eq!(Complex::new(-1.0, 0.0).format_polar(false), "1∠3.14159265358979");
+        assert_eq!(Complex::new(1.0, -1.0).format_polar(true), "1.4142135623731∠-45°");
+    }
+}
+
+/// Tests for arithmetic.
+#[cfg(test)]
+mod arithmetic_tests {
+    use super::*;
+
+    #[test]
+    fn test_basic_arithmetic() {
+        let a = Complex::new(1.0, 2.0);
+        let b = Complex::new(3.0, -1.0);
+        assert_eq!(a.add(&b), Complex::new(4.0, 1.0));
+        assert_eq!(a.subtract(&b), Complex::new(-2.0, 3.0));
+        assert_eq!(a.multiply(&b), Complex::new(5.0, 5.0));
+        assert_close(a.divide(&b).unwrap(), 0.1, 0.7);
+    }
+
+    #[test]
+    fn test_multiplication_overflow() {
+        // (1e200 + 1e200i)² = 2e400 i, whose real part is exactly 0
+        let z = Complex::new(1e200, 1e200);
+        let square = z.multiply(&z);
+        assert_eq!(square.re(), 0.0);
+        assert_eq!(square.checked().unwrap_err().error_type(), CalculatorErrorType::Overflow);
+        let error = z.power(&Complex::real(2.0)).unwrap().checked().unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::Overflow);
+    }
+
+    #[test]
+    fn test_division_by_zero() {
+        let error = Complex::I.divide(&Complex::real(0.0)).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::DivisionByZero);
+    }
+
+    #[test]
+    fn test_modulus_argument_and_conjugate() {
+        let z = Complex::new(3.0, 4.0);
+        assert_eq!(z.modulus(), 5.0);
+        assert_close(Complex::real(z.argument()), 0.9272952180016122, 0.0);
+        assert_eq!(z.conjugate(), Complex::new(3.0, -4.0));
+        assert_eq!(Complex::I.argument(), FRAC_PI_2);
+    }
+
+    #[test]
+    fn test_powers() {
+        assert_eq!(Complex::I.power(&Complex::real(2.0)).unwrap(), Complex::real(-1.0));
+        assert_eq!(Complex::new(1.0, 1.0).power(&Complex::real(-2.0)).unwrap(), Complex::new(0.0, -0.5));
+        // i^i = e^(-π/2)
+        assert_close(Complex::I.power(&Complex::I).unwrap(), (-FRAC_PI_2).exp(), 0.0);
+        assert_eq!(Complex::real(0.0).power(&Complex::real(0.5)).unwrap(), Complex::real(0.0));
+        assert!(Complex::real(0.0).power(&Complex::new(-1.0, 1.0)).is_err());
+    }
+
+    #[test]
+    fn test_checked_clears_rounding_noise() {
+        let euler = Complex::new(0.0, PI).exp();
+        assert_ne!(euler.im(), 0.0);
+        assert_eq!(euler.checked().unwrap(), Complex::real(-1.0));
+        let error = Complex::new(f64::INFINITY, 0.0).checked().unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::Overflow);
+    }
+}
+
+/// Tests for elementary functions.
+#[cfg(test)]
+mod function_tests {
+    use super::*;
+
+    #[test]
+    fn test_roots() {
+        assert_eq!(Complex::real(-4.0).sqrt(), Complex::new(0.0, 2.0));
+        assert_close(Complex::new(3.0, 4.0).sqrt(), 2.0, 1.0);
+        assert_close(Complex::new(3.0, -4.0).sqrt(), 2.0, -1.0);
+        assert_close(Complex::real(-8.0).cbrt(), 1.0, 3f64.sqrt());
+    }
+
+    #[test]
+    fn test_logarithms() {
+        assert_close(Complex::real(-1.0).ln(), 0.0, PI);
+        assert_close(Complex::real(-100.0).log(&Complex::real(10.0)).unwrap(), 2.0, PI / 10f64.ln());
+        assert!(Complex::real(5.0).log(&Complex::real(1.0)).is_err());
+    }
+
+    #[test]
+    fn test_trigonometric_functions() {
+        let z = Complex::new(0.5, -0.25);
+        let identity = z.sin().multiply(&z.sin()).add(&z.cos().multiply(&z.cos()));
+        assert_close(identity, 1.0, 0.0);
+        assert_close(z.asin().sin(), 0.5, -0.25);
+        assert_close(z.acos().cos(), 0.5, -0.25);
+        assert_close(z.atan().unwrap().tan().unwrap(), 0.5, -0.25);
+        assert_close(Complex::real(2.0).asin(), FRAC_PI_2, -(2.0 + 3f64.sqrt()).ln());
+        assert!(Complex::I.atan().is_err());
+    }
+
+    #[test]
+    fn test_hyperbolic_functions() {
+        let z = Complex::new(0.3, 1.2);
+        let identity = z.cosh().multiply(&z.cosh()).subtract(&z.sinh().multiply(&z.sinh()));
+        assert_close(identity, 1.0, 0.0);
+        assert_close(Complex::new(0.0, 1.0).sinh(), 0.0, 1f64.sin());
+        assert_close(z.tanh().unwrap(), z.sinh().divide(&z.cosh()).unwrap().re(), z.sinh().divide(&z.cosh()).unwrap().im());
+    }
+}
@@ -0,0 +1,140 @@
+//! Tests for date and time arithmetic.
+//!
+//! This module contains unit tests for the datetime functions:
+//! - Parsing, formatting and day numbers
+//! - Days between dates and adding durations
+//! - Weekdays and business days
+//! - h:m:s durations
+
+use rust_wasm_calc::calculator::datetime::{
+    add_business_days, add_duration, business_days, days_between, format_duration, parse_duration,
+    weekday, Date, Weekday,
+};
+use rust_wasm_calc::errors::CalculatorErrorType;
+
+/// Converts a list of date literals to a holiday list.
+fn holidays(dates: &[&str]) -> Vec<String> {
+    dates.iter().map(|date| date.to_string()).collect()
+}
+
+/// Tests for dates.
+#[cfg(test)]
+mod date_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display() {
+        assert_eq!(Date::parse("2024-02-29").unwrap().to_string(), "2024-02-29");
+        assert_eq!(Date::parse(" 0001-01-01 ").unwrap().to_string(), "0001-01-01");
+        for text in ["2023-02-29", "2024-13-01", "2024-04-31", "24-01-01", "2024/01/01", "abcd-ef-gh", ""] {
+            let error = Date::parse(text).unwrap_err();
+            assert_eq!(error.error_type(), CalculatorErrorType::InvalidInput, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_day_numbers() {
+        assert_eq!(Date::parse("1970-01-01").unwrap().day_number(), 0);
+        assert_eq!(Date::parse("2000-03-01").unwrap().day_number(), 11017);
+        assert_eq!(Date::parse("1969-12-31").unwrap().day_number(), -1);
+        for text in ["0001-01-01", "1600-02-29", "1900-03-01", "2024-12-31", "9999-12-31"] {
+            let date = Date::parse(text).unwrap();
+            assert_eq!(Date::from_day_number(date.day_number()).unwrap(), date);
+        }
+        assert!(Date::from_day_number(i32::MAX as i64).is_err());
+    }
+
+    #[test]
+    fn test_days_between() {
+        assert_eq!(days_between("2024-01-01", "2024-12-25").unwrap(), 359.0);
+        assert_eq!(days_between("2024-12-25", "2024-01-01").unwrap(), -359.0);
+        assert_eq!(days_between("2023-01-01", "2024-01-01").unwrap(), 365.0);
+        assert_eq!(days_between("2024-01-01", "2025-01-01").unwrap(), 366.0);
+    }
+
+    #[test]
+    fn test_add_duration() {
+        assert_eq!(add_duration("2024-01-31", 0, 1, 0).unwrap(), "2024-02-29");
+        assert_eq!(add_duration("2024-02-29", 1, 0, 0).unwrap(), "2025-02-28");
+        assert_eq!(add_duration("2024-03-15", 0, 0, 20).unwrap(), "2024-04-04");
+        assert_eq!(add_duration("2024-03-15", 0, -3, -15).unwrap(), "2023-11-30");
+        assert_eq!(add_duration("2024-01-01", 0, 0, -1).unwrap(), "2023-12-31");
+        assert!(add_duration("9999-12-31", 0, 0, 1).is_err());
+    }
+}
+
+/// Tests for weekdays and business days.
+#[cfg(test)]
+mod business_day_tests {
+    use super::*;
+
+    #[test]
+    fn test_weekday() {
+        assert_eq!(weekday("1970-01-01").unwrap(), Weekday::Thursday);
+        assert_eq!(weekday("2024-03-15").unwrap(), Weekday::Friday);
+        assert_eq!(weekday("2000-01-01").unwrap(), Weekday::Saturday);
+        assert_eq!(weekday("1969-12-28").unwrap(), Weekday::Sunday);
+        assert!(!Weekday::Sunday.is_business_day());
+        assert!(Weekday::Monday.is_business_day());
+    }
+
+    #[test]
+    fn test_business_days() {
+        assert_eq!(business_days("2024-03-01", "2024-03-31", &[]).unwrap(), 21.0);
+        assert_eq!(business_days("2024-03-31", "2024-03-01", &[]).unwrap(), -21.0);
+        // A weekend day counts as zero
+        assert_eq!(business_days("2024-03-16", "2024-03-16", &[]).unwrap(), 0.0);
+        assert_eq!(business_days("1969-12-26", "1970-01-02", &[]).unwrap(), 6.0);
+    }
+
+    #[test]
+    fn test_business_days_with_holidays() {
+        // Weekend, duplicate and out-of-range holidays are ignored
+        let list = holidays(&["2024-03-29", "2024-03-29", "2024-03-30", "2024-04-01"]);
+        assert_eq!(business_days("2024-03-01", "2024-03-31", &list).unwrap(), 20.0);
+        assert!(business_days("2024-03-01", "2024-03-31", &holidays(&["Easter"])).is_err());
+    }
+
+    #[test]
+    fn test_add_business_days() {
+        assert_eq!(add_business_days("2024-03-15", 1, &[]).unwrap(), "2024-03-18");
+        assert_eq!(add_business_days("2024-03-18", -1, &[]).unwrap(), "2024-03-15");
+        assert_eq!(add_business_days("2024-03-16", 0, &[]).unwrap(), "2024-03-16");
+        let list = holidays(&["2024-03-29", "2024-04-01"]);
+        assert_eq!(add_business_days("2024-03-28", 1, &list).unwrap(), "2024-04-02");
+        assert!(add_business_days("2024-03-28", 2_000_000, &[]).is_err());
+    }
+}
+
+/// Tests for durations.
+#[cfg(test)]
+mod duration_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("1:45:30").unwrap(), 6330.0);
+        assert_eq!(parse_duration("1:30").unwrap(), 5400.0);
+        assert_eq!(parse_duration("-0:00:30").unwrap(), -30.0);
+        assert_eq!(parse_duration("100:00:00.25").unwrap(), 360_000.25);
+        for text in ["1:60:00", "1:00:60", "1", "1:2:3:4", "1::00", "1.5:00:00", "a:00"] {
+            assert_eq!(parse_duration(text).unwrap_err().error_type(), CalculatorErrorType::InvalidInput, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(7575.0), "2:06:15");
+        assert_eq!(format_duration(0.0), "0:00:00");
+        assert_eq!(format_duration(-90.5), "-0:01:30.5");
+        assert_eq!(format_duration(360_000.125), "100:00:00.125");
+    }
+
+    #[test]
+    fn test_duration_arithmetic() {
+        let total = parse_duration("1:45:30").unwrap() + parse_duration("0:20:45").unwrap();
+        assert_eq!(format_duration(total), "2:06:15");
+        let difference = parse_duration("0:20:45").unwrap() - parse_duration("1:45:30").unwrap();
+        assert_eq!(format_duration(difference), "-1:24:45");
+    }
+}
@@ -0,0 +1,278 @@
+//! Tests for time-value-of-money calculations.
+//!
+//! This module contains unit tests for the financial functions:
+//! - Solving the TVM worksheet for each variable
+//! - Payment timing and compounding frequency
+//! - Amortization
+//! - NPV, IRR and interest helpers
+
+use rust_wasm_calc::calculator::financial::{
+    compound_amount, effective_rate, irr, nominal_rate, npv, simple_interest, PaymentTiming,
+    TvmVariable, TvmWorksheet,
+};
+use rust_wasm_calc::errors::CalculatorErrorType;
+
+/// Asserts that two values are equal within a tolerance.
+fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+    assert!((actual - expected).abs() < tolerance, "{} != {}", actual, expected);
+}
+
+/// Sets up a 30-year, $200,000 mortgage at 6% with monthly payments.
+fn mortgage() -> TvmWorksheet {
+    let mut tvm = TvmWorksheet::new();
+    tvm.set_n(360.0);
+    tvm.set_i_y(6.0);
+    tvm.set_p_y(12.0);
+    tvm.set_pv(200_000.0);
+    tvm.set_fv(0.0);
+    tvm
+}
+
+/// Tests for solving the worksheet.
+#[cfg(test)]
+mod solve_tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_payment() {
+        let mut tvm = mortgage();
+        assert_close(tvm.solve(TvmVariable::Payment).unwrap(), -1199.101050305, 1e-6);
+        // The solved value is stored in the worksheet
+        assert_close(tvm.pmt(), -1199.101050305, 1e-6);
+    }
+
+    #[test]
+    fn test_solve_interest_rate() {
+        let mut tvm = mortgage();
+        tvm.solve(TvmVariable::Payment).unwrap();
+        tvm.set_i_y(0.0);
+        assert_close(tvm.solve(TvmVariable::InterestRate).unwrap(), 6.0, 1e-9);
+    }
+
+    #[test]
+    fn test_solve_periods() {
+        let mut tvm = TvmWorksheet::new();
+        tvm.set_i_y(10.0);
+        tvm.set_pv(-1000.0);
+        tvm.set_fv(2000.0);
+        assert_close(tvm.solve(TvmVariable::Periods).unwrap(), 2f64.ln() / 1.1f64.ln(), 1e-9);
+    }
+
+    #[test]
+    fn test_solve_present_and_future_value() {
+        let mut tvm = TvmWorksheet::new();
+        tvm.set_n(10.0);
+        tvm.set_i_y(5.0);
+        tvm.set_pv(-1000.0);
+        assert_close(tvm.solve(TvmVariable::FutureValue).unwrap(), 1628.894626777442, 1e-9);
+
+        tvm.set_pv(0.0);
+        assert_close(tvm.solve(TvmVariable::PresentValue).unwrap(), -1000.0, 1e-9);
+    }
+
+    #[test]
+    fn test_zero_interest() {
+        let mut tvm = TvmWorksheet::new();
+        tvm.set_n(10.0);
+        tvm.set_pmt(-10.0);
+        assert_close(tvm.solve(TvmVariable::PresentValue).unwrap(), 100.0, 1e-9);
+        assert_close(tvm.solve(TvmVariable::Periods).unwrap(), 10.0, 1e-9);
+    }
+
+    #[test]
+    fn test_begin_mode() {
+        let mut tvm = TvmWorksheet::new();
+        tvm.set_n(5.0);
+        tvm.set_i_y(10.0);
+        tvm.set_pmt(-100.0);
+        tvm.set_timing(PaymentTiming::Begin);
+        assert_close(tvm.solve(TvmVariable::PresentValue).unwrap(), 416.98654463, 1e-6);
+    }
+
+    #[test]
+    fn test_compounding_differs_from_payments() {
+        // Canadian mortgages compound semi-annually with monthly payments
+        let mut tvm = mortgage();
+        tvm.set_c_y(2.0);
+        let i = 1.03f64.powf(1.0 / 6.0) - 1.0;
+        let expected = -200_000.0 * i / (1.0 - (1.0 + i).powi(-360));
+        assert_close(tvm.solve(TvmVariable::Payment).unwrap(), expected, 1e-6);
+
+        tvm.set_i_y(0.0);
+        assert_close(tvm.solve(TvmVariable::InterestRate).unwrap(), 6.0, 1e-9);
+    }
+
+    #[test]
+    fn test_set_p_y_also_sets_c_y() {
+        let mut tvm = TvmWorksheet::new();
+        tvm.set_p_y(4.0);
+        assert_eq!(tvm.c_y(), 4.0);
+    }
+
+    #[test]
+    fn test_no_solution() {
+        // Paying in and receiving nothing back has no interest rate
+        let mut tvm = TvmWorksheet::new();
+        tvm.set_n(10.0);
+        tvm.set_pv(-1000.0);
+        tvm.set_pmt(-10.0);
+        let error = tvm.solve(TvmVariable::InterestRate).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::NoSolution);
+        assert!(tvm.solve(TvmVariable::Periods).is_err());
+    }
+
+    #[test]
+    fn test_invalid_worksheet() {
+        let mut tvm = mortgage();
+        tvm.set_p_y(0.0);
+        assert_eq!(
+            tvm.solve(TvmVariable::Payment).unwrap_err().error_type(),
+            CalculatorErrorType::InvalidInput
+        );
+
+        let mut tvm = TvmWorksheet::new();
+        tvm.set_pv(100.0);
+        assert!(tvm.solve(TvmVariable::Payment).is_err());
+    }
+}
+
+/// Tests for amortization.
+#[cfg(test)]
+mod amortization_tests {
+    use super::*;
+
+    #[test]
+    fn test_first_payment() {
+        let mut tvm = mortgage();
+        let pmt = tvm.solve(TvmVariable::Payment).unwrap();
+        let first = tvm.amortize(1, 1).unwrap();
+        assert_close(first.interest(), -1000.0, 1e-9);
+        assert_close(first.principal(), pmt + 1000.0, 1e-9);
+        assert_close(first.balance(), 200_000.0 + pmt + 1000.0, 1e-6);
+    }
+
+    #[test]
+    fn test_full_term_repays_the_loan() {
+        let mut tvm = mortgage();
+        let pmt = tvm.solve(TvmVariable::Payment).unwrap();
+        let all = tvm.amortize(1, 360).unwrap();
+        assert_close(all.balance(), 0.0, 1e-6);
+        assert_close(all.principal(), -200_000.0, 1e-6);
+        assert_close(all.interest(), 360.0 * pmt + 200_000.0, 1e-6);
+    }
+
+    #[test]
+    fn test_begin_mode_first_payment_has_no_interest() {
+        let mut tvm = mortgage();
+        tvm.set_timing(PaymentTiming::Begin);
+        tvm.solve(TvmVariable::Payment).unwrap();
+        assert_eq!(tvm.amortize(1, 1).unwrap().interest(), 0.0);
+        assert_close(tvm.amortize(1, 360).unwrap().balance(), 0.0, 1e-6);
+    }
+
+    #[test]
+    fn test_schedule() {
+        let mut tvm = TvmWorksheet::new();
+        tvm.set_n(12.0);
+        tvm.set_i_y(12.0);
+        tvm.set_p_y(12.0);
+        tvm.set_pv(1000.0);
+        tvm.solve(TvmVariable::Payment).unwrap();
+
+        let schedule = tvm.schedule().unwrap();
+        assert_eq!(schedule.len(), 12);
+        assert_eq!(schedule[3].first(), 4);
+        assert_close(schedule[11].balance(), 0.0, 1e-9);
+        let total: f64 = schedule.iter().map(|row| row.principal()).sum();
+        assert_close(total, -1000.0, 1e-9);
+    }
+
+    #[test]
+    fn test_invalid_ranges() {
+        let tvm = mortgage();
+        assert!(tvm.amortize(0, 5).is_err());
+        assert!(tvm.amortize(5, 4).is_err());
+        assert!(TvmWorksheet::new().schedule().is_err());
+    }
+
+    #[test]
+    fn test_range_is_capped() {
+        let tvm = mortgage();
+        let error = tvm.amortize(1, u32::MAX).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::InvalidInput);
+        assert!(tvm.amortize(1, 100_000).is_ok());
+        assert!(tvm.amortize(100_000, 100_001).is_err());
+    }
+
+    #[test]
+    fn test_growing_balance_overflows() {
+        // At 1000% a period with no payments, the balance outgrows f64
+        let mut tvm = TvmWorksheet::new();
+        tvm.set_i_y(1000.0);
+        tvm.set_pv(1000.0);
+        let error = tvm.amortize(1, 1000).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::Overflow);
+
+        tvm.set_n(1000.0);
+        assert_eq!(tvm.schedule().unwrap_err().error_type(), CalculatorErrorType::Overflow);
+    }
+}
+
+/// Tests for cash flow analysis and interest helpers.
+#[cfg(test)]
+mod cash_flow_tests {
+    use super::*;
+
+    #[test]
+    fn test_npv() {
+        assert_close(npv(10.0, &[-1000.0, 500.0, 500.0, 500.0]).unwrap(), 243.4259954921, 1e-9);
+        assert_eq!(npv(0.0, &[-1.0, 2.0]).unwrap(), 1.0);
+        assert!(npv(10.0, &[]).is_err());
+        assert!(npv(-100.0, &[1.0]).is_err());
+    }
+
+    #[test]
+    fn test_irr() {
+        let flows = [-1000.0, 500.0, 500.0, 500.0];
+        let rate = irr(&flows).unwrap();
+        assert_close(rate, 23.3751928528, 1e-8);
+        assert_close(npv(rate, &flows).unwrap(), 0.0, 1e-8);
+    }
+
+    #[test]
+    fn test_irr_negative_rate() {
+        let rate = irr(&[-1000.0, 400.0, 400.0]).unwrap();
+        assert!(rate < 0.0);
+        assert_close(npv(rate, &[-1000.0, 400.0, 400.0]).unwrap(), 0.0, 1e-8);
+    }
+
+    #[test]
+    fn test_irr_picks_root_closest_to_zero() {
+        // Roots at 10% and 20%
+        let flows = [-1.0, 2.3, -1.32];
+        assert_close(irr(&flows).unwrap(), 10.0, 1e-8);
+    }
+
+    #[test]
+    fn test_irr_exact_grid_root_does_not_win() {
+        // Roots at 10% and exactly 900%, which lies on the search grid
+        let flows = [10.0, -111.0, 110.0];
+        assert_close(irr(&flows).unwrap(), 10.0, 1e-8);
+    }
+
+    #[test]
+    fn test_irr_without_solution() {
+        for flows in [&[100.0, 100.0][..], &[-100.0, -5.0][..], &[-1.0, 1.0, -1.0][..]] {
+            assert_eq!(irr(flows).unwrap_err().error_type(), CalculatorErrorType::NoSolution);
+        }
+    }
+
+    #[test]
+    fn test_interest_helpers() {
+        assert_eq!(simple_interest(1000.0, 5.0, 2.0), 100.0);
+        assert_close(compound_amount(1000.0, 12.0, 12.0, 1.0).unwrap(), 1126.82503013197, 1e-9);
+        assert_close(effective_rate(12.0, 12.0).unwrap(), 12.682503013197, 1e-9);
+        assert_close(nominal_rate(12.682503013197, 12.0).unwrap(), 12.0, 1e-9);
+        assert!(effective_rate(12.0, 0.0).is_err());
+    }
+}
@@ -0,0 +1,179 @@
+//! Tests for vectors and matrices.
+//!
+//! This module contains unit tests for the linear algebra module:
+//! - Construction, shape and display
+//! - Element-wise operations, dot and cross products
+//! - Matrix products, powers and transposes
+//! - Determinants, inverses, rank and linear systems
+
+use rust_wasm_calc::calculator::linalg::{Matrix, Vector, MAX_DIMENSION};
+use rust_wasm_calc::errors::CalculatorErrorType;
+
+/// Builds a matrix from rows.
+fn matrix(rows: &[&[f64]]) -> Matrix {
+    Matrix::from_rows(&rows.iter().map(|row| row.to_vec()).collect::<Vec<_>>()).unwrap()
+}
+
+/// Asserts that two lists of entries agree to within rounding.
+fn assert_close(actual: &[f64], expected: &[f64]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 1e-12, "{:?} != {:?}", actual, expected);
+    }
+}
+
+/// Tests for vectors.
+#[cfg(test)]
+mod vector_tests {
+    use super::*;
+
+    #[test]
+    fn test_element_wise() {
+        let a = Vector::new(&[1.0, 2.0, 3.0]);
+        let b = Vector::new(&[4.0, 5.0, 6.0]);
+        assert_eq!(a.add(&b).unwrap().values(), vec![5.0, 7.0, 9.0]);
+        assert_eq!(a.subtract(&b).unwrap().values(), vec![-3.0, -3.0, -3.0]);
+        assert_eq!(a.multiply_elements(&b).unwrap().values(), vec![4.0, 10.0, 18.0]);
+        assert_eq!(a.scale(2.0).unwrap().to_string(), "[2, 4, 6]");
+        assert_eq!(a.len(), 3);
+        assert!(Vector::new(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_products_and_norm() {
+        let a = Vector::new(&[2.0, 3.0, 4.0]);
+        let b = Vector::new(&[5.0, 6.0, 7.0]);
+        assert_eq!(a.dot(&b).unwrap(), 56.0);
+        assert_eq!(a.cross(&b).unwrap().values(), vec![-3.0, 6.0, -3.0]);
+        assert_eq!(a.cross(&b).unwrap().dot(&a).unwrap(), 0.0);
+        assert_eq!(Vector::new(&[3.0, 4.0]).norm(), 5.0);
+    }
+
+    #[test]
+    fn test_vector_errors() {
+        let a = Vector::new(&[1.0, 2.0]);
+        let b = Vector::new(&[1.0, 2.0, 3.0]);
+        assert_eq!(a.add(&b).unwrap_err().error_type(), CalculatorErrorType::InvalidInput);
+        assert_eq!(a.dot(&b).unwrap_err().error_type(), CalculatorErrorType::InvalidInput);
+        assert_eq!(a.cross(&a).unwrap_err().error_type(), CalculatorErrorType::InvalidInput);
+        assert_eq!(
+            Vector::new(&[f64::MAX]).scale(2.0).unwrap_err().error_type(),
+            CalculatorErrorType::Overflow
+        );
+    }
+}
+
+/// Tests for matrix construction and products.
+#[cfg(test)]
+mod matrix_tests {
+    use super::*;
+
+    #[test]
+    fn test_construction() {
+        let m = Matrix::new(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+        assert_eq!(m.shape(), vec![2, 3]);
+        assert_eq!(m.get(1, 0), Some(4.0));
+        assert_eq!(m.get(2, 0), None);
+        assert_eq!(m.to_string(), "[[1, 2, 3], [4, 5, 6]]");
+        assert_eq!(Matrix::identity(2).to_string(), "[[1, 0], [0, 1]]");
+        assert!(Matrix::new(2, 2, &[1.0, 2.0, 3.0]).is_err());
+        assert!(Matrix::new(0, 0, &[]).is_err());
+    }
+
+    #[test]
+    fn test_dimension_limit() {
+        let row = vec![0.0; MAX_DIMENSION];
+        assert!(Matrix::new(1, MAX_DIMENSION, &row).is_ok());
+        let row = vec![0.0; MAX_DIMENSION + 1];
+        for (rows, cols) in [(1, MAX_DIMENSION + 1), (MAX_DIMENSION + 1, 1)] {
+            let error = Matrix::new(rows, cols, &row).unwrap_err();
+            assert_eq!(error.error_type(), CalculatorErrorType::InvalidInput);
+        }
+        // The number of entries would overflow
+        assert!(Matrix::new(usize::MAX, 2, &[]).is_err());
+    }
+
+    #[test]
+    fn test_transpose_and_products() {
+        let a = matrix(&[&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]]);
+        let t = a.transpose();
+        assert_eq!(t.to_string(), "[[1, 4], [2, 5], [3, 6]]");
+        assert_eq!(a.multiply(&t).unwrap().to_string(), "[[14, 32], [32, 77]]");
+        assert_eq!(a.multiply_vector(&Vector::new(&[1.0, 0.0, -1.0])).unwrap().values(), vec![-2.0, -2.0]);
+        assert_eq!(a.multiply_elements(&a).unwrap().to_string(), "[[1, 4, 9], [16, 25, 36]]");
+        assert_eq!(a.multiply(&a).unwrap_err().error_type(), CalculatorErrorType::InvalidInput);
+        assert_eq!(a.add(&t).unwrap_err().error_type(), CalculatorErrorType::InvalidInput);
+    }
+
+    #[test]
+    fn test_power() {
+        let fibonacci = matrix(&[&[1.0, 1.0], &[1.0, 0.0]]);
+        assert_eq!(fibonacci.power(10).unwrap().get(0, 1), Some(55.0));
+        assert_eq!(fibonacci.power(0).unwrap(), Matrix::identity(2));
+        assert_close(&fibonacci.power(-2).unwrap().data(), &[1.0, -1.0, -1.0, 2.0]);
+        assert!(matrix(&[&[1.0, 2.0]]).power(2).is_err());
+    }
+}
+
+/// Tests for determinants, inverses, rank and linear systems.
+#[cfg(test)]
+mod solve_tests {
+    use super::*;
+
+    #[test]
+    fn test_determinant() {
+        assert_eq!(matrix(&[&[3.0]]).determinant().unwrap(), 3.0);
+        assert_eq!(matrix(&[&[0.0, 1.0], &[1.0, 0.0]]).determinant().unwrap(), -1.0);
+        let m = matrix(&[&[2.0, -3.0, 1.0], &[2.0, 0.0, -1.0], &[1.0, 4.0, 5.0]]);
+        assert!((m.determinant().unwrap() - 49.0).abs() < 1e-12);
+        assert_eq!(matrix(&[&[1.0, 2.0], &[2.0, 4.0]]).determinant().unwrap(), 0.0);
+        assert!(matrix(&[&[1.0, 2.0]]).determinant().is_err());
+    }
+
+    #[test]
+    fn test_determinant_out_of_range() {
+        let tiny = matrix(&[&[1e-200, 0.0], &[0.0, 1e-200]]);
+        assert_eq!(tiny.determinant().unwrap_err().error_type(), CalculatorErrorType::Underflow);
+        let huge = matrix(&[&[1e200, 0.0], &[0.0, 1e200]]);
+        assert_eq!(huge.determinant().unwrap_err().error_type(), CalculatorErrorType::Overflow);
+        let small = matrix(&[&[1e-150, 0.0], &[0.0, 1e-150]]);
+        assert_eq!(small.determinant().unwrap(), 1e-300);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let m = matrix(&[&[2.0, -3.0, 1.0], &[2.0, 0.0, -1.0], &[1.0, 4.0, 5.0]]);
+        let product = m.multiply(&m.inverse().unwrap()).unwrap();
+        assert_close(&product.data(), &Matrix::identity(3).data());
+
+        let singular = matrix(&[&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0], &[7.0, 8.0, 9.0]]);
+        assert_eq!(singular.inverse().unwrap_err().error_type(), CalculatorErrorType::SingularMatrix);
+    }
+
+    #[test]
+    fn test_rank() {
+        assert_eq!(Matrix::identity(3).rank(), 3);
+        assert_eq!(matrix(&[&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0], &[7.0, 8.0, 9.0]]).rank(), 2);
+        assert_eq!(matrix(&[&[1.0, 2.0], &[2.0, 4.0], &[3.0, 6.0]]).rank(), 1);
+        assert_eq!(matrix(&[&[0.0, 0.0]]).rank(), 0);
+    }
+
+    #[test]
+    fn test_solve() {
+        // 2x + y - z = 8, -3x - y + 2z = -11, -2x + y + 2z = -3
+        let a = matrix(&[&[2.0, 1.0, -1.0], &[-3.0, -1.0, 2.0], &[-2.0, 1.0, 2.0]]);
+        let x = a.solve(&Vector::new(&[8.0, -11.0, -3.0])).unwrap();
+        assert_close(&x.values(), &[2.0, 3.0, -1.0]);
+
+        // A zero leading entry needs a row swap
+        let pivoted = matrix(&[&[0.0, 1.0], &[1.0, 0.0]]);
+        assert_eq!(pivoted.solve(&Vector::new(&[5.0, 7.0])).unwrap().values(), vec![7.0, 5.0]);
+
+        assert_eq!(a.solve(&Vector::new(&[1.0])).unwrap_err().error_type(), CalculatorErrorType::InvalidInput);
+        let singular = matrix(&[&[1.0, 2.0], &[2.0, 4.0]]);
+        assert_eq!(
+            singular.solve(&Vector::new(&[1.0, 2.0])).unwrap_err().error_type(),
+            CalculatorErrorType::SingularMatrix
+        );
+    }
+}
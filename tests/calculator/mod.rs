@@ -0,0 +1,19 @@
+//! Tests for the calculator module.
+//!
+//! This module contains tests for the calculator functionality:
+//! - Arithmetic operations
+//! - Memory operations
+
+// Re-export test modules
+pub mod operations_tests;
+pub mod memory_tests;
+pub mod statistics_tests;
+pub mod regression_tests;
+pub mod combinatorics_tests;
+pub mod probability_tests;
+pub mod financial_tests;
+pub mod datetime_tests;
+pub mod complex_tests;
+pub mod linalg_tests;
+pub mod solver_tests;
+pub mod calculus_tests;
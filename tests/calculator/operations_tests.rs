@@ -5,8 +5,11 @@
 //! - Subtraction
 //! - Multiplication
 //! - Division
+//! - Checked variants reporting overflow, underflow and NaN
 
 use rust_wasm_calc::calculator::{add, subtract, multiply, divide};
+use rust_wasm_calc::calculator::{checked_add, checked_subtract, checked_multiply, checked_divide};
+use rust_wasm_calc::errors::CalculatorErrorType;
 
 /// Tests for the add function.
 #[cfg(test)]
@@ -153,7 +156,7 @@ mod divide_tests {
         let result = divide(5.0, 0.0);
         assert!(result.is_err());
         let error = result.unwrap_err();
-        assert_eq!(error.message(), "Division by zero");
+        assert_eq!(error.message(), "Division by zero is not allowed");
     }
 
     #[test]
@@ -166,4 +169,61 @@ mod divide_tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), 2.75);
     }
-} 
\ No newline at end of file
+}
+
+/// Tests for the checked operations.
+#[cfg(test)]
+mod checked_operations_tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_normal_results() {
+        assert_eq!(checked_add(2.0, 3.0).unwrap(), 5.0);
+        assert_eq!(checked_subtract(5.0, 5.0).unwrap(), 0.0);
+        assert_eq!(checked_multiply(0.0, 5.0).unwrap(), 0.0);
+        assert_eq!(checked_divide(0.0, 5.0).unwrap(), 0.0);
+        assert_eq!(checked_divide(1.0, 4.0).unwrap(), 0.25);
+    }
+
+    #[test]
+    fn test_checked_overflow() {
+        for result in [
+            checked_add(f64::MAX, f64::MAX),
+            checked_subtract(-f64::MAX, f64::MAX),
+            checked_multiply(1e200, -1e200),
+            checked_divide(1e300, 1e-300),
+        ] {
+            assert_eq!(result.unwrap_err().error_type(), CalculatorErrorType::Overflow);
+        }
+    }
+
+    #[test]
+    fn test_checked_underflow() {
+        // Flushed to zero
+        let error = checked_multiply(1e-200, 1e-200).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::Underflow);
+
+        let error = checked_divide(1e-300, 1e300).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::Underflow);
+
+        // Subnormal results
+        let error = checked_multiply(1e-300, 1e-10).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::Underflow);
+
+        let error = checked_subtract(f64::MIN_POSITIVE, f64::MIN_POSITIVE / 2.0).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::Underflow);
+    }
+
+    #[test]
+    fn test_checked_nan() {
+        let error = checked_add(f64::INFINITY, f64::NEG_INFINITY).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::CalculationError);
+        assert_eq!(error.message(), "Result is not a number");
+    }
+
+    #[test]
+    fn test_checked_divide_by_zero() {
+        let error = checked_divide(1.0, 0.0).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::DivisionByZero);
+    }
+}
@@ -0,0 +1,147 @@
+//! Tests for probability distributions.
+//!
+//! This module contains unit tests for the distributions:
+//! - Densities and cumulative probabilities against reference values
+//! - Inverse cumulative probabilities
+//! - Parameter and argument validation
+
+use rust_wasm_calc::calculator::Distribution;
+use rust_wasm_calc::errors::CalculatorErrorType;
+
+/// Asserts that two values are equal within a tolerance.
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+}
+
+/// Tests for continuous distributions.
+#[cfg(test)]
+mod continuous_tests {
+    use super::*;
+
+    #[test]
+    fn test_normal() {
+        let standard = Distribution::normal(0.0, 1.0).unwrap();
+        assert_close(standard.pdf(0.0).unwrap(), 0.3989422804014327);
+        assert_close(standard.cdf(1.96).unwrap(), 0.9750021048517795);
+        assert_close(standard.cdf(-3.0).unwrap(), 0.0013498980316301);
+        assert_close(standard.inverse_cdf(0.975).unwrap(), 1.959963984540054);
+
+        let scaled = Distribution::normal(100.0, 15.0).unwrap();
+        assert_close(scaled.cdf(115.0).unwrap(), 0.8413447460685429);
+        assert_close(scaled.inverse_cdf(0.5).unwrap(), 100.0);
+    }
+
+    #[test]
+    fn test_student_t() {
+        // One degree of freedom is the Cauchy distribution
+        let cauchy = Distribution::student_t(1.0).unwrap();
+        assert_close(cauchy.pdf(0.0).unwrap(), 1.0 / std::f64::consts::PI);
+        assert_close(cauchy.cdf(1.0).unwrap(), 0.75);
+
+        let t = Distribution::student_t(10.0).unwrap();
+        assert_close(t.inverse_cdf(0.975).unwrap(), 2.228138851986274);
+        assert_close(t.cdf(-2.228138851986274).unwrap(), 0.025);
+    }
+
+    #[test]
+    fn test_chi_square() {
+        // With two degrees of freedom the CDF is 1 - e^(-x/2)
+        let chi = Distribution::chi_square(2.0).unwrap();
+        assert_close(chi.pdf(0.0).unwrap(), 0.5);
+        assert_close(chi.cdf(2.0).unwrap(), 1.0 - (-1.0f64).exp());
+        assert_close(chi.inverse_cdf(0.95).unwrap(), 5.991464547107979);
+        assert_eq!(chi.cdf(-1.0).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_f() {
+        // With (2, 2) degrees of freedom the CDF is x / (1 + x)
+        let f = Distribution::f(2.0, 2.0).unwrap();
+        assert_close(f.pdf(1.0).unwrap(), 0.25);
+        assert_close(f.cdf(3.0).unwrap(), 0.75);
+        assert_close(f.inverse_cdf(0.5).unwrap(), 1.0);
+
+        let f = Distribution::f(5.0, 10.0).unwrap();
+        assert_close(f.inverse_cdf(0.95).unwrap(), 3.325834530413011);
+    }
+
+    #[test]
+    fn test_infinite_density_is_an_overflow() {
+        let chi = Distribution::chi_square(1.0).unwrap();
+        assert_eq!(chi.pdf(0.0).unwrap_err().error_type(), CalculatorErrorType::Overflow);
+    }
+}
+
+/// Tests for discrete distributions.
+#[cfg(test)]
+mod discrete_tests {
+    use super::*;
+
+    #[test]
+    fn test_binomial() {
+        let coin = Distribution::binomial(10.0, 0.5).unwrap();
+        assert_close(coin.pdf(5.0).unwrap(), 252.0 / 1024.0);
+        assert_close(coin.cdf(5.0).unwrap(), 638.0 / 1024.0);
+        assert_eq!(coin.pdf(2.5).unwrap(), 0.0);
+        assert_eq!(coin.pdf(11.0).unwrap(), 0.0);
+        assert_eq!(coin.cdf(10.0).unwrap(), 1.0);
+        assert_eq!(coin.inverse_cdf(0.5).unwrap(), 5.0);
+    }
+
+    #[test]
+    fn test_binomial_edge_probabilities() {
+        let never = Distribution::binomial(4.0, 0.0).unwrap();
+        assert_eq!(never.pdf(0.0).unwrap(), 1.0);
+        assert_eq!(never.cdf(0.0).unwrap(), 1.0);
+
+        let always = Distribution::binomial(4.0, 1.0).unwrap();
+        assert_eq!(always.pdf(4.0).unwrap(), 1.0);
+        assert_eq!(always.cdf(3.0).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_poisson() {
+        let poisson = Distribution::poisson(3.0).unwrap();
+        assert_close(poisson.pdf(2.0).unwrap(), 4.5 * (-3.0f64).exp());
+        assert_close(poisson.cdf(2.0).unwrap(), 8.5 * (-3.0f64).exp());
+        assert_eq!(poisson.inverse_cdf(0.5).unwrap(), 3.0);
+        assert_eq!(poisson.inverse_cdf(0.4).unwrap(), 2.0);
+    }
+}
+
+/// Tests for validation.
+#[cfg(test)]
+mod validation_tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_parameters() {
+        for result in [
+            Distribution::normal(0.0, -1.0),
+            Distribution::normal(f64::NAN, 1.0),
+            Distribution::binomial(2.5, 0.5),
+            Distribution::binomial(10.0, 1.5),
+            Distribution::poisson(0.0),
+            Distribution::student_t(0.0),
+            Distribution::chi_square(-2.0),
+            Distribution::f(1.0, 0.0),
+        ] {
+            assert_eq!(result.unwrap_err().error_type(), CalculatorErrorType::InvalidInput);
+        }
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let normal = Distribution::normal(0.0, 1.0).unwrap();
+        assert!(normal.pdf(f64::INFINITY).is_err());
+        assert!(normal.inverse_cdf(0.0).is_err());
+        assert!(normal.inverse_cdf(1.0).is_err());
+        assert!(normal.inverse_cdf(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_parameters() {
+        assert_eq!(Distribution::f(3.0, 4.0).unwrap().parameters(), vec![3.0, 4.0]);
+        assert_eq!(Distribution::poisson(2.0).unwrap().parameters(), vec![2.0]);
+    }
+}
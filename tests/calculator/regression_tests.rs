@@ -0,0 +1,205 @@
+//! Tests for two-variable statistics and regression.
+//!
+//! This module contains unit tests for the regression functions:
+//! - Covariance and correlation
+//! - Linear, exponential, logarithmic, power and polynomial fits
+//! - Prediction of y from x and x from y
+//! - Invalid data sets
+
+use rust_wasm_calc::calculator::{PairedStatistics, Regression, RegressionKind};
+
+/// Asserts that two values are equal within a small tolerance.
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+}
+
+/// Tests for covariance and correlation.
+#[cfg(test)]
+mod paired_statistics_tests {
+    use super::*;
+
+    #[test]
+    fn test_means_and_covariance() {
+        let stats = PairedStatistics::from_pairs(&[1.0, 2.0, 3.0], &[2.0, 4.0, 9.0]).unwrap();
+        assert_eq!(stats.count(), 3.0);
+        assert_eq!(stats.mean_x(), 2.0);
+        assert_eq!(stats.mean_y(), 5.0);
+        assert_close(stats.population_covariance(), 7.0 / 3.0);
+        assert_close(stats.sample_covariance().unwrap(), 3.5);
+    }
+
+    #[test]
+    fn test_perfect_correlation() {
+        let up = PairedStatistics::from_pairs(&[1.0, 2.0, 3.0], &[10.0, 20.0, 30.0]).unwrap();
+        assert_close(up.correlation().unwrap(), 1.0);
+
+        let down = PairedStatistics::from_pairs(&[1.0, 2.0, 3.0], &[3.0, 2.0, 1.0]).unwrap();
+        assert_close(down.correlation().unwrap(), -1.0);
+    }
+
+    #[test]
+    fn test_correlation_of_constant_data_is_an_error() {
+        let stats = PairedStatistics::from_pairs(&[1.0, 2.0, 3.0], &[5.0, 5.0, 5.0]).unwrap();
+        assert!(stats.correlation().is_err());
+    }
+
+    #[test]
+    fn test_weights_repeat_pairs() {
+        let weighted = PairedStatistics::from_weighted(&[1.0, 2.0], &[1.0, 3.0], &[2.0, 1.0]).unwrap();
+        let repeated = PairedStatistics::from_pairs(&[1.0, 1.0, 2.0], &[1.0, 1.0, 3.0]).unwrap();
+        assert_eq!(weighted, repeated);
+    }
+
+    #[test]
+    fn test_invalid_data() {
+        assert!(PairedStatistics::from_pairs(&[], &[]).is_err());
+        assert!(PairedStatistics::from_pairs(&[1.0, 2.0], &[1.0]).is_err());
+        assert!(PairedStatistics::from_pairs(&[1.0], &[f64::NAN]).is_err());
+        assert!(PairedStatistics::from_pairs(&[1.0], &[1.0]).unwrap().sample_covariance().is_err());
+    }
+}
+
+/// Tests for straight-line and transformed fits.
+#[cfg(test)]
+mod model_tests {
+    use super::*;
+
+    #[test]
+    fn test_linear() {
+        let fit = Regression::fit(&[1.0, 2.0, 3.0, 4.0], &[2.0, 4.0, 5.0, 7.0], RegressionKind::Linear, 1).unwrap();
+        let c = fit.coefficients();
+        assert_close(c[0], 0.5);
+        assert_close(c[1], 1.6);
+        assert_close(fit.r_squared(), 0.98461538461538);
+    }
+
+    #[test]
+    fn test_exponential() {
+        let xs = [0.0, 1.0, 2.0, 3.0];
+        let ys: Vec<f64> = xs.iter().map(|x: &f64| 2.0 * (0.5 * x).exp()).collect();
+        let fit = Regression::fit(&xs, &ys, RegressionKind::Exponential, 1).unwrap();
+        assert_close(fit.coefficients()[0], 2.0);
+        assert_close(fit.coefficients()[1], 0.5);
+        assert_close(fit.r_squared(), 1.0);
+    }
+
+    #[test]
+    fn test_logarithmic() {
+        let xs = [1.0, 2.0, 4.0, 8.0];
+        let ys: Vec<f64> = xs.iter().map(|x: &f64| 1.0 + 3.0 * x.ln()).collect();
+        let fit = Regression::fit(&xs, &ys, RegressionKind::Logarithmic, 1).unwrap();
+        assert_close(fit.coefficients()[0], 1.0);
+        assert_close(fit.coefficients()[1], 3.0);
+    }
+
+    #[test]
+    fn test_power() {
+        let xs = [1.0, 2.0, 3.0, 4.0];
+        let ys: Vec<f64> = xs.iter().map(|x: &f64| 3.0 * x.powf(1.5)).collect();
+        let fit = Regression::fit(&xs, &ys, RegressionKind::Power, 1).unwrap();
+        assert_close(fit.coefficients()[0], 3.0);
+        assert_close(fit.coefficients()[1], 1.5);
+    }
+
+    #[test]
+    fn test_log_models_need_positive_data() {
+        assert!(Regression::fit(&[0.0, 1.0], &[1.0, 2.0], RegressionKind::Logarithmic, 1).is_err());
+        assert!(Regression::fit(&[1.0, 2.0], &[-1.0, 2.0], RegressionKind::Exponential, 1).is_err());
+        assert!(Regression::fit(&[1.0, 2.0], &[1.0, 0.0], RegressionKind::Power, 1).is_err());
+    }
+
+    #[test]
+    fn test_constant_x_is_an_error() {
+        assert!(Regression::fit(&[2.0, 2.0], &[1.0, 3.0], RegressionKind::Linear, 1).is_err());
+    }
+}
+
+/// Tests for polynomial fits.
+#[cfg(test)]
+mod polynomial_tests {
+    use super::*;
+
+    #[test]
+    fn test_quadratic_exact() {
+        let xs = [-2.0, -1.0, 0.0, 1.0, 2.0];
+        let ys: Vec<f64> = xs.iter().map(|x| 1.0 - 2.0 * x + 0.5 * x * x).collect();
+        let fit = Regression::fit(&xs, &ys, RegressionKind::Polynomial, 2).unwrap();
+        let c = fit.coefficients();
+        assert_eq!(c.len(), 3);
+        assert_close(c[0], 1.0);
+        assert_close(c[1], -2.0);
+        assert_close(c[2], 0.5);
+        assert_close(fit.r_squared(), 1.0);
+        assert_close(fit.predict(4.0).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_quadratic_at_small_scale() {
+        let xs = [1e-3, 2e-3, 3e-3];
+        let ys: Vec<f64> = xs.iter().map(|x| 1.0 + 1e3 * x + 1e6 * x * x).collect();
+        let fit = Regression::fit(&xs, &ys, RegressionKind::Polynomial, 2).unwrap();
+        let c = fit.coefficients();
+        assert!((c[0] - 1.0).abs() < 1e-6, "{}", c[0]);
+        assert!((c[1] / 1e3 - 1.0).abs() < 1e-6, "{}", c[1]);
+        assert!((c[2] / 1e6 - 1.0).abs() < 1e-6, "{}", c[2]);
+    }
+
+    #[test]
+    fn test_degree_one_matches_linear() {
+        let xs = [1.0, 2.0, 3.0, 4.0];
+        let ys = [2.0, 4.0, 5.0, 7.0];
+        let poly = Regression::fit(&xs, &ys, RegressionKind::Polynomial, 1).unwrap();
+        let line = Regression::fit(&xs, &ys, RegressionKind::Linear, 1).unwrap();
+        assert_close(poly.coefficients()[0], line.coefficients()[0]);
+        assert_close(poly.coefficients()[1], line.coefficients()[1]);
+        assert_close(poly.r_squared(), line.r_squared());
+    }
+
+    #[test]
+    fn test_degree_limits() {
+        let xs = [1.0, 2.0, 3.0];
+        let ys = [1.0, 4.0, 9.0];
+        assert!(Regression::fit(&xs, &ys, RegressionKind::Polynomial, 0).is_err());
+        assert!(Regression::fit(&xs, &ys, RegressionKind::Polynomial, 11).is_err());
+        // Three distinct x values cannot determine a cubic
+        assert!(Regression::fit(&xs, &ys, RegressionKind::Polynomial, 3).is_err());
+    }
+}
+
+/// Tests for prediction.
+#[cfg(test)]
+mod prediction_tests {
+    use super::*;
+
+    #[test]
+    fn test_predict_round_trips() {
+        let xs = [1.0, 2.0, 3.0, 4.0];
+        let ys = [3.0, 5.5, 9.0, 14.0];
+        for kind in [
+            RegressionKind::Linear,
+            RegressionKind::Exponential,
+            RegressionKind::Logarithmic,
+            RegressionKind::Power,
+        ] {
+            let fit = Regression::fit(&xs, &ys, kind, 1).unwrap();
+            let y = fit.predict(2.5).unwrap();
+            assert_close(fit.predict_x(y).unwrap(), 2.5);
+        }
+    }
+
+    #[test]
+    fn test_predict_outside_domain() {
+        let fit = Regression::fit(&[1.0, 2.0], &[1.0, 2.0], RegressionKind::Logarithmic, 1).unwrap();
+        assert!(fit.predict(-1.0).is_err());
+    }
+
+    #[test]
+    fn test_predict_x_needs_an_invertible_model() {
+        let flat = Regression::fit(&[1.0, 2.0], &[3.0, 3.0], RegressionKind::Linear, 1).unwrap();
+        assert!(flat.predict_x(3.0).is_err());
+
+        let xs = [-1.0, 0.0, 1.0];
+        let quadratic = Regression::fit(&xs, &[1.0, 0.0, 1.0], RegressionKind::Polynomial, 2).unwrap();
+        assert!(quadratic.predict_x(1.0).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+//! Tests for the solver.
+//!
+//! This module contains unit tests for the solver module:
+//! - Closed-form roots of polynomials up to degree 4
+//! - Iterative roots of higher-degree polynomials
+//! - Roots of general functions near a guess, and failure diagnostics
+
+use rust_wasm_calc::calculator::complex::Complex;
+use rust_wasm_calc::calculator::solver::{find_root, polynomial_roots};
+use rust_wasm_calc::errors::{CalculatorError, CalculatorErrorType};
+
+/// Asserts that roots agree with the expected `(re, im)` pairs, in order.
+fn assert_roots(actual: &[Complex], expected: &[(f64, f64)]) {
+    assert_eq!(actual.len(), expected.len(), "{:?}", actual);
+    for (root, (re, im)) in actual.iter().zip(expected) {
+        assert!(
+            (root.re() - re).abs() < 1e-9 && (root.im() - im).abs() < 1e-9,
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+}
+
+/// Tests for polynomial roots.
+#[cfg(test)]
+mod polynomial_tests {
+    use super::*;
+
+    #[test]
+    fn test_linear_and_quadratic() {
+        assert_roots(&polynomial_roots(&[2.0, -3.0]).unwrap(), &[(1.5, 0.0)]);
+        assert_roots(&polynomial_roots(&[1.0, -3.0, 2.0]).unwrap(), &[(1.0, 0.0), (2.0, 0.0)]);
+        assert_roots(&polynomial_roots(&[1.0, 2.0, 5.0]).unwrap(), &[(-1.0, -2.0), (-1.0, 2.0)]);
+        // Cancellation-free for widely separated roots
+        let roots = polynomial_roots(&[1.0, -1e8, 1.0]).unwrap();
+        assert!((roots[0].re() - 1e-8).abs() < 1e-20);
+    }
+
+    #[test]
+    fn test_cubic() {
+        // (x - 1)(x - 2)(x - 3)
+        assert_roots(&polynomial_roots(&[1.0, -6.0, 11.0, -6.0]).unwrap(), &[(1.0, 0.0), (2.0, 0.0), (3.0, 0.0)]);
+        // x³ - 1 has one real root and a complex pair
+        let half_root_3 = 3f64.sqrt() / 2.0;
+        assert_roots(
+            &polynomial_roots(&[1.0, 0.0, 0.0, -1.0]).unwrap(),
+            &[(-0.5, -half_root_3), (-0.5, half_root_3), (1.0, 0.0)],
+        );
+        // A triple root
+        assert_roots(&polynomial_roots(&[1.0, -3.0, 3.0, -1.0]).unwrap(), &[(1.0, 0.0); 3]);
+    }
+
+    #[test]
+    fn test_quartic() {
+        // (x - 1)(x + 2)(x - 3)(x + 4)
+        assert_roots(
+            &polynomial_roots(&[1.0, 2.0, -13.0, -14.0, 24.0]).unwrap(),
+            &[(-4.0, 0.0), (-2.0, 0.0), (1.0, 0.0), (3.0, 0.0)],
+        );
+        // x⁴ + 1 has roots at odd multiples of 45°
+        let h = std::f64::consts::FRAC_1_SQRT_2;
+        assert_roots(&polynomial_roots(&[1.0, 0.0, 0.0, 0.0, 1.0]).unwrap(), &[(-h, -h), (-h, h), (h, -h), (h, h)]);
+        // x⁴ - 5x² + 4 is biquadratic
+        assert_roots(
+            &polynomial_roots(&[1.0, 0.0, -5.0, 0.0, 4.0]).unwrap(),
+            &[(-2.0, 0.0), (-1.0, 0.0), (1.0, 0.0), (2.0, 0.0)],
+        );
+    }
+
+    #[test]
+    fn test_higher_degree() {
+        // (x - 1)(x - 2)(x - 3)(x - 4)(x - 5)
+        let roots = polynomial_roots(&[1.0, -15.0, 85.0, -225.0, 274.0, -120.0]).unwrap();
+        assert_roots(&roots, &[(1.0, 0.0), (2.0, 0.0), (3.0, 0.0), (4.0, 0.0), (5.0, 0.0)]);
+        // x⁶ - 1 has the sixth roots of unity
+        let roots = polynomial_roots(&[1.0, 0.0, 0.0, 0.0, 0.0, 0.0, -1.0]).unwrap();
+        assert_eq!(roots.len(), 6);
+        assert!(roots.iter().all(|root| (root.modulus() - 1.0).abs() < 1e-12));
+        assert_eq!(roots.iter().filter(|root| root.is_real()).count(), 2);
+    }
+
+    #[test]
+    fn test_zero_coefficients() {
+        // Leading zeros are ignored and trailing zeros are roots at 0
+        assert_roots(&polynomial_roots(&[0.0, 1.0, -1.0, 0.0]).unwrap(), &[(0.0, 0.0), (1.0, 0.0)]);
+        assert!(polynomial_roots(&[5.0]).unwrap().is_empty());
+        assert_eq!(polynomial_roots(&[0.0, 0.0]).unwrap_err().error_type(), CalculatorErrorType::InvalidInput);
+        assert_eq!(polynomial_roots(&[]).unwrap_err().error_type(), CalculatorErrorType::InvalidInput);
+        assert_eq!(polynomial_roots(&[1.0, f64::NAN]).unwrap_err().error_type(), CalculatorErrorType::InvalidInput);
+    }
+}
+
+/// Tests for roots of general functions.
+#[cfg(test)]
+mod find_root_tests {
+    use super::*;
+
+    #[test]
+    fn test_bracketed_roots() {
+        let root = find_root(|x| Ok(x.cos() - x), 0.0).unwrap();
+        assert!((root - 0.7390851332151607).abs() < 1e-14);
+        let root = find_root(|x| Ok(x.exp() - 100.0), 0.0).unwrap();
+        assert!((root - 100f64.ln()).abs() < 1e-12);
+        assert_eq!(find_root(|x| Ok(x - 3.0), 3.0).unwrap(), 3.0);
+    }
+
+    #[test]
+    fn test_touching_root() {
+        // (x - 1)² does not change sign, so Newton's method is used
+        let root = find_root(|x| Ok((x - 1.0) * (x - 1.0)), 3.0).unwrap();
+        assert!((root - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_domain_errors_are_skipped() {
+        let root = find_root(
+            |x| if x < 0.0 { Err(CalculatorError::not_a_number()) } else { Ok(x.sqrt() - 2.0) },
+            1.0,
+        )
+        .unwrap();
+        assert!((root - 4.0).abs() < 1e-12);
+        assert!(find_root(|x| if x < 0.0 { Err(CalculatorError::not_a_number()) } else { Ok(x) }, -1.0).is_err());
+    }
+
+    #[test]
+    fn test_failures() {
+        let error = find_root(|x| Ok(x * x + 1.0), 0.0).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::NoSolution);
+        assert!(error.message().contains("no sign change"));
+
+        // 1/x changes sign at its pole without crossing zero
+        let error = find_root(|x| Ok(1.0 / x), 0.5).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::NoSolution);
+
+        assert_eq!(find_root(Ok, f64::NAN).unwrap_err().error_type(), CalculatorErrorType::InvalidInput);
+    }
+
+    #[test]
+    fn test_underflow_is_not_a_root() {
+        // exp(x) underflows to 0 far below 0, but never reaches it
+        for guess in [0.0, -800.0] {
+            let error = find_root(|x| Ok(x.exp()), guess).unwrap_err();
+            assert_eq!(error.error_type(), CalculatorErrorType::NoSolution);
+        }
+        assert_eq!(find_root(|x| Ok(x.powi(3)), 0.0).unwrap(), 0.0);
+    }
+}
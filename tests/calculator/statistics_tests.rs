@@ -0,0 +1,144 @@
+//! Tests for one-variable statistics.
+//!
+//! This module contains unit tests for the statistics functions:
+//! - Central tendency (mean, median, mode)
+//! - Spread (variance and standard deviation)
+//! - Order statistics (min, max, quartiles, percentiles)
+//! - Frequencies and invalid data sets
+
+use rust_wasm_calc::calculator::{summarize, summarize_weighted};
+use rust_wasm_calc::errors::CalculatorErrorType;
+
+/// Asserts that two values are equal within a small tolerance.
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+}
+
+/// Tests for central tendency.
+#[cfg(test)]
+mod central_tendency_tests {
+    use super::*;
+
+    #[test]
+    fn test_count_sum_mean() {
+        let stats = summarize(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+        assert_eq!(stats.count(), 4.0);
+        assert_eq!(stats.sum(), 10.0);
+        assert_eq!(stats.mean(), 2.5);
+    }
+
+    #[test]
+    fn test_median_odd_and_even() {
+        assert_eq!(summarize(&[3.0, 1.0, 2.0]).unwrap().median(), 2.0);
+        assert_eq!(summarize(&[4.0, 1.0, 3.0, 2.0]).unwrap().median(), 2.5);
+    }
+
+    #[test]
+    fn test_modes() {
+        assert_eq!(summarize(&[1.0, 2.0, 2.0, 3.0]).unwrap().modes(), vec![2.0]);
+        assert_eq!(summarize(&[1.0, 1.0, 2.0, 3.0, 3.0]).unwrap().modes(), vec![1.0, 3.0]);
+        // All values equally frequent: no mode
+        assert!(summarize(&[1.0, 2.0, 3.0]).unwrap().modes().is_empty());
+        assert_eq!(summarize(&[5.0]).unwrap().modes(), vec![5.0]);
+    }
+}
+
+/// Tests for variance and standard deviation.
+#[cfg(test)]
+mod spread_tests {
+    use super::*;
+
+    #[test]
+    fn test_population_and_sample() {
+        let stats = summarize(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]).unwrap();
+        assert_eq!(stats.population_variance(), 4.0);
+        assert_eq!(stats.population_std_dev(), 2.0);
+        assert_close(stats.sample_variance().unwrap(), 32.0 / 7.0);
+        assert_close(stats.sample_std_dev().unwrap(), (32.0f64 / 7.0).sqrt());
+    }
+
+    #[test]
+    fn test_sample_variance_needs_two_points() {
+        let stats = summarize(&[3.0]).unwrap();
+        assert_eq!(stats.population_variance(), 0.0);
+        assert!(stats.sample_variance().is_err());
+    }
+
+    #[test]
+    fn test_large_offset_is_stable() {
+        let stats = summarize(&[1e9 + 4.0, 1e9 + 7.0, 1e9 + 13.0, 1e9 + 16.0]).unwrap();
+        assert_close(stats.sample_variance().unwrap(), 30.0);
+    }
+}
+
+/// Tests for order statistics.
+#[cfg(test)]
+mod order_statistics_tests {
+    use super::*;
+
+    #[test]
+    fn test_min_max() {
+        let stats = summarize(&[3.0, -1.0, 8.5]).unwrap();
+        assert_eq!(stats.min(), -1.0);
+        assert_eq!(stats.max(), 8.5);
+    }
+
+    #[test]
+    fn test_quartiles() {
+        let stats = summarize(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]).unwrap();
+        assert_eq!(stats.q1(), 2.75);
+        assert_eq!(stats.median(), 4.5);
+        assert_eq!(stats.q3(), 6.25);
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let stats = summarize(&[10.0, 20.0, 30.0, 40.0, 50.0]).unwrap();
+        assert_eq!(stats.percentile(0.0).unwrap(), 10.0);
+        assert_eq!(stats.percentile(100.0).unwrap(), 50.0);
+        assert_eq!(stats.percentile(90.0).unwrap(), 46.0);
+        assert!(stats.percentile(-1.0).is_err());
+        assert!(stats.percentile(101.0).is_err());
+    }
+}
+
+/// Tests for frequencies and invalid data.
+#[cfg(test)]
+mod frequency_tests {
+    use super::*;
+
+    #[test]
+    fn test_frequencies_match_repeated_values() {
+        let weighted = summarize_weighted(&[1.0, 2.0, 3.0], &[2, 3, 1]).unwrap();
+        let repeated = summarize(&[1.0, 1.0, 2.0, 2.0, 2.0, 3.0]).unwrap();
+        assert_eq!(weighted, repeated);
+        assert_eq!(weighted.median(), 2.0);
+        assert_eq!(weighted.modes(), vec![2.0]);
+    }
+
+    #[test]
+    fn test_invalid_data_sets() {
+        assert!(summarize(&[]).is_err());
+        assert!(summarize(&[1.0, f64::NAN]).is_err());
+        assert!(summarize_weighted(&[1.0, 2.0], &[1]).is_err());
+        assert!(summarize_weighted(&[1.0], &[0]).is_err());
+    }
+
+    #[test]
+    fn test_merged_frequency_overflow() {
+        let error = summarize_weighted(&[1.0, 1.0], &[u32::MAX, 1]).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::InvalidInput);
+        assert!(error.message().contains("frequency is too large"));
+        // Separate values may have the largest frequency each
+        assert_eq!(summarize_weighted(&[1.0, 2.0], &[u32::MAX, u32::MAX]).unwrap().count(), 2.0 * u32::MAX as f64);
+    }
+
+    #[test]
+    fn test_sum_overflow() {
+        let error = summarize(&[1e308, 1e308]).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::Overflow);
+        let error = summarize(&[-1e308, 1e308]).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::Overflow);
+        assert_eq!(summarize(&[1e307, 1e307]).unwrap().mean(), 1e307);
+    }
+}
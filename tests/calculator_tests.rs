@@ -0,0 +1,3 @@
+//! Entry point for the calculator module tests in `tests/calculator/`.
+
+mod calculator;
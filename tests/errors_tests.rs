@@ -0,0 +1,203 @@
+//! Tests for calculator error handling.
+//!
+//! This module contains tests for the error module:
+//! - Stable numeric and string error codes
+//! - The error catalogue
+//! - Round-tripping errors through coded strings
+//! - Legacy string conversion
+
+use rust_wasm_calc::errors::{
+    error_catalogue, error_to_js_string_by_type, error_type_from_code,
+    string_to_calculator_error, CalculatorError, CalculatorErrorType, ErrorSeverity,
+};
+
+/// Tests for error codes.
+#[cfg(test)]
+mod error_code_tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_are_stable() {
+        assert_eq!(CalculatorErrorType::DivisionByZero.code(), 1);
+        assert_eq!(CalculatorErrorType::InvalidInput.code(), 2);
+        assert_eq!(CalculatorErrorType::Overflow.code(), 3);
+        assert_eq!(CalculatorErrorType::Underflow.code(), 4);
+        assert_eq!(CalculatorErrorType::CalculationError.code(), 5);
+        assert_eq!(CalculatorErrorType::NoSolution.code(), 6);
+        assert_eq!(CalculatorErrorType::IncompatibleUnits.code(), 7);
+        assert_eq!(CalculatorErrorType::SingularMatrix.code(), 8);
+        assert_eq!(CalculatorErrorType::IterationLimit.code(), 9);
+    }
+
+    #[test]
+    fn test_javascript_values_are_unchanged() {
+        assert_eq!(CalculatorErrorType::DivisionByZero as u32, 0);
+        assert_eq!(CalculatorErrorType::InvalidInput as u32, 1);
+        assert_eq!(CalculatorErrorType::Overflow as u32, 2);
+        assert_eq!(CalculatorErrorType::Underflow as u32, 3);
+        assert_eq!(CalculatorErrorType::CalculationError as u32, 4);
+    }
+
+    #[test]
+    fn test_codes_and_names_are_unique() {
+        for (i, a) in CalculatorErrorType::ALL.iter().enumerate() {
+            for b in &CalculatorErrorType::ALL[i + 1..] {
+                assert_ne!(a.code(), b.code());
+                assert_ne!(a.name(), b.name());
+            }
+        }
+    }
+
+    #[test]
+    fn test_lookup_by_code_and_name() {
+        for error_type in CalculatorErrorType::ALL {
+            assert_eq!(CalculatorErrorType::from_code(error_type.code()), Some(error_type.clone()));
+            assert_eq!(CalculatorErrorType::from_name(error_type.name()), Some(error_type.clone()));
+        }
+        assert_eq!(CalculatorErrorType::from_code(0), None);
+        assert_eq!(CalculatorErrorType::from_name("unknown"), None);
+        assert_eq!(error_type_from_code(3), Some(CalculatorErrorType::Overflow));
+    }
+
+    #[test]
+    fn test_error_code() {
+        assert_eq!(CalculatorError::overflow().code(), 3);
+        assert_eq!(CalculatorError::invalid_input(None).code(), 2);
+        assert_eq!(CalculatorError::no_solution(None).code(), 6);
+        assert_eq!(CalculatorError::incompatible_units(None).code(), 7);
+        assert_eq!(CalculatorError::singular_matrix(None).code(), 8);
+        assert_eq!(CalculatorError::iteration_limit(None).code(), 9);
+    }
+}
+
+/// Tests for the error catalogue.
+#[cfg(test)]
+mod catalogue_tests {
+    use super::*;
+
+    #[test]
+    fn test_catalogue_lists_every_type() {
+        let catalogue = error_catalogue();
+        assert_eq!(catalogue.len(), CalculatorErrorType::ALL.len());
+
+        for (entry, error_type) in catalogue.iter().zip(CalculatorErrorType::ALL) {
+            assert_eq!(entry.error_type(), error_type);
+            assert_eq!(entry.code(), error_type.code());
+            assert_eq!(entry.name(), error_type.name());
+            assert!(!entry.default_message().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_severities() {
+        assert_eq!(CalculatorErrorType::InvalidInput.severity(), ErrorSeverity::Warning);
+        assert_eq!(CalculatorErrorType::DivisionByZero.severity(), ErrorSeverity::Error);
+        assert_eq!(CalculatorErrorType::Overflow.severity(), ErrorSeverity::Error);
+    }
+
+    #[test]
+    fn test_default_messages_match_constructors() {
+        assert_eq!(
+            CalculatorError::division_by_zero().message(),
+            CalculatorErrorType::DivisionByZero.default_message()
+        );
+        assert_eq!(
+            CalculatorError::overflow().message(),
+            CalculatorErrorType::Overflow.default_message()
+        );
+        assert_eq!(
+            CalculatorError::from_type(CalculatorErrorType::Underflow).message(),
+            "Result is too small to represent"
+        );
+    }
+}
+
+/// Tests for converting errors to and from strings.
+#[cfg(test)]
+mod conversion_tests {
+    use super::*;
+
+    #[test]
+    fn test_code_string_round_trip() {
+        let errors = [
+            CalculatorError::division_by_zero(),
+            CalculatorError::invalid_input(Some("1: 2")),
+            CalculatorError::overflow(),
+            CalculatorError::underflow(),
+            CalculatorError::not_a_number(),
+        ];
+
+        for error in errors {
+            let parsed = CalculatorError::from_code_string(&error.to_code_string()).unwrap();
+            assert_eq!(parsed.error_type(), error.error_type());
+            assert_eq!(parsed.message(), error.message());
+        }
+    }
+
+    #[test]
+    fn test_code_string_format() {
+        assert_eq!(
+            CalculatorError::overflow().to_code_string(),
+            "E003 overflow: Result is too large to represent"
+        );
+    }
+
+    #[test]
+    fn test_from_code_string_rejects_other_text() {
+        assert!(CalculatorError::from_code_string("Invalid input").is_none());
+        assert!(CalculatorError::from_code_string("E999 unknown: text").is_none());
+    }
+
+    #[test]
+    fn test_string_to_calculator_error() {
+        let coded = CalculatorError::underflow().to_code_string();
+        assert_eq!(string_to_calculator_error(&coded).error_type(), CalculatorErrorType::Underflow);
+
+        // Legacy message strings
+        let error = string_to_calculator_error("Invalid input: abc");
+        assert_eq!(error.error_type(), CalculatorErrorType::InvalidInput);
+        assert_eq!(error.message(), "Invalid input: abc");
+
+        let error = string_to_calculator_error("Invalid input");
+        assert_eq!(error.error_type(), CalculatorErrorType::InvalidInput);
+
+        let error = string_to_calculator_error("Division by zero is not allowed");
+        assert_eq!(error.error_type(), CalculatorErrorType::DivisionByZero);
+
+        let error = string_to_calculator_error("Something else");
+        assert_eq!(error.error_type(), CalculatorErrorType::CalculationError);
+        assert_eq!(error.message(), "Something else");
+    }
+
+    #[test]
+    fn test_error_to_js_string_by_type() {
+        assert_eq!(
+            error_to_js_string_by_type(CalculatorErrorType::InvalidInput, "abc"),
+            "Invalid input: abc"
+        );
+        assert_eq!(
+            error_to_js_string_by_type(CalculatorErrorType::Overflow, "ignored"),
+            "Result is too large to represent"
+        );
+        assert_eq!(
+            error_to_js_string_by_type(CalculatorErrorType::CalculationError, "bad"),
+            "Calculation error: bad"
+        );
+        assert_eq!(
+            error_to_js_string_by_type(CalculatorErrorType::NoSolution, "no sign change"),
+            "No solution exists: no sign change"
+        );
+        assert_eq!(
+            error_to_js_string_by_type(CalculatorErrorType::IncompatibleUnits, "km to kg"),
+            "Units are not compatible: km to kg"
+        );
+        assert_eq!(
+            error_to_js_string_by_type(CalculatorErrorType::SingularMatrix, "determinant is 0"),
+            "Matrix is singular: determinant is 0"
+        );
+        assert_eq!(
+            error_to_js_string_by_type(CalculatorErrorType::IterationLimit, "too many terms"),
+            "Iteration limit reached: too many terms"
+        );
+    }
+}
@@ -0,0 +1,829 @@
+//! Tests for expression evaluation.
+//!
+//! This module contains tests for the expression module:
+//! - Operator precedence and associativity
+//! - Variables, constants and built-in functions
+//! - Combinatorics and probability functions in expressions
+//! - Dates and h:m:s durations in expressions
+//! - Quantities with units and conversions
+//! - Complex mode
+//! - Matrix literals
+//! - Equations solved with `solve`
+//! - Derivatives, integrals, sums and products
+//! - Printing, symbolic derivatives and simplification
+//! - Step-by-step evaluation traces
+//! - Compiled expressions
+//! - Syntax and evaluation errors
+
+use rust_wasm_calc::calculator::datetime::format_duration;
+use rust_wasm_calc::errors::CalculatorErrorType;
+use rust_wasm_calc::expression::{
+    derivative_steps, differentiate, evaluate, evaluate_complex, evaluate_matrix, evaluate_quantity, parse, simplify,
+    trace, CompiledExpression, Context,
+};
+use rust_wasm_calc::units::{install_rates, RateTable};
+
+/// Asserts that two values are equal within a small tolerance.
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+}
+
+/// Evaluates an expression and returns the error type it fails with.
+fn error_type_of(input: &str) -> CalculatorErrorType {
+    evaluate(input).unwrap_err().error_type()
+}
+
+/// Tests for operators.
+#[cfg(test)]
+mod operator_tests {
+    use super::*;
+
+    #[test]
+    fn test_precedence() {
+        assert_eq!(evaluate("1 + 2 * 3").unwrap(), 7.0);
+        assert_eq!(evaluate("(1 + 2) * 3").unwrap(), 9.0);
+        assert_eq!(evaluate("10 - 4 - 3").unwrap(), 3.0);
+        assert_eq!(evaluate("24 / 4 / 2").unwrap(), 3.0);
+    }
+
+    #[test]
+    fn test_power() {
+        assert_eq!(evaluate("2^3^2").unwrap(), 512.0);
+        assert_eq!(evaluate("-2^2").unwrap(), -4.0);
+        assert_eq!(evaluate("2^-1").unwrap(), 0.5);
+    }
+
+    #[test]
+    fn test_factorial() {
+        assert_eq!(evaluate("5!").unwrap(), 120.0);
+        assert_eq!(evaluate("3!!").unwrap(), 720.0);
+        assert_eq!(evaluate("2 * 3!").unwrap(), 12.0);
+    }
+
+    #[test]
+    fn test_unicode_operators() {
+        assert_eq!(evaluate("6 × 7").unwrap(), 42.0);
+        assert_eq!(evaluate("8 ÷ 2 − 1").unwrap(), 3.0);
+    }
+
+    #[test]
+    fn test_scientific_literals() {
+        assert_eq!(evaluate("1.5e3").unwrap(), 1500.0);
+        assert_eq!(evaluate("2e-1").unwrap(), 0.2);
+        assert_close(evaluate("2 * e").unwrap(), 2.0 * std::f64::consts::E);
+    }
+}
+
+/// Tests for names and functions.
+#[cfg(test)]
+mod function_tests {
+    use super::*;
+
+    #[test]
+    fn test_constants() {
+        assert_close(evaluate("pi").unwrap(), std::f64::consts::PI);
+        assert_close(evaluate("2 * π").unwrap(), 2.0 * std::f64::consts::PI);
+    }
+
+    #[test]
+    fn test_variables() {
+        let mut context = Context::new();
+        context.set("x", 2.0);
+        context.set("e", 10.0);
+        let expr = parse("3 * x + e").unwrap();
+        // Variables shadow constants
+        assert_eq!(expr.evaluate(&context).unwrap(), 16.0);
+    }
+
+    #[test]
+    fn test_elementary_functions() {
+        assert_eq!(evaluate("sqrt(16)").unwrap(), 4.0);
+        assert_close(evaluate("log(1000)").unwrap(), 3.0);
+        assert_close(evaluate("log(8, 2)").unwrap(), 3.0);
+        assert_close(evaluate("sin(pi / 2)").unwrap(), 1.0);
+        assert_eq!(evaluate("max(1, 5, 3)").unwrap(), 5.0);
+    }
+
+    #[test]
+    fn test_combinatorics() {
+        assert_eq!(evaluate("nCr(52, 5)").unwrap(), 2598960.0);
+        assert_eq!(evaluate("nPr(5, 2)").unwrap(), 20.0);
+        assert_eq!(evaluate("fact(6)").unwrap(), 720.0);
+    }
+
+    #[test]
+    fn test_distributions() {
+        assert_close(evaluate("normal_cdf(1.96, 0, 1)").unwrap(), 0.9750021048517795);
+        assert_close(evaluate("normal_inv(0.5, 100, 15)").unwrap(), 100.0);
+        assert_close(evaluate("binomial_pdf(5, 10, 0.5)").unwrap(), 252.0 / 1024.0);
+        assert_eq!(evaluate("poisson_inv(0.5, 3)").unwrap(), 3.0);
+        assert_close(evaluate("t_inv(0.975, 10)").unwrap(), 2.228138851986274);
+        assert_close(evaluate("chi2_cdf(2, 2)").unwrap(), 1.0 - (-1.0f64).exp());
+        assert_close(evaluate("f_cdf(3, 2, 2)").unwrap(), 0.75);
+    }
+}
+
+/// Tests for dates and durations.
+#[cfg(test)]
+mod datetime_tests {
+    use super::*;
+
+    #[test]
+    fn test_duration_literals() {
+        let total = evaluate("1:45:30 + 0:20:45").unwrap();
+        assert_eq!(total, 7575.0);
+        assert_eq!(format_duration(total), "2:06:15");
+        assert_eq!(evaluate("1:30 * 2").unwrap(), 10800.0);
+        assert_eq!(evaluate("0:00:01.5").unwrap(), 1.5);
+        assert_eq!(error_type_of("1:75:00"), CalculatorErrorType::InvalidInput);
+    }
+
+    #[test]
+    fn test_dates() {
+        assert_eq!(evaluate("date(1970, 1, 1)").unwrap(), 0.0);
+        assert_eq!(evaluate("date(2024, 12, 25) - date(2024, 1, 1)").unwrap(), 359.0);
+        // 2024-03-15 was a Friday
+        assert_eq!(evaluate("weekday(date(2024, 3, 15))").unwrap(), 5.0);
+        assert_eq!(evaluate("add_months(date(2024, 1, 31), 1)").unwrap(), evaluate("date(2024, 2, 29)").unwrap());
+        assert_eq!(error_type_of("date(2023, 2, 29)"), CalculatorErrorType::InvalidInput);
+    }
+
+    #[test]
+    fn test_business_days() {
+        assert_eq!(evaluate("workdays(date(2024, 3, 1), date(2024, 3, 31))").unwrap(), 21.0);
+        assert_eq!(evaluate("workdays(date(2024, 3, 1), date(2024, 3, 31), date(2024, 3, 29))").unwrap(), 20.0);
+        assert_eq!(
+            evaluate("add_workdays(date(2024, 3, 15), 1)").unwrap(),
+            evaluate("date(2024, 3, 18)").unwrap()
+        );
+    }
+}
+
+/// Tests for quantities with units.
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    /// Evaluates an expression and renders the quantity.
+    fn render(input: &str) -> String {
+        evaluate_quantity(input).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_units_carry_through_arithmetic() {
+        assert_eq!(render("5 km / 20 min"), "15 km/h");
+        assert_eq!(render("3 m * 4 m"), "12 m²");
+        assert_eq!(render("3 m * 4 km"), "12000 m²");
+        assert_eq!(render("1 km + 300 m"), "1.3 km");
+        assert_eq!(render("(5 km/h) * 2 h"), "10 km");
+        assert_eq!(render("9.81 m/s^2 * 70 kg"), "686.7 m·kg/s²");
+        assert_eq!(render("1 / 5 s"), "0.2 s⁻¹");
+    }
+
+    #[test]
+    fn test_simplification() {
+        assert_eq!(render("2 kW * 3 h"), "6 kWh");
+        assert_eq!(render("100 W * 1 h"), "100 Wh");
+        assert_eq!(render("5 J / 2 s"), "2.5 W");
+        assert_eq!(render("60 mi / 1 h"), "60 mph");
+        assert_eq!(render("1 m / 1 km"), "0.001");
+        assert_eq!(render("sqrt(4 m²)"), "2 m");
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(render("100 °C to °F"), "212 °F");
+        assert_eq!(render("12 in to cm"), "30.48 cm");
+        assert_eq!(render("5 ft in m"), "1.524 m");
+        assert_eq!(render("5 ft in in"), "60 in");
+        assert_eq!(render("1 mph to km/min"), "0.0268224 km/min");
+        assert_eq!(render("1 L to cm^3"), "1000 cm³");
+    }
+
+    #[test]
+    fn test_functions_with_units() {
+        assert_close(evaluate("sin(30 deg)").unwrap(), 0.5);
+        assert_eq!(render("max(1 km, 900 m)"), "1 km");
+        assert_eq!(render("abs(-3 kg)"), "3 kg");
+        assert_eq!(error_type_of("ln(5 m)"), CalculatorErrorType::IncompatibleUnits);
+    }
+
+    #[test]
+    fn test_dimension_errors() {
+        assert_eq!(error_type_of("1 km + 1 kg"), CalculatorErrorType::IncompatibleUnits);
+        assert_eq!(error_type_of("1 h - 5"), CalculatorErrorType::IncompatibleUnits);
+        assert_eq!(error_type_of("10 km to kg"), CalculatorErrorType::IncompatibleUnits);
+        assert_eq!(error_type_of("20 °C * 2 m"), CalculatorErrorType::IncompatibleUnits);
+        assert_eq!(error_type_of("2 ^ (1 m)"), CalculatorErrorType::IncompatibleUnits);
+        assert_eq!(error_type_of("3 m to 2 m"), CalculatorErrorType::InvalidInput);
+        // A plain-number evaluation rejects results with units
+        assert_eq!(error_type_of("5 km"), CalculatorErrorType::IncompatibleUnits);
+    }
+
+    #[test]
+    fn test_variables_shadow_units() {
+        let mut context = Context::new();
+        context.set("h", 2.0);
+        assert_eq!(parse("3 h").unwrap().evaluate(&context).unwrap(), 6.0);
+    }
+
+    #[test]
+    fn test_currency_amounts() {
+        install_rates(RateTable::from_json(r#"{"base": "USD", "rates": {"EUR": 0.9, "JPY": 150}}"#).unwrap());
+        assert_eq!(render("100 EUR to USD"), "111.11 USD");
+        assert_eq!(render("20 USD + 10 EUR"), "31.11 USD");
+        assert_eq!(render("1000 JPY in EUR"), "6.00 EUR");
+        assert_eq!(render("30 USD / 3"), "10.00 USD");
+        assert_eq!(render("99.99 USD to JPY"), "14999 JPY");
+        assert_eq!(error_type_of("10 EUR to kg"), CalculatorErrorType::IncompatibleUnits);
+    }
+}
+
+/// Tests for complex mode.
+#[cfg(test)]
+mod complex_tests {
+    use super::*;
+
+    /// Evaluates an expression in complex mode and renders the result.
+    fn render(input: &str) -> String {
+        evaluate_complex(input).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_imaginary_unit() {
+        assert_eq!(render("i^2"), "-1");
+        assert_eq!(render("3 + 4i"), "3 + 4i");
+        assert_eq!(render("(1 + 2j) * (3 - j)"), "5 + 5i");
+        assert_eq!(render("1 / i"), "-i");
+        assert_eq!(render("e^(i pi)"), "-1");
+    }
+
+    #[test]
+    fn test_complex_results_of_real_functions() {
+        assert_eq!(render("sqrt(-4)"), "2i");
+        assert_eq!(render("ln(-1)"), "3.14159265358979i");
+        assert_eq!(render("asin(2)"), "1.5707963267949 - 1.31695789692482i");
+        // Functions keep their real value where they have one
+        assert_eq!(render("cbrt(-8)"), "-2");
+        assert_eq!(render("sqrt(16)"), "4");
+        assert_eq!(render("2^0.5"), "1.4142135623731");
+    }
+
+    #[test]
+    fn test_polar_entry_and_parts() {
+        assert_eq!(render("5∠53.13010235415598°"), "3 + 4i");
+        assert_eq!(render("2 * 1∠90°"), "2i");
+        assert_eq!(render("abs(3 - 4i)"), "5");
+        assert_eq!(render("conj(3 - 4i)"), "3 + 4i");
+        assert_eq!(render("re(3 - 4i) + im(3 - 4i)"), "-1");
+        assert_eq!(evaluate_complex("arg(-1)").unwrap().re(), std::f64::consts::PI);
+    }
+
+    #[test]
+    fn test_impedance() {
+        // A 50 Ω resistor in series with a 10 µF capacitor at 1 kHz
+        let impedance = evaluate_complex("50 + 1 / (i * 2 pi * 1000 * 10e-6)").unwrap();
+        assert_eq!(impedance.re(), 50.0);
+        assert!((impedance.im() + 15.915494309189533).abs() < 1e-12);
+        assert_eq!(impedance.format_polar(true), "52.4719254373788∠-17.6567871514129°");
+    }
+
+    #[test]
+    fn test_real_mode_is_unchanged() {
+        assert_eq!(error_type_of("sqrt(-4)"), CalculatorErrorType::CalculationError);
+        assert_eq!(error_type_of("i^2"), CalculatorErrorType::InvalidInput);
+        assert_eq!(parse("sqrt(-4)").unwrap().evaluate_complex(&Context::new()).unwrap_err().error_type(), CalculatorErrorType::CalculationError);
+    }
+
+    #[test]
+    fn test_real_evaluation_in_complex_mode() {
+        let mut context = Context::new();
+        context.set_complex_mode(true);
+        assert_eq!(parse("sqrt(-4)^2").unwrap().evaluate(&context).unwrap(), -4.0);
+        let error = parse("sqrt(-4)").unwrap().evaluate(&context).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::InvalidInput);
+    }
+
+    #[test]
+    fn test_complex_mode_errors() {
+        for (input, error_type) in [
+            ("(2i)!", CalculatorErrorType::InvalidInput),
+            ("nCr(5i, 2)", CalculatorErrorType::InvalidInput),
+            ("polar(1, i)", CalculatorErrorType::InvalidInput),
+            ("re(1, 2)", CalculatorErrorType::InvalidInput),
+            ("3 km + i", CalculatorErrorType::IncompatibleUnits),
+            ("1 m to cm", CalculatorErrorType::IncompatibleUnits),
+            ("i / 0", CalculatorErrorType::DivisionByZero),
+        ] {
+            assert_eq!(evaluate_complex(input).unwrap_err().error_type(), error_type, "{}", input);
+        }
+    }
+}
+
+/// Tests for matrix literals.
+#[cfg(test)]
+mod matrix_tests {
+    use super::*;
+
+    /// Evaluates an expression to a matrix and renders the result.
+    fn render(input: &str) -> String {
+        evaluate_matrix(input).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(render("[[1, 2], [3, 4]]"), "[[1, 2], [3, 4]]");
+        assert_eq!(render("[1, 2 + 3, 2^3]"), "[[1], [5], [8]]");
+        assert_eq!(render("[[sqrt(4), -pi / pi]]"), "[[2, -1]]");
+        assert_eq!(render("7"), "[[7]]");
+    }
+
+    #[test]
+    fn test_operators() {
+        assert_eq!(render("[[1, 2], [3, 4]] + [[4, 3], [2, 1]]"), "[[5, 5], [5, 5]]");
+        assert_eq!(render("[[1, 2], [3, 4]] - [[1, 1], [1, 1]]"), "[[0, 1], [2, 3]]");
+        assert_eq!(render("[[1, 2], [3, 4]] * [[0, 1], [1, 0]]"), "[[2, 1], [4, 3]]");
+        assert_eq!(render("[[1, 2], [3, 4]] [5, 6]"), "[[17], [39]]");
+        assert_eq!(render("2 [[1, 2], [3, 4]] / 4"), "[[0.5, 1], [1.5, 2]]");
+        assert_eq!(render("-[1, 2]"), "[[-1], [-2]]");
+        assert_eq!(render("[[1, 1], [0, 1]]^3"), "[[1, 3], [0, 1]]");
+        assert_eq!(render("[[2, 0], [0, 4]]^-1"), "[[0.5, 0], [0, 0.25]]");
+    }
+
+    #[test]
+    fn test_functions() {
+        assert_eq!(evaluate("det([[1, 2], [3, 4]])").unwrap(), -2.0);
+        assert_eq!(evaluate("rank([[1, 2], [2, 4]])").unwrap(), 1.0);
+        assert_eq!(evaluate("dot([1, 2, 3], [4, 5, 6])").unwrap(), 32.0);
+        assert_eq!(evaluate("norm([3, 4])").unwrap(), 5.0);
+        assert_eq!(evaluate("det([[1, 2], [3, 4]]) + 1").unwrap(), -1.0);
+        assert_eq!(render("inv([[4, 7], [2, 6]])"), "[[0.6, -0.7], [-0.2, 0.4]]");
+        assert_eq!(render("transpose([1, 2, 3])"), "[[1, 2, 3]]");
+        assert_eq!(render("cross([1, 0, 0], [0, 1, 0])"), "[[0], [0], [1]]");
+        assert_eq!(render("hadamard([[1, 2]], [[3, 4]])"), "[[3, 8]]");
+        assert_eq!(render("solve([[1, 1], [1, -1]], [3, 1])"), "[[2], [1]]");
+    }
+
+    #[test]
+    fn test_matrix_errors() {
+        for (input, error_type) in [
+            ("[[1, 2], [3]]", CalculatorErrorType::InvalidInput),
+            ("[]", CalculatorErrorType::InvalidInput),
+            ("[[1, 2]", CalculatorErrorType::InvalidInput),
+            ("[[1, 2], [3, 4]] + [1, 2]", CalculatorErrorType::InvalidInput),
+            ("[1, 2] * [3, 4]", CalculatorErrorType::InvalidInput),
+            ("1 + [1, 2]", CalculatorErrorType::InvalidInput),
+            ("[[1, 2], [3, 4]]^0.5", CalculatorErrorType::InvalidInput),
+            ("sin([1, 2])", CalculatorErrorType::InvalidInput),
+            ("det([[1, 2, 3]])", CalculatorErrorType::InvalidInput),
+            ("inv([[1, 2], [2, 4]])", CalculatorErrorType::SingularMatrix),
+            ("solve([[1, 2], [2, 4]], [1, 2])", CalculatorErrorType::SingularMatrix),
+            ("[1 m, 2]", CalculatorErrorType::IncompatibleUnits),
+            ("[1, 2] / 0", CalculatorErrorType::DivisionByZero),
+        ] {
+            assert_eq!(evaluate_matrix(input).unwrap_err().error_type(), error_type, "{}", input);
+        }
+        assert!(evaluate("[1, 2]").unwrap_err().message().contains("is not a number"));
+        assert_eq!(evaluate_complex("[1, i]").unwrap_err().error_type(), CalculatorErrorType::InvalidInput);
+    }
+}
+
+/// Tests for equations.
+#[cfg(test)]
+mod equation_tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        assert_close(evaluate("solve(x^3 - 2x = 5, x, 2)").unwrap(), 2.0945514815423265);
+        assert_close(evaluate("solve(x^2 = 2, x, 1)").unwrap(), 2f64.sqrt());
+        assert_close(evaluate("solve(x^2 = 2, x, -1)").unwrap(), -(2f64.sqrt()));
+        // Without '=' the expression is solved for zero, from 0 by default
+        assert_close(evaluate("solve(cos(t) - t, t)").unwrap(), 0.7390851332151607);
+        assert_close(evaluate("2 * solve(3y + 1 = 7, y)").unwrap(), 4.0);
+    }
+
+    #[test]
+    fn test_solve_with_context_and_units() {
+        let mut context = Context::new();
+        context.set("a", 9.0);
+        context.set("x", 100.0);
+        assert_close(parse("solve(x^2 = a, x, 1)").unwrap().evaluate(&context).unwrap(), 3.0);
+        assert_eq!(context.get("x"), Some(100.0));
+        // Both sides may have units of the same dimension
+        assert_close(evaluate("solve(d km = 500 m, d, 1)").unwrap(), 0.5);
+    }
+
+    #[test]
+    fn test_solve_errors() {
+        let error = evaluate("solve(x^2 + 1 = 0, x)").unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::NoSolution);
+        assert!(error.message().contains("Newton's method"));
+        assert_eq!(error_type_of("solve(x = 1, x, 1, 2)"), CalculatorErrorType::InvalidInput);
+        assert_eq!(error_type_of("solve(x = 1)"), CalculatorErrorType::InvalidInput);
+        assert_eq!(error_type_of("sqrt(x = 1)"), CalculatorErrorType::InvalidInput);
+        assert_eq!(error_type_of("x = 1"), CalculatorErrorType::InvalidInput);
+        assert_eq!(error_type_of("solve(x m = 5 s, x, 1)"), CalculatorErrorType::IncompatibleUnits);
+        assert_eq!(error_type_of("solve(exp(x) = 0, x, 0)"), CalculatorErrorType::NoSolution);
+        // The matrix form of solve is unchanged
+        assert_eq!(evaluate_matrix("solve([[2]], [4])").unwrap().to_string(), "[[2]]");
+    }
+}
+
+/// Tests for derivatives, integrals, sums and products.
+#[cfg(test)]
+mod calculus_tests {
+    use super::*;
+
+    #[test]
+    fn test_derivative_and_integral() {
+        assert_close(evaluate("derivative(x^3, x, 2)").unwrap(), 12.0);
+        assert_close(evaluate("derivative(sin(t), t, 0)").unwrap(), 1.0);
+        assert_close(evaluate("integral(x^2, x, 0, 3)").unwrap(), 9.0);
+        assert_close(evaluate("integral(1 / x, x, 1, e)").unwrap(), 1.0);
+        assert_close(evaluate("2 * integral(sqrt(1 - x^2), x, -1, 1)").unwrap(), std::f64::consts::PI);
+    }
+
+    #[test]
+    fn test_sum_and_product() {
+        assert_eq!(evaluate("sum(k^2, k, 1, 100)").unwrap(), 338350.0);
+        assert_eq!(evaluate("product(k, k, 1, 6)").unwrap(), 720.0);
+        assert_eq!(evaluate("sum(sum(j, j, 1, k), k, 1, 4)").unwrap(), 20.0);
+        assert_close(evaluate("sum(1 / k!, k, 0, 20)").unwrap(), std::f64::consts::E);
+    }
+
+    #[test]
+    fn test_bound_variable_is_local() {
+        let mut context = Context::new();
+        context.set("k", 10.0);
+        context.set("n", 4.0);
+        assert_eq!(parse("sum(k, k, 1, n) + k").unwrap().evaluate(&context).unwrap(), 20.0);
+    }
+
+    #[test]
+    fn test_calculus_errors() {
+        assert_eq!(error_type_of("sum(k, k, 1, 1e9)"), CalculatorErrorType::IterationLimit);
+        assert_eq!(error_type_of("integral(1 / x, x, 0, 1)"), CalculatorErrorType::IterationLimit);
+        assert_eq!(error_type_of("sum(k, k, 1.5, 3)"), CalculatorErrorType::InvalidInput);
+        assert_eq!(error_type_of("sum(k, 2, 1, 3)"), CalculatorErrorType::InvalidInput);
+        assert_eq!(error_type_of("integral(x, x, 0)"), CalculatorErrorType::InvalidInput);
+        assert_eq!(error_type_of("sum(k m, k, 1, 3)"), CalculatorErrorType::IncompatibleUnits);
+        assert_eq!(error_type_of("integral(x, x, 0 m, 1 m)"), CalculatorErrorType::IncompatibleUnits);
+        assert!(evaluate("sum(k, 2, 1, 3)").unwrap_err().message().contains("variable name"));
+    }
+
+    #[test]
+    fn test_nested_calls_share_a_budget() {
+        assert_close(evaluate("sum(sum(j, j, 1, k), k, 1, 100)").unwrap(), 171700.0);
+        let error = evaluate("sum(sum(j, j, 1, 1e6), k, 1, 1e6)").unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::IterationLimit);
+    }
+
+    #[test]
+    fn test_underflowing_values_are_zero() {
+        assert_close(evaluate("integral(exp(-x^2), x, -30, 30)").unwrap(), std::f64::consts::PI.sqrt());
+        assert_close(evaluate("integral(exp(-x), x, 0, 800)").unwrap(), 1.0);
+        assert_close(evaluate("integral(x^300, x, 0, 1)").unwrap(), 1.0 / 301.0);
+        assert_close(evaluate("sum(0.5^k, k, 0, 2000)").unwrap(), 2.0);
+        assert_eq!(evaluate("derivative(exp(-x^2), x, 30)").unwrap(), 0.0);
+    }
+}
+
+/// Tests for printing and symbolic algebra.
+#[cfg(test)]
+mod symbolic_tests {
+    use super::*;
+
+    #[test]
+    fn test_printing() {
+        let mut context = Context::new();
+        context.set("x", 2.0);
+        context.set("y", 5.0);
+        for input in ["1 + 2 * 3", "(1 + 2) * 3", "10 - (4 - 3)", "2^3^2", "(2^3)^2", "-2^2", "(-2)^2", "2^-1", "3!", "x * -y"] {
+            let printed = parse(input).unwrap().to_string();
+            assert_eq!(
+                parse(&printed).unwrap().evaluate(&context).unwrap(),
+                parse(input).unwrap().evaluate(&context).unwrap(),
+                "{} printed as {}",
+                input,
+                printed
+            );
+        }
+        assert_eq!(parse("10 - (4 - 3)").unwrap().to_string(), "10 - (4 - 3)");
+        assert_eq!(parse("x * -y").unwrap().to_string(), "x * (-y)");
+        assert_eq!(parse("max(1, 2x)").unwrap().to_string(), "max(1, 2 * x)");
+        assert_eq!(parse("[[1, 2], [3, 4]]").unwrap().to_string(), "[[1, 2], [3, 4]]");
+    }
+
+    #[test]
+    fn test_simplification() {
+        assert_eq!(simplify("x + 0").unwrap(), "x");
+        assert_eq!(simplify("1 * x^1").unwrap(), "x");
+        assert_eq!(simplify("x * 0 + 1").unwrap(), "1");
+        assert_eq!(simplify("x - x").unwrap(), "0");
+        assert_eq!(simplify("-(-x)").unwrap(), "x");
+        assert_eq!(simplify("3x - 5 + 2x + 7").unwrap(), "5 * x + 2");
+        assert_eq!(simplify("x * y * x * 2").unwrap(), "2 * x^2 * y");
+        assert_eq!(simplify("(x^2)^3 / x^6").unwrap(), "1");
+        // Inexact quotients are kept as fractions
+        assert_eq!(simplify("1 / 3 + 6 / 3").unwrap(), "1 / 3 + 2");
+    }
+
+    #[test]
+    fn test_simplification_keeps_unrepresentable_folds() {
+        // Folding these numbers would overflow or underflow, so they stay apart
+        for input in ["1e308 * 10", "1e-200 * 1e-200", "1e308 + 1e308", "1e308 x + 1e308 x"] {
+            assert_eq!(simplify(input).unwrap(), parse(input).unwrap().to_string(), "{}", input);
+        }
+        assert_eq!(error_type_of(&simplify("1e308 * 10").unwrap()), CalculatorErrorType::Overflow);
+        assert_eq!(simplify("1e-200 * x * 1e200").unwrap(), "x");
+    }
+
+    #[test]
+    fn test_derivatives() {
+        assert_eq!(differentiate("a x^2 + b x + c", "x").unwrap(), "2 * a * x + b");
+        assert_eq!(differentiate("1 / x", "x").unwrap(), "-1 / x^2");
+        assert_eq!(differentiate("x / (x + 1)", "x").unwrap(), "1 / (x + 1)^2");
+        assert_eq!(differentiate("(x + 1)^3", "x").unwrap(), "3 * (x + 1)^2");
+        assert_eq!(differentiate("exp(-x^2)", "x").unwrap(), "-2 * x * exp(-x^2)");
+        assert_eq!(differentiate("e^x", "x").unwrap(), "e^x");
+        assert_eq!(differentiate("2^x", "x").unwrap(), "2^x * ln(2)");
+        assert_eq!(differentiate("x^x", "x").unwrap(), "x^x * (ln(x) + 1)");
+        assert_eq!(differentiate("log(x, 2)", "x").unwrap(), "1 / (x * ln(2))");
+        assert_eq!(differentiate("y^2", "x").unwrap(), "0");
+    }
+
+    #[test]
+    fn test_derivatives_agree_with_numeric_ones() {
+        let mut context = Context::new();
+        context.set("x", 0.7);
+        for input in ["sin(x) cos(x)", "sqrt(x^2 + 1)", "atan(x) / x", "ln(x) x^3", "tanh(2x) - cbrt(x)"] {
+            let symbolic = parse(input).unwrap().derivative("x").unwrap().to_string();
+            let exact = parse(&symbolic).unwrap().evaluate(&context).unwrap();
+            let numeric = evaluate(&format!("derivative({}, x, 0.7)", input)).unwrap();
+            assert!((exact - numeric).abs() < 1e-8, "{}: {} != {}", input, symbolic, numeric);
+        }
+    }
+
+    #[test]
+    fn test_derivative_steps() {
+        let steps = derivative_steps("x sin(x^2)", "x").unwrap();
+        let rules: Vec<String> = steps.iter().map(|step| step.rule()).collect();
+        assert_eq!(rules, ["product rule", "variable", "chain rule: d/du sin(u) = cos(u)", "power rule", "variable"]);
+        assert_eq!(steps[0].expression(), "x * sin(x^2)");
+        assert_eq!(steps[0].result(), "sin(x^2) + 2 * x^2 * cos(x^2)");
+        assert_eq!(steps[2].result(), "2 * x * cos(x^2)");
+
+        let steps = derivative_steps("pi", "x").unwrap();
+        assert_eq!((steps.len(), steps[0].rule(), steps[0].result()), (1, "constant".to_string(), "0".to_string()));
+    }
+
+    #[test]
+    fn test_derivative_errors() {
+        for input in ["floor(x)", "x!", "max(x, 1)", "log(2, x)", "[x, 1]"] {
+            let error = differentiate(input, "x").unwrap_err();
+            assert_eq!(error.error_type(), CalculatorErrorType::InvalidInput, "{}", input);
+            assert!(error.message().contains("cannot be differentiated"), "{}", error.message());
+        }
+        // Parts that do not depend on the variable are constants
+        assert_eq!(differentiate("floor(y) x", "x").unwrap(), "floor(y)");
+    }
+}
+
+/// Tests for evaluation traces.
+#[cfg(test)]
+mod trace_tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_order() {
+        let steps = trace("-sqrt(16) + 2^3!").unwrap();
+        let calculations: Vec<String> = steps.iter().map(|step| step.calculation()).collect();
+        assert_eq!(calculations, ["sqrt(16)", "-4", "3!", "2^6", "-4 + 64"]);
+        let rules: Vec<String> = steps.iter().map(|step| step.rule()).collect();
+        assert_eq!(rules, ["function sqrt", "negation", "factorial", "power", "addition"]);
+        assert_eq!(steps[4].expression(), "-sqrt(16) + 2^3!");
+        assert_eq!(steps[4].value(), 60.0);
+    }
+
+    #[test]
+    fn test_trace_names() {
+        let mut context = Context::new();
+        context.set("r", 2.0);
+        let steps = parse("pi r^2").unwrap().trace(&context).unwrap();
+        assert_eq!((steps[0].rule(), steps[1].rule()), ("constant".to_string(), "variable".to_string()));
+        assert_eq!(steps[2].calculation(), "2^2");
+        assert_eq!(steps[3].calculation(), "3.14159265358979 * 4");
+        assert_close(steps[3].value(), 4.0 * std::f64::consts::PI);
+        // Literals alone have nothing to explain
+        assert!(trace("-3").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_trace_quantities_and_matrices() {
+        let steps = trace("5 km / 20 min to mph").unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_eq!((steps[0].calculation(), steps[0].result()), ("(5 km) / (20 min)".to_string(), "15 km/h".to_string()));
+        assert_eq!(steps[0].value(), 15.0);
+        assert_eq!(steps[1].rule(), "conversion");
+
+        let steps = trace("det([[1, 2], [3, 4 + 1]])").unwrap();
+        assert_eq!(steps[1].rule(), "matrix");
+        assert!(steps[1].value().is_nan());
+        assert_eq!(steps[2].calculation(), "det([[1, 2], [3, 5]])");
+    }
+
+    #[test]
+    fn test_trace_bound_variables_and_complex_mode() {
+        // The body of an integral is not traced on its own
+        let steps = trace("integral(x^2, x, 0, 3) + 1").unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[1].calculation(), "9 + 1");
+
+        let mut context = Context::new();
+        context.set_complex_mode(true);
+        let steps = parse("(3 + 4i) * 2").unwrap().trace(&context).unwrap();
+        let last = steps.last().unwrap();
+        assert_eq!((last.calculation(), last.result()), ("(3 + 4i) * 2".to_string(), "6 + 8i".to_string()));
+        assert!(last.value().is_nan());
+    }
+
+    #[test]
+    fn test_trace_long_chain() {
+        // Each step takes its operands' values rather than evaluating them again
+        let input = vec!["sum(k, k, 1, 10000)"; 300].join(" + ");
+        let steps = trace(&input).unwrap();
+        assert_eq!(steps.len(), 599);
+        assert_eq!(steps.last().unwrap().value(), 300.0 * 50005000.0);
+    }
+
+    #[test]
+    fn test_trace_errors() {
+        assert_eq!(trace("2 + 1 / 0").unwrap_err().error_type(), CalculatorErrorType::DivisionByZero);
+        assert_eq!(trace("y + 1").unwrap_err().error_type(), CalculatorErrorType::InvalidInput);
+    }
+}
+
+/// Tests for compiled expressions.
+#[cfg(test)]
+mod compiled_tests {
+    use super::*;
+
+    /// Asserts that a compiled expression gives the same value or error
+    /// type as evaluating the tree, at several values of `x`.
+    fn assert_matches_tree(input: &str) {
+        let expr = parse(input).unwrap();
+        let compiled = expr.compile("x").unwrap();
+        for x in [-2.5, -1.0, 0.0, 0.5, 1.0, 3.0, 1e200] {
+            let mut context = Context::new();
+            context.set("x", x);
+            match (compiled.eval(x), expr.evaluate(&context)) {
+                (Ok(actual), Ok(expected)) => assert_eq!(actual, expected, "{} at {}", input, x),
+                (Err(actual), Err(expected)) => {
+                    assert_eq!(actual.error_type(), expected.error_type(), "{} at {}", input, x)
+                }
+                (actual, expected) => panic!("{} at {}: {:?} != {:?}", input, x, actual, expected),
+            }
+        }
+    }
+
+    #[test]
+    fn test_matches_tree_evaluation() {
+        for input in [
+            "x^2 + 3x - 1",
+            "-x^2",
+            "2^-x",
+            "1 / x",
+            "x / (x - 1)",
+            "sqrt(x) + ln(x)",
+            "x!",
+            "(x + 3)!",
+            "sin(x)^2 + cos(x)^2",
+            "max(x, 1, -x) - min(x, 0)",
+            "log(x, 2) * e^x",
+            "x * x * x * x",
+            "normal_cdf(x, 0, 1)",
+            "nCr(5, x)",
+        ] {
+            assert_matches_tree(input);
+        }
+    }
+
+    #[test]
+    fn test_tree_evaluated_parts() {
+        // Calls that bind a variable, and units, go through the tree evaluator
+        assert_matches_tree("integral(t^2, t, 0, x)");
+        assert_matches_tree("sum(k * x, k, 1, 4)");
+        assert_matches_tree("x * 1 km / 1 m");
+        assert_matches_tree("det([[x, 1], [2, 3]])");
+        assert_eq!(parse("x * 1 km / 1 m").unwrap().compile("x").unwrap().eval(2.0).unwrap(), 2000.0);
+        assert!(parse("x m").unwrap().compile("x").unwrap().eval(2.0).is_err());
+    }
+
+    #[test]
+    fn test_constant_folding() {
+        let compiled = parse("sin(pi / 6) * x + sum(k, k, 1, 10)").unwrap().compile("x").unwrap();
+        assert_close(compiled.eval(2.0).unwrap(), 56.0);
+        // A constant part that fails still fails at each value
+        let compiled = parse("x + 1 / 0").unwrap().compile("x").unwrap();
+        assert_eq!(compiled.eval(1.0).unwrap_err().error_type(), CalculatorErrorType::DivisionByZero);
+    }
+
+    #[test]
+    fn test_variable_shadows_constants() {
+        let compiled = CompiledExpression::new("e^2", "e").unwrap();
+        assert_eq!(compiled.eval(3.0).unwrap(), 9.0);
+        assert_eq!(compiled.variable(), "e");
+        assert_eq!(compiled.expression(), "e^2");
+    }
+
+    #[test]
+    fn test_eval_many() {
+        let compiled = CompiledExpression::new("1 / x", "x").unwrap();
+        let values = compiled.eval_many(&[-2.0, 0.0, 4.0]);
+        assert_eq!(values[0], -0.5);
+        assert!(values[1].is_nan());
+        assert_eq!(values[2], 0.25);
+        assert!(compiled.eval_many(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        for input in ["x + y", "foo(x)", "sin(x, 2)", "x = 2"] {
+            let error = CompiledExpression::new(input, "x").unwrap_err();
+            assert_eq!(error.error_type(), CalculatorErrorType::InvalidInput, "{}", input);
+        }
+        assert!(CompiledExpression::new("x +", "x").is_err());
+    }
+}
+
+/// Tests for errors.
+#[cfg(test)]
+mod error_tests {
+    use super::*;
+
+    #[test]
+    fn test_syntax_errors() {
+        for input in ["", "1 +", "(1 + 2", "1 + * 2", "2 3", "1 $ 2", "max(1,)"] {
+            assert_eq!(error_type_of(input), CalculatorErrorType::InvalidInput, "{}", input);
+        }
+        assert!(evaluate("(1 + 2").unwrap_err().message().contains("missing ')'"));
+    }
+
+    #[test]
+    fn test_unknown_names_and_arity() {
+        assert!(evaluate("y + 1").unwrap_err().message().contains("unknown variable 'y'"));
+        assert!(evaluate("foo(1)").unwrap_err().message().contains("unknown function 'foo'"));
+        assert_eq!(error_type_of("sqrt(1, 2)"), CalculatorErrorType::InvalidInput);
+        assert_eq!(error_type_of("nCr(5)"), CalculatorErrorType::InvalidInput);
+    }
+
+    #[test]
+    fn test_evaluation_errors() {
+        assert_eq!(error_type_of("1 / 0"), CalculatorErrorType::DivisionByZero);
+        assert_eq!(error_type_of("10^400"), CalculatorErrorType::Overflow);
+        assert_eq!(error_type_of("200!"), CalculatorErrorType::Overflow);
+        assert_eq!(error_type_of("sqrt(-1)"), CalculatorErrorType::CalculationError);
+    }
+
+    #[test]
+    fn test_parameter_validation() {
+        assert_eq!(error_type_of("2.5!"), CalculatorErrorType::InvalidInput);
+        assert_eq!(error_type_of("normal_pdf(0, 0, -1)"), CalculatorErrorType::InvalidInput);
+        assert_eq!(error_type_of("binomial_cdf(1, 10, 2)"), CalculatorErrorType::InvalidInput);
+        assert_eq!(error_type_of("t_inv(1.5, 3)"), CalculatorErrorType::InvalidInput);
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        let nested = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        assert_eq!(evaluate(&nested(150)).unwrap(), 1.0);
+
+        // Deep input is rejected rather than overflowing the stack
+        for input in [
+            nested(100_000),
+            format!("{}1", "-".repeat(100_000)),
+            format!("{}1", "1^".repeat(100_000)),
+            format!("{}1{}", "sin(".repeat(100_000), ")".repeat(100_000)),
+            format!("{}1{}", "[".repeat(100_000), "]".repeat(100_000)),
+        ] {
+            let error = evaluate(&input).unwrap_err();
+            assert_eq!(error.error_type(), CalculatorErrorType::InvalidInput);
+            assert!(error.message().contains("nested too deeply"));
+        }
+    }
+
+    #[test]
+    fn test_long_chains() {
+        assert_eq!(evaluate(&format!("{}1", "1 + ".repeat(249))).unwrap(), 250.0);
+        assert_eq!(evaluate(&format!("{}2", "2 * ".repeat(299))).unwrap(), 2f64.powi(300));
+        assert_eq!(evaluate(&format!("({}1)", "1 - ".repeat(150).repeat(2))).unwrap(), -299.0);
+
+        // Chains too long to walk safely are rejected
+        for input in [format!("{}1", "1 + ".repeat(100_000)), "(2)".repeat(100_000)] {
+            let error = evaluate(&input).unwrap_err();
+            assert_eq!(error.error_type(), CalculatorErrorType::InvalidInput);
+            assert!(error.message().contains("too long"));
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Tests for localized messages.
+//!
+//! This module contains tests for the message catalogue:
+//! - Completeness of every bundled catalogue
+//! - Language selection and fallback rules
+//! - Localized error messages and display strings
+
+use rust_wasm_calc::errors::{CalculatorError, CalculatorErrorType, error_to_js_string_by_type, string_to_calculator_error};
+use rust_wasm_calc::i18n::{
+    error_key, language, message, message_in, message_keys, set_language, translate, Language,
+};
+use rust_wasm_calc::state::types::{CalculatorState, Operation};
+
+/// Tests for catalogue completeness.
+#[cfg(test)]
+mod catalogue_tests {
+    use super::*;
+
+    #[test]
+    fn test_every_error_type_translated_in_every_language() {
+        for language in Language::ALL {
+            for error_type in CalculatorErrorType::ALL {
+                assert!(
+                    translate(language, &error_key(&error_type)).is_some(),
+                    "{:?} has no {:?} translation",
+                    error_type,
+                    language
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_key_translated_in_every_language() {
+        for language in Language::ALL {
+            for key in message_keys() {
+                assert!(translate(language, key).is_some(), "{} has no {:?} translation", key, language);
+            }
+        }
+    }
+}
+
+/// Tests for language selection and fallback.
+#[cfg(test)]
+mod language_selection_tests {
+    use super::*;
+
+    #[test]
+    fn test_from_tag() {
+        assert_eq!(Language::from_tag("fr"), Some(Language::French));
+        assert_eq!(Language::from_tag("de-AT"), Some(Language::German));
+        assert_eq!(Language::from_tag("es_MX"), Some(Language::Spanish));
+        assert_eq!(Language::from_tag("HI"), Some(Language::Hindi));
+        assert_eq!(Language::from_tag("ja"), None);
+    }
+
+    #[test]
+    fn test_set_language_falls_back_to_english() {
+        assert_eq!(set_language("es"), Language::Spanish);
+        assert_eq!(language(), Language::Spanish);
+
+        assert_eq!(set_language("ja-JP"), Language::English);
+        assert_eq!(language(), Language::English);
+    }
+
+    #[test]
+    fn test_unknown_key_falls_back_to_key() {
+        assert_eq!(message_in(Language::German, "no.such.key"), "no.such.key");
+    }
+
+    #[test]
+    fn test_message_uses_current_language() {
+        set_language("fr");
+        assert_eq!(message("ui.error"), "Erreur");
+        set_language("en");
+        assert_eq!(message("ui.error"), "Error");
+    }
+}
+
+/// Tests for localized error messages.
+#[cfg(test)]
+mod localized_error_tests {
+    use super::*;
+
+    #[test]
+    fn test_error_constructors_use_current_language() {
+        set_language("de");
+        assert_eq!(CalculatorError::division_by_zero().message(), "Division durch null ist nicht erlaubt");
+        assert_eq!(CalculatorError::invalid_input(None).message(), "Ungültige Eingabe");
+        assert_eq!(
+            error_to_js_string_by_type(CalculatorErrorType::Overflow, ""),
+            "Das Ergebnis ist zu groß für die Darstellung"
+        );
+
+        // Default messages stay English
+        assert_eq!(CalculatorErrorType::DivisionByZero.default_message(), "Division by zero is not allowed");
+        set_language("en");
+    }
+
+    #[test]
+    fn test_english_details_are_left_out() {
+        set_language("de");
+        assert_eq!(CalculatorError::incompatible_units(Some("cannot add m and s")).message(), "Die Einheiten sind nicht kompatibel");
+        assert_eq!(CalculatorError::singular_matrix(Some("it has no inverse")).message(), "Die Matrix ist singulär");
+        assert_eq!(error_to_js_string_by_type(CalculatorErrorType::InvalidInput, "abc"), "Ungültige Eingabe");
+        set_language("en");
+        assert_eq!(CalculatorError::invalid_input(Some("x")).message(), "Invalid input: x");
+    }
+
+    #[test]
+    fn test_legacy_strings_recognized_in_any_language() {
+        let error = string_to_calculator_error("Entrada no válida: abc");
+        assert_eq!(error.error_type(), CalculatorErrorType::InvalidInput);
+    }
+
+    #[test]
+    fn test_display_error_is_localized() {
+        set_language("hi");
+        let mut state = CalculatorState::new();
+        state.input_digit(1);
+        state.set_operation(Operation::Divide);
+        state.input_digit(0);
+        state.calculate();
+        assert_eq!(state.display_value(), "त्रुटि: शून्य से भाग देने की अनुमति नहीं है");
+        set_language("en");
+    }
+}
@@ -0,0 +1,273 @@
+//! Tests for function plotting.
+//!
+//! This module contains tests for the plot module:
+//! - Sampling and refinement of smooth curves
+//! - Breaks at poles and steps
+//! - Points where the expression is undefined
+//! - The suggested vertical range
+//! - Invalid ranges and expressions that are undefined everywhere
+//! - Tables of values, with errors in single rows, and their CSV text
+
+use rust_wasm_calc::errors::CalculatorErrorType;
+use rust_wasm_calc::plot::{plot, table};
+
+/// Tests for sampling smooth curves.
+#[cfg(test)]
+mod sampling_tests {
+    use super::*;
+
+    #[test]
+    fn test_samples_cover_the_range_in_order() {
+        let plot = plot("x^2", "x", -10.0, 10.0).unwrap();
+        let xs = plot.xs();
+        assert_eq!(xs[0], -10.0);
+        assert_eq!(xs[xs.len() - 1], 10.0);
+        assert!(xs.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(xs.len(), plot.ys().len());
+        for (x, y) in xs.iter().zip(plot.ys()) {
+            assert_eq!(y, x * x);
+        }
+    }
+
+    #[test]
+    fn test_smooth_curves_are_one_line() {
+        for input in ["sin(x)", "x^3 - x", "exp(x)", "atan(1000x)", "abs(x)"] {
+            let plot = plot(input, "x", -2.0, 2.0).unwrap();
+            assert_eq!(plot.segments().len(), 1, "{}", input);
+            assert!(plot.undefined().is_empty(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_steep_regions_are_refined() {
+        let plot = plot("atan(1000x)", "x", -1.0, 1.0).unwrap();
+        let near_zero = plot.xs().iter().filter(|x| x.abs() < 0.01).count();
+        // The even grid has only one point there
+        assert!(near_zero > 10, "{}", near_zero);
+    }
+
+    #[test]
+    fn test_other_variable_names() {
+        let plot = plot("2t + 1", "t", 0.0, 1.0).unwrap();
+        assert_eq!(plot.ys()[0], 1.0);
+        assert_eq!(*plot.ys().last().unwrap(), 3.0);
+    }
+}
+
+/// Tests for breaks at discontinuities.
+#[cfg(test)]
+mod break_tests {
+    use super::*;
+
+    #[test]
+    fn test_poles() {
+        assert_eq!(plot("1 / x", "x", -1.0, 1.0).unwrap().segments().len(), 2);
+        assert_eq!(plot("1 / (x - 0.3)", "x", -1.0, 1.0).unwrap().segments().len(), 2);
+        assert_eq!(plot("1 / x^2", "x", -1.0, 1.0).unwrap().segments().len(), 2);
+        // Asymptotes at ±π/2 and ±3π/2
+        assert_eq!(plot("tan(x)", "x", -5.0, 5.0).unwrap().segments().len(), 5);
+    }
+
+    #[test]
+    fn test_steps() {
+        assert_eq!(plot("floor(x)", "x", -3.0, 3.0).unwrap().segments().len(), 7);
+        assert_eq!(plot("x / abs(x)", "x", -1.0, 1.0).unwrap().segments().len(), 2);
+    }
+
+    #[test]
+    fn test_lines_do_not_cross_breaks() {
+        let plot = plot("tan(x)", "x", -5.0, 5.0).unwrap();
+        for segment in plot.segments() {
+            // Each branch of tan rises from left to right
+            assert!(segment.windows(2).all(|pair| pair[0].1 < pair[1].1));
+        }
+    }
+
+    #[test]
+    fn test_breaks_are_not_undefined_points() {
+        let plot = plot("floor(x)", "x", -3.0, 3.0).unwrap();
+        assert!(plot.undefined().is_empty());
+        assert!(plot.ys().iter().any(|y| y.is_nan()));
+    }
+}
+
+/// Tests for points where the expression is undefined.
+#[cfg(test)]
+mod undefined_tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_edges() {
+        let plot = plot("sqrt(x)", "x", -2.0, 2.0).unwrap();
+        let segments = plot.segments();
+        assert_eq!(segments.len(), 1);
+        assert!(plot.undefined().iter().all(|x| *x < 0.0));
+        assert!(!plot.undefined().is_empty());
+        // The line starts close to the edge of the domain
+        assert!(segments[0][0].0 < 1e-3);
+        assert_eq!(segments[0].last().unwrap().0, 2.0);
+    }
+
+    #[test]
+    fn test_undefined_points_are_broken() {
+        let plot = plot("ln(x)", "x", -1.0, 2.0).unwrap();
+        for x in plot.undefined() {
+            let index = plot.xs().iter().position(|sample| *sample == x).unwrap();
+            assert!(plot.ys()[index].is_nan());
+        }
+    }
+
+    #[test]
+    fn test_underflow_is_not_undefined() {
+        let plot = plot("exp(-x^2)", "x", -30.0, 30.0).unwrap();
+        assert!(plot.undefined().is_empty());
+        assert_eq!(plot.segments().len(), 1);
+        assert_eq!(plot.ys()[0], 0.0);
+    }
+}
+
+/// Tests for the suggested vertical range.
+#[cfg(test)]
+mod range_tests {
+    use super::*;
+
+    #[test]
+    fn test_range_holds_the_curve() {
+        let plot = plot("sin(x)", "x", 0.0, 10.0).unwrap();
+        assert!(plot.y_min() < -1.0 && plot.y_min() > -1.2);
+        assert!(plot.y_max() > 1.0 && plot.y_max() < 1.2);
+    }
+
+    #[test]
+    fn test_range_ignores_asymptotes() {
+        let plot = plot("tan(x)", "x", -5.0, 5.0).unwrap();
+        assert!(plot.y_max() < 100.0, "{}", plot.y_max());
+        assert!(plot.y_min() > -100.0, "{}", plot.y_min());
+    }
+
+    #[test]
+    fn test_range_of_a_constant() {
+        let plot = plot("3", "x", 0.0, 1.0).unwrap();
+        assert!(plot.y_min() < 3.0 && plot.y_max() > 3.0);
+        assert_eq!(plot.segments().len(), 1);
+    }
+}
+
+/// Tests for plotting errors.
+#[cfg(test)]
+mod error_tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_ranges() {
+        for (from, to) in [(1.0, 1.0), (2.0, 1.0), (0.0, f64::INFINITY), (f64::NAN, 1.0)] {
+            let error = plot("x", "x", from, to).unwrap_err();
+            assert_eq!(error.error_type(), CalculatorErrorType::InvalidInput);
+        }
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        assert!(plot("x +", "x", 0.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_undefined_everywhere() {
+        assert!(plot("sqrt(x)", "x", -2.0, -1.0).is_err());
+        let error = plot("x + y", "x", 0.0, 1.0).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::InvalidInput);
+        assert!(error.message().contains("'y'"));
+    }
+}
+
+/// Tests for tables of values.
+#[cfg(test)]
+mod table_tests {
+    use super::*;
+
+    #[test]
+    fn test_rows() {
+        let table = table("x^2 + 1", "x", -1.0, 0.5, 5).unwrap();
+        let rows = table.rows();
+        assert_eq!(rows.len(), 5);
+        let xs: Vec<f64> = rows.iter().map(|row| row.x()).collect();
+        assert_eq!(xs, vec![-1.0, -0.5, 0.0, 0.5, 1.0]);
+        let values: Vec<f64> = rows.iter().map(|row| row.value()).collect();
+        assert_eq!(values, vec![2.0, 1.25, 1.0, 1.25, 2.0]);
+        assert!(rows.iter().all(|row| row.error().is_none() && row.error_code().is_none()));
+        assert_eq!(table.variable(), "x");
+        assert_eq!(table.expression(), "x^2 + 1");
+    }
+
+    #[test]
+    fn test_errors_stay_in_their_rows() {
+        let rows = table("1 / x", "x", -2.0, 1.0, 5).unwrap().rows();
+        assert_eq!(rows[2].x(), 0.0);
+        assert!(rows[2].value().is_nan());
+        assert_eq!(rows[2].error().unwrap().error_type(), CalculatorErrorType::DivisionByZero);
+        assert_eq!(rows[2].error_code(), Some(CalculatorErrorType::DivisionByZero.code()));
+        assert!(rows[2].error_message().is_some());
+        assert_eq!(rows[3].value(), 1.0);
+
+        let rows = table("sqrt(x)", "x", -1.0, 1.0, 3).unwrap().rows();
+        assert!(rows[0].error().is_some());
+        assert_eq!(rows[1].value(), 0.0);
+        assert_eq!(rows[2].value(), 1.0);
+    }
+
+    #[test]
+    fn test_underflow_is_zero() {
+        let rows = table("exp(-x)", "x", 700.0, 10.0, 4).unwrap().rows();
+        assert!(rows[0].value() > 0.0);
+        for row in &rows[1..] {
+            assert_eq!(row.value(), 0.0);
+            assert!(row.error().is_none());
+        }
+    }
+
+    #[test]
+    fn test_steps_do_not_drift() {
+        let rows = table("t", "t", 0.0, 0.1, 101).unwrap().rows();
+        assert_eq!(rows[100].x(), 10.0);
+        assert_eq!(table("x", "x", 5.0, -2.0, 3).unwrap().rows()[2].x(), 1.0);
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = table("1 / x", "x", -1.0, 0.5, 4).unwrap().to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "x,1 / x,error");
+        assert_eq!(lines[1], "-1,-1,");
+        assert_eq!(lines[2], "-0.5,-2,");
+        assert!(lines[3].starts_with("0,,"));
+        assert!(lines[3].len() > 3);
+        assert_eq!(lines[4], "0.5,2,");
+        assert!(csv.ends_with('\n'));
+
+        // Rounding noise is hidden
+        let csv = table("x", "x", 0.0, 0.1, 4).unwrap().to_csv();
+        assert_eq!(csv.lines().last(), Some("0.3,0.3,"));
+    }
+
+    #[test]
+    fn test_csv_quotes_fields() {
+        let csv = table("max(x, 2)", "x", 0.0, 1.0, 1).unwrap().to_csv();
+        assert_eq!(csv.lines().next(), Some("x,\"max(x, 2)\",error"));
+    }
+
+    #[test]
+    fn test_empty_table() {
+        let table = table("x", "x", 0.0, 1.0, 0).unwrap();
+        assert!(table.rows().is_empty());
+        assert_eq!(table.to_csv(), "x,x,error\n");
+    }
+
+    #[test]
+    fn test_invalid_tables() {
+        for (start, step, count) in [(0.0, 0.0, 5), (f64::NAN, 1.0, 5), (0.0, f64::INFINITY, 5), (0.0, 1.0, 10_001)] {
+            let error = table("x", "x", start, step, count).unwrap_err();
+            assert_eq!(error.error_type(), CalculatorErrorType::InvalidInput);
+        }
+        assert!(table("x +", "x", 0.0, 1.0, 5).is_err());
+    }
+}
@@ -0,0 +1,175 @@
+//! Tests for the calculator error state.
+//!
+//! This module contains tests for:
+//! - Error details stored in the state
+//! - Configurable recovery from the error state
+
+use rust_wasm_calc::errors::CalculatorErrorType;
+use rust_wasm_calc::state::types::{CalculatorState, ErrorRecovery, Operation};
+
+/// Puts the calculator into the error state by dividing by zero.
+fn divide_by_zero(state: &mut CalculatorState) {
+    state.input_digit(5);
+    state.set_operation(Operation::Divide);
+    state.input_digit(0);
+    state.calculate();
+}
+
+/// Tests for error details.
+#[cfg(test)]
+mod error_details_tests {
+    use super::*;
+
+    #[test]
+    fn test_no_error_initially() {
+        let state = CalculatorState::new();
+        assert!(!state.is_error());
+        assert!(state.last_error().is_none());
+        assert_eq!(state.last_error_type(), None);
+        assert_eq!(state.last_error_message(), None);
+    }
+
+    #[test]
+    fn test_division_by_zero_details() {
+        let mut state = CalculatorState::new();
+        divide_by_zero(&mut state);
+
+        assert!(state.is_error());
+        assert_eq!(state.last_error_type(), Some(CalculatorErrorType::DivisionByZero));
+        assert_eq!(state.last_error_code(), Some(1));
+        assert_eq!(
+            state.last_error_message().as_deref(),
+            Some("Division by zero is not allowed")
+        );
+        assert_eq!(state.display_value(), "Error: Division by zero is not allowed");
+
+        let error = state.last_error().unwrap();
+        assert_eq!(error.error_type(), CalculatorErrorType::DivisionByZero);
+    }
+
+    #[test]
+    fn test_chained_operator_keeps_error() {
+        let mut state = CalculatorState::new();
+        state.input_digit(5);
+        state.set_operation(Operation::Divide);
+        state.input_digit(0);
+        state.set_operation(Operation::Add);
+
+        assert!(state.is_error());
+        assert_eq!(state.last_error_type(), Some(CalculatorErrorType::DivisionByZero));
+    }
+
+    #[test]
+    fn test_overflow_details() {
+        let mut state = CalculatorState::new();
+        state.input_value("1e308");
+        state.set_operation(Operation::Multiply);
+        state.input_digit(9);
+        state.calculate();
+
+        assert_eq!(state.last_error_type(), Some(CalculatorErrorType::Overflow));
+    }
+
+    #[test]
+    fn test_underflow_details() {
+        let mut state = CalculatorState::new();
+        state.input_value("1e-200");
+        state.set_operation(Operation::Multiply);
+        state.input_value("1e-200");
+        state.calculate();
+
+        assert_eq!(state.last_error_type(), Some(CalculatorErrorType::Underflow));
+    }
+
+    #[test]
+    fn test_invalid_input_details() {
+        let mut state = CalculatorState::new();
+        state.input_value("12x");
+        assert_eq!(state.last_error_type(), Some(CalculatorErrorType::InvalidInput));
+    }
+
+    #[test]
+    fn test_clear_resets_error() {
+        let mut state = CalculatorState::new();
+        divide_by_zero(&mut state);
+        state.clear_entry();
+
+        assert!(!state.is_error());
+        assert!(state.last_error().is_none());
+    }
+}
+
+/// Tests for error recovery settings.
+#[cfg(test)]
+mod error_recovery_tests {
+    use super::*;
+
+    #[test]
+    fn test_clear_only_is_default() {
+        let mut state = CalculatorState::new();
+        assert_eq!(state.error_recovery(), ErrorRecovery::ClearOnly);
+
+        divide_by_zero(&mut state);
+        state.input_digit(7);
+        state.backspace();
+        assert!(state.is_error());
+
+        state.clear();
+        assert!(!state.is_error());
+    }
+
+    #[test]
+    fn test_backspace_recovery() {
+        let mut state = CalculatorState::new();
+        state.set_error_recovery(ErrorRecovery::Backspace);
+        divide_by_zero(&mut state);
+
+        // Digits are still ignored
+        state.input_digit(7);
+        assert!(state.is_error());
+
+        state.backspace();
+        assert!(!state.is_error());
+        assert_eq!(state.display_value(), "0");
+    }
+
+    #[test]
+    fn test_input_recovery_starts_fresh() {
+        let mut state = CalculatorState::new();
+        state.set_error_recovery(ErrorRecovery::Input);
+        divide_by_zero(&mut state);
+
+        state.input_digit(7);
+        assert!(!state.is_error());
+        assert_eq!(state.display_value(), "7");
+
+        // The failed calculation is gone
+        state.set_operation(Operation::Add);
+        state.input_digit(1);
+        state.calculate();
+        assert_eq!(state.display_value(), "8");
+    }
+
+    #[test]
+    fn test_input_recovery_with_decimal() {
+        let mut state = CalculatorState::new();
+        state.set_error_recovery(ErrorRecovery::Input);
+        divide_by_zero(&mut state);
+
+        state.input_decimal();
+        assert_eq!(state.display_value(), "0.");
+    }
+
+    #[test]
+    fn test_operations_ignored_in_error_state() {
+        let mut state = CalculatorState::new();
+        state.set_error_recovery(ErrorRecovery::Input);
+        divide_by_zero(&mut state);
+
+        state.toggle_sign();
+        state.set_operation(Operation::Add);
+        state.calculate();
+        assert!(state.is_error());
+        assert_eq!(state.last_error_type(), Some(CalculatorErrorType::DivisionByZero));
+    }
+}
@@ -0,0 +1,272 @@
+//! Tests for exponent (EE/EXP) entry.
+//!
+//! This module contains tests for:
+//! - Entering an exponent and its display rendering
+//! - Interaction with toggle_sign, backspace and input_decimal
+//! - Exponent range validation against `f64`
+//! - Using exponent entries in calculations
+
+use rust_wasm_calc::errors::CalculatorError;
+use rust_wasm_calc::state::locale::Locale;
+use rust_wasm_calc::state::types::{CalculatorState, Operation};
+
+/// Enters a number and an exponent through the keypad.
+fn enter(state: &mut CalculatorState, mantissa: &[u8], exponent: &[u8]) {
+    for digit in mantissa {
+        state.input_digit(*digit);
+    }
+    state.input_exponent();
+    for digit in exponent {
+        state.input_digit(*digit);
+    }
+}
+
+/// Tests for basic exponent entry.
+#[cfg(test)]
+mod entry_tests {
+    use super::*;
+
+    #[test]
+    fn test_input_exponent() {
+        let mut state = CalculatorState::new();
+        state.input_digit(6);
+        state.input_decimal();
+        for digit in [0, 2, 2] {
+            state.input_digit(digit);
+        }
+
+        state.input_exponent();
+        assert_eq!(state.display_value(), "6.022E");
+
+        state.input_digit(2);
+        state.input_digit(3);
+        assert_eq!(state.display_value(), "6.022E23");
+        assert_eq!(state.raw_display_value(), "6.022e23");
+    }
+
+    #[test]
+    fn test_input_exponent_on_fresh_entry() {
+        let mut state = CalculatorState::new();
+        state.input_exponent();
+        state.input_digit(3);
+        assert_eq!(state.display_value(), "1E3");
+    }
+
+    #[test]
+    fn test_second_exponent_ignored() {
+        let mut state = CalculatorState::new();
+        enter(&mut state, &[2], &[5]);
+        state.input_exponent();
+        assert_eq!(state.raw_display_value(), "2e5");
+    }
+
+    #[test]
+    fn test_decimal_ignored_in_exponent() {
+        let mut state = CalculatorState::new();
+        enter(&mut state, &[2], &[5]);
+        state.input_decimal();
+        assert_eq!(state.raw_display_value(), "2e5");
+    }
+
+    #[test]
+    fn test_trailing_decimal_dropped() {
+        let mut state = CalculatorState::new();
+        state.input_digit(4);
+        state.input_decimal();
+        state.input_exponent();
+        assert_eq!(state.raw_display_value(), "4e");
+    }
+
+    #[test]
+    fn test_exponent_rendered_with_locale() {
+        let mut state = CalculatorState::new();
+        state.set_locale(Locale::hi_in());
+        state.input_digit(1);
+        state.input_decimal();
+        state.input_digit(5);
+        state.input_exponent();
+        state.input_digit(1);
+        state.input_digit(2);
+        assert_eq!(state.display_value(), "१.५E१२");
+    }
+
+    #[test]
+    fn test_exponent_digits_not_counted_toward_limit() {
+        let mut state = CalculatorState::new();
+        state.set_max_digits(2);
+        enter(&mut state, &[1, 2], &[1, 0, 0]);
+        assert_eq!(state.raw_display_value(), "12e100");
+        assert!(!state.input_truncated());
+    }
+}
+
+/// Tests for sign toggling and backspace during exponent entry.
+#[cfg(test)]
+mod editing_tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_sign_affects_exponent() {
+        let mut state = CalculatorState::new();
+        enter(&mut state, &[5], &[3]);
+
+        state.toggle_sign();
+        assert_eq!(state.display_value(), "5E-3");
+
+        state.toggle_sign();
+        assert_eq!(state.display_value(), "5E3");
+    }
+
+    #[test]
+    fn test_toggle_sign_before_exponent_digits() {
+        let mut state = CalculatorState::new();
+        enter(&mut state, &[5], &[]);
+        state.toggle_exponent_sign();
+        state.input_digit(7);
+        assert_eq!(state.raw_display_value(), "5e-7");
+    }
+
+    #[test]
+    fn test_toggle_sign_mantissa_before_exponent() {
+        let mut state = CalculatorState::new();
+        state.input_digit(5);
+        state.toggle_sign();
+        state.input_exponent();
+        state.input_digit(2);
+        assert_eq!(state.raw_display_value(), "-5e2");
+    }
+
+    #[test]
+    fn test_toggle_exponent_sign_outside_exponent_entry() {
+        let mut state = CalculatorState::new();
+        state.input_digit(5);
+        state.toggle_exponent_sign();
+        assert_eq!(state.raw_display_value(), "5");
+    }
+
+    #[test]
+    fn test_backspace_through_exponent() {
+        let mut state = CalculatorState::new();
+        enter(&mut state, &[5], &[1, 2]);
+        state.toggle_sign();
+        assert_eq!(state.raw_display_value(), "5e-12");
+
+        state.backspace();
+        assert_eq!(state.raw_display_value(), "5e-1");
+        state.backspace();
+        assert_eq!(state.raw_display_value(), "5e-");
+        state.backspace();
+        assert_eq!(state.raw_display_value(), "5e");
+        state.backspace();
+        assert_eq!(state.raw_display_value(), "5");
+
+        // Back to mantissa entry: the decimal point works again
+        state.input_decimal();
+        assert_eq!(state.raw_display_value(), "5.");
+    }
+}
+
+/// Tests for exponent range validation.
+#[cfg(test)]
+mod range_tests {
+    use super::*;
+
+    #[test]
+    fn test_overflowing_exponent_rejected() {
+        let mut state = CalculatorState::new();
+        enter(&mut state, &[2], &[3, 0, 8]);
+        assert_eq!(state.raw_display_value(), "2e30");
+        assert!(state.input_truncated());
+
+        state.input_digit(7);
+        assert_eq!(state.raw_display_value(), "2e307");
+        assert!(!state.input_truncated());
+    }
+
+    #[test]
+    fn test_underflowing_exponent_rejected() {
+        let mut state = CalculatorState::new();
+        enter(&mut state, &[1], &[]);
+        state.toggle_sign();
+        for digit in [4, 0, 0] {
+            state.input_digit(digit);
+        }
+        assert_eq!(state.raw_display_value(), "1e-40");
+        assert!(state.input_truncated());
+    }
+
+    #[test]
+    fn test_sign_toggle_out_of_range_rejected() {
+        let mut state = CalculatorState::new();
+        enter(&mut state, &[1], &[]);
+        state.toggle_sign();
+        for digit in [3, 2, 0] {
+            state.input_digit(digit);
+        }
+        assert_eq!(state.raw_display_value(), "1e-320");
+
+        state.toggle_sign();
+        assert_eq!(state.raw_display_value(), "1e-320");
+        assert!(state.input_truncated());
+    }
+
+    #[test]
+    fn test_exponent_limited_to_three_digits() {
+        let mut state = CalculatorState::new();
+        enter(&mut state, &[0], &[]);
+        for digit in [1, 2, 3, 4] {
+            state.input_digit(digit);
+        }
+        assert_eq!(state.raw_display_value(), "1e123");
+    }
+
+    #[test]
+    fn test_input_value_out_of_range() {
+        let mut state = CalculatorState::new();
+        state.input_value("1e400");
+        assert_eq!(state.display_value(), format!("Error: {}", CalculatorError::overflow().message()));
+
+        let mut state = CalculatorState::new();
+        state.input_value("1e-400");
+        assert_eq!(state.display_value(), format!("Error: {}", CalculatorError::underflow().message()));
+
+        let mut state = CalculatorState::new();
+        state.input_value("0e-400");
+        assert!(!state.display_value().contains("Error"));
+    }
+}
+
+/// Tests for calculations with exponent entries.
+#[cfg(test)]
+mod calculation_tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_with_exponent() {
+        let mut state = CalculatorState::new();
+        enter(&mut state, &[2], &[3]);
+        state.set_operation(Operation::Multiply);
+        state.input_digit(4);
+        state.calculate();
+        assert_eq!(state.display_value(), "8000");
+    }
+
+    #[test]
+    fn test_incomplete_exponent_treated_as_zero() {
+        let mut state = CalculatorState::new();
+        enter(&mut state, &[7], &[]);
+        state.set_operation(Operation::Add);
+        state.input_digit(1);
+        state.calculate();
+        assert_eq!(state.display_value(), "8");
+    }
+
+    #[test]
+    fn test_input_value_with_exponent() {
+        let mut state = CalculatorState::new();
+        state.set_locale(Locale::de_de());
+        state.input_value("6,022E23");
+        assert_eq!(state.raw_display_value(), "6.022e23");
+        assert!(!state.precision_warning());
+    }
+}
@@ -0,0 +1,136 @@
+//! Tests for input length limits and the precision guard.
+//!
+//! This module contains tests for:
+//! - The maximum digit count for keypad and pasted input
+//! - The truncation signal
+//! - The precision warning for values that `f64` cannot hold exactly
+
+use rust_wasm_calc::state::types::{CalculatorState, DEFAULT_MAX_DIGITS};
+
+/// Tests for the maximum digit count.
+#[cfg(test)]
+mod max_digits_tests {
+    use super::*;
+
+    #[test]
+    fn test_default_max_digits() {
+        let state = CalculatorState::new();
+        assert_eq!(state.max_digits(), DEFAULT_MAX_DIGITS);
+        assert!(!state.input_truncated());
+    }
+
+    #[test]
+    fn test_input_digit_stops_at_limit() {
+        let mut state = CalculatorState::new();
+        state.set_max_digits(4);
+
+        for digit in [1, 2, 3, 4] {
+            state.input_digit(digit);
+        }
+        assert!(!state.input_truncated());
+
+        state.input_digit(5);
+        assert_eq!(state.display_value(), "1234");
+        assert!(state.input_truncated());
+
+        // Editing makes room again
+        state.backspace();
+        state.input_digit(9);
+        assert_eq!(state.display_value(), "1239");
+        assert!(!state.input_truncated());
+    }
+
+    #[test]
+    fn test_leading_zero_not_counted() {
+        let mut state = CalculatorState::new();
+        state.set_max_digits(3);
+
+        state.input_decimal();
+        for digit in [1, 2, 3, 4] {
+            state.input_digit(digit);
+        }
+        assert_eq!(state.display_value(), "0.123");
+        assert!(state.input_truncated());
+    }
+
+    #[test]
+    fn test_leading_fraction_zeros_not_counted() {
+        let mut state = CalculatorState::new();
+        state.input_value("0.000000000000000001");
+        assert_eq!(state.display_value(), "0.000000000000000001");
+        assert!(!state.input_truncated());
+
+        let mut state = CalculatorState::new();
+        state.set_max_digits(3);
+        state.input_decimal();
+        for digit in [0, 0, 1, 2, 3, 4] {
+            state.input_digit(digit);
+        }
+        assert_eq!(state.display_value(), "0.00123");
+        assert!(state.input_truncated());
+    }
+
+    #[test]
+    fn test_set_max_digits_minimum() {
+        let mut state = CalculatorState::new();
+        state.set_max_digits(0);
+        assert_eq!(state.max_digits(), 1);
+    }
+
+    #[test]
+    fn test_input_value_drops_fraction_digits() {
+        let mut state = CalculatorState::new();
+        state.set_max_digits(5);
+
+        state.input_value("123.4567");
+        assert_eq!(state.display_value(), "123.45");
+        assert!(state.input_truncated());
+    }
+
+    #[test]
+    fn test_input_value_rejects_long_integer() {
+        let mut state = CalculatorState::new();
+        state.set_max_digits(5);
+        state.input_digit(7);
+
+        state.input_value("1234567");
+        assert_eq!(state.display_value(), "7");
+        assert!(state.input_truncated());
+
+        state.clear_entry();
+        assert!(!state.input_truncated());
+    }
+}
+
+/// Tests for the precision warning.
+#[cfg(test)]
+mod precision_warning_tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_values() {
+        let mut state = CalculatorState::new();
+        assert!(!state.precision_warning());
+
+        state.input_value("0.1");
+        assert!(!state.precision_warning());
+
+        state.input_value("9007199254740992");
+        assert!(!state.precision_warning());
+
+        state.input_value("1.500");
+        assert!(!state.precision_warning());
+    }
+
+    #[test]
+    fn test_inexact_values() {
+        let mut state = CalculatorState::new();
+
+        state.input_value("9007199254740993");
+        assert!(state.precision_warning());
+
+        state.set_max_digits(20);
+        state.input_value("0.12345678901234567891");
+        assert!(state.precision_warning());
+    }
+}
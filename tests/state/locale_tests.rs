@@ -0,0 +1,246 @@
+//! Tests for locale-aware number formatting and parsing.
+//!
+//! This module contains tests for the locale settings:
+//! - Locale construction and validation
+//! - Formatting with separators, grouping styles and digit scripts
+//! - Parsing localized input
+//! - Locale integration with the calculator state
+//! - Digit grouping in the live display
+
+use rust_wasm_calc::state::locale::{DigitScript, GroupingStyle, Locale};
+use rust_wasm_calc::state::types::{CalculatorState, Operation};
+
+/// Tests for locale construction.
+#[cfg(test)]
+mod construction_tests {
+    use super::*;
+
+    #[test]
+    fn test_try_new_valid() {
+        let locale = Locale::try_new(',', '.', GroupingStyle::Thousands, DigitScript::Latin).unwrap();
+        assert_eq!(locale.decimal_separator(), ',');
+        assert_eq!(locale.grouping_separator(), '.');
+        assert_eq!(locale.grouping_style(), GroupingStyle::Thousands);
+        assert_eq!(locale.digit_script(), DigitScript::Latin);
+    }
+
+    #[test]
+    fn test_try_new_rejects_invalid_separators() {
+        assert!(Locale::try_new('.', '.', GroupingStyle::Thousands, DigitScript::Latin).is_err());
+        assert!(Locale::try_new('5', ',', GroupingStyle::Thousands, DigitScript::Latin).is_err());
+        assert!(Locale::try_new('.', '-', GroupingStyle::Thousands, DigitScript::Latin).is_err());
+    }
+
+    #[test]
+    fn test_default_is_en_us() {
+        assert_eq!(Locale::default(), Locale::en_us());
+    }
+}
+
+/// Tests for formatting numbers.
+#[cfg(test)]
+mod formatting_tests {
+    use super::*;
+
+    #[test]
+    fn test_format_thousands() {
+        assert_eq!(Locale::en_us().format_number(1234567.25), "1,234,567.25");
+        assert_eq!(Locale::de_de().format_number(1234567.25), "1.234.567,25");
+        assert_eq!(Locale::fr_fr().format_number(1234.5), "1\u{202F}234,5");
+        assert_eq!(Locale::en_us().format_number(-1000.0), "-1,000");
+        assert_eq!(Locale::en_us().format_number(999.0), "999");
+    }
+
+    #[test]
+    fn test_format_indian_grouping() {
+        assert_eq!(Locale::en_in().format_number(1234567.0), "12,34,567");
+        assert_eq!(Locale::en_in().format_number(123456789.0), "12,34,56,789");
+        assert_eq!(Locale::en_in().format_number(1000.0), "1,000");
+        assert_eq!(Locale::en_in().format_number(100.0), "100");
+    }
+
+    #[test]
+    fn test_format_digit_scripts() {
+        assert_eq!(Locale::hi_in().format_number(123456.5), "१,२३,४५६.५");
+
+        let arabic = Locale::try_new('\u{066B}', '\u{066C}', GroupingStyle::Thousands, DigitScript::ArabicIndic).unwrap();
+        assert_eq!(arabic.format_number(1234.5), "١\u{066C}٢٣٤\u{066B}٥");
+    }
+
+    #[test]
+    fn test_localize_partial_input() {
+        assert_eq!(Locale::de_de().localize("12.", false), "12,");
+        assert_eq!(Locale::de_de().localize("-0.5", false), "-0,5");
+        assert_eq!(Locale::de_de().localize("Error: Overflow", false), "Error: Overflow");
+    }
+}
+
+/// Tests for parsing localized input.
+#[cfg(test)]
+mod parsing_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_european_input() {
+        assert_eq!(Locale::de_de().parse("1.234,5").unwrap(), 1234.5);
+        assert_eq!(Locale::de_de().parse("-0,25").unwrap(), -0.25);
+        assert_eq!(Locale::fr_fr().parse("1 234,5").unwrap(), 1234.5);
+        assert_eq!(Locale::fr_fr().parse("1\u{00A0}234,5").unwrap(), 1234.5);
+    }
+
+    #[test]
+    fn test_parse_native_digits() {
+        assert_eq!(Locale::hi_in().parse("१२,३४,५६७.५").unwrap(), 1234567.5);
+        // Western digits are always accepted
+        assert_eq!(Locale::hi_in().parse("12,34,567").unwrap(), 1234567.0);
+    }
+
+    #[test]
+    fn test_parse_invalid_input() {
+        assert!(Locale::de_de().parse("1,2,3").is_err());
+        assert!(Locale::en_us().parse("12a").is_err());
+        assert!(Locale::en_us().parse("").is_err());
+        assert!(Locale::en_us().parse("1-2").is_err());
+    }
+
+    #[test]
+    fn test_to_canonical() {
+        assert_eq!(Locale::de_de().to_canonical("+1.000,50").unwrap(), "1000.50");
+        assert_eq!(Locale::en_us().to_canonical("\u{2212}7").unwrap(), "-7");
+    }
+}
+
+/// Tests for locale integration with the calculator state.
+#[cfg(test)]
+mod state_locale_tests {
+    use super::*;
+
+    #[test]
+    fn test_input_decimal_uses_locale_separator() {
+        let mut state = CalculatorState::new();
+        state.set_locale(Locale::de_de());
+
+        state.input_digit(1);
+        state.input_decimal();
+        assert_eq!(state.display_value(), "1,");
+
+        state.input_digit(5);
+        assert_eq!(state.display_value(), "1,5");
+
+        // The internal value stays canonical, so arithmetic still works
+        state.set_operation(Operation::Multiply);
+        state.input_digit(2);
+        state.calculate();
+        assert_eq!(state.display_value(), "3");
+    }
+
+    #[test]
+    fn test_input_value_accepts_localized_text() {
+        let mut state = CalculatorState::new();
+        state.set_locale(Locale::de_de());
+
+        state.input_value("1.234,5");
+        assert_eq!(state.display_value(), "1234,5");
+
+        state.set_operation(Operation::Add);
+        state.input_value("0,5");
+        state.calculate();
+        assert_eq!(state.display_value(), "1235");
+    }
+
+    #[test]
+    fn test_input_value_invalid_text() {
+        let mut state = CalculatorState::new();
+        state.input_value("abc");
+        assert!(state.display_value().contains("Error"));
+    }
+
+    #[test]
+    fn test_set_locale_keeps_value() {
+        let mut state = CalculatorState::new();
+        state.input_digit(2);
+        state.input_decimal();
+        state.input_digit(5);
+
+        state.set_locale(Locale::hi_in());
+        assert_eq!(state.display_value(), "२.५");
+        assert_eq!(state.locale(), Locale::hi_in());
+    }
+}
+
+/// Tests for digit grouping in the live display.
+#[cfg(test)]
+mod display_grouping_tests {
+    use super::*;
+
+    fn type_digits(state: &mut CalculatorState, digits: &[u8]) {
+        for digit in digits {
+            state.input_digit(*digit);
+        }
+    }
+
+    #[test]
+    fn test_grouping_disabled_by_default() {
+        let mut state = CalculatorState::new();
+        type_digits(&mut state, &[1, 2, 3, 4, 5]);
+        assert!(!state.digit_grouping());
+        assert_eq!(state.display_value(), "12345");
+    }
+
+    #[test]
+    fn test_grouping_while_typing() {
+        let mut state = CalculatorState::new();
+        state.set_digit_grouping(true);
+
+        type_digits(&mut state, &[1, 2, 3]);
+        assert_eq!(state.display_value(), "123");
+
+        state.input_digit(4);
+        assert_eq!(state.display_value(), "1,234");
+
+        state.input_decimal();
+        state.input_digit(5);
+        assert_eq!(state.display_value(), "1,234.5");
+        assert_eq!(state.raw_display_value(), "1234.5");
+    }
+
+    #[test]
+    fn test_grouping_follows_locale() {
+        let mut state = CalculatorState::new();
+        state.set_digit_grouping(true);
+        state.set_locale(Locale::en_in());
+        type_digits(&mut state, &[1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(state.display_value(), "12,34,567");
+
+        state.set_locale(Locale::de_de());
+        assert_eq!(state.display_value(), "1.234.567");
+    }
+
+    #[test]
+    fn test_editing_ignores_grouping() {
+        let mut state = CalculatorState::new();
+        state.set_digit_grouping(true);
+        type_digits(&mut state, &[1, 2, 3, 4]);
+
+        state.backspace();
+        assert_eq!(state.display_value(), "123");
+
+        state.input_digit(4);
+        state.toggle_sign();
+        assert_eq!(state.display_value(), "-1,234");
+        assert_eq!(state.raw_display_value(), "-1234");
+    }
+
+    #[test]
+    fn test_grouping_applies_to_results() {
+        let mut state = CalculatorState::new();
+        state.set_digit_grouping(true);
+        type_digits(&mut state, &[1, 0, 0, 0]);
+        state.set_operation(Operation::Multiply);
+        type_digits(&mut state, &[1, 0, 0, 0]);
+        state.calculate();
+
+        assert_eq!(state.display_value(), "1,000,000");
+        assert_eq!(state.raw_display_value(), "1000000");
+    }
+}
@@ -8,6 +8,12 @@
 // Re-export test modules
 pub mod types_tests;
 pub mod operations_tests;
+pub mod locale_tests;
+pub mod input_limits_tests;
+pub mod exponent_tests;
+pub mod error_state_tests;
+pub mod statistics_tests;
+pub mod trace_tests;
 
 // Integration tests that combine multiple state operations
 #[cfg(test)]
@@ -0,0 +1,177 @@
+//! Tests for statistics mode in the calculator state.
+//!
+//! This module contains tests for:
+//! - Entering data points through the keypad
+//! - Entering (x, y) pairs and fitting regressions
+//! - Frequencies, removing and clearing data points
+//! - Mode handling
+
+use rust_wasm_calc::calculator::RegressionKind;
+use rust_wasm_calc::state::types::{CalculatorMode, CalculatorState};
+
+/// Enters a number and adds it as a data point.
+fn add_point(state: &mut CalculatorState, text: &str, frequency: u32) {
+    state.input_value(text);
+    state.stats_add_with_frequency(frequency);
+}
+
+/// Tests for statistics data entry.
+#[cfg(test)]
+mod data_entry_tests {
+    use super::*;
+
+    #[test]
+    fn test_enter_data_and_summarize() {
+        let mut state = CalculatorState::new();
+        state.set_mode(CalculatorMode::Statistics);
+
+        for digit in [2, 4, 6] {
+            state.input_digit(digit);
+            state.stats_add();
+        }
+
+        assert_eq!(state.stats_count(), 3.0);
+        let stats = state.statistics().unwrap();
+        assert_eq!(stats.mean(), 4.0);
+        assert_eq!(stats.median(), 4.0);
+    }
+
+    #[test]
+    fn test_entry_starts_fresh_after_add() {
+        let mut state = CalculatorState::new();
+        state.set_mode(CalculatorMode::Statistics);
+        state.input_digit(1);
+        state.input_digit(2);
+        state.stats_add();
+        state.input_digit(3);
+        assert_eq!(state.display_value(), "3");
+    }
+
+    #[test]
+    fn test_frequencies() {
+        let mut state = CalculatorState::new();
+        state.set_mode(CalculatorMode::Statistics);
+        add_point(&mut state, "10", 3);
+        add_point(&mut state, "20", 1);
+
+        assert_eq!(state.stats_count(), 4.0);
+        assert_eq!(state.statistics().unwrap().mean(), 12.5);
+    }
+
+    #[test]
+    fn test_zero_frequency_is_an_error() {
+        let mut state = CalculatorState::new();
+        state.set_mode(CalculatorMode::Statistics);
+        add_point(&mut state, "10", 0);
+        assert!(state.is_error());
+        assert_eq!(state.stats_count(), 0.0);
+    }
+
+    #[test]
+    fn test_remove_last_and_clear() {
+        let mut state = CalculatorState::new();
+        state.set_mode(CalculatorMode::Statistics);
+        add_point(&mut state, "1", 1);
+        add_point(&mut state, "100", 1);
+
+        state.stats_remove_last();
+        assert_eq!(state.statistics().unwrap().max(), 1.0);
+
+        state.stats_clear();
+        assert!(state.statistics().is_err());
+    }
+}
+
+/// Enters an (x, y) pair.
+fn add_pair(state: &mut CalculatorState, x: &str, y: &str) {
+    state.input_value(x);
+    state.stats_enter_x();
+    state.input_value(y);
+    state.stats_add();
+}
+
+/// Tests for paired data entry.
+#[cfg(test)]
+mod paired_entry_tests {
+    use super::*;
+
+    #[test]
+    fn test_enter_pairs_and_fit() {
+        let mut state = CalculatorState::new();
+        state.set_mode(CalculatorMode::Statistics);
+        add_pair(&mut state, "1", "3");
+        add_pair(&mut state, "2", "5");
+        add_pair(&mut state, "3", "7");
+
+        let fit = state.regression(RegressionKind::Linear, 1).unwrap();
+        assert_eq!(fit.coefficients(), vec![1.0, 2.0]);
+        assert_eq!(state.paired_statistics().unwrap().mean_y(), 5.0);
+        // One-variable statistics use the x values
+        assert_eq!(state.statistics().unwrap().mean(), 2.0);
+    }
+
+    #[test]
+    fn test_pending_x() {
+        let mut state = CalculatorState::new();
+        state.set_mode(CalculatorMode::Statistics);
+        state.input_value("4");
+        state.stats_enter_x();
+        assert_eq!(state.stats_pending_x(), Some(4.0));
+
+        state.input_value("8");
+        state.stats_add();
+        assert_eq!(state.stats_pending_x(), None);
+    }
+
+    #[test]
+    fn test_single_values_are_left_out_of_pairs() {
+        let mut state = CalculatorState::new();
+        state.set_mode(CalculatorMode::Statistics);
+        add_point(&mut state, "100", 1);
+        assert!(state.paired_statistics().is_err());
+
+        add_pair(&mut state, "1", "2");
+        assert_eq!(state.paired_statistics().unwrap().count(), 1.0);
+    }
+
+    #[test]
+    fn test_clear_drops_pending_x() {
+        let mut state = CalculatorState::new();
+        state.set_mode(CalculatorMode::Statistics);
+        state.input_value("4");
+        state.stats_enter_x();
+        state.stats_clear();
+        assert_eq!(state.stats_pending_x(), None);
+    }
+}
+
+/// Tests for mode handling.
+#[cfg(test)]
+mod mode_tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_mode_by_default() {
+        let state = CalculatorState::new();
+        assert_eq!(state.mode(), CalculatorMode::Standard);
+    }
+
+    #[test]
+    fn test_stats_add_ignored_in_standard_mode() {
+        let mut state = CalculatorState::new();
+        state.input_digit(5);
+        state.stats_add();
+        assert_eq!(state.stats_count(), 0.0);
+        assert_eq!(state.display_value(), "5");
+    }
+
+    #[test]
+    fn test_data_kept_across_mode_switch() {
+        let mut state = CalculatorState::new();
+        state.set_mode(CalculatorMode::Statistics);
+        add_point(&mut state, "7", 1);
+        state.set_mode(CalculatorMode::Standard);
+        state.set_mode(CalculatorMode::Statistics);
+        assert_eq!(state.stats_count(), 1.0);
+    }
+}
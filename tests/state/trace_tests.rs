@@ -0,0 +1,94 @@
+//! Tests for the keypad calculation trace.
+//!
+//! This module contains tests for:
+//! - Steps recorded by `=` and chained operators
+//! - Operations that record no step, and clearing the trace
+
+use rust_wasm_calc::state::types::{CalculatorState, Operation};
+
+/// Enters a number digit by digit.
+fn enter(state: &mut CalculatorState, digits: &[u8]) {
+    for digit in digits {
+        state.input_digit(*digit);
+    }
+}
+
+/// Tests for recorded steps.
+#[cfg(test)]
+mod recorded_steps_tests {
+    use super::*;
+
+    #[test]
+    fn test_chained_operations() {
+        // 12 + 4 × 2 = evaluates left to right on the keypad
+        let mut state = CalculatorState::new();
+        enter(&mut state, &[1, 2]);
+        state.set_operation(Operation::Add);
+        enter(&mut state, &[4]);
+        state.set_operation(Operation::Multiply);
+        enter(&mut state, &[2]);
+        state.calculate();
+
+        let steps = state.trace();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].calculation(), "12 + 4");
+        assert_eq!(steps[0].rule(), "addition");
+        assert_eq!(steps[0].value(), 16.0);
+        assert_eq!(steps[1].calculation(), "16 * 2");
+        assert_eq!(steps[1].rule(), "multiplication");
+        assert_eq!(steps[1].result(), "32");
+    }
+
+    #[test]
+    fn test_negative_operands() {
+        let mut state = CalculatorState::new();
+        enter(&mut state, &[3]);
+        state.set_operation(Operation::Subtract);
+        enter(&mut state, &[5]);
+        state.toggle_sign();
+        state.calculate();
+        assert_eq!(state.trace()[0].expression(), "3 - (-5)");
+        assert_eq!(state.trace()[0].value(), 8.0);
+    }
+}
+
+/// Tests for operations that do not add steps.
+#[cfg(test)]
+mod unrecorded_steps_tests {
+    use super::*;
+
+    #[test]
+    fn test_no_step_without_operation() {
+        let mut state = CalculatorState::new();
+        enter(&mut state, &[7]);
+        state.calculate();
+        assert!(state.trace().is_empty());
+    }
+
+    #[test]
+    fn test_failed_operation() {
+        let mut state = CalculatorState::new();
+        enter(&mut state, &[6]);
+        state.set_operation(Operation::Divide);
+        enter(&mut state, &[0]);
+        state.calculate();
+        assert!(state.is_error());
+        assert!(state.trace().is_empty());
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut state = CalculatorState::new();
+        enter(&mut state, &[2]);
+        state.set_operation(Operation::Add);
+        enter(&mut state, &[2]);
+        state.calculate();
+        assert_eq!(state.trace().len(), 1);
+
+        // Clearing the entry keeps the steps; clearing everything does not
+        state.clear_entry();
+        assert_eq!(state.trace().len(), 1);
+        state.clear();
+        assert!(state.trace().is_empty());
+    }
+}
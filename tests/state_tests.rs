@@ -0,0 +1,3 @@
+//! Entry point for the calculator state tests in `tests/state/`.
+
+mod state;
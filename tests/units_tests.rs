@@ -0,0 +1,363 @@
+//! Tests for unit conversion.
+//!
+//! This module contains tests for the units module:
+//! - Looking up units by symbol, alias, name and prefix
+//! - Conversions in each category, including temperature offsets
+//! - Incompatible dimensions and unknown units
+//! - The catalogue used by the UI
+//! - Quantity arithmetic, simplification and formatting
+//! - Currency rate tables, triangulation and minor-unit rounding
+
+use rust_wasm_calc::errors::CalculatorErrorType;
+use rust_wasm_calc::state::Locale;
+use rust_wasm_calc::units::currency::{clear_rates, round_to_minor_units};
+use rust_wasm_calc::units::{
+    convert, install_rates, lookup, unit_catalogue, units_in_category, Dimension, Quantity, RateTable,
+    UnitCategory,
+};
+
+/// A fixed rate table, so currency tests run offline.
+const RATES: &str = r#"{
+    "base": "USD",
+    "timestamp": "2024-03-15T16:00:00Z",
+    "rates": {
+        "EUR": 0.92,
+        "GBP": 0.79,
+        "JPY": {"rate": 149.5, "timestamp": "2024-03-15T09:00:00Z"},
+        "KWD": 0.3075
+    }
+}"#;
+
+/// Installs the fixed rate table for the current test thread.
+fn install_test_rates() {
+    install_rates(RateTable::from_json(RATES).unwrap());
+}
+
+/// Asserts that two values agree to a relative tolerance, or an absolute
+/// one near zero.
+fn assert_close(actual: f64, expected: f64) {
+    let scale = expected.abs().max(1.0);
+    assert!(((actual - expected) / scale).abs() < 1e-12, "{} != {}", actual, expected);
+}
+
+/// Tests for unit lookup.
+#[cfg(test)]
+mod lookup_tests {
+    use super::*;
+
+    #[test]
+    fn test_symbols_aliases_and_names() {
+        assert_eq!(lookup("km").unwrap().name(), "kilometre");
+        assert_eq!(lookup("kph").unwrap().symbol(), "km/h");
+        assert_eq!(lookup("Feet").unwrap().symbol(), "ft");
+        assert_eq!(lookup("degrees Celsius").unwrap().symbol(), "°C");
+        assert_eq!(lookup(" lbs ").unwrap().symbol(), "lb");
+    }
+
+    #[test]
+    fn test_symbols_are_case_sensitive() {
+        assert_eq!(lookup("mm").unwrap().factor(), 1e-3);
+        assert_eq!(lookup("Mm").unwrap().factor(), 1e6);
+        assert_eq!(lookup("Mm").unwrap().name(), "megametre");
+    }
+
+    #[test]
+    fn test_prefixes() {
+        assert_eq!(lookup("Gm").unwrap().factor(), 1e9);
+        assert_eq!(lookup("mbar").unwrap().factor(), 100.0);
+        assert_eq!(lookup("dam").unwrap().factor(), 10.0);
+        assert_eq!(lookup("µs").unwrap().factor(), 1e-6);
+        assert_eq!(lookup("PiB").unwrap().factor(), 8.0 * 1024f64.powi(5));
+        assert_eq!(lookup("PiB").unwrap().name(), "pebibyte");
+        // Binary prefixes are only for data, and unprefixable units take none
+        assert!(lookup("Kim").is_err());
+        assert!(lookup("kft").is_err());
+        assert!(lookup("k°C").is_err());
+    }
+
+    #[test]
+    fn test_unknown_unit() {
+        let error = lookup("furlong").unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::InvalidInput);
+        assert!(error.message().contains("unknown unit 'furlong'"));
+    }
+}
+
+/// Tests for conversions.
+#[cfg(test)]
+mod convert_tests {
+    use super::*;
+
+    #[test]
+    fn test_linear_conversions() {
+        assert_close(convert(1.0, "mi", "km").unwrap(), 1.609344);
+        assert_close(convert(1.0, "lb", "g").unwrap(), 453.59237);
+        assert_close(convert(90.0, "min", "h").unwrap(), 1.5);
+        assert_close(convert(1.0, "acre", "ft²").unwrap(), 43_560.0);
+        assert_close(convert(1.0, "gal", "L").unwrap(), 3.785411784);
+        assert_close(convert(100.0, "km/h", "m/s").unwrap(), 250.0 / 9.0);
+        assert_close(convert(1.0, "atm", "psi").unwrap(), 14.695948775513449);
+        assert_close(convert(1.0, "kWh", "MJ").unwrap(), 3.6);
+        assert_close(convert(1.0, "hp", "W").unwrap(), 745.69987158227);
+        assert_close(convert(180.0, "deg", "rad").unwrap(), std::f64::consts::PI);
+    }
+
+    #[test]
+    fn test_data_sizes() {
+        assert_close(convert(1.0, "GB", "MB").unwrap(), 1000.0);
+        assert_close(convert(1.0, "GiB", "MiB").unwrap(), 1024.0);
+        assert_close(convert(1.0, "B", "bit").unwrap(), 8.0);
+        assert_close(convert(1.0, "TB", "GiB").unwrap(), 931.3225746154785);
+    }
+
+    #[test]
+    fn test_temperatures() {
+        assert_close(convert(100.0, "°C", "°F").unwrap(), 212.0);
+        assert_close(convert(-40.0, "F", "C").unwrap(), -40.0);
+        assert_close(convert(0.0, "K", "°R").unwrap(), 0.0);
+        assert!(convert(491.67, "°R", "°C").unwrap().abs() < 1e-9);
+        // A result of zero is not an underflow when the scales are offset
+        assert_eq!(convert(273.15, "K", "°C").unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for unit in unit_catalogue() {
+            let si = &units_in_category(unit.category())[0];
+            let there = convert(12.5, &unit.symbol(), &si.symbol()).unwrap();
+            assert_close(convert(there, &si.symbol(), &unit.symbol()).unwrap(), 12.5);
+        }
+    }
+
+    #[test]
+    fn test_incompatible_units() {
+        let error = convert(1.0, "km", "kg").unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::IncompatibleUnits);
+        assert!(error.message().contains("km (length)"));
+        assert_eq!(convert(1.0, "J", "W").unwrap_err().error_type(), CalculatorErrorType::IncompatibleUnits);
+        assert_eq!(convert(1.0, "km", "parsec").unwrap_err().error_type(), CalculatorErrorType::InvalidInput);
+    }
+
+    #[test]
+    fn test_range_errors() {
+        assert_eq!(convert(f64::MAX, "ly", "nm").unwrap_err().error_type(), CalculatorErrorType::Overflow);
+        assert_eq!(convert(1e-300, "nm", "ly").unwrap_err().error_type(), CalculatorErrorType::Underflow);
+    }
+}
+
+/// Tests for the catalogue.
+#[cfg(test)]
+mod catalogue_tests {
+    use super::*;
+
+    #[test]
+    fn test_every_category_starts_with_its_si_unit() {
+        install_test_rates();
+        for category in UnitCategory::ALL {
+            let units = units_in_category(category);
+            assert!(!units.is_empty(), "{}", category.name());
+            assert_eq!(units[0].factor(), 1.0, "{}", category.name());
+            assert_eq!(units[0].offset(), 0.0, "{}", category.name());
+        }
+    }
+
+    #[test]
+    fn test_symbols_are_unique_and_resolve() {
+        install_test_rates();
+        let catalogue = unit_catalogue();
+        for (i, unit) in catalogue.iter().enumerate() {
+            assert!(catalogue[i + 1..].iter().all(|other| other.symbol() != unit.symbol()), "{}", unit.symbol());
+            assert_eq!(lookup(&unit.symbol()).unwrap(), *unit);
+            assert_eq!(lookup(&unit.plural()).unwrap().symbol(), unit.symbol());
+        }
+    }
+
+    #[test]
+    fn test_dimensions() {
+        assert_eq!(UnitCategory::Speed.dimension(), Dimension::new([1, 0, -1, 0, 0, 0, 0]));
+        assert_ne!(UnitCategory::Energy.dimension(), UnitCategory::Power.dimension());
+        assert!(Dimension::NONE.is_dimensionless());
+        assert!(!Dimension::ANGLE.is_dimensionless());
+    }
+}
+
+/// Tests for quantities.
+#[cfg(test)]
+mod quantity_tests {
+    use super::*;
+
+    /// Creates a quantity of a unit.
+    fn quantity(value: f64, unit: &str) -> Quantity {
+        Quantity::new(value, lookup(unit).unwrap())
+    }
+
+    #[test]
+    fn test_values_are_stored_in_si_units() {
+        let distance = quantity(5.0, "km");
+        assert_eq!(distance.si_value(), 5000.0);
+        assert_eq!(distance.value(), 5.0);
+        assert_eq!(distance.unit(), "km");
+        assert_eq!(distance.dimension().unwrap(), Dimension::LENGTH);
+    }
+
+    #[test]
+    fn test_combining_units() {
+        let area = quantity(3.0, "m").multiply(&quantity(4.0, "km")).unwrap();
+        assert_eq!(area.unit(), "m²");
+        assert_eq!(area.value(), 12_000.0);
+
+        let speed = quantity(5.0, "km").divide(&quantity(20.0, "min")).unwrap();
+        assert_eq!(speed.unit(), "km/min");
+        assert_eq!(speed.simplify().to_string(), "15 km/h");
+
+        let ratio = quantity(1.0, "m").divide(&quantity(1.0, "km")).unwrap();
+        assert!(ratio.is_number());
+        assert_eq!(ratio.value(), 0.001);
+    }
+
+    #[test]
+    fn test_powers() {
+        let volume = quantity(2.0, "m").power(&Quantity::number(3.0)).unwrap();
+        assert_eq!(volume.to_string(), "8 m³");
+        let side = quantity(16.0, "m²").power(&Quantity::number(0.5)).unwrap();
+        assert_eq!(side.to_string(), "4 m");
+        let error = quantity(4.0, "m").power(&Quantity::number(0.5)).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::IncompatibleUnits);
+    }
+
+    #[test]
+    fn test_temperature_conversion() {
+        let boiling = quantity(100.0, "°C").convert_to(&quantity(1.0, "°F")).unwrap();
+        assert!((boiling.value() - 212.0).abs() < 1e-9);
+        let absolute = quantity(0.0, "°C").convert_to(&quantity(1.0, "K")).unwrap();
+        assert!((absolute.value() - 273.15).abs() < 1e-9);
+        // Sums of the same scale stay on that scale
+        let sum = quantity(20.0, "°C").add(&quantity(5.0, "°C")).unwrap();
+        assert_eq!(sum.to_string(), "25 °C");
+    }
+
+    #[test]
+    fn test_format_with_locale() {
+        assert_eq!(quantity(1234.5, "km").format(&Locale::de_de()), "1.234,5 km");
+        assert_eq!(Quantity::number(0.1).add(&Quantity::number(0.2)).unwrap().to_string(), "0.3");
+    }
+}
+
+/// Tests for currencies.
+#[cfg(test)]
+mod currency_tests {
+    use super::*;
+
+    #[test]
+    fn test_json_rate_table() {
+        let table = RateTable::from_json(RATES).unwrap();
+        assert_eq!(table.base(), "USD");
+        assert_eq!(table.codes(), vec!["USD", "EUR", "GBP", "JPY", "KWD"]);
+        assert_eq!(table.rate("USD").unwrap(), 1.0);
+        assert_eq!(table.timestamp("EUR").unwrap(), Some("2024-03-15T16:00:00Z"));
+        assert_eq!(table.timestamp("JPY").unwrap(), Some("2024-03-15T09:00:00Z"));
+        assert!(table.rate("CHF").is_err());
+    }
+
+    #[test]
+    fn test_csv_rate_table() {
+        let table = RateTable::from_csv("EUR", "code,rate,timestamp\nUSD,1.087\nCHF,0.96,2024-03-14\n").unwrap();
+        assert_eq!(table.codes(), vec!["EUR", "USD", "CHF"]);
+        assert_eq!(table.timestamp("CHF").unwrap(), Some("2024-03-14"));
+        assert_eq!(table.timestamp("USD").unwrap(), None);
+        assert!(RateTable::from_csv("EUR", "USD,1.087\nCHF").is_err());
+
+        // A row with a currency code, or after the data, is not a header
+        for csv in ["EUR,abc\nUSD,1.087", "USD,1.087\ncode,rate", "code,1.087", "1,2\nUSD,1.087"] {
+            let error = RateTable::from_csv("EUR", csv).unwrap_err();
+            assert_eq!(error.error_type(), CalculatorErrorType::InvalidInput, "{}", csv);
+        }
+        assert!(RateTable::from_csv("EUR", "\ncurrency,rate\nUSD,1.087").is_ok());
+    }
+
+    #[test]
+    fn test_invalid_rate_tables() {
+        for json in [
+            r#"{"rates": {"EUR": 0.9}}"#,
+            r#"{"base": "USD"}"#,
+            r#"{"base": "usd", "rates": {}}"#,
+            r#"{"base": "USD", "rates": {"EUR": -1}}"#,
+            r#"{"base": "USD", "rates": {"EUR": "0.9"}}"#,
+            r#"{"base": "USD", "timestamp": "yesterday", "rates": {}}"#,
+            r#"{"base": "USD", "rates": {"EUR": 0.9}"#,
+            r#"{"base": "\u+055SD", "rates": {}}"#,
+        ] {
+            let error = RateTable::from_json(json).unwrap_err();
+            assert_eq!(error.error_type(), CalculatorErrorType::InvalidInput, "{}", json);
+        }
+    }
+
+    #[test]
+    fn test_json_escapes() {
+        let table = RateTable::from_json(r#"{"base": "\u0055SD", "rates": {"EUR": 0.9}}"#).unwrap();
+        assert_eq!(table.convert(100.0, "USD", "EUR").unwrap(), 90.0);
+
+        // A surrogate pair is one character
+        let error = RateTable::from_json(r#"{"base": "\ud83d\ude00", "rates": {}}"#).unwrap_err();
+        assert!(error.message().ends_with(": 😀 is not an ISO 4217 currency code"));
+    }
+
+    #[test]
+    fn test_json_nesting_limit() {
+        let json = "[".repeat(100_000);
+        let error = RateTable::from_json(&json).unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::InvalidInput);
+        assert!(error.message().contains("nested too deeply"));
+    }
+
+    #[test]
+    fn test_triangulation() {
+        let table = RateTable::from_json(RATES).unwrap();
+        assert_close(table.cross_rate("EUR", "GBP").unwrap(), 0.79 / 0.92);
+        assert_eq!(table.convert(100.0, "EUR", "GBP").unwrap(), 85.87);
+        assert_eq!(table.convert(100.0, "GBP", "JPY").unwrap(), 18924.0);
+        assert_eq!(table.convert(1000.0, "JPY", "KWD").unwrap(), 2.057);
+    }
+
+    #[test]
+    fn test_minor_unit_rounding() {
+        assert_eq!(round_to_minor_units(1.005, "USD"), 1.01);
+        assert_eq!(round_to_minor_units(-2.675, "EUR"), -2.68);
+        assert_eq!(round_to_minor_units(99.5, "JPY"), 100.0);
+        assert_eq!(round_to_minor_units(1.23456, "BHD"), 1.235);
+        assert_eq!(round_to_minor_units(0.99999, "CLF"), 1.0);
+        assert_eq!(round_to_minor_units(12.345, "XYZ"), 12.35);
+    }
+
+    #[test]
+    fn test_currency_units() {
+        install_test_rates();
+        assert_eq!(lookup("EUR").unwrap().category(), UnitCategory::Currency);
+        assert_eq!(lookup("euro").unwrap().symbol(), "EUR");
+        assert_eq!(convert(10.0, "USD", "JPY").unwrap(), 1495.0);
+        assert_eq!(convert(10.0, "EUR", "USD").unwrap(), 10.87);
+        let error = convert(10.0, "EUR", "kg").unwrap_err();
+        assert_eq!(error.error_type(), CalculatorErrorType::IncompatibleUnits);
+
+        let amount = Quantity::new(10.0, lookup("EUR").unwrap());
+        let yen = amount.convert_to(&Quantity::new(1.0, lookup("JPY").unwrap())).unwrap();
+        assert_eq!(yen.to_string(), "1625 JPY");
+        let pounds = amount.convert_to(&Quantity::new(1.0, lookup("GBP").unwrap())).unwrap();
+        assert_eq!(pounds.to_string(), "8.59 GBP");
+        assert_eq!(Quantity::new(1234.5, lookup("EUR").unwrap()).format(&Locale::de_de()), "1.234,50 EUR");
+
+        // Amounts too large for minor units are shown in scientific notation
+        let huge = Quantity::new(1e300, lookup("USD").unwrap());
+        let yen = huge.convert_to(&Quantity::new(1.0, lookup("JPY").unwrap())).unwrap();
+        assert_eq!(yen.to_string(), "1.495e302 JPY");
+        assert_eq!(huge.format(&Locale::de_de()), "1E300 USD");
+        assert_eq!(Quantity::new(1e14, lookup("USD").unwrap()).to_string(), "100000000000000.00 USD");
+    }
+
+    #[test]
+    fn test_currencies_need_rates() {
+        clear_rates();
+        assert!(lookup("EUR").is_err());
+        assert!(units_in_category(UnitCategory::Currency).is_empty());
+    }
+}
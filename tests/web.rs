@@ -8,6 +8,7 @@ use wasm_bindgen_test::*;
 use rust_wasm_calc::hello;
 use rust_wasm_calc::calculator::{
     add, subtract, multiply, divide_wasm,
+    checked_add_wasm, checked_multiply_wasm, checked_divide_wasm,
     memory_store, memory_recall, memory_clear, memory_add, memory_subtract
 };
 use rust_wasm_calc::state::types::{CalculatorState, Operation};
@@ -131,16 +132,14 @@ async fn test_concurrent_calls() {
     use std::sync::atomic::{AtomicUsize, Ordering};
 
     let counter = Arc::new(AtomicUsize::new(0));
-    let mut handles = vec![];
 
     for i in 0..5 {
         let counter = counter.clone();
-        let handle = spawn_local(async move {
+        spawn_local(async move {
             let result = hello(&format!("Concurrent{}", i));
             assert_eq!(result, format!("Hello, Concurrent{}!", i));
             counter.fetch_add(1, Ordering::SeqCst);
         });
-        handles.push(handle);
     }
 
     // Wait for all tests to complete
@@ -176,6 +175,19 @@ fn test_calculator_operations() {
     assert!(error.is_err());
 }
 
+/// Tests checked calculator operations in a browser environment.
+///
+/// This test verifies that:
+/// - Checked operations return results through WASM
+/// - Overflow, underflow and division by zero are reported as errors
+#[wasm_bindgen_test]
+fn test_checked_operations() {
+    assert_eq!(checked_add_wasm(2.0, 3.0).unwrap(), 5.0);
+    assert!(checked_multiply_wasm(1e200, 1e200).is_err());
+    assert!(checked_multiply_wasm(1e-200, 1e-200).is_err());
+    assert!(checked_divide_wasm(1.0, 0.0).is_err());
+}
+
 /// Tests memory operations in a browser environment.
 ///
 /// This test verifies that: